@@ -78,6 +78,14 @@ pub struct ClientCli {
     #[arg(long)]
     pub headless: bool,
 
+    /// Record the player's input to the given file, so that the session can be replayed with `--replay-input`
+    #[arg(long, conflicts_with = "replay_input")]
+    pub record_input: Option<PathBuf>,
+
+    /// Replay the input recorded with `--record-input` from the given file in headless mode, and report where the client diverges from the recording (the server is not replayed)
+    #[arg(long)]
+    pub replay_input: Option<PathBuf>,
+
     /// Run golden image test
     #[command(subcommand)]
     pub golden_image: Option<GoldenImageCommand>,
@@ -72,6 +72,7 @@ pub async fn handle_inner(
         false,
         release_build,
         build_wasm_only,
+        None,
         HashSet::new(),
         |_| async { Ok(()) },
         |_, _, _| async { Ok(()) },
@@ -99,6 +100,8 @@ pub async fn build<
     deploy: bool,
     release: bool,
     wasm_only: bool,
+    // If set, only the asset pipelines of the main package that process these files are run
+    changed_assets: Option<&[PathBuf]>,
     // Used by deploy to avoid building packages that have already been built
     // by other packages
     skip_building: HashSet<PathBuf>,
@@ -128,6 +131,9 @@ pub async fn build<
         let primary_package_scope_id = semantic
             .add_package(RetrievableFile::Url(main_manifest_url.0.clone()), None)
             .await?;
+        if let Some(lockfile) = &semantic.lockfile {
+            super::write_lockfile(&main_package_fs_path, lockfile)?;
+        }
         semantic
             .resolve_all()
             .context("Failed to resolve dependencies for pre-build")?;
@@ -136,7 +142,10 @@ pub async fn build<
             .items
             .scope_and_dependencies(primary_package_scope_id)
             .into_iter()
-            .flat_map(|id| semantic.items.get(id).source.as_local_path())
+            .map(|id| &semantic.items.get(id).source)
+            // Vendored deployments are local, but have already been built
+            .filter(|source| !matches!(source, RetrievableFile::Deployment(_)))
+            .flat_map(|source| source.as_local_path())
             .filter(|path| !skip_building.contains(path))
             .rev()
             .collect()
@@ -166,8 +175,15 @@ pub async fn build<
             build_path,
             package_name,
             was_built,
-        } = ambient_build::build_package(assets, &settings, &manifest_path, &root_build_path)
-            .await?;
+        } = ambient_build::build_package(
+            assets,
+            &settings,
+            &manifest_path,
+            &root_build_path,
+            changed_assets
+                .filter(|_| AbsAssetUrl::from_file_path(&manifest_path) == main_manifest_url),
+        )
+        .await?;
 
         post_build(manifest_path.clone(), build_path.clone(), was_built).await?;
 
@@ -124,6 +124,7 @@ pub async fn handle(args: &Deploy, assets: &AssetCache, release_build: bool) ->
             true,
             release_build,
             package.build_wasm_only,
+            None,
             skip_building,
             |package_manifest_path| {
                 // Before the build, rewrite all known dependencies to use their deployed version
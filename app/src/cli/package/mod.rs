@@ -3,8 +3,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use ambient_native_std::asset_cache::AssetCache;
-use ambient_package::PackageId;
+use ambient_native_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
+use ambient_package::{Lockfile, PackageId};
+use ambient_package_semantic::{RetrievableFile, Semantic};
 use anyhow::Context;
 use clap::{Args, Subcommand};
 
@@ -15,6 +16,8 @@ pub mod deploy;
 pub mod new;
 pub mod run;
 pub mod serve;
+pub mod vendor;
+pub mod watch;
 
 #[derive(Subcommand, Clone, Debug)]
 /// Package-related commands.
@@ -24,11 +27,25 @@ pub enum Package {
         #[command(flatten)]
         package: PackageArgs,
     },
+    /// Re-resolve the versions of all remote dependencies to the newest compatible versions,
+    /// ignoring the existing lockfile, and write the result to `ambient.lock`.
+    Update {
+        #[command(flatten)]
+        package: PackageArgs,
+    },
+    /// Copy the deployments of all resolved remote dependencies into the package's `vendor`
+    /// directory, so that it can be built without network access.
+    Vendor {
+        #[command(flatten)]
+        package: PackageArgs,
+    },
 }
 impl Package {
     pub fn args(&self) -> &PackageArgs {
         match self {
             Package::RegenerateId { package } => package,
+            Package::Update { package } => package,
+            Package::Vendor { package } => package,
         }
     }
 }
@@ -131,15 +148,27 @@ pub struct HostCli {
     /// Shutdown the server after the specified number of seconds of inactivity
     #[arg(long)]
     pub shutdown_after_inactivity_seconds: Option<u64>,
+
+    /// Watch the package for changes, rebuilding it and reloading it on the server and all connected clients
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Maximum number of bytes each package can keep in persistent storage
+    ///
+    /// Defaults to 64 MiB
+    #[arg(long)]
+    pub storage_quota: Option<u64>,
 }
 
 pub fn handle(
     args: &Package,
-    _rt: &tokio::runtime::Runtime,
-    _assets: AssetCache,
+    rt: &tokio::runtime::Runtime,
+    assets: AssetCache,
 ) -> anyhow::Result<()> {
     match args {
         Package::RegenerateId { package } => regenerate_id(package),
+        Package::Update { package } => rt.block_on(update(package)),
+        Package::Vendor { package } => rt.block_on(vendor::handle(package, &assets)),
     }
 }
 
@@ -159,6 +188,57 @@ fn regenerate_id(package: &PackageArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn update(package: &PackageArgs) -> anyhow::Result<()> {
+    let package_path = package.package_path()?;
+    let Some(package_path) = &package_path.fs_path else {
+        anyhow::bail!("Cannot update the dependencies of a remote package.");
+    };
+
+    let manifest_path = package_path.join("ambient.toml");
+    if !manifest_path.is_file() {
+        anyhow::bail!("Package does not have a manifest");
+    }
+
+    let mut semantic = Semantic::new(false).await?;
+    let lockfile = semantic
+        .resolve_versions(
+            &RetrievableFile::Url(AbsAssetUrl::from_file_path(manifest_path).0),
+            None,
+        )
+        .await
+        .context("Failed to resolve dependency versions")?;
+
+    for package in &lockfile.packages {
+        tracing::info!(
+            "Resolved {} to {} (deployment {})",
+            package.id,
+            package.version,
+            package.deployment
+        );
+    }
+
+    write_lockfile(package_path, lockfile)
+}
+
+/// Writes `lockfile` to the package at `package_path`, if it differs from what is already there.
+pub(crate) fn write_lockfile(package_path: &Path, lockfile: &Lockfile) -> anyhow::Result<()> {
+    let lockfile_path = package_path.join(Lockfile::FILENAME);
+    let existing = std::fs::read_to_string(&lockfile_path).ok();
+
+    // Avoid creating a lockfile for packages without any remote dependencies
+    if existing.is_none() && lockfile.packages.is_empty() {
+        return Ok(());
+    }
+
+    let contents = lockfile.to_toml_string()?;
+    if existing.as_deref() != Some(contents.as_str()) {
+        std::fs::write(&lockfile_path, contents)
+            .with_context(|| format!("Failed to write lockfile to {lockfile_path:?}"))?;
+    }
+
+    Ok(())
+}
+
 fn update_id_for_manifest(manifest_path: &Path) -> anyhow::Result<()> {
     let mut toml = std::fs::read_to_string(manifest_path)?.parse::<toml_edit::Document>()?;
     // Only regenerate if an ID is already present
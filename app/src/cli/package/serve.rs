@@ -9,7 +9,7 @@ use crate::{
 
 use super::{
     build::{self, BuildDirectories},
-    HostCli, PackageArgs,
+    watch, HostCli, PackageArgs,
 };
 
 #[derive(Parser, Clone, Debug)]
@@ -59,7 +59,7 @@ pub async fn handle_inner(
         .unwrap_or(std::env::current_dir()?);
 
     let server_handle = server::start(
-        assets,
+        assets.clone(),
         host,
         build_root_path,
         main_package_path,
@@ -69,6 +69,15 @@ pub async fn handle_inner(
     )
     .await;
 
+    if host.watch {
+        watch::spawn(
+            package,
+            assets,
+            release_build,
+            server_handle.rebuild_notifier(),
+        )?;
+    }
+
     Ok(server_handle)
 }
 
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use ambient_native_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
+use ambient_package::{BuildMetadata, Lockfile, Manifest};
+use ambient_package_semantic::{
+    PackageVersion, Registries, Registry, RetrievableDeployment, RetrievableFile, Semantic,
+};
+use anyhow::Context;
+
+use super::PackageArgs;
+
+/// Copies the deployments of all resolved remote dependencies into the package's
+/// `vendor` directory, so that the package can be built without network access.
+pub async fn handle(package: &PackageArgs, assets: &AssetCache) -> anyhow::Result<()> {
+    let package_path = package.package_path()?;
+    let Some(package_path) = &package_path.fs_path else {
+        anyhow::bail!("Cannot vendor the dependencies of a remote package.");
+    };
+
+    let manifest_path = package_path.join("ambient.toml");
+    if !manifest_path.is_file() {
+        anyhow::bail!("Package does not have a manifest");
+    }
+
+    let lockfile_path = package_path.join(Lockfile::FILENAME);
+    let existing_lockfile = match std::fs::read_to_string(&lockfile_path) {
+        Ok(contents) => Some(
+            Lockfile::parse(&contents)
+                .with_context(|| format!("Failed to parse lockfile at {lockfile_path:?}"))?,
+        ),
+        Err(_) => None,
+    };
+
+    let vendor_path = package_path.join(Registries::VENDOR_DIRECTORY);
+
+    let mut semantic = Semantic::new(false).await?;
+    let lockfile = semantic
+        .resolve_versions(
+            &RetrievableFile::Url(AbsAssetUrl::from_file_path(manifest_path).0),
+            existing_lockfile.as_ref(),
+        )
+        .await
+        .context("Failed to resolve dependency versions")?
+        .clone();
+
+    for package in &lockfile.packages {
+        let deployment_path = Registry::deployment_path(&vendor_path, &package.deployment);
+        if !deployment_path.is_dir() {
+            tracing::info!(
+                "Vendoring {} {} (deployment {})",
+                package.id,
+                package.version,
+                package.deployment
+            );
+
+            // Download to a temporary directory first, so that an interrupted download does
+            // not leave a partial deployment behind.
+            let partial_path = deployment_path.with_extension("partial");
+            let source = semantic.registries.deployment_manifest(&package.deployment);
+            vendor_deployment(assets, &source, &partial_path)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to vendor deployment {} of {}",
+                        package.deployment, package.id
+                    )
+                })?;
+            std::fs::rename(&partial_path, &deployment_path)?;
+        }
+
+        let versions_path = Registry::package_versions_path(&vendor_path, &package.id);
+        let mut versions: Vec<PackageVersion> = std::fs::read_to_string(&versions_path)
+            .ok()
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default();
+        if !versions.iter().any(|v| v.version == package.version) {
+            versions.push(PackageVersion {
+                version: package.version.clone(),
+                deployment: package.deployment.clone(),
+            });
+            versions.sort_by(|a, b| a.version.cmp(&b.version));
+        }
+
+        std::fs::create_dir_all(versions_path.parent().unwrap())?;
+        std::fs::write(&versions_path, serde_json::to_string_pretty(&versions)?)?;
+    }
+
+    super::write_lockfile(package_path, &lockfile)?;
+
+    tracing::info!(
+        "Vendored {} package(s) into {vendor_path:?}",
+        lockfile.packages.len()
+    );
+
+    Ok(())
+}
+
+/// Downloads all of the files of `deployment` to `output_path`.
+async fn vendor_deployment(
+    assets: &AssetCache,
+    deployment: &RetrievableDeployment,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    let download = |path: PathBuf| async move {
+        let url = RetrievableDeployment {
+            path: path.clone(),
+            ..deployment.clone()
+        }
+        .url()?;
+        let bytes = AbsAssetUrl(url).download_bytes(assets).await?;
+
+        let output_file = output_path.join(&path);
+        std::fs::create_dir_all(output_file.parent().unwrap())?;
+        std::fs::write(&output_file, &bytes)?;
+
+        anyhow::Ok(bytes)
+    };
+
+    if output_path.exists() {
+        std::fs::remove_dir_all(output_path)?;
+    }
+
+    let metadata = BuildMetadata::parse(&String::from_utf8(
+        download(PathBuf::from(BuildMetadata::FILENAME)).await?,
+    )?)?;
+    if !metadata.files.is_empty() {
+        for file in &metadata.files {
+            download(PathBuf::from(file)).await?;
+        }
+        return Ok(());
+    }
+
+    // Deployments built before the build metadata listed their files only have what is needed
+    // to build against them copied: their manifests, and the components and assets they list
+    tracing::warn!(
+        "Deployment {} does not list its files; only its manifests, components and assets will be vendored",
+        deployment.id
+    );
+    let mut manifests = vec![PathBuf::from("ambient.toml")];
+    while let Some(manifest_path) = manifests.pop() {
+        let manifest =
+            Manifest::parse(&String::from_utf8(download(manifest_path.clone()).await?)?)?;
+        let parent = manifest_path.parent().unwrap_or(Path::new(""));
+        manifests.extend(manifest.includes.values().map(|p| parent.join(p)));
+    }
+    let files = metadata
+        .client_component_paths
+        .iter()
+        .chain(metadata.server_component_paths.iter())
+        .map(PathBuf::from)
+        .chain(metadata.asset.iter().map(|a| a.output.clone()));
+    for file in files {
+        download(file).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ambient_native_std::asset_cache::AssetCache;
+use ambient_std::path::path_to_unix_string_lossy;
+use anyhow::Context;
+use notify::{RecursiveMode, Watcher};
+
+use crate::server::RebuildNotifier;
+
+use super::{build, PackageArgs};
+
+/// How long to wait for further changes before rebuilding, so that a burst of writes
+/// (e.g. saving several files at once) only results in a single rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the package's directory and rebuilds it whenever its contents change,
+/// notifying the running server of the outcome.
+pub fn spawn(
+    package: &PackageArgs,
+    assets: AssetCache,
+    release_build: bool,
+    notifier: RebuildNotifier,
+) -> anyhow::Result<()> {
+    let Some(package_path) = package.package_path()?.fs_path else {
+        anyhow::bail!("Can only watch packages on the local filesystem");
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let root = package_path.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if event.kind.is_access() {
+                return;
+            }
+
+            let paths: Vec<_> = event
+                .paths
+                .into_iter()
+                .filter(|v| filter_path(&root, v))
+                .collect();
+
+            if !paths.is_empty() {
+                let _ = tx.send(paths);
+            }
+        }
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(&package_path, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {package_path:?}"))?;
+
+    tracing::info!("Watching {package_path:?} for changes");
+
+    let build_root = package_path.join("build");
+    let assets_root = package_path.join("assets");
+
+    tokio::task::spawn(async move {
+        // The watcher stops when dropped, so keep it alive for as long as we're listening
+        let _watcher = watcher;
+
+        while let Some(mut paths) = rx.recv().await {
+            tokio::time::sleep(DEBOUNCE).await;
+            while let Ok(more) = rx.try_recv() {
+                paths.extend(more);
+            }
+            paths.sort();
+            paths.dedup();
+
+            // If only Rust code changed, there's no need to run the asset pipelines. If only
+            // assets changed, only the pipelines that process them need to run.
+            let wasm_only = paths.iter().all(|p| is_wasm_source(p));
+            let changed_assets = paths
+                .iter()
+                .all(|p| p.starts_with(&assets_root))
+                .then_some(paths.as_slice());
+
+            tracing::info!("Changed paths: {paths:?}");
+            tracing::info!(
+                "Rebuilding{}...",
+                if wasm_only {
+                    " WASM modules"
+                } else if changed_assets.is_some() {
+                    " changed assets"
+                } else {
+                    ""
+                }
+            );
+
+            let before = hash_outputs(build_root.clone()).await;
+            let result = build::build(
+                &assets,
+                package_path.clone(),
+                false,
+                false,
+                release_build,
+                wasm_only,
+                changed_assets,
+                HashSet::new(),
+                |_| async { Ok(()) },
+                |_, _, _| async { Ok(()) },
+            )
+            .await;
+
+            match result {
+                Ok(_) => {
+                    let rebuilt = changed_outputs(&before, &hash_outputs(build_root.clone()).await);
+                    tracing::info!(
+                        "Rebuilt package; reloading {} changed outputs",
+                        rebuilt.len()
+                    );
+                    notifier.notify(None, rebuilt);
+                }
+                Err(err) => {
+                    tracing::error!("Failed to rebuild package: {err:?}");
+                    notifier.notify(Some(format!("{err:#}")), vec![]);
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Hashes the contents of every file in the build directory, keyed by their path relative to it.
+///
+/// This reads the whole build directory, so it runs on the blocking thread pool.
+async fn hash_outputs(build_root: PathBuf) -> HashMap<String, u64> {
+    tokio::task::spawn_blocking(move || {
+        walkdir::WalkDir::new(&build_root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let contents = std::fs::read(e.path()).ok()?;
+                let key = e.path().strip_prefix(&build_root).ok()?;
+
+                let mut hasher = DefaultHasher::new();
+                contents.hash(&mut hasher);
+                Some((path_to_unix_string_lossy(key), hasher.finish()))
+            })
+            .collect()
+    })
+    .await
+    .expect("hashing the build outputs panicked")
+}
+
+/// The outputs that were added or modified between two [hash_outputs] calls.
+fn changed_outputs(before: &HashMap<String, u64>, after: &HashMap<String, u64>) -> Vec<String> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, hash)| before.get(*path) != Some(hash))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    changed.sort();
+    changed
+}
+
+fn filter_path(root: &Path, path: &Path) -> bool {
+    let path = path.strip_prefix(root).unwrap_or(path);
+    if path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| matches!(name, "ambient.lock" | "Cargo.lock"))
+    {
+        return false;
+    }
+
+    path.components().all(|seg| {
+        let seg: &Path = seg.as_ref();
+        !matches!(
+            seg.to_str(),
+            Some("build" | "target" | ".git" | "vendor" | "data" | "storage")
+        )
+    })
+}
+
+fn is_wasm_source(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "rs")
+        || path.file_name().is_some_and(|name| name == "Cargo.toml")
+}
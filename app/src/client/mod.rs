@@ -20,21 +20,26 @@ use ambient_native_std::{
 };
 use ambient_network::{
     client::{client_network_stats, GameClientRenderTarget},
-    hooks::use_remote_resource,
+    hooks::{use_remote_resource, use_remote_synced_resource},
     native::client::{ClientView, ResolvedAddr},
 };
 use ambient_settings::SettingsKey;
 use ambient_sys::time::Instant;
-use ambient_ui_native::{Dock, WindowSized};
-use glam::uvec2;
+use ambient_ui_native::{
+    docking, layout::Docking, padding, Borders, Dock, StylesExt, Text, UIExt, WindowSized, STREET,
+};
+use glam::{uvec2, vec4};
 
 use crate::{
     cli::{ClientCli, GoldenImageCommand},
     shared::{self, certs::CERT},
 };
 
+mod replay;
 mod wasm;
 
+use replay::InputReplay;
+
 /// Construct an app and enter the main client view
 pub fn run(
     rt: &tokio::runtime::Runtime,
@@ -69,7 +74,14 @@ pub fn run(
         }
     };
 
-    let headless = if args.headless {
+    let input_replay = match (&args.record_input, &args.replay_input) {
+        (_, Some(path)) => Some(InputReplay::replay(path)?),
+        (Some(path), None) => Some(InputReplay::record(path)?),
+        (None, None) => None,
+    };
+
+    // Replays use the recorded input instead of live input, so they never need a window
+    let headless = if args.headless || args.replay_input.is_some() {
         Some(uvec2(600, 600))
     } else {
         None
@@ -132,6 +144,7 @@ pub fn run(
         golden_image_output_dir,
         cert,
         mixer,
+        input_replay,
     }
     .el()
     .spawn_interactive(&mut app.world);
@@ -176,6 +189,22 @@ fn TitleUpdater(hooks: &mut Hooks) -> Element {
     Element::new()
 }
 
+/// Shows the error from the last failed watch-mode rebuild, if any
+#[element_component]
+fn RebuildErrorOverlay(hooks: &mut Hooks) -> Element {
+    let error = use_remote_synced_resource(hooks, ambient_wasm::shared::rebuild_error())
+        .and_then(|(error, _)| error);
+
+    let Some(error) = error else {
+        return Element::new();
+    };
+
+    Dock::el([Text::el("Build failed").error_text_style(), Text::el(error)])
+        .with(docking(), Docking::Top)
+        .with_background(vec4(0., 0., 0., 0.8))
+        .with(padding(), Borders::even(STREET).into())
+}
+
 #[element_component]
 fn MainApp(
     hooks: &mut Hooks,
@@ -187,12 +216,18 @@ fn MainApp(
     golden_image_cmd: Option<GoldenImageCommand>,
     cert: Option<Vec<u8>>,
     mixer: Option<AudioMixer>,
+    input_replay: Option<InputReplay>,
 ) -> Element {
     let (loaded, set_loaded) = use_state(hooks, false);
+    let replaying = input_replay.as_ref().is_some_and(|r| r.is_replaying());
 
     Group::el([
         UICamera.el(),
-        ambient_client_shared::player::PlayerRawInputHandler.el(),
+        if replaying {
+            Element::new()
+        } else {
+            ambient_client_shared::player::PlayerRawInputHandler.el()
+        },
         WindowSized::el([ClientView {
             server_addr,
             user_id,
@@ -216,7 +251,7 @@ fn MainApp(
                     tracing::info!("Disconnecting client");
                 }))
             }),
-            systems_and_resources: cb(|| {
+            systems_and_resources: cb(move || {
                 let mut resources = Entity::new();
 
                 let bistream_handlers = HashMap::new();
@@ -234,12 +269,26 @@ fn MainApp(
                 let dgram_handlers = HashMap::new();
                 resources.set(ambient_network::client::datagram_handlers(), dgram_handlers);
 
-                (systems(), resources)
+                // Recordings capture the modules' random seed and the messages they receive,
+                // so that replays can use the same ones
+                if let Some(input_replay) = &input_replay {
+                    resources.set(
+                        ambient_wasm::shared::module_rng_seed(),
+                        input_replay.rng_seed(),
+                    );
+                    resources.set(
+                        ambient_wasm::shared::server_message_hook(),
+                        input_replay.server_message_hook(),
+                    );
+                }
+
+                (systems(input_replay.clone()), resources)
             }),
             cert,
             create_rpc_registry: cb(shared::create_server_rpc_registry),
             inner: Dock::el(vec![
                 TitleUpdater.el(),
+                RebuildErrorOverlay.el(),
                 if let Some(golden_image_cmd) = golden_image_cmd.filter(|_| loaded) {
                     GoldenImageTest::el(golden_image_output_dir, golden_image_cmd)
                 } else {
@@ -399,10 +448,18 @@ fn GoldenImageTest(
     Element::new()
 }
 
-fn systems() -> SystemGroup {
+fn systems(input_replay: Option<InputReplay>) -> SystemGroup {
     SystemGroup::new(
         "client",
         vec![
+            // Runs first, so that every other system sees the replayed input
+            Box::new(SystemGroup::new(
+                "client/input_replay",
+                input_replay
+                    .iter()
+                    .map(|r| Box::new(r.system()) as _)
+                    .collect(),
+            )),
             Box::new(ambient_prefab::systems()),
             Box::new(ambient_decals::client_systems()),
             Box::new(ambient_primitives::systems()),
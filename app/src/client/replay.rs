@@ -0,0 +1,603 @@
+//! Recording and replaying of the local player's session.
+//!
+//! A recording is a JSON Lines file: a [RecordingHeader], followed by one [RecordedFrame] per
+//! frame. Each frame stores the input the player had during that frame and the messages that the
+//! client's modules received from the server since the previous frame, as well as a [Checkpoint]
+//! of the game state at the start of it. The header stores the seed of the modules' random number
+//! generators and the build of the packages that were running.
+//!
+//! When replaying, the recorded input and messages are used instead of the live ones, and the
+//! checkpoints are compared with the replayed game to find where it diverges from the recording.
+//!
+//! Only the client is replayed. The server runs live, with its own seeds and timing, so the
+//! entities it spawns (including the local player) are not part of the checkpoints.
+use std::{
+    collections::hash_map::RandomState,
+    fs::File,
+    hash::{BuildHasher, Hasher},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ambient_core::{
+    player::{get_by_user_id, local_user_id},
+    transform::{rotation, translation},
+    window::{window_ctl, ExitStatus, WindowCtl},
+};
+use ambient_ecs::{
+    generated::{network::components::is_remote_entity, package::components as package},
+    query, EntityId, FnSystem, World,
+};
+use ambient_input::{player_raw_input, PlayerRawInput};
+use ambient_wasm::shared::{
+    bytecode_from_url, is_module, is_module_on_server,
+    message::{self, Target, WorldEventSource},
+    module_bytecode, module_name, ServerMessageHook,
+};
+use anyhow::Context;
+use glam::{Quat, Vec3};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Digest;
+
+/// The version of the recording format. Bumped whenever the format changes incompatibly.
+const FORMAT_VERSION: u32 = 3;
+
+/// How far an entity can be from its recorded position before the replay is considered to have
+/// diverged.
+const POSITION_TOLERANCE: f32 = 0.1;
+
+/// How far, in radians, an entity can be rotated from its recorded rotation before the replay is
+/// considered to have diverged.
+const ROTATION_TOLERANCE: f32 = 0.05;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordingHeader {
+    format_version: u32,
+    /// The seed of the random number generators of the client's modules.
+    rng_seed: u64,
+    build: PackageBuild,
+}
+
+/// Identifies the build of the packages that were running, so that a recording is only replayed
+/// against the same code.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PackageBuild {
+    /// The ID and version of each loaded package.
+    packages: Vec<(String, String)>,
+    /// The name and SHA-256 hash of the bytecode of each client module.
+    modules: Vec<(String, String)>,
+}
+impl PackageBuild {
+    /// Returns `None` until the bytecode of every client module has been loaded.
+    fn loaded(world: &World) -> Option<Self> {
+        let mut packages = query((package::id(), package::version()))
+            .incl(package::is_package())
+            .iter(world, None)
+            .map(|(_, (id, version))| (id.clone(), version.clone()))
+            .collect::<Vec<_>>();
+        packages.sort();
+
+        let mut modules = query(bytecode_from_url())
+            .incl(is_module())
+            .excl(is_module_on_server())
+            .iter(world, None)
+            .map(|(id, url)| {
+                let bytecode = world.get_ref(id, module_bytecode()).ok()?;
+                let name = world
+                    .get_cloned(id, module_name())
+                    .unwrap_or_else(|_| url.clone());
+                let hash = data_encoding::HEXLOWER.encode(&sha2::Sha256::digest(&bytecode.0));
+                Some((name, hash))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        modules.sort();
+
+        Some(Self { packages, modules })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RecordedFrame {
+    frame: u64,
+    input: PlayerRawInput,
+    /// The messages received from the server since the previous frame.
+    messages: Vec<RecordedMessage>,
+    checkpoint: Checkpoint,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordedMessage {
+    /// The ID of the package the message was sent to. Entity IDs differ between sessions,
+    /// so the package is looked up again when replaying.
+    package: String,
+    name: String,
+    data: Vec<u8>,
+}
+
+/// A summary of the game state that is compared between the recording and the replay.
+///
+/// Only state that follows from the recorded input, messages and seeds is included, which is the
+/// transforms of the entities that the client spawned itself. Entities spawned by the server
+/// depend on the live server, so they are not compared.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The position and rotation of each local entity, sorted by position.
+    ///
+    /// Entity IDs differ between sessions, so entities are matched by their transforms instead.
+    transforms: Vec<(Vec3, Quat)>,
+}
+impl Checkpoint {
+    fn capture(world: &World) -> Self {
+        let mut transforms = query((translation(), rotation()))
+            .excl(is_remote_entity())
+            .iter(world, None)
+            .map(|(_, (translation, rotation))| (*translation, *rotation))
+            .collect::<Vec<_>>();
+        transforms.sort_by(|(a, _), (b, _)| {
+            (a.x.total_cmp(&b.x))
+                .then(a.y.total_cmp(&b.y))
+                .then(a.z.total_cmp(&b.z))
+        });
+        Self { transforms }
+    }
+
+    /// Returns true unless every transform in `self` matches a distinct transform in `other`
+    /// within the tolerances, and both have the same number of transforms.
+    fn diverges_from(&self, other: &Checkpoint) -> bool {
+        if self.transforms.len() != other.transforms.len() {
+            return true;
+        }
+
+        let mut matched = vec![false; other.transforms.len()];
+        self.transforms.iter().any(|(position, rotation)| {
+            let found = other
+                .transforms
+                .iter()
+                .enumerate()
+                .find(|(index, (other_position, other_rotation))| {
+                    !matched[*index]
+                        && position.distance(*other_position) <= POSITION_TOLERANCE
+                        && rotation.angle_between(*other_rotation) <= ROTATION_TOLERANCE
+                })
+                .map(|(index, _)| index);
+            match found {
+                Some(index) => {
+                    matched[index] = true;
+                    false
+                }
+                None => true,
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record {
+        writer: BufWriter<File>,
+        rng_seed: u64,
+        frame: u64,
+        messages: Vec<RecordedMessage>,
+    },
+    Replay {
+        path: PathBuf,
+        header: RecordingHeader,
+        frames: Vec<RecordedFrame>,
+        frame: usize,
+        diverged_frames: usize,
+    },
+}
+
+/// Records the local player's session to a file, or replays it from one.
+#[derive(Clone, Debug)]
+pub struct InputReplay(Arc<Mutex<Mode>>);
+impl InputReplay {
+    /// Starts recording to `path`, overwriting it if it exists.
+    pub fn record(path: &Path) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create input recording {path:?}"))?;
+        Ok(Self(Arc::new(Mutex::new(Mode::Record {
+            writer: BufWriter::new(file),
+            // A randomly-keyed hasher is a convenient source of a random seed
+            rng_seed: RandomState::new().build_hasher().finish(),
+            frame: 0,
+            messages: vec![],
+        }))))
+    }
+
+    /// Loads the recording at `path` for replaying.
+    pub fn replay(path: &Path) -> anyhow::Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open input recording {path:?}"))?;
+        let (header, frames) = read_recording(BufReader::new(file))?;
+
+        Ok(Self(Arc::new(Mutex::new(Mode::Replay {
+            path: path.to_path_buf(),
+            header,
+            frames,
+            frame: 0,
+            diverged_frames: 0,
+        }))))
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(*self.0.lock(), Mode::Replay { .. })
+    }
+
+    /// The seed to use for the random number generators of the client's modules.
+    pub fn rng_seed(&self) -> u64 {
+        match &*self.0.lock() {
+            Mode::Record { rng_seed, .. } => *rng_seed,
+            Mode::Replay { header, .. } => header.rng_seed,
+        }
+    }
+
+    /// Returns a hook that records the messages from the server, or that drops them while
+    /// replaying, as the recorded messages are delivered instead.
+    pub fn server_message_hook(&self) -> ServerMessageHook {
+        let mode = self.0.clone();
+        Arc::new(
+            move |world: &mut World, package_id: EntityId, name: &str, data: &[u8]| {
+                match &mut *mode.lock() {
+                    Mode::Record {
+                        frame, messages, ..
+                    } => {
+                        // Messages that arrive before the recording starts are not part of it
+                        if *frame > 0 {
+                            match world.get_cloned(package_id, package::id()) {
+                        Ok(package) => messages.push(RecordedMessage {
+                            package,
+                            name: name.to_string(),
+                            data: data.to_vec(),
+                        }),
+                        Err(_) => tracing::warn!(
+                            "Not recording message {name:?}, as its target {package_id} is not a package"
+                        ),
+                    }
+                        }
+                        true
+                    }
+                    Mode::Replay { frame, .. } => *frame == 0,
+                }
+            },
+        )
+    }
+
+    /// Returns a system that records or replays the session. It must run at the start of the
+    /// frame, before any system that reads the player's input.
+    pub fn system(&self) -> FnSystem {
+        let mode = self.0.clone();
+        FnSystem::new(move |world, _| {
+            // Nothing is recorded or replayed until the local player has spawned and the client's
+            // modules have loaded, so that both start from the same point of the game
+            if world
+                .resource_opt(local_user_id())
+                .and_then(|user_id| get_by_user_id(world, user_id))
+                .is_none()
+            {
+                return;
+            }
+
+            let mut mode = mode.lock();
+            let started = match &*mode {
+                Mode::Record { frame, .. } => *frame > 0,
+                Mode::Replay { frame, .. } => *frame > 0,
+            };
+            let build = if started {
+                None
+            } else {
+                let Some(build) = PackageBuild::loaded(world) else {
+                    return;
+                };
+                Some(build)
+            };
+
+            let checkpoint = Checkpoint::capture(world);
+
+            match &mut *mode {
+                Mode::Record {
+                    writer,
+                    rng_seed,
+                    frame,
+                    messages,
+                } => {
+                    let header = build.map(|build| RecordingHeader {
+                        format_version: FORMAT_VERSION,
+                        rng_seed: *rng_seed,
+                        build,
+                    });
+                    let recorded = RecordedFrame {
+                        frame: *frame,
+                        input: world.resource(player_raw_input()).clone(),
+                        messages: std::mem::take(messages),
+                        checkpoint,
+                    };
+                    if let Err(err) = record_frame(writer, header.as_ref(), &recorded) {
+                        tracing::error!("Failed to record input: {err:?}");
+                    }
+                    *frame += 1;
+                }
+                Mode::Replay {
+                    path,
+                    header,
+                    frames,
+                    frame,
+                    diverged_frames,
+                } => {
+                    if let Some(build) = build {
+                        if build != header.build {
+                            tracing::error!(
+                                "Refusing to replay {path:?}: it was recorded with {:?}, but {build:?} is running",
+                                header.build
+                            );
+                            exit(world, ExitStatus::FAILURE);
+                            // Skip the rest of the replay
+                            *frame = frames.len() + 1;
+                            return;
+                        }
+                    }
+
+                    let Some(recorded) = frames.get(*frame) else {
+                        if *frame == frames.len() {
+                            finish_replay(world, path, frames.len(), *diverged_frames);
+                            *frame += 1;
+                        }
+                        return;
+                    };
+
+                    if checkpoint.diverges_from(&recorded.checkpoint) {
+                        if *diverged_frames == 0 {
+                            tracing::warn!(
+                                "Replay diverged at frame {}: expected {:?}, got {checkpoint:?}",
+                                recorded.frame,
+                                recorded.checkpoint
+                            );
+                        }
+                        *diverged_frames += 1;
+                    }
+
+                    *world.resource_mut(player_raw_input()) = recorded.input.clone();
+                    for recorded_message in &recorded.messages {
+                        deliver_message(world, recorded_message);
+                    }
+                    *frame += 1;
+                }
+            }
+        })
+    }
+}
+
+fn record_frame(
+    writer: &mut BufWriter<File>,
+    header: Option<&RecordingHeader>,
+    recorded: &RecordedFrame,
+) -> anyhow::Result<()> {
+    if let Some(header) = header {
+        write_line(writer, header)?;
+    }
+    write_line(writer, recorded)?;
+    // Flush every frame, so that the recording is complete even if the client crashes
+    writer.flush()?;
+    Ok(())
+}
+
+fn deliver_message(world: &mut World, recorded: &RecordedMessage) {
+    let package_id = query(package::id())
+        .incl(package::is_package())
+        .iter(world, None)
+        .find(|(_, id)| **id == recorded.package)
+        .map(|(id, _)| id);
+    let Some(package_id) = package_id else {
+        tracing::warn!(
+            "Not replaying message {:?}, as package {} is not loaded",
+            recorded.name,
+            recorded.package
+        );
+        return;
+    };
+
+    message::send(
+        world,
+        Target::PackageOrModule(package_id),
+        WorldEventSource::Server,
+        recorded.name.clone(),
+        recorded.data.clone(),
+    );
+}
+
+fn finish_replay(world: &World, path: &Path, frame_count: usize, diverged_frames: usize) {
+    let status = if diverged_frames == 0 {
+        tracing::info!(
+            "Replayed {frame_count} frames from {path:?} without diverging, exiting with 0"
+        );
+        ExitStatus::SUCCESS
+    } else {
+        tracing::error!(
+            "Replay of {path:?} diverged in {diverged_frames} of {frame_count} frames, exiting with 1"
+        );
+        ExitStatus::FAILURE
+    };
+    exit(world, status);
+}
+
+fn exit(world: &World, status: ExitStatus) {
+    world
+        .resource(window_ctl())
+        .send(WindowCtl::ExitProcess(status))
+        .ok();
+}
+
+fn write_line(writer: &mut impl Write, value: &impl Serialize) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut *writer, value)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn read_recording(reader: impl BufRead) -> anyhow::Result<(RecordingHeader, Vec<RecordedFrame>)> {
+    fn parse<T: DeserializeOwned>(line: std::io::Result<String>) -> anyhow::Result<T> {
+        Ok(serde_json::from_str(
+            &line.context("Failed to read input recording")?,
+        )?)
+    }
+
+    let mut lines = reader.lines();
+    let header: RecordingHeader = parse(lines.next().context("Input recording is empty")?)
+        .context("Failed to parse input recording header")?;
+    anyhow::ensure!(
+        header.format_version == FORMAT_VERSION,
+        "Input recording has format version {}, but only version {FORMAT_VERSION} is supported",
+        header.format_version
+    );
+
+    let frames = lines
+        .map(|line| parse(line).context("Failed to parse input recording frame"))
+        .collect::<anyhow::Result<Vec<RecordedFrame>>>()?;
+
+    Ok((header, frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(position: Vec3, rotation: Quat) -> Checkpoint {
+        Checkpoint {
+            transforms: vec![(position, rotation)],
+        }
+    }
+
+    #[test]
+    fn checkpoints_within_tolerance_do_not_diverge() {
+        let a = checkpoint(Vec3::new(1.0, 2.0, 3.0), Quat::IDENTITY);
+        assert!(!a.diverges_from(&a));
+
+        let b = checkpoint(
+            Vec3::new(1.0, 2.0, 3.0 + POSITION_TOLERANCE / 2.0),
+            Quat::from_rotation_z(ROTATION_TOLERANCE / 2.0),
+        );
+        assert!(!a.diverges_from(&b));
+        assert!(!b.diverges_from(&a));
+
+        let none = Checkpoint { transforms: vec![] };
+        assert!(!none.diverges_from(&none));
+    }
+
+    #[test]
+    fn checkpoints_match_entities_regardless_of_order() {
+        let a = Checkpoint {
+            transforms: vec![
+                (Vec3::ZERO, Quat::IDENTITY),
+                (Vec3::X, Quat::from_rotation_z(1.0)),
+                (Vec3::X, Quat::IDENTITY),
+            ],
+        };
+        let b = Checkpoint {
+            transforms: vec![
+                (Vec3::X, Quat::IDENTITY),
+                (Vec3::ZERO, Quat::IDENTITY),
+                (Vec3::X, Quat::from_rotation_z(1.0)),
+            ],
+        };
+        assert!(!a.diverges_from(&b));
+        assert!(!b.diverges_from(&a));
+
+        // Every entity must be matched by a different one
+        let duplicated = Checkpoint {
+            transforms: vec![
+                (Vec3::ZERO, Quat::IDENTITY),
+                (Vec3::ZERO, Quat::IDENTITY),
+                (Vec3::X, Quat::IDENTITY),
+            ],
+        };
+        assert!(a.diverges_from(&duplicated));
+        assert!(duplicated.diverges_from(&a));
+    }
+
+    #[test]
+    fn checkpoints_outside_tolerance_diverge() {
+        let a = checkpoint(Vec3::ZERO, Quat::IDENTITY);
+
+        let moved = checkpoint(Vec3::X * POSITION_TOLERANCE * 2.0, Quat::IDENTITY);
+        assert!(a.diverges_from(&moved));
+
+        let rotated = checkpoint(Vec3::ZERO, Quat::from_rotation_z(ROTATION_TOLERANCE * 2.0));
+        assert!(a.diverges_from(&rotated));
+
+        let missing = Checkpoint { transforms: vec![] };
+        assert!(a.diverges_from(&missing));
+        assert!(missing.diverges_from(&a));
+
+        let moved_second = Checkpoint {
+            transforms: vec![(Vec3::ZERO, Quat::IDENTITY), (Vec3::Y, Quat::IDENTITY)],
+        };
+        let second = Checkpoint {
+            transforms: vec![
+                (Vec3::ZERO, Quat::IDENTITY),
+                (Vec3::Y * (1.0 + POSITION_TOLERANCE * 2.0), Quat::IDENTITY),
+            ],
+        };
+        assert!(moved_second.diverges_from(&second));
+    }
+
+    #[test]
+    fn recording_round_trips() {
+        let header = RecordingHeader {
+            format_version: FORMAT_VERSION,
+            rng_seed: 1234,
+            build: PackageBuild {
+                packages: vec![("abc".to_string(), "0.1.0".to_string())],
+                modules: vec![("client".to_string(), "deadbeef".to_string())],
+            },
+        };
+        let input = PlayerRawInput {
+            mouse_position: glam::vec2(10.0, 20.0),
+            ..Default::default()
+        };
+        let frames = (0..3)
+            .map(|frame| RecordedFrame {
+                frame,
+                input: input.clone(),
+                messages: vec![RecordedMessage {
+                    package: "abc".to_string(),
+                    name: format!("Message{frame}"),
+                    data: vec![frame as u8, 1, 2],
+                }],
+                checkpoint: checkpoint(Vec3::splat(frame as f32), Quat::IDENTITY),
+            })
+            .collect::<Vec<_>>();
+
+        let mut recording = vec![];
+        write_line(&mut recording, &header).unwrap();
+        for frame in &frames {
+            write_line(&mut recording, frame).unwrap();
+        }
+
+        let (read_header, read_frames) = read_recording(recording.as_slice()).unwrap();
+        assert_eq!(read_header, header);
+        assert_eq!(read_frames.len(), frames.len());
+        for (read, written) in read_frames.iter().zip(&frames) {
+            assert_eq!(read.frame, written.frame);
+            assert_eq!(read.input.mouse_position, written.input.mouse_position);
+            assert_eq!(read.messages, written.messages);
+            assert_eq!(read.checkpoint, written.checkpoint);
+        }
+    }
+
+    #[test]
+    fn recordings_with_other_format_versions_are_rejected() {
+        let header = RecordingHeader {
+            format_version: FORMAT_VERSION + 1,
+            rng_seed: 0,
+            build: PackageBuild {
+                packages: vec![],
+                modules: vec![],
+            },
+        };
+        let mut recording = vec![];
+        write_line(&mut recording, &header).unwrap();
+
+        assert!(read_recording(recording.as_slice()).is_err());
+    }
+}
@@ -12,11 +12,13 @@ use ambient_ecs::{
     dont_store, generated::network::components::no_sync, world_events, ComponentDesc, Entity,
     Networked, SystemGroup, World, WorldContext, WorldEventsSystem, WorldStreamCompEvent,
 };
+use ambient_input::{player_raw_input, PlayerRawInput};
 use ambient_native_std::{
     ambient_version,
     asset_cache::{AssetCache, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, ContentBaseUrlKey, ServerBaseUrlKey},
     cb,
+    download_asset::evict_changed_assets,
 };
 use ambient_network::{
     is_persistent_resources, is_synced_resources,
@@ -24,7 +26,8 @@ use ambient_network::{
         client::ResolvedAddr,
         server::{Crypto, GameServer},
     },
-    server::{ForkingEvent, ProxySettings, SharedServerState, ShutdownEvent},
+    server::{DatagramHandlers, ForkingEvent, ProxySettings, SharedServerState, ShutdownEvent},
+    PLAYER_INPUT_DATAGRAM_ID,
 };
 use ambient_sys::task::RuntimeHandle;
 use anyhow::Context;
@@ -36,6 +39,7 @@ use axum::{
     Router,
 };
 use axum_server::tls_rustls::RustlsConfig;
+use bytes::Bytes;
 use parking_lot::Mutex;
 use rustls::{Certificate, PrivateKey, ServerConfig};
 use tower_http::{cors::CorsLayer, services::ServeDir};
@@ -47,6 +51,7 @@ pub mod wasm;
 pub struct ServerHandle {
     addr: SocketAddr,
     join_handle: tokio::task::JoinHandle<()>,
+    rebuild_notifier: RebuildNotifier,
 }
 impl ServerHandle {
     pub async fn join(self) -> Result<(), tokio::task::JoinError> {
@@ -56,6 +61,42 @@ impl ServerHandle {
     pub fn resolve_as_localhost(&self) -> ResolvedAddr {
         ResolvedAddr::localhost_with_port(self.addr.port())
     }
+
+    pub fn rebuild_notifier(&self) -> RebuildNotifier {
+        self.rebuild_notifier.clone()
+    }
+}
+
+/// Lets the running server know that the packages it is serving have been rebuilt.
+#[derive(Clone)]
+pub struct RebuildNotifier {
+    server_state: Arc<Mutex<Option<SharedServerState>>>,
+    assets: AssetCache,
+}
+impl RebuildNotifier {
+    /// Reloads the changed modules and the prefabs in every world instance if the rebuild
+    /// succeeded, and reports the outcome to the modules and to the clients.
+    ///
+    /// `rebuilt_assets` are the build outputs that changed, relative to the build root.
+    pub fn notify(&self, error: Option<String>, rebuilt_assets: Vec<String>) {
+        if error.is_none() {
+            // Drop the rebuilt assets we've already loaded, so that they're fetched again
+            evict_changed_assets(&self.assets, &rebuilt_assets);
+        }
+
+        let Some(state) = self.server_state.lock().clone() else {
+            tracing::warn!("Rebuilt packages before the server was running; ignoring");
+            return;
+        };
+
+        let mut state = state.lock();
+        for instance in state.instances.values_mut() {
+            wasm::on_rebuild(&mut instance.world, error.clone(), rebuilt_assets.clone());
+            if error.is_none() {
+                ambient_prefab::reload_prefabs(&mut instance.world);
+            }
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -117,6 +158,7 @@ pub async fn start(
 
     tracing::info!("Created server, running at {addr}");
     let http_interface_port = host_cli.http_interface_port.unwrap_or(HTTP_INTERFACE_PORT);
+    let storage_quota = host_cli.storage_quota;
     let use_https = host_cli.use_https.then(|| crypto.clone());
 
     let public_host = match (&host_cli.public_host, addr.ip()) {
@@ -162,6 +204,11 @@ pub async fn start(
         );
     }
 
+    let rebuild_notifier = RebuildNotifier {
+        server_state: server_state_holder.clone(),
+        assets: assets.clone(),
+    };
+
     let join_handle = tokio::task::spawn(async move {
         let mut server_world = World::new_with_config("server", WorldContext::Server, true);
         server_world.init_shape_change_tracking();
@@ -197,9 +244,15 @@ pub async fn start(
             .with(is_persistent_resources(), ())
             .spawn(&mut server_world);
 
-        wasm::initialize(&mut server_world, &assets, working_directory.join("data"))
-            .await
-            .unwrap();
+        wasm::initialize(
+            &mut server_world,
+            &assets,
+            working_directory.join("data"),
+            working_directory.join("storage"),
+            storage_quota,
+        )
+        .await
+        .unwrap();
 
         ambient_package_semantic_native::initialize(
             &mut server_world,
@@ -222,7 +275,11 @@ pub async fn start(
             .await;
     });
 
-    ServerHandle { addr, join_handle }
+    ServerHandle {
+        addr,
+        join_handle,
+        rebuild_notifier,
+    }
 }
 
 fn systems(_world: &mut World) -> SystemGroup {
@@ -234,6 +291,8 @@ fn systems(_world: &mut World) -> SystemGroup {
             Box::new(ambient_core::async_ecs::async_ecs_systems()),
             Box::new(ambient_prefab::systems()),
             Box::new(ambient_core::hierarchy::systems()),
+            Box::new(ambient_core::relationship::relationship_systems()),
+            Box::new(ambient_core::index::index_systems()),
             // Happens after the physics step
             ambient_physics::fetch_simulation_system(),
             Box::new(ambient_animation::animation_systems()),
@@ -244,6 +303,7 @@ fn systems(_world: &mut World) -> SystemGroup {
             Box::new(WorldEventsSystem),
             Box::new(ambient_core::camera::camera_systems()),
             Box::new(ambient_physics::server_systems()),
+            Box::new(ambient_core::spatial::spatial_systems()),
             Box::new(ambient_package_semantic_native::server_systems()),
             Box::new(wasm::systems()),
         ],
@@ -280,6 +340,9 @@ fn create_resources(assets: AssetCache) -> Entity {
         .with(world_events(), Default::default());
     ambient_physics::create_server_resources(&assets, &mut server_resources);
     server_resources.merge(ambient_core::async_ecs::async_ecs_resources());
+    server_resources.merge(ambient_core::spatial::spatial_resources());
+    server_resources.merge(ambient_core::relationship::relationship_resources());
+    server_resources.merge(ambient_core::index::index_resources());
     server_resources.set(ambient_core::runtime(), RuntimeHandle::current());
 
     server_resources.merge(ambient_core::time_resources_start(FIXED_SERVER_TICK_TIME));
@@ -300,12 +363,38 @@ fn create_resources(assets: AssetCache) -> Entity {
         unistream_handlers,
     );
 
-    let dgram_handlers = HashMap::new();
+    let mut dgram_handlers: DatagramHandlers = HashMap::new();
+    dgram_handlers.insert(
+        PLAYER_INPUT_DATAGRAM_ID,
+        ("server_player_input", Arc::new(on_player_input)),
+    );
     server_resources.set(ambient_network::server::datagram_handlers(), dgram_handlers);
 
     server_resources
 }
 
+/// Stores the most recent raw input sent by a player's client on their entity.
+#[allow(clippy::ptr_arg)]
+fn on_player_input(state: SharedServerState, _assets: AssetCache, user_id: &str, bytes: Bytes) {
+    let input: PlayerRawInput = match bincode::deserialize(&bytes) {
+        Ok(input) => input,
+        Err(err) => {
+            tracing::warn!("Failed to deserialize input from {user_id}: {err}");
+            return;
+        }
+    };
+
+    let mut state = state.lock();
+    let Some(world) = state.get_player_world_mut(user_id) else {
+        return;
+    };
+    if let Some(player_id) = ambient_core::player::get_by_user_id(world, user_id) {
+        world
+            .add_component(player_id, player_raw_input(), input)
+            .ok();
+    }
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ServerStatus {
@@ -3,7 +3,7 @@ use std::{fmt::Display, path::PathBuf, sync::Arc};
 use ambient_ecs::{Entity, EntityId, SystemGroup, World};
 use ambient_native_std::asset_cache::AssetCache;
 use ambient_package_semantic_native::{WasmSpawnRequest, WasmSpawnResponse};
-pub use ambient_wasm::server::{on_forking_systems, on_shutdown_systems};
+pub use ambient_wasm::server::{on_forking_systems, on_rebuild, on_shutdown_systems};
 use ambient_wasm::shared::{
     bytecode_from_url, is_module, is_module_on_server, module_enabled, module_name, package_ref,
     MessageType,
@@ -17,6 +17,8 @@ pub async fn initialize(
     world: &mut World,
     assets: &AssetCache,
     data_path: PathBuf,
+    storage_path: PathBuf,
+    storage_quota: Option<u64>,
 ) -> anyhow::Result<()> {
     let messenger = Arc::new(
         |world: &World, id: EntityId, ty: MessageType, message: &str| {
@@ -32,7 +34,15 @@ pub async fn initialize(
     );
 
     let hosted = std::env::var("AMBIENT_HOSTED").is_ok();
-    ambient_wasm::server::initialize(world, assets, hosted, data_path, messenger)?;
+    ambient_wasm::server::initialize(
+        world,
+        assets,
+        hosted,
+        data_path,
+        storage_path,
+        storage_quota.unwrap_or(ambient_wasm::server::DEFAULT_STORAGE_QUOTA),
+        messenger,
+    )?;
 
     Ok(())
 }
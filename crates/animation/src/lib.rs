@@ -1,7 +1,10 @@
 use ambient_ecs::SystemGroup;
 use player::animation_player_systems;
+pub use player::sample_joint_transform;
+use ragdoll::ragdoll_systems;
 
 mod player;
+mod ragdoll;
 mod resources;
 mod retargeting;
 
@@ -13,7 +16,13 @@ pub fn init_all_components() {
 }
 
 pub fn animation_systems() -> SystemGroup {
-    animation_player_systems()
+    SystemGroup::new(
+        "animation_systems",
+        vec![
+            Box::new(animation_player_systems()),
+            Box::new(ragdoll_systems()),
+        ],
+    )
 }
 
 #[test]
@@ -5,7 +5,12 @@ use std::{
     time::Duration,
 };
 
-use ambient_core::{asset_cache, async_ecs::async_run, epoch_time, runtime};
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    epoch_time, runtime,
+    transform::{rotation, scale, translation},
+};
 use ambient_ecs::{
     components,
     generated::animation::components::{
@@ -24,7 +29,7 @@ use ambient_native_std::{
     download_asset::AssetResult,
 };
 use anyhow::Context;
-use glam::{Quat, Vec3};
+use glam::{Mat4, Quat, Vec3};
 use itertools::Itertools;
 
 use crate::{
@@ -328,6 +333,52 @@ pub fn animation_player_systems() -> SystemGroup {
     )
 }
 
+/// The transform of the joint `bind_id` relative to its parent, as last sampled by the animation
+/// player `player`. Anything that the animation doesn't set is taken from `base`.
+pub fn sample_joint_transform(world: &World, player: EntityId, bind_id: &str, base: Mat4) -> Mat4 {
+    let (mut scl, mut rot, mut pos) = base.to_scale_rotation_translation();
+    if let Ok(outputs) = world.get_ref(player, animation_output()) {
+        for (key, value) in outputs.0.iter() {
+            if key.target.bind_id() != Some(bind_id) {
+                continue;
+            }
+            match value {
+                AnimationOutput::Vec3 { component, value } => {
+                    if *component == translation() {
+                        pos = *value;
+                    } else if *component == scale() {
+                        scl = *value;
+                    }
+                }
+                AnimationOutput::Quat { component, value } => {
+                    if *component == rotation() {
+                        rot = *value;
+                    }
+                }
+                AnimationOutput::Vec3Field {
+                    component,
+                    field,
+                    value,
+                } => {
+                    let target = if *component == translation() {
+                        &mut pos
+                    } else if *component == scale() {
+                        &mut scl
+                    } else {
+                        continue;
+                    };
+                    match field {
+                        Vec3Field::X => target.x = *value,
+                        Vec3Field::Y => target.y = *value,
+                        Vec3Field::Z => target.z = *value,
+                    }
+                }
+            }
+        }
+    }
+    Mat4::from_scale_rotation_translation(scl, rot, pos)
+}
+
 fn build_base_pose(
     assets: &AssetCache,
     clip_url: &str,
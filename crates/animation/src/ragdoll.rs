@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use ambient_core::{
+    hierarchy::parent,
+    transform::{local_to_parent, rotation, scale, translation},
+};
+use ambient_ecs::{
+    generated::physics::components::{ragdoll_blend, ragdoll_bone, ragdoll_bones},
+    query, EntityId, SystemGroup, World,
+};
+use ambient_model::animation_binder;
+use glam::{Mat4, Quat, Vec3};
+
+/// Blends the animated pose of models with an active ragdoll towards the pose simulated by the server.
+pub(crate) fn ragdoll_systems() -> SystemGroup {
+    SystemGroup::new(
+        "ragdoll_systems",
+        vec![
+            query((ragdoll_blend(), ragdoll_bones(), animation_binder())).to_system(
+                |q, world, qs, _| {
+                    for (_, (blend, bones, binder)) in q.collect_cloned(world, qs) {
+                        let blend = blend.clamp(0., 1.);
+                        // The bones are parents first, so each bone is blended after its parent
+                        let mut world_transforms = HashMap::new();
+                        for bone in bones {
+                            let Some(&target) = world
+                                .get_ref(bone, ragdoll_bone())
+                                .ok()
+                                .and_then(|bind_id| binder.get(bind_id))
+                            else {
+                                continue;
+                            };
+                            let (Ok(sim_pos), Ok(sim_rot), Ok(target_parent)) = (
+                                world.get(bone, translation()),
+                                world.get(bone, rotation()),
+                                world.get(target, parent()),
+                            ) else {
+                                continue;
+                            };
+                            let parent_transform =
+                                world_transform(world, target_parent, &mut world_transforms);
+                            let local = local_transform(world, target);
+                            let (local_scale, _, _) = local.to_scale_rotation_translation();
+                            let (_, anim_rot, anim_pos) =
+                                (parent_transform * local).to_scale_rotation_translation();
+                            let (_, parent_rot, _) =
+                                parent_transform.to_scale_rotation_translation();
+
+                            let local_rot = parent_rot.inverse() * anim_rot.slerp(sim_rot, blend);
+                            let local_pos = parent_transform
+                                .inverse()
+                                .transform_point3(anim_pos.lerp(sim_pos, blend));
+                            world.set(target, rotation(), local_rot).ok();
+                            world.set(target, translation(), local_pos).ok();
+                            world_transforms.insert(
+                                target,
+                                parent_transform
+                                    * Mat4::from_scale_rotation_translation(
+                                        local_scale,
+                                        local_rot,
+                                        local_pos,
+                                    ),
+                            );
+                        }
+                    }
+                },
+            ),
+        ],
+    )
+}
+
+/// The transform of an entity relative to its parent, as it is after animation.
+fn local_transform(world: &World, id: EntityId) -> Mat4 {
+    if world.has_component(id, translation())
+        || world.has_component(id, rotation())
+        || world.has_component(id, scale())
+    {
+        Mat4::from_scale_rotation_translation(
+            world.get(id, scale()).unwrap_or(Vec3::ONE),
+            world.get(id, rotation()).unwrap_or(Quat::IDENTITY),
+            world.get(id, translation()).unwrap_or(Vec3::ZERO),
+        )
+    } else {
+        world.get(id, local_to_parent()).unwrap_or_default()
+    }
+}
+
+/// `local_to_world` isn't updated until after the animations have been applied, so it's
+/// recomputed from the hierarchy.
+fn world_transform(world: &World, id: EntityId, cache: &mut HashMap<EntityId, Mat4>) -> Mat4 {
+    if let Some(transform) = cache.get(&id) {
+        return *transform;
+    }
+    let local = local_transform(world, id);
+    let transform = match world.get(id, parent()) {
+        Ok(parent) if world.exists(parent) => world_transform(world, parent, cache) * local,
+        _ => local,
+    };
+    cache.insert(id, transform);
+    transform
+}
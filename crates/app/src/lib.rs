@@ -9,7 +9,12 @@ use ambient_core::{
     camera::camera_systems,
     frame_index,
     hierarchy::dump_world_hierarchy_to_user,
-    name, performance_samples, refcount_system, remove_at_time_system, runtime, timing,
+    index::index_systems,
+    name, performance_samples, refcount_system,
+    relationship::relationship_systems,
+    remove_at_time_system, runtime,
+    spatial::spatial_systems,
+    timing,
     transform::TransformSystem,
     window::{
         cursor_position, get_window_sizes, window_logical_size, window_physical_size,
@@ -28,6 +33,7 @@ use ambient_gpu::{
     mesh_buffer::MeshBufferKey,
 };
 use ambient_gpu_ecs::{gpu_world, GpuWorld, GpuWorldSyncEvent, GpuWorldUpdate};
+use ambient_input::{action::ActionRegistry, action_registry, gamepad::GamepadCtl, gamepad_ctl};
 use ambient_native_std::{
     asset_cache::{AssetCache, SyncAssetKeyExt},
     fps_counter::{FpsCounter, FpsSample},
@@ -42,7 +48,9 @@ use parking_lot::Mutex;
 use renderers::{main_renderer, ui_renderer, MainRenderer, UiRenderer};
 use winit::{
     dpi::PhysicalPosition,
-    event::{ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent},
+    event::{
+        ElementState, Event, KeyboardInput, ModifiersState, TouchPhase, VirtualKeyCode, WindowEvent,
+    },
     event_loop::{ControlFlow, EventLoop},
     window::{CursorGrabMode, Fullscreen, Window, WindowBuilder},
 };
@@ -103,6 +111,8 @@ pub fn world_instance_systems(full: bool) -> SystemGroup {
             remove_at_time_system(),
             refcount_system(),
             Box::new(ambient_core::hierarchy::systems()),
+            Box::new(relationship_systems()),
+            Box::new(index_systems()),
             Box::new(WorldEventsSystem),
             Box::new(ambient_focus::systems()),
             if full {
@@ -123,6 +133,7 @@ pub fn world_instance_systems(full: bool) -> SystemGroup {
             Box::new(TransformSystem::new()),
             Box::new(ambient_renderer::skinning::skinning_systems()),
             Box::new(bounding_systems()),
+            Box::new(spatial_systems()),
             Box::new(camera_systems()),
             Box::new(ambient_procedurals::client_systems()),
             Box::<ambient_timings::ProcessTimingEventsSystem>::default(),
@@ -136,6 +147,7 @@ pub struct AppResources {
     pub gpu: Arc<Gpu>,
     pub runtime: RuntimeHandle,
     pub ctl_tx: flume::Sender<WindowCtl>,
+    pub gamepad_ctl_tx: flume::Sender<GamepadCtl>,
     window_physical_size: UVec2,
     window_logical_size: UVec2,
     window_scale_factor: f64,
@@ -149,6 +161,7 @@ impl AppResources {
             gpu: world.resource(self::gpu()).clone(),
             runtime: world.resource(self::runtime()).clone(),
             ctl_tx: world.resource(ambient_core::window::window_ctl()).clone(),
+            gamepad_ctl_tx: world.resource(gamepad_ctl()).clone(),
             window_physical_size: *world.resource(ambient_core::window::window_physical_size()),
             window_logical_size: *world.resource(ambient_core::window::window_logical_size()),
             window_scale_factor: *world.resource(ambient_core::window::window_scale_factor()),
@@ -158,6 +171,8 @@ impl AppResources {
 }
 
 pub fn world_instance_resources(resources: AppResources) -> Entity {
+    let input_settings = SettingsKey.get(&resources.assets).input;
+
     Entity::new()
         .with(name(), "Resources".to_string())
         .with(self::gpu(), resources.gpu.clone())
@@ -178,6 +193,9 @@ pub fn world_instance_resources(resources: AppResources) -> Entity {
         .with_merge(ambient_input::resources())
         .with_merge(ambient_input::picking::resources())
         .with_merge(ambient_core::async_ecs::async_ecs_resources())
+        .with_merge(ambient_core::spatial::spatial_resources())
+        .with_merge(ambient_core::relationship::relationship_resources())
+        .with_merge(ambient_core::index::index_resources())
         .with(
             ambient_core::window::window_physical_size(),
             resources.window_physical_size,
@@ -191,6 +209,11 @@ pub fn world_instance_resources(resources: AppResources) -> Entity {
             resources.window_scale_factor,
         )
         .with(ambient_core::window::window_ctl(), resources.ctl_tx)
+        .with(gamepad_ctl(), resources.gamepad_ctl_tx)
+        .with(
+            action_registry(),
+            ActionRegistry::from_settings(&input_settings),
+        )
         .with(procedural_storage(), ProceduralStorage::new())
         .with(focus(), Default::default())
         .with(timing::reporter(), resources.timings_reporter)
@@ -439,8 +462,10 @@ impl AppBuilder {
 
                 // Set a background color for the canvas to make it easier to tell where the canvas is for debugging purposes.
                 // Use the maximum available width and height as the canvas dimensions.
+                // `touch-action: none` stops the browser from scrolling or zooming the page on touch, so
+                // that the touches reach the game.
                 canvas.style().set_css_text(&format!(
-                    "background-color: black; width: {}px; height: {}px; z-index: 50",
+                    "background-color: black; width: {}px; height: {}px; z-index: 50; touch-action: none",
                     max_width, max_height
                 ));
 
@@ -505,6 +530,7 @@ impl AppBuilder {
 
         tracing::debug!("Inserting app resources");
         let (ctl_tx, ctl_rx) = self.ctl.unwrap_or_else(flume::unbounded);
+        let (gamepad_ctl_tx, gamepad_ctl_rx) = flume::unbounded();
 
         let (window_physical_size, window_logical_size, window_scale_factor) =
             if let Some(window) = window.as_ref() {
@@ -519,6 +545,7 @@ impl AppBuilder {
             runtime: runtime.clone(),
             assets,
             ctl_tx,
+            gamepad_ctl_tx,
             window_physical_size,
             window_logical_size,
             window_scale_factor,
@@ -562,8 +589,16 @@ impl AppBuilder {
             window_event_systems.add(Box::new(ExamplesSystem));
         }
 
+        // Headless apps have no one to play them, so there's no need to poll the gamepads
+        let gamepad_system: DynSystem = if window.is_some() {
+            ambient_input::gamepad::gamepad_system(gamepad_ctl_rx)
+        } else {
+            Box::new(DummySystem)
+        };
+
         Ok(App {
             window_focused: true,
+            primary_touch: None,
             window,
             runtime,
             systems: SystemGroup::new(
@@ -572,6 +607,7 @@ impl AppBuilder {
                     Box::new(MeshBufferUpdate),
                     Box::new(world_instance_systems(true)),
                     ambient_input::cursor_lock_system(cursor_lock_rx),
+                    gamepad_system,
                 ],
             ),
             world,
@@ -637,6 +673,8 @@ pub struct App {
     modifiers: ModifiersState,
 
     window_focused: bool,
+    /// The touch that moves the cursor, so that UI can be used by touch.
+    primary_touch: Option<u64>,
     update_title_with_fps_stats: bool,
     #[cfg(target_os = "unknown")]
     _drop_handles: Vec<Box<dyn std::fmt::Debug>>,
@@ -960,6 +998,27 @@ impl App {
                             .unwrap();
                     }
                 }
+                WindowEvent::Touch(touch) => {
+                    if self.primary_touch.is_none() && touch.phase == TouchPhase::Started {
+                        self.primary_touch = Some(touch.id);
+                    }
+                    if self.primary_touch == Some(touch.id) {
+                        if matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+                            self.primary_touch = None;
+                        }
+                        if self.window_focused {
+                            let p = vec2(touch.location.x as f32, touch.location.y as f32)
+                                / self
+                                    .window
+                                    .as_ref()
+                                    .map(|x| x.scale_factor() as f32)
+                                    .unwrap_or(1.);
+                            world
+                                .set(world.resource_entity(), cursor_position(), p)
+                                .unwrap();
+                        }
+                    }
+                }
                 _ => {}
             },
             _ => {}
@@ -273,6 +273,14 @@ impl AssetCache {
         );
     }
 
+    /// Evicts all loaded assets whose key matches `predicate`, so that they are loaded afresh
+    /// the next time they are requested. Assets that are still loading are left untouched.
+    pub fn evict_loaded(&self, predicate: impl Fn(&str) -> bool) {
+        self.async_cache
+            .lock()
+            .retain(|key, loc| loc.content.is_loading() || !predicate(key));
+    }
+
     fn clean_up_dropped(&self) {
         let mut async_ = self.async_cache.lock();
         for (key, asset) in &mut *async_ {
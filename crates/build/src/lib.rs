@@ -10,9 +10,11 @@ use std::{
 use ambient_asset_cache::{AssetCache, SyncAssetKeyExt};
 use ambient_native_std::{asset_url::AbsAssetUrl, AmbientVersion};
 use ambient_package::{
-    BuildMetadata, BuildMetadataError, BuildSettings, Manifest as PackageManifest,
+    BuildMetadata, BuildMetadataError, BuildSettings, Lockfile, Manifest as PackageManifest,
+};
+use ambient_package_semantic::{
+    package_dependency_to_retrievable_file, Registries, RetrievableFile, Semantic,
 };
-use ambient_package_semantic::{package_dependency_to_retrievable_file, RetrievableFile, Semantic};
 use ambient_package_semantic_native::add_to_semantic_and_register_components;
 use ambient_shared_types::asset::BuildAsset;
 use ambient_std::path::path_to_unix_string_lossy;
@@ -50,11 +52,15 @@ const BUILD_ASSETS: bool = true;
 /// src/**  This is where you store Rust source files
 /// build  This is the output directory, and is created when building
 /// ambient.toml  This is a metadata file to describe the package
+///
+/// If `changed_assets` is specified, only the asset pipelines that process those files are run,
+/// and the assets built previously by the other pipelines are kept.
 pub async fn build_package(
     assets: &AssetCache,
     settings: &BuildSettings,
     package_path: &Path,
     root_build_path: &Path,
+    changed_assets: Option<&[PathBuf]>,
 ) -> anyhow::Result<BuildResult> {
     let _span = tracing::info_span!("register_semantic", ?package_path).entered();
     let mut semantic = Semantic::new(settings.deploy).await?;
@@ -128,9 +134,9 @@ pub async fn build_package(
         build_path.join(BuildMetadata::FILENAME),
     ))
     .await?;
-    let (last_build_time, last_build_settings) = match build_metadata {
-        Some(md) => (md.last_build_time()?, Some(md.settings)),
-        None => (None, None),
+    let (last_build_time, last_build_settings, previous_assets) = match build_metadata {
+        Some(md) => (md.last_build_time()?, Some(md.settings), md.asset),
+        None => (None, None, vec![]),
     };
 
     // Get the last build time of all dependencies to determine if this package needs to be rebuilt
@@ -138,12 +144,16 @@ pub async fn build_package(
         async fn dependency_to_build_time(
             omr: &RetrievableFile,
             deploy: bool,
+            registries: &Registries,
+            lockfile: Option<&Lockfile>,
             dependency: &ambient_package::Dependency,
         ) -> anyhow::Result<Option<chrono::DateTime<chrono::Utc>>> {
-            let metadata_path = package_dependency_to_retrievable_file(omr, deploy, dependency)
-                .await?
-                .map(|p| p.parent_join(Path::new(BuildMetadata::FILENAME)))
-                .transpose()?;
+            let metadata_path = package_dependency_to_retrievable_file(
+                omr, deploy, registries, lockfile, dependency,
+            )
+            .await?
+            .map(|p| p.parent_join(Path::new(BuildMetadata::FILENAME)))
+            .transpose()?;
 
             Ok(match metadata_path {
                 Some(metadata_path) => get_build_metadata(metadata_path)
@@ -156,7 +166,13 @@ pub async fn build_package(
 
         let output_manifest_retrievable = RetrievableFile::Path(output_manifest_path.clone());
         futures::future::try_join_all(manifest.dependencies.values().map(|dep| {
-            dependency_to_build_time(&output_manifest_retrievable, settings.deploy, dep)
+            dependency_to_build_time(
+                &output_manifest_retrievable,
+                settings.deploy,
+                &semantic.registries,
+                semantic.lockfile.as_ref(),
+                dep,
+            )
         }))
         .await?
         .into_iter()
@@ -199,14 +215,20 @@ pub async fn build_package(
 
     tracing::info!("Building package");
 
-    if BUILD_ASSETS {
+    let built_assets = if BUILD_ASSETS {
         let assets_path = package_path.join("assets");
         tokio::fs::create_dir_all(&build_path)
             .await
             .context("Failed to create build directory")?;
 
         let assets = if !settings.wasm_only {
-            build_assets(assets, &assets_path, &build_path, false).await?
+            build_assets(assets, &assets_path, &build_path, false, changed_assets).await?
+        } else {
+            vec![]
+        };
+        // Assets that weren't rebuilt this time are still in the build directory
+        let previous_assets = if settings.wasm_only || changed_assets.is_some() {
+            previous_assets
         } else {
             vec![]
         };
@@ -221,8 +243,10 @@ pub async fn build_package(
 
         tokio::fs::write(&output_manifest_path, toml::to_string(&manifest)?).await?;
 
-        write_metadata(&package_path, &build_path, settings, &assets).await?;
-    }
+        Some((assets, previous_assets))
+    } else {
+        None
+    };
 
     // Deploy implies docs are always built, as they are required for deployment
     let build_docs = settings.build_docs || settings.deploy;
@@ -238,6 +262,18 @@ pub async fn build_package(
         tracing::info!("Docs built");
     }
 
+    // The metadata is written last, so that it can list every file in the build
+    if let Some((assets, previous_assets)) = built_assets {
+        write_metadata(
+            &package_path,
+            &build_path,
+            settings,
+            &assets,
+            previous_assets,
+        )
+        .await?;
+    }
+
     Ok(BuildResult {
         build_path,
         package_name: package_name.clone(),
@@ -264,13 +300,18 @@ fn get_files_in_path(path: &Path) -> impl Iterator<Item = PathBuf> {
         .map(|x| x.into_path())
 }
 
+/// Runs the asset pipelines in `assets_path`. If `changed_files` is specified, only the
+/// pipelines that process one of those files are run.
 pub async fn build_assets(
     assets: &AssetCache,
     assets_path: &Path,
     build_path: &Path,
     for_import_only: bool,
+    changed_files: Option<&[PathBuf]>,
 ) -> anyhow::Result<Vec<OutAsset>> {
     let files = get_files_in_path(assets_path).map(Into::into).collect_vec();
+    let changed_files =
+        changed_files.map(|files| Arc::new(files.iter().cloned().map(Into::into).collect_vec()));
 
     let has_errored = Arc::new(AtomicBool::new(false));
 
@@ -285,6 +326,7 @@ pub async fn build_assets(
         in_root: AbsAssetUrl::from_directory_path(assets_path),
         out_root: AbsAssetUrl::from_directory_path(build_path.join("assets")),
         input_file_filter: None,
+        changed_files,
         package_name: "".to_string(),
         write_file: Arc::new({
             let build_path = build_path.to_owned();
@@ -405,6 +447,7 @@ async fn write_metadata(
     build_path: &Path,
     settings: &BuildSettings,
     assets: &[OutAsset],
+    previous_assets: Vec<BuildAsset>,
 ) -> anyhow::Result<BuildMetadata> {
     fn strip_path(path: PathBuf, prefix: &Path) -> PathBuf {
         path.strip_prefix(prefix)
@@ -412,6 +455,25 @@ async fn write_metadata(
             .unwrap_or(path)
     }
 
+    let assets = assets
+        .iter()
+        .flat_map(|a| {
+            Some(BuildAsset {
+                type_: a.type_,
+                input: a
+                    .source
+                    .as_ref()
+                    .and_then(|s| s.to_file_path().ok().flatten())
+                    .map(|p| strip_path(p, package_path)),
+                output: strip_path(a.content.as_content()?.to_file_path().ok()??, build_path),
+            })
+        })
+        .collect_vec();
+    let previous_assets = previous_assets
+        .into_iter()
+        .filter(|previous| !assets.iter().any(|a| a.output == previous.output))
+        .collect_vec();
+
     let AmbientVersion { version, revision } = AmbientVersion::default();
     let metadata = BuildMetadata {
         ambient_version: version,
@@ -420,19 +482,11 @@ async fn write_metadata(
         server_component_paths: get_component_paths("server", build_path),
         last_build_time: Some(chrono::Utc::now().to_rfc3339()),
         settings: settings.clone(),
-        asset: assets
-            .iter()
-            .flat_map(|a| {
-                Some(BuildAsset {
-                    type_: a.type_,
-                    input: a
-                        .source
-                        .as_ref()
-                        .and_then(|s| s.to_file_path().ok().flatten())
-                        .map(|p| strip_path(p, package_path)),
-                    output: strip_path(a.content.as_content()?.to_file_path().ok()??, build_path),
-                })
-            })
+        asset: previous_assets.into_iter().chain(assets).collect(),
+        files: get_files_in_path(build_path)
+            .filter_map(|p| Some(path_to_unix_string_lossy(p.strip_prefix(build_path).ok()?)))
+            .filter(|p| p != BuildMetadata::FILENAME)
+            .sorted()
             .collect(),
     };
     let metadata_path = build_path.join(BuildMetadata::FILENAME);
@@ -280,6 +280,8 @@ impl From<json_pipeline::ModelsPipeline> for ModelsPipeline {
                     ambient_pipeline_types::models::ColliderType::Picking
                 }
             },
+            collider_material: None,
+            ragdoll: None,
             cap_texture_sizes: value.cap_texture_sizes,
             collection_of_variants: value.collection_of_variants,
             output_prefabs: value.output_prefabs,
@@ -36,6 +36,12 @@ pub fn write(
                 .collect(),
             vec_items: semantic.items.vec_items().to_json(),
             option_items: semantic.items.option_items().to_json(),
+            map_items: semantic
+                .items
+                .map_items()
+                .values()
+                .map(|id| id.to_json())
+                .collect(),
         })?,
     )?;
 
@@ -231,7 +237,12 @@ impl SemanticToJson for sema::TypeInner {
             sema::TypeInner::Primitive(v) => json::TypeInner::Primitive(v.to_json()),
             sema::TypeInner::Vec(v) => json::TypeInner::Vec(v.to_json()),
             sema::TypeInner::Option(v) => json::TypeInner::Option(v.to_json()),
+            sema::TypeInner::Map { key, value } => json::TypeInner::Map {
+                key: key.to_json(),
+                value: value.to_json(),
+            },
             sema::TypeInner::Enum(v) => json::TypeInner::Enum(v.to_json()),
+            sema::TypeInner::Struct(v) => json::TypeInner::Struct(v.to_json()),
         }
     }
 }
@@ -259,6 +270,15 @@ impl SemanticToJson for sema::Enum {
         }
     }
 }
+impl SemanticToJson for sema::Struct {
+    type Json = json::Struct;
+    fn to_json(&self) -> Self::Json {
+        json::Struct {
+            description: self.description.to_json(),
+            fields: self.fields.to_json(),
+        }
+    }
+}
 
 impl SemanticToJson for sema::Attribute {
     type Json = json::Attribute;
@@ -327,10 +347,17 @@ impl SemanticToJson for sema::Value {
             sema::Value::Scalar(v) => json::Value::Scalar(v.to_json()),
             sema::Value::Vec(v) => json::Value::Vec(v.to_json()),
             sema::Value::Option(v) => json::Value::Option(v.to_json()),
+            sema::Value::Map(v) => {
+                json::Value::Map(v.iter().map(|(k, v)| (k.to_json(), v.to_json())).collect())
+            }
             sema::Value::Enum(ty, id) => json::Value::Enum(json::EnumValue {
                 ty: ty.to_json(),
                 member: id.to_json(),
             }),
+            sema::Value::Struct(ty, fields) => json::Value::Struct(json::StructValue {
+                ty: ty.to_json(),
+                fields: fields.to_json(),
+            }),
         }
     }
 }
@@ -0,0 +1,39 @@
+use ambient_native_std::asset_url::AssetType;
+use ambient_pipeline_types::DataPipeline;
+
+use super::{
+    context::PipelineCtx,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+pub async fn pipeline(ctx: &PipelineCtx, _config: DataPipeline) -> Vec<OutAsset> {
+    ctx.process_files(
+        |file| {
+            !file
+                .decoded_path()
+                .file_name()
+                .unwrap_or_default()
+                .ends_with("pipeline.toml")
+        },
+        move |ctx, file| async move {
+            let contents = file.download_bytes(ctx.assets()).await?;
+
+            let filename = file.decoded_path().file_name().unwrap().to_string();
+            let rel_path = ctx.in_root().relative_path(file.decoded_path());
+            let content_url = ctx.write_file(&rel_path, contents).await;
+
+            Ok(vec![OutAsset {
+                id: asset_id_from_url(&file),
+                type_: AssetType::Data,
+                hidden: false,
+                name: filename,
+                tags: Vec::new(),
+                categories: Default::default(),
+                preview: OutAssetPreview::None,
+                content: OutAssetContent::Content(content_url),
+                source: Some(file.clone()),
+            }])
+        },
+    )
+    .await
+}
@@ -14,6 +14,7 @@ use out_asset::{OutAsset, OutAssetContent, OutAssetPreview};
 
 pub mod audio;
 pub mod context;
+pub mod data;
 pub mod importer;
 pub mod materials;
 pub mod models;
@@ -27,6 +28,7 @@ pub async fn process_pipeline(pipeline: &Pipeline, ctx: PipelineCtx) -> Vec<OutA
         PipelineProcessor::Models(config) => models::pipeline(&ctx, config.clone()).await,
         PipelineProcessor::Materials(config) => materials::pipeline(&ctx, config.clone()).await,
         PipelineProcessor::Audio(config) => audio::pipeline(&ctx, config.clone()).await,
+        PipelineProcessor::Data(config) => data::pipeline(&ctx, config.clone()).await,
     };
 
     for asset in &mut assets {
@@ -85,8 +87,19 @@ pub async fn process_pipelines(ctx: &ProcessCtx) -> anyhow::Result<Vec<OutAsset>
         .try_flatten()
         .map_ok(|(pipeline_file, pipeline): (AbsAssetUrl, Pipeline)| {
             let root = pipeline_file.join(".").unwrap();
+            let files = ctx.files.sub_directory(root.decoded_path().as_str());
+
+            // If only some files have changed, only the pipelines that can see them need to run
+            let skip = ctx.changed_files.as_ref().is_some_and(|changed| {
+                let mut pipelines_file = pipeline_file.clone();
+                pipelines_file.0.set_fragment(None);
+                !changed
+                    .iter()
+                    .any(|file| *file == pipelines_file || files.has_input_file(file))
+            });
+
             let ctx = PipelineCtx {
-                files: ctx.files.sub_directory(root.decoded_path().as_str()),
+                files,
                 process_ctx: ctx.clone(),
                 pipeline: Arc::new(pipeline.clone()),
                 pipeline_file,
@@ -94,6 +107,11 @@ pub async fn process_pipelines(ctx: &ProcessCtx) -> anyhow::Result<Vec<OutAsset>
             };
 
             async move {
+                if skip {
+                    tracing::debug!("Skipping unchanged pipeline: {:?}", ctx.pipeline_path());
+                    return Ok(vec![]);
+                }
+
                 tokio::spawn(async move { process_pipeline(&pipeline, ctx).await })
                     .await
                     .context("Pipeline processing panicked")
@@ -115,6 +133,8 @@ pub struct ProcessCtx {
     pub(crate) assets: AssetCache,
     pub(crate) files: FileCollection,
     pub(crate) input_file_filter: Option<String>,
+    /// If set, only the pipelines that process one of these files are run.
+    pub(crate) changed_files: Option<Arc<Vec<AbsAssetUrl>>>,
     pub(crate) package_name: String,
     pub(crate) in_root: AbsAssetUrl,
     pub(crate) out_root: AbsAssetUrl,
@@ -130,6 +150,7 @@ impl std::fmt::Debug for ProcessCtx {
             .field("assets", &self.assets)
             .field("files", &self.files)
             .field("input_file_filter", &self.input_file_filter)
+            .field("changed_files", &self.changed_files)
             .field("package_name", &self.package_name)
             .field("in_root", &self.in_root)
             .field("out_root", &self.out_root)
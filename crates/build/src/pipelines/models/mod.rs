@@ -3,8 +3,12 @@ use std::{path::PathBuf, sync::Arc};
 use ambient_core::hierarchy::children;
 use ambient_model_import::{apply_model_transform, model_crate::ModelCrate, TextureResolver};
 use ambient_native_std::asset_url::AssetType;
-use ambient_physics::collider::collider_type;
-use ambient_pipeline_types::models::{Collider, ModelImporter, ModelsPipeline};
+use ambient_physics::{
+    collider::collider_type, dynamic_friction, friction_combine_mode, ragdoll::RagdollBoneLimits,
+    restitution, restitution_combine_mode, static_friction,
+};
+use ambient_pipeline_types::models::{Collider, CombineMode, ModelImporter, ModelsPipeline};
+use anyhow::Context;
 use futures::FutureExt;
 use relative_path::RelativePath;
 
@@ -13,7 +17,7 @@ use super::{
     download_image,
     out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
 };
-use ambient_ecs::Entity;
+use ambient_ecs::{generated::physics::types, Entity};
 
 pub mod quixel;
 pub mod regular;
@@ -85,6 +89,29 @@ async fn apply(
             model_crate.create_character_collider(radius, height)
         }
     }
+    if let Some(ragdoll) = &pipeline.ragdoll {
+        let bone_limits = ragdoll
+            .bone_limits
+            .iter()
+            .map(|(bone, limits)| {
+                let limits = RagdollBoneLimits {
+                    hinge: limits.hinge,
+                    swing_limit: limits.swing_limit.map(f32::to_radians),
+                    twist_limit: limits.twist_limit.map(f32::to_radians),
+                };
+                (bone.clone(), limits)
+            })
+            .collect();
+        model_crate
+            .create_ragdoll(
+                &ragdoll.bones,
+                ragdoll.radius.unwrap_or(0.25),
+                ragdoll.swing_limit.unwrap_or(45.).to_radians(),
+                ragdoll.twist_limit.unwrap_or(30.).to_radians(),
+                &bone_limits,
+            )
+            .context("Failed to create ragdoll")?;
+    }
     model_crate.add_component_to_prefab(
         collider_type(),
         match pipeline.collider_type {
@@ -102,6 +129,23 @@ async fn apply(
             }
         },
     );
+    if let Some(material) = pipeline.collider_material {
+        if let Some(value) = material.static_friction {
+            model_crate.add_component_to_prefab(static_friction(), value);
+        }
+        if let Some(value) = material.dynamic_friction {
+            model_crate.add_component_to_prefab(dynamic_friction(), value);
+        }
+        if let Some(value) = material.restitution {
+            model_crate.add_component_to_prefab(restitution(), value);
+        }
+        if let Some(mode) = material.friction_combine_mode {
+            model_crate.add_component_to_prefab(friction_combine_mode(), combine_mode(mode));
+        }
+        if let Some(mode) = material.restitution_combine_mode {
+            model_crate.add_component_to_prefab(restitution_combine_mode(), combine_mode(mode));
+        }
+    }
     let world = model_crate.prefab_world_mut();
     let obj = world.resource(children())[0];
     if let Some(e) = &pipeline.prefab_components {
@@ -111,6 +155,15 @@ async fn apply(
     Ok(())
 }
 
+fn combine_mode(mode: CombineMode) -> types::CombineMode {
+    match mode {
+        CombineMode::Average => types::CombineMode::Average,
+        CombineMode::Min => types::CombineMode::Min,
+        CombineMode::Multiply => types::CombineMode::Multiply,
+        CombineMode::Max => types::CombineMode::Max,
+    }
+}
+
 fn create_texture_resolver(ctx: &PipelineCtx) -> TextureResolver {
     let ctx = ctx.clone();
     Arc::new(move |path| {
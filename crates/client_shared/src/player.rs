@@ -1,22 +1,54 @@
 use std::str::FromStr;
 
-use ambient_core::window::cursor_position;
-use ambient_ecs::{generated::messages, query_mut, SystemGroup, World};
-use ambient_element::{element_component, use_runtime_message, use_state, Element, Hooks};
-use ambient_input::{player_prev_raw_input, player_raw_input, PlayerRawInput};
-use ambient_network::client::client_state;
-use ambient_shared_types::VirtualKeyCode;
+use ambient_core::{runtime, window::cursor_position};
+use ambient_ecs::{generated::messages, query_mut, FnSystem, SystemGroup, World};
+use ambient_element::{
+    element_component, use_frame, use_ref_with, use_runtime_message, use_state, Element, Hooks,
+};
+use ambient_input::{
+    gamepad::{apply_stick_deadzone, apply_trigger_deadzone, Gamepad, GamepadDeadzones},
+    gamepad_deadzones, player_prev_raw_input, player_raw_input,
+    touch::Touch,
+    PlayerRawInput,
+};
+use ambient_network::{client::client_state, log_network_result, PLAYER_INPUT_DATAGRAM_ID};
+use ambient_shared_types::{
+    GamepadButton, GamepadStick, GamepadTrigger, TouchPhase, VirtualKeyCode,
+};
 use glam::Vec2;
 
 pub fn systems_final() -> SystemGroup {
     SystemGroup::new(
         "player/client_systems_final",
         vec![
+            // The server keeps the most recent input of each player on their entity. It's sent
+            // every frame, so a lost datagram is replaced by the next one.
+            Box::new(FnSystem::new(|world, _| {
+                let Some(Some(client)) = world.resource_opt(client_state()) else {
+                    return;
+                };
+                let Ok(input) = bincode::serialize(world.resource(player_raw_input())) else {
+                    return;
+                };
+
+                let transport = client.transport.clone();
+                world.resource(runtime()).spawn(async move {
+                    log_network_result!(
+                        transport
+                            .send_datagram(PLAYER_INPUT_DATAGRAM_ID, input.into())
+                            .await
+                    );
+                });
+            })),
             query_mut((player_prev_raw_input(), player_raw_input()), ()).to_system(
                 |q, world, qs, _| {
                     for (_, (prev, input), ()) in q.iter(world, qs) {
                         input.mouse_delta = glam::Vec2::ZERO;
                         *prev = input.clone();
+                        input.touches.retain(|touch| !touch.phase.is_finished());
+                        for touch in &mut input.touches {
+                            touch.phase = TouchPhase::Moved;
+                        }
                     }
                 },
             ),
@@ -78,6 +110,100 @@ pub fn PlayerRawInputHandler(hooks: &mut Hooks) -> Element {
         });
     });
 
+    use_runtime_message::<messages::WindowTouchInput>(hooks, move |world, event| {
+        let Ok(phase) = TouchPhase::try_from(event.phase) else {
+            return;
+        };
+        process_input(world, has_focus, |input, _| {
+            let touch = Touch {
+                id: event.touch_id,
+                position: event.position,
+                pressure: event.pressure,
+                phase,
+            };
+            match input.touch_mut(touch.id) {
+                // A touch that started this frame stays `Started` until the next frame, so that
+                // it isn't missed when it also moves
+                Some(existing)
+                    if existing.phase == TouchPhase::Started && phase == TouchPhase::Moved =>
+                {
+                    existing.position = touch.position;
+                    existing.pressure = touch.pressure;
+                }
+                Some(existing) => *existing = touch,
+                None => input.touches.push(touch),
+            }
+        });
+    });
+
+    // Gamepads can be connected before the game client exists (or while it's being replaced),
+    // so they're tracked here and synchronized with the player's input every frame
+    let connected_gamepads = use_ref_with(hooks, |_| Vec::<(u32, String)>::new());
+    use_runtime_message::<messages::GamepadConnected>(hooks, {
+        let connected_gamepads = connected_gamepads.clone();
+        move |_, event| {
+            let mut connected_gamepads = connected_gamepads.lock();
+            if !connected_gamepads
+                .iter()
+                .any(|(id, _)| *id == event.gamepad_id)
+            {
+                connected_gamepads.push((event.gamepad_id, event.name.clone()));
+            }
+        }
+    });
+    use_runtime_message::<messages::GamepadDisconnected>(hooks, {
+        let connected_gamepads = connected_gamepads.clone();
+        move |_, event| {
+            connected_gamepads
+                .lock()
+                .retain(|(id, _)| *id != event.gamepad_id);
+        }
+    });
+    use_frame(hooks, move |world| {
+        let connected_gamepads = connected_gamepads.lock();
+        process_input(world, true, |input, _| {
+            input
+                .gamepads
+                .retain(|gamepad| connected_gamepads.iter().any(|(id, _)| *id == gamepad.id));
+            for (id, name) in connected_gamepads.iter() {
+                if input.gamepad_mut(*id).is_none() {
+                    input.gamepads.push(Gamepad::new(*id, name.clone()));
+                }
+            }
+        });
+    });
+
+    use_runtime_message::<messages::GamepadButtonInput>(hooks, move |world, event| {
+        let Ok(button) = GamepadButton::try_from(event.button) else {
+            return;
+        };
+        process_gamepad_input(world, has_focus, event.gamepad_id, |gamepad, _| {
+            if event.pressed {
+                gamepad.buttons.insert(button);
+            } else {
+                gamepad.buttons.remove(&button);
+            }
+        });
+    });
+
+    use_runtime_message::<messages::GamepadStickInput>(hooks, move |world, event| {
+        let Ok(stick) = GamepadStick::try_from(event.stick) else {
+            return;
+        };
+        process_gamepad_input(world, has_focus, event.gamepad_id, |gamepad, deadzones| {
+            *gamepad.stick_mut(stick) = apply_stick_deadzone(event.value, deadzones.stick);
+        });
+    });
+
+    use_runtime_message::<messages::GamepadTriggerInput>(hooks, move |world, event| {
+        let Ok(trigger) = GamepadTrigger::try_from(event.trigger) else {
+            return;
+        };
+        process_gamepad_input(world, has_focus, event.gamepad_id, |gamepad, deadzones| {
+            *gamepad.trigger_mut(trigger) = apply_trigger_deadzone(event.value, deadzones.trigger);
+        });
+    });
+
     fn process_input(
         ui_world: &World,
         has_focus: bool,
@@ -97,5 +223,27 @@ pub fn PlayerRawInputHandler(hooks: &mut Hooks) -> Element {
         });
     }
 
+    fn process_gamepad_input(
+        ui_world: &World,
+        has_focus: bool,
+        gamepad_id: u32,
+        processor: impl Fn(&mut Gamepad, &GamepadDeadzones),
+    ) {
+        if !has_focus {
+            return;
+        }
+
+        let Some(Some(gc)) = ui_world.resource_opt(client_state()).cloned() else {
+            return;
+        };
+        gc.with_physics_world(|w| {
+            let deadzones = *w.resource(gamepad_deadzones());
+            let input = w.resource_mut(player_raw_input());
+            if let Some(gamepad) = input.gamepad_mut(gamepad_id) {
+                processor(gamepad, &deadzones);
+            }
+        });
+    }
+
     Element::new()
 }
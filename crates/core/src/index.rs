@@ -0,0 +1,236 @@
+use std::{collections::HashMap, ops::Bound};
+
+use ambient_ecs::{
+    components, primitive_comparator, with_component_registry, ArchetypeFilter, ComponentDesc,
+    ComponentEntry, Entity, EntityId, FnSystem, Index, IndexColumns, IndexComparator, IndexField,
+    IndexFieldValue, IndexKey, Indexed, Query, QueryState, Resource, SystemGroup, World,
+};
+use itertools::Itertools;
+
+components!("app", {
+    /// Sorted indexes for all the components with the `Indexed` attribute.
+    @[Resource]
+    component_indexes: ComponentIndexes,
+});
+
+/// A sorted index for every component with the `Indexed` attribute.
+///
+/// The indexes are updated from change queries, so writes through `get_mut` and mutable queries
+/// are picked up too. They are brought up to date once per frame by [index_systems], and before
+/// each [IndexQuery] is evaluated.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentIndexes {
+    indexes: HashMap<ComponentDesc, ComponentIndex>,
+    /// The number of components in the registry the last time it was checked for indexes
+    checked_components: usize,
+}
+impl ComponentIndexes {
+    /// Returns true if `component` has the `Indexed` attribute and has been registered.
+    pub fn is_indexed(&self, component: impl Into<ComponentDesc>) -> bool {
+        self.indexes.contains_key(&component.into())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ComponentIndex {
+    index: Index,
+    comparator: IndexComparator,
+    /// Spawned, despawned and changed queries for the entities with the component
+    queries: [(Query, QueryState); 3],
+}
+impl ComponentIndex {
+    fn new(component: ComponentDesc, comparator: IndexComparator) -> Self {
+        let filter = ArchetypeFilter::new().incl_ref(component);
+        Self {
+            index: Index::new(
+                IndexColumns::new().add_column_with_comparator(component, comparator),
+            ),
+            comparator,
+            queries: [
+                Query::new(filter.clone()).spawned(),
+                Query::new(filter.clone()).despawned(),
+                Query::any_changed(vec![component]).filter(&filter),
+            ]
+            .map(|query| (query, QueryState::new())),
+        }
+    }
+
+    fn update(&mut self, world: &World) {
+        let mut ids = Vec::new();
+        for (query, state) in &mut self.queries {
+            ids.extend(query.iter(world, Some(state)).map(|ea| ea.id()));
+        }
+        for id in ids.into_iter().unique() {
+            self.index.sync_entity(world, id);
+        }
+    }
+
+    fn range(
+        &self,
+        start: &Bound<ComponentEntry>,
+        end: &Bound<ComponentEntry>,
+        descending: bool,
+        matches: impl Fn(EntityId) -> bool,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Vec<EntityId> {
+        let field = |value: &ComponentEntry| {
+            vec![IndexField::Exact(IndexFieldValue::from_entry(
+                value.clone(),
+                self.comparator,
+            ))]
+        };
+        // Keys sort before (`min`) or after (`max`) all the entities with the same value
+        let start = match start {
+            Bound::Included(value) => Bound::Included(IndexKey::min(field(value))),
+            Bound::Excluded(value) => Bound::Excluded(IndexKey::max(field(value))),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match end {
+            Bound::Included(value) => Bound::Included(IndexKey::max(field(value))),
+            Bound::Excluded(value) => Bound::Excluded(IndexKey::min(field(value))),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        // `BTreeSet::range` panics on an inverted range
+        if let (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) = (&start, &end)
+        {
+            if start > end {
+                return Vec::new();
+            }
+        }
+
+        let keys = self.index.range((start, end));
+        let ids: Box<dyn Iterator<Item = EntityId> + '_> = if descending {
+            Box::new(keys.rev().filter_map(|key| key.id()))
+        } else {
+            Box::new(keys.filter_map(|key| key.id()))
+        };
+        ids.filter(|&id| matches(id))
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// A sorted query over the entities with an indexed component.
+#[derive(Debug, Clone)]
+pub struct IndexQuery {
+    pub component: ComponentDesc,
+    /// The other components the entities must (or must not) have.
+    pub filter: ArchetypeFilter,
+    /// The lowest value to include. Must be a value of `component`.
+    pub start: Bound<ComponentEntry>,
+    /// The highest value to include. Must be a value of `component`.
+    pub end: Bound<ComponentEntry>,
+    /// Return the entities from the highest value to the lowest instead.
+    pub descending: bool,
+}
+impl IndexQuery {
+    /// Creates a query for all the entities with `component`, from its lowest value to its highest.
+    pub fn new(component: impl Into<ComponentDesc>) -> Self {
+        Self {
+            component: component.into(),
+            filter: ArchetypeFilter::new(),
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            descending: false,
+        }
+    }
+
+    /// Returns the entities matching the range and the filter in order, skipping the first `offset`
+    /// and returning at most `limit` of them. Entities with equal values are ordered by their ID.
+    ///
+    /// Returns `None` if `component` does not have the `Indexed` attribute.
+    pub fn evaluate(
+        &self,
+        world: &mut World,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Option<Vec<EntityId>> {
+        register_indexes(world);
+        let mut index = world
+            .resource_mut_opt(component_indexes())?
+            .indexes
+            .remove(&self.component)?;
+        index.update(world);
+        let ids = index.range(
+            &self.start,
+            &self.end,
+            self.descending,
+            |id| self.filter.matches_entity(world, id),
+            offset,
+            limit,
+        );
+        world
+            .resource_mut(component_indexes())
+            .indexes
+            .insert(self.component, index);
+        Some(ids)
+    }
+}
+
+pub fn index_resources() -> Entity {
+    Entity::new().with(component_indexes(), ComponentIndexes::default())
+}
+
+pub fn index_systems() -> SystemGroup {
+    SystemGroup::new(
+        "component_indexes",
+        vec![Box::new(FnSystem::new(|world, _| {
+            register_indexes(world);
+            update_indexes(world);
+        }))],
+    )
+}
+
+/// Starts indexing the indexed components that have been registered since the last call.
+fn register_indexes(world: &mut World) {
+    let Some(checked) = world
+        .resource_opt(component_indexes())
+        .map(|r| r.checked_components)
+    else {
+        return;
+    };
+    let (count, new) = with_component_registry(|cr| {
+        let count = cr.component_count();
+        let new = (checked..count)
+            .filter_map(|index| {
+                let pc = cr.get_primitive_component(index as _)?;
+                if !pc.desc.has_attribute::<Indexed>() {
+                    return None;
+                }
+                let Some(comparator) = primitive_comparator(pc.ty) else {
+                    tracing::warn!(
+                        "The indexed component {} has no order; ignoring it",
+                        pc.desc.path()
+                    );
+                    return None;
+                };
+                Some((pc.desc, comparator))
+            })
+            .collect_vec();
+        (count, new)
+    });
+
+    // The first update of each index adds the entities that already have the component
+    let r = world.resource_mut(component_indexes());
+    r.checked_components = count;
+    for (component, comparator) in new {
+        r.indexes
+            .insert(component, ComponentIndex::new(component, comparator));
+    }
+}
+
+fn update_indexes(world: &mut World) {
+    let Some(r) = world.resource_mut_opt(component_indexes()) else {
+        return;
+    };
+    let mut indexes = std::mem::take(&mut r.indexes);
+    for index in indexes.values_mut() {
+        index.update(world);
+    }
+    world.resource_mut(component_indexes()).indexes = indexes;
+}
@@ -21,7 +21,10 @@ pub mod bounding;
 pub mod camera;
 
 pub mod hierarchy;
+pub mod index;
 pub mod player;
+pub mod relationship;
+pub mod spatial;
 pub mod timing;
 pub mod transform;
 pub mod window;
@@ -84,6 +87,9 @@ pub fn init_all_components() {
     transform::init_gpu_components();
     bounding::init_components();
     bounding::init_gpu_components();
+    relationship::init_components();
+    index::init_components();
+    spatial::init_components();
 }
 
 #[derive(Debug, Clone)]
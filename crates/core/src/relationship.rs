@@ -0,0 +1,282 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use ambient_ecs::{
+    components, query, with_component_registry, ArchetypeFilter, Component, ComponentDesc,
+    ComponentEntry, Entity, EntityId, FnSystem, Networked, ObservedChange, ObserverEvent,
+    PrimitiveComponentType, Query, Relationship, Resource, SystemGroup, World, WorldContext,
+};
+use itertools::Itertools;
+
+components!("app", {
+    /// Reverse lookups for all the components with the `Relationship` attribute.
+    @[Resource]
+    relationships: Relationships,
+});
+
+/// How many entities a relationship component refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipKind {
+    /// The component is an `EntityId`.
+    ManyToOne,
+    /// The component is a `Vec<EntityId>`.
+    ManyToMany,
+}
+
+/// For every relationship component, the entities that refer to each entity through it.
+///
+/// The index is updated by observers on the relationship components, so it reflects every
+/// change up to the last observer flush. Relationship components are found by
+/// [relationship_systems], which also picks up the ones registered by packages at runtime.
+///
+/// Observers are not called for values that are modified in place, so relationships must be
+/// written with `World::set`, `World::add_component` or `World::remove_component`. Changes
+/// made through `World::get_mut` or a mutable query are not reflected in the index.
+#[derive(Debug, Clone, Default)]
+pub struct Relationships {
+    kinds: HashMap<ComponentDesc, RelationshipKind>,
+    /// Relationship component -> target -> the entities referring to the target
+    sources: HashMap<ComponentDesc, HashMap<EntityId, HashSet<EntityId>>>,
+    /// The number of components in the registry the last time it was checked for relationships
+    checked_components: usize,
+}
+impl Relationships {
+    /// Returns the kind of relationship `component` is, or `None` if it does not have
+    /// the `Relationship` attribute.
+    pub fn kind(&self, component: impl Into<ComponentDesc>) -> Option<RelationshipKind> {
+        self.kinds.get(&component.into()).copied()
+    }
+    /// Returns the entities that refer to `target` through `component`, in no particular order.
+    pub fn sources(
+        &self,
+        component: impl Into<ComponentDesc>,
+        target: EntityId,
+    ) -> impl Iterator<Item = EntityId> + '_ {
+        self.sources
+            .get(&component.into())
+            .and_then(|sources| sources.get(&target))
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+    fn add(&mut self, component: ComponentDesc, source: EntityId, targets: &[EntityId]) {
+        let sources = self.sources.entry(component).or_default();
+        for target in targets {
+            sources.entry(*target).or_default().insert(source);
+        }
+    }
+    fn remove(&mut self, component: ComponentDesc, source: EntityId, targets: &[EntityId]) {
+        let Some(sources) = self.sources.get_mut(&component) else {
+            return;
+        };
+        for target in targets {
+            if let Entry::Occupied(mut entry) = sources.entry(*target) {
+                entry.get_mut().remove(&source);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the entities that refer to `target` through the relationship `component`.
+pub fn get_related(
+    world: &World,
+    component: impl Into<ComponentDesc>,
+    target: EntityId,
+) -> Vec<EntityId> {
+    world
+        .resource_opt(relationships())
+        .map(|r| r.sources(component, target).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the entities that `source` refers to through the relationship `component`.
+pub fn get_targets(world: &World, component: ComponentDesc, source: EntityId) -> Vec<EntityId> {
+    match world
+        .resource_opt(relationships())
+        .and_then(|r| r.kind(component))
+    {
+        Some(RelationshipKind::ManyToOne) => world
+            .get(source, Component::<EntityId>::new(component))
+            .into_iter()
+            .collect(),
+        Some(RelationshipKind::ManyToMany) => world
+            .get_ref(source, Component::<Vec<EntityId>>::new(component))
+            .cloned()
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns true if `source` refers to `target` through the relationship `component`.
+pub fn refers_to(
+    world: &World,
+    component: ComponentDesc,
+    source: EntityId,
+    target: EntityId,
+) -> bool {
+    if component.is::<EntityId>() {
+        world
+            .get(source, Component::<EntityId>::new(component))
+            .ok()
+            == Some(target)
+    } else if component.is::<Vec<EntityId>>() {
+        world
+            .get_ref(source, Component::<Vec<EntityId>>::new(component))
+            .is_ok_and(|targets| targets.contains(&target))
+    } else {
+        false
+    }
+}
+
+pub fn relationship_resources() -> Entity {
+    Entity::new().with(relationships(), Relationships::default())
+}
+
+pub fn relationship_systems() -> SystemGroup {
+    SystemGroup::new(
+        "relationships",
+        vec![
+            Box::new(FnSystem::new(|world, _| register_relationships(world))),
+            query(()).despawned().to_system_with_name(
+                "remove_despawned_targets",
+                |q, world, qs, _| {
+                    let despawned = q.collect_ids(world, qs);
+                    remove_despawned_targets(world, &despawned);
+                },
+            ),
+        ],
+    )
+}
+
+/// Starts indexing the relationship components that have been registered since the last call.
+fn register_relationships(world: &mut World) {
+    let Some(checked) = world
+        .resource_opt(relationships())
+        .map(|r| r.checked_components)
+    else {
+        return;
+    };
+    let (count, new) = with_component_registry(|cr| {
+        let count = cr.component_count();
+        let new = (checked..count)
+            .filter_map(|index| {
+                let pc = cr.get_primitive_component(index as _)?;
+                if !pc.desc.has_attribute::<Relationship>() {
+                    return None;
+                }
+                match pc.ty {
+                    PrimitiveComponentType::EntityId => {
+                        Some((pc.desc, RelationshipKind::ManyToOne))
+                    }
+                    PrimitiveComponentType::VecEntityId => {
+                        Some((pc.desc, RelationshipKind::ManyToMany))
+                    }
+                    _ => {
+                        tracing::warn!(
+                            "The relationship {} is not an EntityId or Vec<EntityId>; ignoring it",
+                            pc.desc.path()
+                        );
+                        None
+                    }
+                }
+            })
+            .collect_vec();
+        (count, new)
+    });
+
+    let r = world.resource_mut(relationships());
+    r.checked_components = count;
+    for (component, kind) in &new {
+        r.kinds.insert(*component, *kind);
+    }
+
+    for (component, _) in new {
+        for event in [
+            ObserverEvent::Added,
+            ObserverEvent::Removed,
+            ObserverEvent::Changed,
+        ] {
+            world.observe(component, event, update_index);
+        }
+
+        // Entities that had the component before it was observed
+        let ids = Query::new(ArchetypeFilter::new().incl_ref(component))
+            .iter(world, None)
+            .map(|ea| ea.id())
+            .collect_vec();
+        for id in ids {
+            let targets = get_targets(world, component, id);
+            world
+                .resource_mut(relationships())
+                .add(component, id, &targets);
+        }
+    }
+}
+
+fn update_index(world: &mut World, change: &ObservedChange) {
+    fn targets(value: &Option<ComponentEntry>) -> &[EntityId] {
+        let Some(value) = value else {
+            return &[];
+        };
+        if let Some(target) = value.try_downcast_ref::<EntityId>() {
+            std::slice::from_ref(target)
+        } else {
+            value
+                .try_downcast_ref::<Vec<EntityId>>()
+                .map(|targets| targets.as_slice())
+                .unwrap_or_default()
+        }
+    }
+
+    if let Some(r) = world.resource_mut_opt(relationships()) {
+        r.remove(change.component, change.id, targets(&change.old));
+        r.add(change.component, change.id, targets(&change.new));
+    }
+}
+
+/// Removes all references to the `despawned` entities.
+fn remove_despawned_targets(world: &mut World, despawned: &[EntityId]) {
+    let Some(r) = world.resource_opt(relationships()) else {
+        return;
+    };
+    if r.sources.is_empty() {
+        return;
+    }
+    // Networked relationships are cleaned up by the server, and the changes are synchronized
+    // to the client
+    let is_client = world.context() == WorldContext::Client;
+
+    let mut references = Vec::new();
+    for (&component, sources) in &r.sources {
+        if is_client && component.has_attribute::<Networked>() {
+            continue;
+        }
+        let kind = r.kinds[&component];
+        for target in despawned {
+            for &source in sources.get(target).into_iter().flatten() {
+                references.push((component, kind, source, *target));
+            }
+        }
+    }
+
+    for (component, kind, source, target) in references {
+        match kind {
+            RelationshipKind::ManyToOne => {
+                world.remove_component(source, component).ok();
+            }
+            RelationshipKind::ManyToMany => {
+                let component = Component::<Vec<EntityId>>::new(component);
+                if let Ok(targets) = world.get_ref(source, component) {
+                    let targets = targets
+                        .iter()
+                        .copied()
+                        .filter(|t| *t != target)
+                        .collect_vec();
+                    world.set(source, component, targets).ok();
+                }
+            }
+        }
+    }
+}
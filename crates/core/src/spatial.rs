@@ -0,0 +1,638 @@
+use std::collections::{HashMap, HashSet};
+
+use ambient_ecs::{
+    components, query, Entity, EntityId, FnSystem, QueryState, Resource, SystemGroup, World,
+};
+use ambient_native_std::shapes::{CullResult, Cullable, Frustum, AABB};
+use glam::{I64Vec3, IVec3, Vec3};
+use ordered_float::OrderedFloat;
+
+use crate::{bounding::world_bounding_aabb, transform::translation};
+
+components!("app", {
+    /// Spatial index over all entities with a `translation` or `world_bounding_aabb`.
+    @[Resource]
+    spatial_index: SpatialIndex,
+    @[Resource]
+    spatial_index_queries: SpatialIndexQueries,
+});
+
+/// The default edge length of a grid cell in the spatial index.
+pub const DEFAULT_CELL_SIZE: f32 = 8.;
+/// Entities whose bounds cover more cells than this are kept in a separate list
+/// that is checked by every query, rather than being inserted into every cell.
+const MAX_CELLS_PER_ENTRY: i64 = 64;
+const MAX_CELL_COORD: f32 = 1e9;
+
+#[derive(Debug, Clone, Copy)]
+struct SpatialEntry {
+    bounds: AABB,
+    /// The range of cells covered by the bounds, or `None` for large entries.
+    cells: Option<(IVec3, IVec3)>,
+}
+
+/// A uniform hash grid over entity bounds, used to answer proximity queries
+/// without visiting every entity in the world.
+///
+/// Entities are indexed by their `world_bounding_aabb` if they have one, or as a point at
+/// their `translation` otherwise. The index is kept up to date by [spatial_systems], so it
+/// reflects the world as of the last time those systems ran, or the last [flush_spatial_index].
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<EntityId>>,
+    entries: HashMap<EntityId, SpatialEntry>,
+    large: HashSet<EntityId>,
+}
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0., "cell size must be positive");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            large: HashSet::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.entries.contains_key(&id)
+    }
+    /// The bounds the entity was indexed with
+    pub fn bounds(&self, id: EntityId) -> Option<AABB> {
+        self.entries.get(&id).map(|entry| entry.bounds)
+    }
+
+    /// Inserts the entity, or moves it if it's already in the index.
+    pub fn insert(&mut self, id: EntityId, bounds: AABB) {
+        let cells = self.cell_range(&bounds);
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.bounds = bounds;
+            if entry.cells == cells {
+                return;
+            }
+        }
+        self.remove(id);
+
+        match cells {
+            Some((min, max)) => {
+                for cell in cell_iter(min, max) {
+                    self.cells.entry(cell).or_default().push(id);
+                }
+            }
+            None => {
+                self.large.insert(id);
+            }
+        }
+        self.entries.insert(id, SpatialEntry { bounds, cells });
+    }
+    pub fn remove(&mut self, id: EntityId) -> Option<AABB> {
+        let entry = self.entries.remove(&id)?;
+        match entry.cells {
+            Some((min, max)) => {
+                for cell in cell_iter(min, max) {
+                    if let Some(ids) = self.cells.get_mut(&cell) {
+                        ids.retain(|x| *x != id);
+                        if ids.is_empty() {
+                            self.cells.remove(&cell);
+                        }
+                    }
+                }
+            }
+            None => {
+                self.large.remove(&id);
+            }
+        }
+        Some(entry.bounds)
+    }
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        self.large.clear();
+    }
+
+    /// All entities whose bounds intersect `aabb`.
+    pub fn query_aabb(&self, aabb: &AABB) -> Vec<EntityId> {
+        self.query_filtered(aabb, |bounds| overlaps(bounds, aabb))
+    }
+    /// All entities whose bounds are closer than `radius` to `center`.
+    pub fn query_sphere(&self, center: Vec3, radius: f32) -> Vec<EntityId> {
+        let aabb = AABB {
+            min: center - Vec3::splat(radius),
+            max: center + Vec3::splat(radius),
+        };
+        self.query_filtered(&aabb, |bounds| {
+            distance_squared(bounds, center) < radius * radius
+        })
+    }
+    /// All entities whose bounds are at least partially inside `frustum`.
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<EntityId> {
+        let mut seen = HashSet::new();
+        let mut res = Vec::new();
+        for (&cell, ids) in &self.cells {
+            let cell_bounds = AABB {
+                min: cell.as_vec3() * self.cell_size,
+                max: (cell + IVec3::ONE).as_vec3() * self.cell_size,
+            };
+            let cull = cell_bounds.cull(frustum);
+            if cull == CullResult::Outside {
+                continue;
+            }
+            for &id in ids {
+                if !seen.insert(id) {
+                    continue;
+                }
+                if cull == CullResult::BContainsA
+                    || self.entries[&id].bounds.cull(frustum) != CullResult::Outside
+                {
+                    res.push(id);
+                }
+            }
+        }
+        for &id in &self.large {
+            if self.entries[&id].bounds.cull(frustum) != CullResult::Outside {
+                res.push(id);
+            }
+        }
+        res
+    }
+    /// The `count` entities closest to `position`, sorted by distance.
+    ///
+    /// The distance to an entity is measured to the closest point of its bounds.
+    pub fn nearest(&self, position: Vec3, count: usize) -> Vec<EntityId> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let mut candidates = self
+            .large
+            .iter()
+            .map(|&id| (self.distance_squared(id, position), id))
+            .collect::<Vec<_>>();
+
+        // Search outwards in shells of cells around `position`. Anything that hasn't been
+        // visited after `ring` shells is at least `ring * cell_size` away.
+        let center = self.cell(position);
+        let mut seen = HashSet::new();
+        let mut ring = 0;
+        loop {
+            let side = 2 * ring as usize + 1;
+            if side.saturating_pow(3) > self.cells.len() * 8 {
+                // Visiting the remaining shells would cost more than a scan
+                return self.nearest_brute_force(position, count);
+            }
+            for cell in shell_iter(center, ring) {
+                for &id in self.cells.get(&cell).into_iter().flatten() {
+                    if seen.insert(id) {
+                        candidates.push((self.distance_squared(id, position), id));
+                    }
+                }
+            }
+            if candidates.len() >= count {
+                candidates.select_nth_unstable_by_key(count - 1, |(d, _)| OrderedFloat(*d));
+                candidates.truncate(count);
+                let searched = ring as f32 * self.cell_size;
+                if candidates[count - 1].0 <= searched * searched {
+                    break;
+                }
+            }
+            if seen.len() + self.large.len() == self.entries.len() {
+                break;
+            }
+            ring += 1;
+        }
+        candidates.sort_by_key(|(d, _)| OrderedFloat(*d));
+        candidates.truncate(count);
+        candidates.into_iter().map(|(_, id)| id).collect()
+    }
+    fn nearest_brute_force(&self, position: Vec3, count: usize) -> Vec<EntityId> {
+        let mut candidates = self
+            .entries
+            .iter()
+            .map(|(&id, entry)| (distance_squared(&entry.bounds, position), id))
+            .collect::<Vec<_>>();
+        if candidates.len() > count {
+            candidates.select_nth_unstable_by_key(count - 1, |(d, _)| OrderedFloat(*d));
+            candidates.truncate(count);
+        }
+        candidates.sort_by_key(|(d, _)| OrderedFloat(*d));
+        candidates.into_iter().map(|(_, id)| id).collect()
+    }
+
+    fn query_filtered(&self, aabb: &AABB, filter: impl Fn(&AABB) -> bool) -> Vec<EntityId> {
+        let mut res = Vec::new();
+        let (min, max) = (self.cell(aabb.min), self.cell(aabb.max));
+        if cell_count(min, max) > self.cells.len() as i64 {
+            // The query covers more cells than are occupied, so just check everything
+            for (&id, entry) in &self.entries {
+                if filter(&entry.bounds) {
+                    res.push(id);
+                }
+            }
+            return res;
+        }
+        for cell in cell_iter(min, max) {
+            for &id in self.cells.get(&cell).into_iter().flatten() {
+                let entry = &self.entries[&id];
+                // An entry spanning several cells is only reported from the first cell
+                // it shares with the query
+                let (entry_min, _) = entry.cells.unwrap();
+                if cell == entry_min.max(min) && filter(&entry.bounds) {
+                    res.push(id);
+                }
+            }
+        }
+        for &id in &self.large {
+            if filter(&self.entries[&id].bounds) {
+                res.push(id);
+            }
+        }
+        res
+    }
+    fn distance_squared(&self, id: EntityId, position: Vec3) -> f32 {
+        distance_squared(&self.entries[&id].bounds, position)
+    }
+    fn cell(&self, position: Vec3) -> IVec3 {
+        // Clamped so that neighbouring cells can always be addressed without overflowing
+        (position / self.cell_size)
+            .floor()
+            .clamp(Vec3::splat(-MAX_CELL_COORD), Vec3::splat(MAX_CELL_COORD))
+            .as_ivec3()
+    }
+    fn cell_range(&self, bounds: &AABB) -> Option<(IVec3, IVec3)> {
+        if !bounds.min.is_finite() || !bounds.max.is_finite() {
+            return None;
+        }
+        let (min, max) = (self.cell(bounds.min), self.cell(bounds.max));
+        if cell_count(min, max) > MAX_CELLS_PER_ENTRY {
+            None
+        } else {
+            Some((min, max))
+        }
+    }
+}
+
+fn overlaps(a: &AABB, b: &AABB) -> bool {
+    a.min.cmple(b.max).all() && b.min.cmple(a.max).all()
+}
+fn distance_squared(bounds: &AABB, position: Vec3) -> f32 {
+    position
+        .clamp(bounds.min, bounds.max.max(bounds.min))
+        .distance_squared(position)
+}
+fn cell_count(min: IVec3, max: IVec3) -> i64 {
+    let size = (max.as_i64vec3() - min.as_i64vec3() + I64Vec3::ONE).max(I64Vec3::ZERO);
+    size.x.saturating_mul(size.y).saturating_mul(size.z)
+}
+fn cell_iter(min: IVec3, max: IVec3) -> impl Iterator<Item = IVec3> {
+    (min.z..=max.z).flat_map(move |z| {
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec3::new(x, y, z)))
+    })
+}
+/// The cells at exactly `ring` steps (Chebyshev distance) from `center`
+fn shell_iter(center: IVec3, ring: i32) -> impl Iterator<Item = IVec3> {
+    cell_iter(center - IVec3::splat(ring), center + IVec3::splat(ring))
+        .filter(move |cell| (*cell - center).abs().max_element() == ring)
+}
+
+fn entity_bounds(world: &World, id: EntityId) -> Option<AABB> {
+    if let Ok(aabb) = world.get(id, world_bounding_aabb()) {
+        Some(aabb)
+    } else if let Ok(position) = world.get(id, translation()) {
+        Some(AABB {
+            min: position,
+            max: position,
+        })
+    } else {
+        None
+    }
+}
+fn update_entities(world: &mut World, ids: Vec<EntityId>) {
+    if ids.is_empty() {
+        return;
+    }
+    let bounds = ids
+        .into_iter()
+        .map(|id| (id, entity_bounds(world, id)))
+        .collect::<Vec<_>>();
+    let index = world.resource_mut(spatial_index());
+    for (id, bounds) in bounds {
+        match bounds {
+            Some(bounds) => index.insert(id, bounds),
+            None => {
+                index.remove(id);
+            }
+        }
+    }
+}
+
+/// The change tracking used to find the entities that [flush_spatial_index] has to update.
+#[derive(Debug, Clone)]
+pub struct SpatialIndexQueries {
+    translation_despawned: QueryState,
+    bounds_despawned: QueryState,
+    translation_changed: QueryState,
+    bounds_changed: QueryState,
+}
+impl Default for SpatialIndexQueries {
+    fn default() -> Self {
+        Self {
+            translation_despawned: QueryState::new(),
+            bounds_despawned: QueryState::new(),
+            translation_changed: QueryState::new(),
+            bounds_changed: QueryState::new(),
+        }
+    }
+}
+
+pub fn spatial_resources() -> Entity {
+    Entity::new()
+        .with(spatial_index(), SpatialIndex::default())
+        .with(spatial_index_queries(), SpatialIndexQueries::default())
+}
+
+/// Updates the [spatial_index] with every entity that was spawned, moved or despawned since
+/// it was last updated. This runs every frame in [spatial_systems]; call it before querying
+/// the index if the result must include changes made earlier in the frame.
+pub fn flush_spatial_index(world: &mut World) {
+    let Some(queries) = world.resource_mut_opt(spatial_index_queries()) else {
+        return;
+    };
+    let mut queries = std::mem::take(queries);
+    let mut ids = Vec::new();
+    ids.extend(
+        query(())
+            .incl(translation())
+            .despawned()
+            .iter(world, Some(&mut queries.translation_despawned))
+            .map(|(id, _)| id),
+    );
+    ids.extend(
+        query(())
+            .incl(world_bounding_aabb())
+            .despawned()
+            .iter(world, Some(&mut queries.bounds_despawned))
+            .map(|(id, _)| id),
+    );
+    ids.extend(
+        query(translation().changed())
+            .iter(world, Some(&mut queries.translation_changed))
+            .map(|(id, _)| id),
+    );
+    ids.extend(
+        query(world_bounding_aabb().changed())
+            .iter(world, Some(&mut queries.bounds_changed))
+            .map(|(id, _)| id),
+    );
+    *world.resource_mut(spatial_index_queries()) = queries;
+    update_entities(world, ids);
+}
+
+/// Keeps the [spatial_index] resource in sync with the world.
+pub fn spatial_systems() -> SystemGroup {
+    SystemGroup::new(
+        "spatial",
+        vec![Box::new(FnSystem::new(|world, _| {
+            flush_spatial_index(world)
+        }))],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{vec3, Mat4};
+
+    use super::*;
+
+    fn point(position: Vec3) -> AABB {
+        AABB {
+            min: position,
+            max: position,
+        }
+    }
+
+    /// An index whose occupied cells outnumber the cells of small queries, so that those go
+    /// through the grid rather than checking every entry
+    fn index_with_filler() -> SpatialIndex {
+        let mut index = SpatialIndex::default();
+        for i in 0..100 {
+            index.insert(EntityId::new(), point(vec3(1000. + i as f32 * 10., 0., 0.)));
+        }
+        index
+    }
+
+    fn sorted(mut ids: Vec<EntityId>) -> Vec<EntityId> {
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn query_aabb_reports_entities_once() {
+        let mut index = index_with_filler();
+        let spanning = EntityId::new();
+        index.insert(
+            spanning,
+            AABB {
+                min: vec3(-4., -4., -4.),
+                max: vec3(12., 4., 4.),
+            },
+        );
+        let large = EntityId::new();
+        index.insert(
+            large,
+            AABB {
+                min: Vec3::splat(-500.),
+                max: Vec3::splat(500.),
+            },
+        );
+        let inside = EntityId::new();
+        index.insert(inside, point(vec3(1., 0., 0.)));
+        index.insert(EntityId::new(), point(vec3(20., 0., 0.)));
+
+        let query = AABB {
+            min: vec3(-1., -1., -1.),
+            max: vec3(9., 1., 1.),
+        };
+        assert!(
+            cell_count(index.cell(query.min), index.cell(query.max)) <= index.cells.len() as i64
+        );
+        let expected = sorted(vec![spanning, large, inside]);
+        assert_eq!(sorted(index.query_aabb(&query)), expected);
+
+        // Without the filler, a taller query covers more cells than are occupied, so it checks
+        // every entry instead
+        for id in index.query_aabb(&AABB {
+            min: vec3(900., -1., -1.),
+            max: vec3(2000., 1., 1.),
+        }) {
+            if id != large {
+                index.remove(id);
+            }
+        }
+        let query = AABB {
+            min: query.min,
+            max: vec3(9., 1., 100.),
+        };
+        assert!(
+            cell_count(index.cell(query.min), index.cell(query.max)) > index.cells.len() as i64
+        );
+        assert_eq!(sorted(index.query_aabb(&query)), expected);
+    }
+
+    #[test]
+    fn moved_and_removed_entities_are_updated() {
+        let mut index = index_with_filler();
+        let id = EntityId::new();
+        index.insert(id, point(vec3(1., 0., 0.)));
+        index.insert(id, point(vec3(50., 0., 0.)));
+        assert_eq!(index.query_sphere(Vec3::ZERO, 5.), vec![]);
+        assert_eq!(index.query_sphere(vec3(50., 0., 0.), 5.), vec![id]);
+
+        assert_eq!(index.remove(id), Some(point(vec3(50., 0., 0.))));
+        assert!(!index.contains(id));
+        assert_eq!(index.query_sphere(vec3(50., 0., 0.), 5.), vec![]);
+    }
+
+    #[test]
+    fn nearest_is_sorted_by_distance_to_bounds() {
+        let mut index = SpatialIndex::default();
+        let ids = (1..=5)
+            .map(|i| {
+                let id = EntityId::new();
+                index.insert(id, point(vec3(i as f32 * 3., 0., 0.)));
+                id
+            })
+            .collect::<Vec<_>>();
+        // Its center is far away, but its bounds are the closest
+        let wide = EntityId::new();
+        index.insert(
+            wide,
+            AABB {
+                min: vec3(-20., 1., 0.),
+                max: vec3(-2., 2., 0.),
+            },
+        );
+
+        assert_eq!(index.nearest(Vec3::ZERO, 0), vec![]);
+        assert_eq!(index.nearest(Vec3::ZERO, 3), vec![wide, ids[0], ids[1]]);
+        let all = index.nearest(Vec3::ZERO, 10);
+        assert_eq!(all.len(), 6);
+        assert_eq!(all[1..], ids[..]);
+    }
+
+    #[test]
+    fn nearest_matches_a_full_scan() {
+        let mut index = SpatialIndex::default();
+        for i in 0..300 {
+            let position = vec3((i * 7 % 97) as f32, (i * 13 % 89) as f32, (i % 11) as f32);
+            index.insert(EntityId::new(), point(position * 3.));
+        }
+        for (position, count) in [
+            (Vec3::ZERO, 1),
+            (vec3(100., 100., 10.), 7),
+            (vec3(-500., 0., 0.), 20),
+            (vec3(150., 130., 15.), 300),
+        ] {
+            let distances = |ids: Vec<EntityId>| {
+                ids.into_iter()
+                    .map(|id| index.distance_squared(id, position))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                distances(index.nearest(position, count)),
+                distances(index.nearest_brute_force(position, count)),
+            );
+        }
+    }
+
+    #[test]
+    fn query_frustum() {
+        let mut index = index_with_filler();
+        // Looking down from above at the square from -10 to 10 on the XY plane
+        let frustum = Frustum::from_projection_view(
+            Mat4::orthographic_rh(-10., 10., -10., 10., 0., 100.)
+                * Mat4::look_at_rh(vec3(0., 0., 50.), Vec3::ZERO, Vec3::Y),
+        );
+        let inside = EntityId::new();
+        index.insert(inside, point(vec3(1., 1., 0.)));
+        let spanning = EntityId::new();
+        index.insert(
+            spanning,
+            AABB {
+                min: vec3(5., -30., -1.),
+                max: vec3(30., 30., 1.),
+            },
+        );
+        let large = EntityId::new();
+        index.insert(
+            large,
+            AABB {
+                min: Vec3::splat(-1000.),
+                max: Vec3::splat(1000.),
+            },
+        );
+        // Outside the frustum, but in a cell that it intersects
+        index.insert(EntityId::new(), point(vec3(15., 0., 0.)));
+        index.insert(EntityId::new(), point(vec3(0., 0., 60.)));
+        index.insert(
+            EntityId::new(),
+            AABB {
+                min: Vec3::splat(3000.),
+                max: Vec3::splat(4000.),
+            },
+        );
+
+        assert_eq!(
+            sorted(index.query_frustum(&frustum)),
+            sorted(vec![inside, spanning, large])
+        );
+    }
+
+    #[test]
+    fn flush_reflects_changes_made_this_frame() {
+        ambient_ecs::init_components();
+        crate::bounding::init_components();
+        init_components();
+        let mut world = World::new("flush_spatial_index", ambient_ecs::WorldContext::Server);
+        world
+            .add_components(world.resource_entity(), spatial_resources())
+            .unwrap();
+        flush_spatial_index(&mut world);
+
+        let id = Entity::new()
+            .with(translation(), vec3(1., 0., 0.))
+            .spawn(&mut world);
+        assert!(world
+            .resource(spatial_index())
+            .query_sphere(Vec3::ZERO, 2.)
+            .is_empty());
+        flush_spatial_index(&mut world);
+        assert_eq!(
+            world.resource(spatial_index()).query_sphere(Vec3::ZERO, 2.),
+            vec![id]
+        );
+
+        world.set(id, translation(), vec3(10., 0., 0.)).unwrap();
+        flush_spatial_index(&mut world);
+        assert!(world
+            .resource(spatial_index())
+            .query_sphere(Vec3::ZERO, 2.)
+            .is_empty());
+
+        world.despawn(id);
+        flush_spatial_index(&mut world);
+        assert!(world
+            .resource(spatial_index())
+            .query_sphere(vec3(10., 0., 0.), 2.)
+            .is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Bound;
+
+use ambient_core::index::{index_resources, index_systems, IndexQuery};
+use ambient_ecs::{
+    components, ArchetypeFilter, ComponentEntry, Entity, EntityId, FrameEvent, Indexed, System,
+    World, WorldContext,
+};
+use itertools::Itertools;
+
+components!("test", {
+    @[Indexed]
+    score: i32,
+    tag: (),
+});
+
+fn init() {
+    init_components();
+    ambient_core::index::init_components();
+}
+
+fn world(name: &'static str) -> World {
+    let mut world = World::new(name, WorldContext::Server);
+    world
+        .add_components(world.resource_entity(), index_resources())
+        .unwrap();
+    world
+}
+
+fn spawn(world: &mut World, value: i32) -> EntityId {
+    world.spawn(Entity::new().with(score(), value))
+}
+
+fn entry(value: i32) -> ComponentEntry {
+    ComponentEntry::new(score(), value)
+}
+
+fn bound(value: Bound<i32>) -> Bound<ComponentEntry> {
+    match value {
+        Bound::Included(value) => Bound::Included(entry(value)),
+        Bound::Excluded(value) => Bound::Excluded(entry(value)),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn range(world: &mut World, start: Bound<i32>, end: Bound<i32>) -> Vec<EntityId> {
+    let mut query = IndexQuery::new(score());
+    query.start = bound(start);
+    query.end = bound(end);
+    query.evaluate(world, 0, None).unwrap()
+}
+
+/// Entities with equal values are ordered by their ID
+fn sorted(ids: impl IntoIterator<Item = EntityId>) -> Vec<EntityId> {
+    ids.into_iter().sorted().collect()
+}
+
+#[test]
+fn bounds_on_duplicate_values() {
+    init();
+    let mut world = world("index_bounds");
+    let a = spawn(&mut world, 1);
+    let b = [spawn(&mut world, 2), spawn(&mut world, 2)];
+    let c = spawn(&mut world, 3);
+
+    use Bound::*;
+    let all_b = sorted(b);
+    assert_eq!(range(&mut world, Included(2), Included(2)), all_b);
+    assert_eq!(range(&mut world, Excluded(2), Unbounded), [c]);
+    assert_eq!(range(&mut world, Unbounded, Excluded(2)), [a]);
+    assert_eq!(
+        range(&mut world, Included(2), Unbounded),
+        [all_b.clone(), vec![c]].concat()
+    );
+    assert_eq!(
+        range(&mut world, Unbounded, Included(2)),
+        [vec![a], all_b].concat()
+    );
+    assert_eq!(range(&mut world, Excluded(1), Excluded(3)), sorted(b));
+    assert_eq!(range(&mut world, Excluded(2), Excluded(2)), []);
+}
+
+#[test]
+fn inverted_range_is_empty() {
+    init();
+    let mut world = world("index_inverted");
+    spawn(&mut world, 1);
+    spawn(&mut world, 2);
+
+    use Bound::*;
+    assert_eq!(range(&mut world, Included(2), Included(1)), []);
+    assert_eq!(range(&mut world, Excluded(2), Excluded(1)), []);
+    assert_eq!(range(&mut world, Included(5), Excluded(0)), []);
+}
+
+#[test]
+fn descending_order() {
+    init();
+    let mut world = world("index_descending");
+    let a = spawn(&mut world, 1);
+    let b = [spawn(&mut world, 2), spawn(&mut world, 2)];
+    let c = spawn(&mut world, 3);
+
+    let mut query = IndexQuery::new(score());
+    query.descending = true;
+    let mut expected = vec![a];
+    expected.extend(sorted(b));
+    expected.push(c);
+    expected.reverse();
+    assert_eq!(query.evaluate(&mut world, 0, None).unwrap(), expected);
+
+    query.end = Bound::Excluded(entry(3));
+    assert_eq!(
+        query.evaluate(&mut world, 0, Some(2)).unwrap(),
+        expected[1..3]
+    );
+}
+
+#[test]
+fn offset_and_limit_apply_after_the_filter() {
+    init();
+    let mut world = world("index_filter");
+    let tagged = (0..6)
+        .map(|value| {
+            let id = spawn(&mut world, value);
+            if value % 2 == 0 {
+                world.add_component(id, tag(), ()).unwrap();
+            }
+            (id, value % 2 == 0)
+        })
+        .collect_vec();
+    let with_tag = tagged
+        .iter()
+        .filter(|(_, t)| *t)
+        .map(|(id, _)| *id)
+        .collect_vec();
+    let without_tag = tagged
+        .iter()
+        .filter(|(_, t)| !*t)
+        .map(|(id, _)| *id)
+        .collect_vec();
+
+    let mut query = IndexQuery::new(score());
+    query.filter = ArchetypeFilter::new().incl(tag());
+    assert_eq!(query.evaluate(&mut world, 0, None).unwrap(), with_tag);
+    assert_eq!(
+        query.evaluate(&mut world, 1, Some(1)).unwrap(),
+        [with_tag[1]]
+    );
+    assert_eq!(query.evaluate(&mut world, 1, None).unwrap(), with_tag[1..]);
+    assert_eq!(query.evaluate(&mut world, 3, None).unwrap(), []);
+
+    query.filter = ArchetypeFilter::new().excl(tag());
+    assert_eq!(query.evaluate(&mut world, 0, None).unwrap(), without_tag);
+    assert_eq!(
+        query.evaluate(&mut world, 2, Some(5)).unwrap(),
+        [without_tag[2]]
+    );
+}
+
+#[test]
+fn index_follows_changes_and_despawns() {
+    init();
+    let mut world = world("index_changes");
+    let mut systems = index_systems();
+    let a = spawn(&mut world, 1);
+    let b = spawn(&mut world, 2);
+    let c = spawn(&mut world, 3);
+    systems.run(&mut world, &FrameEvent);
+    assert_eq!(
+        range(&mut world, Bound::Unbounded, Bound::Unbounded),
+        [a, b, c]
+    );
+
+    *world.get_mut(a, score()).unwrap() = 4;
+    assert_eq!(
+        range(&mut world, Bound::Unbounded, Bound::Unbounded),
+        [b, c, a]
+    );
+    assert_eq!(
+        range(&mut world, Bound::Included(1), Bound::Included(1)),
+        []
+    );
+
+    world.set(c, score(), 0).unwrap();
+    world.despawn(b);
+    systems.run(&mut world, &FrameEvent);
+    assert_eq!(
+        range(&mut world, Bound::Unbounded, Bound::Unbounded),
+        [c, a]
+    );
+
+    world.remove_component(c, score()).unwrap();
+    let d = spawn(&mut world, 5);
+    assert_eq!(
+        range(&mut world, Bound::Unbounded, Bound::Unbounded),
+        [a, d]
+    );
+}
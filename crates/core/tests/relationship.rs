@@ -0,0 +1,178 @@
+use ambient_core::relationship::{
+    get_related, refers_to, relationship_resources, relationship_systems, relationships,
+    RelationshipKind,
+};
+use ambient_ecs::{
+    components, ArchetypeFilter, ComponentDesc, Entity, EntityId, FrameEvent, Networked, Query,
+    Relationship, System, World, WorldContext,
+};
+use itertools::Itertools;
+
+components!("test", {
+    @[Relationship]
+    target: EntityId,
+    @[Relationship]
+    targets: Vec<EntityId>,
+    @[Networked, Relationship]
+    networked_target: EntityId,
+    @[Networked, Relationship]
+    networked_targets: Vec<EntityId>,
+    not_a_relationship: EntityId,
+});
+
+fn init() {
+    init_components();
+    ambient_core::relationship::init_components();
+}
+
+fn world(name: &'static str, context: WorldContext) -> World {
+    let mut world = World::new(name, context);
+    world
+        .add_components(world.resource_entity(), relationship_resources())
+        .unwrap();
+    world
+}
+
+fn related(world: &World, component: impl Into<ComponentDesc>, id: EntityId) -> Vec<EntityId> {
+    get_related(world, component, id)
+        .into_iter()
+        .sorted()
+        .collect()
+}
+
+#[test]
+fn index_follows_changes() {
+    init();
+    let mut world = world("index_follows_changes", WorldContext::Server);
+    let mut systems = relationship_systems();
+    systems.run(&mut world, &FrameEvent);
+    let r = world.resource(relationships());
+    assert_eq!(r.kind(target()), Some(RelationshipKind::ManyToOne));
+    assert_eq!(r.kind(targets()), Some(RelationshipKind::ManyToMany));
+    assert_eq!(r.kind(not_a_relationship()), None);
+
+    let (x, y, z) = (EntityId::new(), EntityId::new(), EntityId::new());
+    let a = world.spawn(Entity::new().with(target(), x).with(targets(), vec![x, y]));
+    let b = world.spawn(Entity::new().with(target(), x));
+    world.spawn(Entity::new().with(not_a_relationship(), x));
+    world.flush_observers();
+    assert_eq!(
+        related(&world, target(), x),
+        [a, b].into_iter().sorted().collect_vec()
+    );
+    assert_eq!(related(&world, targets(), x), [a]);
+    assert_eq!(related(&world, targets(), y), [a]);
+    assert_eq!(related(&world, not_a_relationship(), x), []);
+
+    world.set(a, target(), y).unwrap();
+    world.set(a, targets(), vec![y, z]).unwrap();
+    world.flush_observers();
+    assert_eq!(related(&world, target(), x), [b]);
+    assert_eq!(related(&world, target(), y), [a]);
+    assert_eq!(related(&world, targets(), x), []);
+    assert_eq!(related(&world, targets(), z), [a]);
+
+    world.remove_component(b, target()).unwrap();
+    world.despawn(a);
+    world.flush_observers();
+    assert_eq!(related(&world, target(), x), []);
+    assert_eq!(related(&world, target(), y), []);
+    assert_eq!(related(&world, targets(), y), []);
+    assert_eq!(related(&world, targets(), z), []);
+}
+
+#[test]
+fn existing_components_are_indexed_when_registered() {
+    init();
+    let mut world = world("relationship_backfill", WorldContext::Server);
+    let mut systems = relationship_systems();
+    let x = EntityId::new();
+    let a = world.spawn(Entity::new().with(target(), x));
+    let b = world.spawn(Entity::new().with(targets(), vec![x]));
+    world.flush_observers();
+    assert_eq!(related(&world, target(), x), []);
+
+    systems.run(&mut world, &FrameEvent);
+    assert_eq!(related(&world, target(), x), [a]);
+    assert_eq!(related(&world, targets(), x), [b]);
+}
+
+#[test]
+fn despawned_targets_are_removed() {
+    init();
+    let mut world = world("despawned_targets", WorldContext::Server);
+    let mut systems = relationship_systems();
+    systems.run(&mut world, &FrameEvent);
+    let x = world.spawn(Entity::new());
+    let y = world.spawn(Entity::new());
+    let a = world.spawn(
+        Entity::new()
+            .with(target(), x)
+            .with(targets(), vec![x, y, x]),
+    );
+    let b = world.spawn(Entity::new().with(target(), y));
+    systems.run(&mut world, &FrameEvent);
+
+    world.despawn(x);
+    systems.run(&mut world, &FrameEvent);
+    // Many-to-one relationships to the entity are removed
+    assert!(!world.has_component(a, target()));
+    assert_eq!(world.get(b, target()), Ok(y));
+    // Many-to-many relationships keep their other targets
+    assert_eq!(world.get_ref(a, targets()), Ok(&vec![y]));
+    assert_eq!(related(&world, targets(), x), []);
+    assert_eq!(related(&world, targets(), y), [a]);
+}
+
+#[test]
+fn clients_leave_networked_relationships_to_the_server() {
+    init();
+    let mut world = world("client_relationships", WorldContext::Client);
+    let mut systems = relationship_systems();
+    systems.run(&mut world, &FrameEvent);
+    let x = world.spawn(Entity::new());
+    let a = world.spawn(
+        Entity::new()
+            .with(target(), x)
+            .with(targets(), vec![x])
+            .with(networked_target(), x)
+            .with(networked_targets(), vec![x]),
+    );
+    systems.run(&mut world, &FrameEvent);
+
+    world.despawn(x);
+    systems.run(&mut world, &FrameEvent);
+    assert!(!world.has_component(a, target()));
+    assert_eq!(world.get_ref(a, targets()), Ok(&vec![]));
+    assert_eq!(world.get(a, networked_target()), Ok(x));
+    assert_eq!(world.get_ref(a, networked_targets()), Ok(&vec![x]));
+}
+
+#[test]
+fn related_query_filter() {
+    init();
+    let mut world = world("related_query_filter", WorldContext::Server);
+    let (x, y) = (EntityId::new(), EntityId::new());
+    let a = world.spawn(Entity::new().with(target(), x).with(targets(), vec![y]));
+    let b = world.spawn(Entity::new().with(target(), y).with(targets(), vec![x, y]));
+    world.spawn(Entity::new().with(not_a_relationship(), x));
+
+    // Queries are filtered like `query_related` in the wasm host: the relationship component is
+    // required, and the entity must refer to the target through it
+    let filtered = |component: ComponentDesc, target: EntityId| {
+        Query::new(ArchetypeFilter::new().incl_ref(component))
+            .iter(&world, None)
+            .map(|ea| ea.id())
+            .filter(|id| refers_to(&world, component, *id, target))
+            .sorted()
+            .collect_vec()
+    };
+    assert_eq!(filtered(target().desc(), x), [a]);
+    assert_eq!(filtered(target().desc(), y), [b]);
+    assert_eq!(filtered(targets().desc(), x), [b]);
+    assert_eq!(
+        filtered(targets().desc(), y),
+        [a, b].into_iter().sorted().collect_vec()
+    );
+    assert_eq!(filtered(not_a_relationship().desc(), y), []);
+}
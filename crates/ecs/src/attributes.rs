@@ -8,7 +8,7 @@ use std::{
 use as_any::{AsAny, Downcast};
 use serde::{Deserialize, Serialize};
 
-use crate::{ComponentDesc, ComponentEntry, ComponentValue, EnumComponent};
+use crate::{ComponentDesc, ComponentEntry, ComponentValue, EnumComponent, PrimitiveComponentType};
 
 /// Represents a single attribute attached to a component
 pub trait ComponentAttribute: 'static + Send + Sync + AsAny {}
@@ -293,6 +293,40 @@ impl<T: ComponentValue> AttributeConstructor<T, ()> for MaybeResource {
     }
 }
 
+/// Indicates that this component refers to other entities: an `EntityId` for a many-to-one
+/// relationship, or a `Vec<EntityId>` for a many-to-many relationship.
+///
+/// References to entities that are despawned are removed, and the entities referring to an
+/// entity can be looked up in reverse.
+#[derive(Clone)]
+pub struct Relationship;
+impl ComponentAttribute for Relationship {}
+impl<T: ComponentValue> AttributeConstructor<T, ()> for Relationship {
+    fn construct(store: &mut AttributeStore, _: ()) {
+        store.set(Self)
+    }
+}
+
+/// Indicates that this component should be kept in a sorted index, so that entities can be
+/// looked up in order of its value, or within a range of values.
+///
+/// Only components with a scalar type (numbers, `bool`, `String`, `EntityId` or `Duration`)
+/// can be indexed.
+#[derive(Clone)]
+pub struct Indexed;
+impl ComponentAttribute for Indexed {}
+impl<T: ComponentValue> AttributeConstructor<T, ()> for Indexed {
+    fn construct(store: &mut AttributeStore, _: ()) {
+        store.set(Self)
+    }
+}
+
+/// This component stores a struct as its serialized bytes. Contains the types of its fields in
+/// order, with the fields of nested structs in place of the struct.
+#[derive(Clone, Debug)]
+pub struct StructFields(pub Vec<PrimitiveComponentType>);
+impl ComponentAttribute for StructFields {}
+
 /// This component can be converted to/from a U32.
 pub struct Enum {
     pub to_u32: fn(&dyn Any) -> u32,
@@ -44,6 +44,9 @@ pub struct ExternalComponentDesc {
     pub name: Option<String>,
     pub description: Option<String>,
     pub attributes: ExternalComponentAttributes,
+    /// The layout of a struct component; see [StructFields].
+    #[serde(default)]
+    pub struct_fields: Option<Vec<PrimitiveComponentType>>,
 }
 
 impl From<&PrimitiveComponent> for ExternalComponentDesc {
@@ -54,6 +57,7 @@ impl From<&PrimitiveComponent> for ExternalComponentDesc {
             name: pc.desc.attribute::<Name>().map(|n| n.0.clone()),
             description: pc.desc.attribute::<Description>().map(|n| n.0.clone()),
             attributes: ExternalComponentAttributes::from_existing_component(pc.desc),
+            struct_fields: pc.desc.attribute::<StructFields>().map(|f| f.0.clone()),
         }
     }
 }
@@ -118,7 +122,9 @@ define_external_component_attribute! {
         networked: Networked,
         resource: Resource,
         store: Store,
-        maybe_resource: MaybeResource
+        maybe_resource: MaybeResource,
+        relationship: Relationship,
+        indexed: Indexed
     },
     special: {
         enum_: Enum
@@ -151,6 +157,7 @@ impl ComponentRegistry {
                 desc.name.as_deref(),
                 desc.description.as_deref(),
                 desc.attributes,
+                desc.struct_fields,
             );
         }
 
@@ -162,6 +162,9 @@ impl Entity {
     pub fn iter(&self) -> impl Iterator<Item = &ComponentEntry> {
         self.content.iter()
     }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ComponentEntry> {
+        self.content.iter_mut()
+    }
 
     pub fn filter(&mut self, filter: &dyn Fn(ComponentDesc) -> bool) {
         let comps = self.components();
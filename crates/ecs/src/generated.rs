@@ -21,8 +21,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -37,8 +37,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -53,8 +53,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -69,8 +69,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -85,8 +85,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -101,8 +101,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -117,8 +117,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -212,8 +212,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -221,7 +221,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("layout" , { # [doc = "**Align horizontal**: Layout alignment: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align horizontal"] , Description ["Layout alignment: horizontal."]] align_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Align vertical**: Layout alignment: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align vertical"] , Description ["Layout alignment: vertical."]] align_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Docking**: Layout docking.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Docking"] , Description ["Layout docking."]] docking : crate :: generated :: raw :: ambient_core :: layout :: types :: Docking , # [doc = "**Fit horizontal**: Layout fit: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit horizontal"] , Description ["Layout fit: horizontal."]] fit_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Fit vertical**: Layout fit: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit vertical"] , Description ["Layout fit: vertical."]] fit_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Layout**: Layout.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Layout"] , Description ["Layout."]] layout : crate :: generated :: raw :: ambient_core :: layout :: types :: Layout , # [doc = "**Orientation**: Layout orientation.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Orientation"] , Description ["Layout orientation."]] orientation : crate :: generated :: raw :: ambient_core :: layout :: types :: Orientation , # [doc = "**Is book file**: This is a file in a `layout_bookcase`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is book file"] , Description ["This is a file in a `layout_bookcase`."]] is_book_file : () , # [doc = "**Margin**: Layout margin: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Margin"] , Description ["Layout margin: [top, right, bottom, left]."]] margin : Vec4 , # [doc = "**Padding**: Layout padding: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Padding"] , Description ["Layout padding: [top, right, bottom, left]."]] padding : Vec4 , # [doc = "**Mesh to local from size**: Update the `mesh_to_local` based on the width and height of this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Mesh to local from size"] , Description ["Update the `mesh_to_local` based on the width and height of this entity."]] mesh_to_local_from_size : () , # [doc = "**Minimum height**: The minimum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum height"] , Description ["The minimum height of a UI element."]] min_height : f32 , # [doc = "**Minimum width**: The minimum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum width"] , Description ["The minimum width of a UI element."]] min_width : f32 , # [doc = "**Maximum height**: The maximum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum height"] , Description ["The maximum height of a UI element."]] max_height : f32 , # [doc = "**Maximum width**: The maximum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum width"] , Description ["The maximum width of a UI element."]] max_width : f32 , # [doc = "**Is screen**: This entity will be treated as a screen. Used by the Screen ui component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is screen"] , Description ["This entity will be treated as a screen. Used by the Screen ui component."]] is_screen : () , # [doc = "**Space between items**: Space between items in a layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Space between items"] , Description ["Space between items in a layout."]] space_between_items : f32 , # [doc = "**Width**: The width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Width"] , Description ["The width of a UI element."]] width : f32 , # [doc = "**Height**: The height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Height"] , Description ["The height of a UI element."]] height : f32 , # [doc = "**GPU UI size**: Upload the width and height of this UI element to the GPU.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["GPU UI size"] , Description ["Upload the width and height of this UI element to the GPU."]] gpu_ui_size : Vec4 , });
+                components ! ("layout" , { # [doc = "**Align horizontal**: Layout alignment: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align horizontal"] , Description ["Layout alignment: horizontal."]] align_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Align vertical**: Layout alignment: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align vertical"] , Description ["Layout alignment: vertical."]] align_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Docking**: Layout docking.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Docking"] , Description ["Layout docking."]] docking : crate :: generated :: raw :: ambient_core :: layout :: types :: Docking , # [doc = "**Fit horizontal**: Layout fit: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit horizontal"] , Description ["Layout fit: horizontal."]] fit_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Fit vertical**: Layout fit: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit vertical"] , Description ["Layout fit: vertical."]] fit_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Layout**: Layout.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Layout"] , Description ["Layout."]] layout : crate :: generated :: raw :: ambient_core :: layout :: types :: Layout , # [doc = "**Orientation**: Layout orientation.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Orientation"] , Description ["Layout orientation."]] orientation : crate :: generated :: raw :: ambient_core :: layout :: types :: Orientation , # [doc = "**Is book file**: This is a file in a `layout_bookcase`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is book file"] , Description ["This is a file in a `layout_bookcase`."]] is_book_file : () , # [doc = "**Margin**: Layout margin: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Margin"] , Description ["Layout margin: [top, right, bottom, left]."]] margin : Vec4 , # [doc = "**Padding**: Layout padding: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Padding"] , Description ["Layout padding: [top, right, bottom, left]."]] padding : Vec4 , # [doc = "**Mesh to local from size**: Update the `mesh_to_local` based on the width and height of this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Mesh to local from size"] , Description ["Update the `mesh_to_local` based on the width and height of this entity."]] mesh_to_local_from_size : () , # [doc = "**Minimum height**: The minimum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum height"] , Description ["The minimum height of a UI element."]] min_height : f32 , # [doc = "**Minimum width**: The minimum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum width"] , Description ["The minimum width of a UI element."]] min_width : f32 , # [doc = "**Maximum height**: The maximum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum height"] , Description ["The maximum height of a UI element."]] max_height : f32 , # [doc = "**Maximum width**: The maximum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum width"] , Description ["The maximum width of a UI element."]] max_width : f32 , # [doc = "**Is screen**: This entity will be treated as a screen. Used by the Screen ui component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is screen"] , Description ["This entity will be treated as a screen. Used by the Screen ui component."]] is_screen : () , # [doc = "**Space between items**: Space between items in a layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Space between items"] , Description ["Space between items in a layout."]] space_between_items : f32 , # [doc = "**Width**: The width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Width"] , Description ["The width of a UI element."]] width : f32 , # [doc = "**Height**: The height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Height"] , Description ["The height of a UI element."]] height : f32 , # [doc = "**GPU UI size**: Upload the width and height of this UI element to the GPU.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["GPU UI size"] , Description ["Upload the width and height of this UI element to the GPU."]] gpu_ui_size : Vec4 , # [doc = "**Grid columns**: The column tracks of a `Grid` layout, from left to right.\n\nEach track is `[pixels, fraction]`: `[100, 0]` is 100 pixels wide, `[0, 1]` takes one fraction of the remaining space, `[80, 1]` does the same but is at least 80 pixels wide, and `[0, 0]` fits its content.\n\nIf not attached, the grid has a single column that takes all of the space.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid columns"] , Description ["The column tracks of a `Grid` layout, from left to right.\nEach track is `[pixels, fraction]`: `[100, 0]` is 100 pixels wide, `[0, 1]` takes one fraction of the remaining space, `[80, 1]` does the same but is at least 80 pixels wide, and `[0, 0]` fits its content.\nIf not attached, the grid has a single column that takes all of the space."]] grid_columns : Vec :: < Vec2 > , # [doc = "**Grid rows**: The row tracks of a `Grid` layout, from top to bottom. See `grid_columns` for the format of each track.\n\nRows that are needed to fit the children but are not specified fit their content.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid rows"] , Description ["The row tracks of a `Grid` layout, from top to bottom. See `grid_columns` for the format of each track.\nRows that are needed to fit the children but are not specified fit their content."]] grid_rows : Vec :: < Vec2 > , # [doc = "**Grid gap**: Space between the columns (x) and the rows (y) of a `Grid` layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid gap"] , Description ["Space between the columns (x) and the rows (y) of a `Grid` layout."]] grid_gap : Vec2 , # [doc = "**Grid column**: The column this element is placed in, when it is a child of a `Grid` layout.\n\nChildren without a `grid_column` or `grid_row` are placed in the first free cells, row by row.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid column"] , Description ["The column this element is placed in, when it is a child of a `Grid` layout.\nChildren without a `grid_column` or `grid_row` are placed in the first free cells, row by row."]] grid_column : u32 , # [doc = "**Grid row**: The row this element is placed in, when it is a child of a `Grid` layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid row"] , Description ["The row this element is placed in, when it is a child of a `Grid` layout."]] grid_row : u32 , # [doc = "**Grid column span**: The number of columns this element spans, when it is a child of a `Grid` layout. Defaults to 1.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid column span"] , Description ["The number of columns this element spans, when it is a child of a `Grid` layout. Defaults to 1."]] grid_column_span : u32 , # [doc = "**Grid row span**: The number of rows this element spans, when it is a child of a `Grid` layout. Defaults to 1.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid row span"] , Description ["The number of rows this element spans, when it is a child of a `Grid` layout. Defaults to 1."]] grid_row_span : u32 , # [doc = "**Flex grow**: How much of the remaining space this element takes, relative to its siblings, when it is a child of a `Flow` layout.\n\nElements with a `flex_grow`, `flex_shrink` or `flex_basis` are sized by the `Flow` along its orientation, and the `Flow` no longer wraps them onto multiple lines. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Flex grow"] , Description ["How much of the remaining space this element takes, relative to its siblings, when it is a child of a `Flow` layout.\nElements with a `flex_grow`, `flex_shrink` or `flex_basis` are sized by the `Flow` along its orientation, and the `Flow` no longer wraps them onto multiple lines. Defaults to 0."]] flex_grow : f32 , # [doc = "**Flex shrink**: How much this element shrinks, relative to its siblings and its `flex_basis`, when the children of a `Flow` layout do not fit. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Flex shrink"] , Description ["How much this element shrinks, relative to its siblings and its `flex_basis`, when the children of a `Flow` layout do not fit. Defaults to 0."]] flex_shrink : f32 , # [doc = "**Flex basis**: The size of this element along the orientation of its parent `Flow` layout, before any remaining space is distributed with `flex_grow` and `flex_shrink`. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Flex basis"] , Description ["The size of this element along the orientation of its parent `Flow` layout, before any remaining space is distributed with `flex_grow` and `flex_shrink`. Defaults to 0."]] flex_basis : f32 , });
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -479,6 +479,8 @@ mod raw {
                     Bookcase,
                     #[doc = "Width to children."]
                     WidthToChildren,
+                    #[doc = "Grid layout with column and row tracks."]
+                    Grid,
                 }
                 impl crate::EnumComponent for Layout {
                     fn to_u32(&self) -> u32 {
@@ -487,6 +489,7 @@ mod raw {
                             Self::Dock => Layout::Dock as u32,
                             Self::Bookcase => Layout::Bookcase as u32,
                             Self::WidthToChildren => Layout::WidthToChildren as u32,
+                            Self::Grid => Layout::Grid as u32,
                         }
                     }
                     fn from_u32(value: u32) -> Option<Self> {
@@ -502,6 +505,9 @@ mod raw {
                         if value == Layout::WidthToChildren as u32 {
                             return Some(Self::WidthToChildren);
                         }
+                        if value == Layout::Grid as u32 {
+                            return Some(Self::Grid);
+                        }
                         None
                     }
                 }
@@ -525,8 +531,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -541,8 +547,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -557,8 +563,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -566,7 +572,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("package" , { # [doc = "**Main Package ID**: The ID of the main package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Main Package ID"] , Description ["The ID of the main package."]] main_package_id : EntityId , # [doc = "**Is Package**: Whether or not this entity is a package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is Package"] , Description ["Whether or not this entity is a package."]] is_package : () , # [doc = "**Enabled**: Whether or not this package is enabled.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Enabled"] , Description ["Whether or not this package is enabled."]] enabled : bool , # [doc = "**ID**: The ID of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["ID"] , Description ["The ID of the package."]] id : String , # [doc = "**Name**: The name of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Name"] , Description ["The name of the package."]] name : String , # [doc = "**Version**: The version of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Version"] , Description ["The version of the package."]] version : String , # [doc = "**Authors**: The authors of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Authors"] , Description ["The authors of the package."]] authors : Vec :: < String > , # [doc = "**Description**: The description of the package. If not attached, the package does not have a description.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Description"] , Description ["The description of the package. If not attached, the package does not have a description."]] description : String , # [doc = "**Repository**: The repository of the package. If not attached, the package does not have a repository.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Repository"] , Description ["The repository of the package. If not attached, the package does not have a repository."]] repository : String , # [doc = "**For Playables**: The playable IDs that this package is for. This package must be a `Mod`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["For Playables"] , Description ["The playable IDs that this package is for. This package must be a `Mod`."]] for_playables : Vec :: < String > , # [doc = "**Asset URL**: The asset URL (i.e. where the built assets are) of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Asset URL"] , Description ["The asset URL (i.e. where the built assets are) of the package."]] asset_url : String , # [doc = "**Client Modules**: The clientside WASM modules spawned by this package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Client Modules"] , Description ["The clientside WASM modules spawned by this package."]] client_modules : Vec :: < EntityId > , # [doc = "**Server Modules**: The serverside WASM modules spawned by this package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Server Modules"] , Description ["The serverside WASM modules spawned by this package."]] server_modules : Vec :: < EntityId > , });
+                components ! ("package" , { # [doc = "**Main Package ID**: The ID of the main package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Main Package ID"] , Description ["The ID of the main package."]] main_package_id : EntityId , # [doc = "**Is Package**: Whether or not this entity is a package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is Package"] , Description ["Whether or not this entity is a package."]] is_package : () , # [doc = "**Enabled**: Whether or not this package is enabled.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Enabled"] , Description ["Whether or not this package is enabled."]] enabled : bool , # [doc = "**ID**: The ID of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["ID"] , Description ["The ID of the package."]] id : String , # [doc = "**Name**: The name of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Name"] , Description ["The name of the package."]] name : String , # [doc = "**Version**: The version of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Version"] , Description ["The version of the package."]] version : String , # [doc = "**Authors**: The authors of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Authors"] , Description ["The authors of the package."]] authors : Vec :: < String > , # [doc = "**Description**: The description of the package. If not attached, the package does not have a description.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Description"] , Description ["The description of the package. If not attached, the package does not have a description."]] description : String , # [doc = "**Repository**: The repository of the package. If not attached, the package does not have a repository.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Repository"] , Description ["The repository of the package. If not attached, the package does not have a repository."]] repository : String , # [doc = "**For Playables**: The playable IDs that this package is for. This package must be a `Mod`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["For Playables"] , Description ["The playable IDs that this package is for. This package must be a `Mod`."]] for_playables : Vec :: < String > , # [doc = "**Asset URL**: The asset URL (i.e. where the built assets are) of the package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Asset URL"] , Description ["The asset URL (i.e. where the built assets are) of the package."]] asset_url : String , # [doc = "**Client Modules**: The clientside WASM modules spawned by this package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Client Modules"] , Description ["The clientside WASM modules spawned by this package."]] client_modules : Vec :: < EntityId > , # [doc = "**Server Modules**: The serverside WASM modules spawned by this package.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Server Modules"] , Description ["The serverside WASM modules spawned by this package."]] server_modules : Vec :: < EntityId > , # [doc = "**Input Actions**: The input actions declared in the package's manifest, mapped to their comma-separated default bindings (e.g. `Key:Space, Gamepad:South`). If not attached, the package does not declare any actions.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Input Actions"] , Description ["The input actions declared in the package's manifest, mapped to their comma-separated default bindings (e.g. `Key:Space, Gamepad:South`). If not attached, the package does not declare any actions."]] input_actions : std :: collections :: HashMap :: < String , String > , });
             }
             #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
             #[doc = r" and with other modules."]
@@ -715,8 +721,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -724,15 +730,82 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("physics" , { # [doc = "**Angular velocity**: Angular velocity (radians/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's angular velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Angular velocity"] , Description ["Angular velocity (radians/second) of this entity in the physics scene.\nUpdating this component will update the entity's angular velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead."]] angular_velocity : Vec3 , # [doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cube collider"] , Description ["If attached, this entity will have a cube physics collider.\n`x, y, z` is the size of the cube."]] cube_collider : Vec3 , # [doc = "**Character controller height**: The height of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller height"] , Description ["The height of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider."]] character_controller_height : f32 , # [doc = "**Character controller radius**: The radius of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller radius"] , Description ["The radius of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider."]] character_controller_radius : f32 , # [doc = "**Collider from URL**: This entity will load its physics collider from the URL.\n\nThe value is the URL to load from.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider from URL"] , Description ["This entity will load its physics collider from the URL.\nThe value is the URL to load from."]] collider_from_url : String , # [doc = "**Collider loaded**: This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider loaded"] , Description ["This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`)."]] collider_loaded : () , # [doc = "**Collider loads**: Contains all colliders that were loaded in this physics tick.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Collider loads"] , Description ["Contains all colliders that were loaded in this physics tick."]] collider_loads : Vec :: < EntityId > , # [doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact offset"] , Description ["Contact offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene."]] contact_offset : f32 , # [doc = "**Density**: The density of this entity.\n\nThis is used to update the `mass` when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Density"] , Description ["The density of this entity.\nThis is used to update the `mass` when the entity is rescaled."]] density : f32 , # [doc = "**Dynamic**: If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Dynamic"] , Description ["If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static."]] dynamic : bool , # [doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic"] , Description ["If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally."]] kinematic : () , # [doc = "**Linear velocity**: Linear velocity (meters/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's linear velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Linear velocity"] , Description ["Linear velocity (meters/second) of this entity in the physics scene.\nUpdating this component will update the entity's linear velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead."]] linear_velocity : Vec3 , # [doc = "**Make physics static**: All physics objects will be made static when loaded.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Make physics static"] , Description ["All physics objects will be made static when loaded."]] make_physics_static : bool , # [doc = "**Mass**: The mass of this entity, measured in kilograms.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Mass"] , Description ["The mass of this entity, measured in kilograms."]] mass : f32 , # [doc = "**Physics controlled**: If attached, this entity will be controlled by physics.\n\nNote that this requires the entity to have a collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Physics controlled"] , Description ["If attached, this entity will be controlled by physics.\nNote that this requires the entity to have a collider."]] physics_controlled : () , # [doc = "**Plane collider**: If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Plane collider"] , Description ["If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead."]] plane_collider : () , # [doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rest offset"] , Description ["Rest offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene."]] rest_offset : f32 , # [doc = "**Sphere collider**: If attached, this entity will have a sphere physics collider.\n\nThe value corresponds to the radius of the sphere.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sphere collider"] , Description ["If attached, this entity will have a sphere physics collider.\nThe value corresponds to the radius of the sphere."]] sphere_collider : f32 , # [doc = "**Unit mass**: The mass of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit mass"] , Description ["The mass of a character/unit."]] unit_mass : f32 , # [doc = "**Unit velocity**: The velocity of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit velocity"] , Description ["The velocity of a character/unit."]] unit_velocity : Vec3 , # [doc = "**Unit yaw**: The yaw of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit yaw"] , Description ["The yaw of a character/unit."]] unit_yaw : f32 , # [doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Visualize collider"] , Description ["If attached, the collider will be rendered.\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n"]] visualize_collider : () , });
+                components ! ("physics" , { # [doc = "**Angular velocity**: Angular velocity (radians/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's angular velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Angular velocity"] , Description ["Angular velocity (radians/second) of this entity in the physics scene.\nUpdating this component will update the entity's angular velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead."]] angular_velocity : Vec3 , # [doc = "**CCD**: Whether swept continuous collision detection (CCD) is enabled for this dynamic entity, which stops fast-moving objects from passing through thin colliders.\n\nIf not attached, it is enabled for all dynamic entities that are not kinematic. It is not supported for kinematic entities; use `speculative_ccd` for those instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["CCD"] , Description ["Whether swept continuous collision detection (CCD) is enabled for this dynamic entity, which stops fast-moving objects from passing through thin colliders.\nIf not attached, it is enabled for all dynamic entities that are not kinematic. It is not supported for kinematic entities; use `speculative_ccd` for those instead."]] ccd : bool , # [doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cube collider"] , Description ["If attached, this entity will have a cube physics collider.\n`x, y, z` is the size of the cube."]] cube_collider : Vec3 , # [doc = "**Character controller height**: The height of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller height"] , Description ["The height of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider."]] character_controller_height : f32 , # [doc = "**Character controller radius**: The radius of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller radius"] , Description ["The radius of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider."]] character_controller_radius : f32 , # [doc = "**Collider from URL**: This entity will load its physics collider from the URL.\n\nThe value is the URL to load from.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider from URL"] , Description ["This entity will load its physics collider from the URL.\nThe value is the URL to load from."]] collider_from_url : String , # [doc = "**Collider loaded**: This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider loaded"] , Description ["This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`)."]] collider_loaded : () , # [doc = "**Collider loads**: Contains all colliders that were loaded in this physics tick.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Collider loads"] , Description ["Contains all colliders that were loaded in this physics tick."]] collider_loads : Vec :: < EntityId > , # [doc = "**Collision layer**: The collision layers this entity's collider is on, as a bitmask (one bit per layer).\n\nLayers can be named in the package manifest under `[collision_layers]`. If not attached, the collider is on layer 0 only.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"] @ [Debuggable , Networked , Store , Name ["Collision layer"] , Description ["The collision layers this entity's collider is on, as a bitmask (one bit per layer).\nLayers can be named in the package manifest under `[collision_layers]`. If not attached, the collider is on layer 0 only."]] collision_layer : u32 , # [doc = "**Collision mask**: The collision layers this entity's collider collides with, as a bitmask (one bit per layer).\n\nTwo colliders only collide if each one's `collision_layer` overlaps the other's `collision_mask`. If not attached, the collider collides with all layers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 4294967295"] @ [Debuggable , Networked , Store , Name ["Collision mask"] , Description ["The collision layers this entity's collider collides with, as a bitmask (one bit per layer).\nTwo colliders only collide if each one's `collision_layer` overlaps the other's `collision_mask`. If not attached, the collider collides with all layers."]] collision_mask : u32 , # [doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact offset"] , Description ["Contact offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene."]] contact_offset : f32 , # [doc = "**Density**: The density of this entity.\n\nThis is used to update the `mass` when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Density"] , Description ["The density of this entity.\nThis is used to update the `mass` when the entity is rescaled."]] density : f32 , # [doc = "**Dynamic**: If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Dynamic"] , Description ["If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static."]] dynamic : bool , # [doc = "**Dynamic friction**: The friction coefficient of this entity's collider when it is sliding against another collider.\n\nCombined with the other collider's friction using `friction_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Dynamic friction"] , Description ["The friction coefficient of this entity's collider when it is sliding against another collider.\nCombined with the other collider's friction using `friction_combine_mode`."]] dynamic_friction : f32 , # [doc = "**Entities in trigger**: The entities that are currently inside this trigger area collider (a collider whose type is `TriggerArea`).\n\nThis is updated by the physics simulation; use the `TriggerEnter` and `TriggerExit` messages to react to changes.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Entities in trigger"] , Description ["The entities that are currently inside this trigger area collider (a collider whose type is `TriggerArea`).\nThis is updated by the physics simulation; use the `TriggerEnter` and `TriggerExit` messages to react to changes."]] entities_in_trigger : Vec :: < EntityId > , # [doc = "**Friction combine mode**: How the friction of this entity's collider is combined with the friction of the collider it touches.\n\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Friction combine mode"] , Description ["How the friction of this entity's collider is combined with the friction of the collider it touches.\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used."]] friction_combine_mode : crate :: generated :: raw :: ambient_core :: physics :: types :: CombineMode , # [doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic"] , Description ["If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally."]] kinematic : () , # [doc = "**Linear velocity**: Linear velocity (meters/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's linear velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Linear velocity"] , Description ["Linear velocity (meters/second) of this entity in the physics scene.\nUpdating this component will update the entity's linear velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead."]] linear_velocity : Vec3 , # [doc = "**Make physics static**: All physics objects will be made static when loaded.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Make physics static"] , Description ["All physics objects will be made static when loaded."]] make_physics_static : bool , # [doc = "**Mass**: The mass of this entity, measured in kilograms.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Mass"] , Description ["The mass of this entity, measured in kilograms."]] mass : f32 , # [doc = "**Physics controlled**: If attached, this entity will be controlled by physics.\n\nNote that this requires the entity to have a collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Physics controlled"] , Description ["If attached, this entity will be controlled by physics.\nNote that this requires the entity to have a collider."]] physics_controlled : () , # [doc = "**Plane collider**: If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Plane collider"] , Description ["If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead."]] plane_collider : () , # [doc = "**Ragdoll blend**: How much this entity's ragdoll (see `ragdoll_from_url`) overrides its animation, from 0 (fully animated) to 1 (fully simulated).\n\nThe ragdoll is simulated while this is above 0, starting from the model's bind pose, and is removed when it goes back to 0.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Ragdoll blend"] , Description ["How much this entity's ragdoll (see `ragdoll_from_url`) overrides its animation, from 0 (fully animated) to 1 (fully simulated).\nThe ragdoll is simulated while this is above 0, starting from the model's bind pose, and is removed when it goes back to 0."]] ragdoll_blend : f32 , # [doc = "**Ragdoll bone**: The bind ID of the model bone that this ragdoll bone entity (see `ragdoll_bones`) simulates.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Ragdoll bone"] , Description ["The bind ID of the model bone that this ragdoll bone entity (see `ragdoll_bones`) simulates."]] ragdoll_bone : String , # [doc = "**Ragdoll bones**: The entities simulating the bones of this entity's ragdoll while it is active (see `ragdoll_blend`), with parents before their children.\n\nEach of them has a `ragdoll_bone`, and its `translation` and `rotation` are the world transform of that bone in the physics scene.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Ragdoll bones"] , Description ["The entities simulating the bones of this entity's ragdoll while it is active (see `ragdoll_blend`), with parents before their children.\nEach of them has a `ragdoll_bone`, and its `translation` and `rotation` are the world transform of that bone in the physics scene."]] ragdoll_bones : Vec :: < EntityId > , # [doc = "**Ragdoll from URL**: Load a ragdoll for this entity's model from this URL. The ragdoll is generated by the model pipeline when `ragdoll` is set.\n\nUse `ragdoll_blend` to switch between the animated and the simulated pose.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Ragdoll from URL"] , Description ["Load a ragdoll for this entity's model from this URL. The ragdoll is generated by the model pipeline when `ragdoll` is set.\nUse `ragdoll_blend` to switch between the animated and the simulated pose."]] ragdoll_from_url : String , # [doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rest offset"] , Description ["Rest offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene."]] rest_offset : f32 , # [doc = "**Restitution**: The bounciness of this entity's collider, from 0 (no bounce) to 1 (a perfectly elastic bounce).\n\nCombined with the other collider's restitution using `restitution_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"] @ [Debuggable , Networked , Store , Name ["Restitution"] , Description ["The bounciness of this entity's collider, from 0 (no bounce) to 1 (a perfectly elastic bounce).\nCombined with the other collider's restitution using `restitution_combine_mode`."]] restitution : f32 , # [doc = "**Restitution combine mode**: How the restitution of this entity's collider is combined with the restitution of the collider it touches.\n\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Restitution combine mode"] , Description ["How the restitution of this entity's collider is combined with the restitution of the collider it touches.\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used."]] restitution_combine_mode : crate :: generated :: raw :: ambient_core :: physics :: types :: CombineMode , # [doc = "**Speculative CCD**: If true, speculative contacts are generated for this dynamic entity, based on its velocity.\n\nThis is cheaper than swept CCD (see `ccd`) and also works for kinematic entities, but can miss collisions with objects that rotate quickly.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"] @ [Debuggable , Networked , Store , Name ["Speculative CCD"] , Description ["If true, speculative contacts are generated for this dynamic entity, based on its velocity.\nThis is cheaper than swept CCD (see `ccd`) and also works for kinematic entities, but can miss collisions with objects that rotate quickly."]] speculative_ccd : bool , # [doc = "**Sphere collider**: If attached, this entity will have a sphere physics collider.\n\nThe value corresponds to the radius of the sphere.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sphere collider"] , Description ["If attached, this entity will have a sphere physics collider.\nThe value corresponds to the radius of the sphere."]] sphere_collider : f32 , # [doc = "**Static friction**: The friction coefficient of this entity's collider when it is at rest against another collider.\n\nCombined with the other collider's friction using `friction_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Static friction"] , Description ["The friction coefficient of this entity's collider when it is at rest against another collider.\nCombined with the other collider's friction using `friction_combine_mode`."]] static_friction : f32 , # [doc = "**Unit mass**: The mass of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit mass"] , Description ["The mass of a character/unit."]] unit_mass : f32 , # [doc = "**Unit velocity**: The velocity of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit velocity"] , Description ["The velocity of a character/unit."]] unit_velocity : Vec3 , # [doc = "**Unit yaw**: The yaw of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit yaw"] , Description ["The yaw of a character/unit."]] unit_yaw : f32 , # [doc = "**Vehicle brake**: How hard this vehicle is braking, from 0 to 1. Each wheel (see `vehicle_wheels`) is braked with this fraction of its `wheel_brake_torque`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle brake"] , Description ["How hard this vehicle is braking, from 0 to 1. Each wheel (see `vehicle_wheels`) is braked with this fraction of its `wheel_brake_torque`."]] vehicle_brake : f32 , # [doc = "**Vehicle max speed**: The top speed (in meters/second) of this vehicle's wheels. The drive torque of each wheel fades out as it spins up to this speed, which also stops wheels that are in the air or skidding from spinning up forever.\n\nIf not attached, it is 50 m/s.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 50.0"] @ [Debuggable , Networked , Store , Name ["Vehicle max speed"] , Description ["The top speed (in meters/second) of this vehicle's wheels. The drive torque of each wheel fades out as it spins up to this speed, which also stops wheels that are in the air or skidding from spinning up forever.\nIf not attached, it is 50 m/s."]] vehicle_max_speed : f32 , # [doc = "**Vehicle steering**: How much this vehicle is steering, from -1 (right) to 1 (left). Each wheel (see `vehicle_wheels`) is turned by this fraction of its `wheel_max_steering_angle`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle steering"] , Description ["How much this vehicle is steering, from -1 (right) to 1 (left). Each wheel (see `vehicle_wheels`) is turned by this fraction of its `wheel_max_steering_angle`."]] vehicle_steering : f32 , # [doc = "**Vehicle throttle**: How much this vehicle is accelerating, from -1 (full reverse) to 1 (full forward). Each wheel (see `vehicle_wheels`) is driven with this fraction of its `wheel_drive_torque`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle throttle"] , Description ["How much this vehicle is accelerating, from -1 (full reverse) to 1 (full forward). Each wheel (see `vehicle_wheels`) is driven with this fraction of its `wheel_drive_torque`."]] vehicle_throttle : f32 , # [doc = "**Vehicle wheels**: The wheels of this vehicle, which are entities with the `VehicleWheel` concept. The vehicle itself must be a dynamic physics object (with a collider, `dynamic` and `physics_controlled`), which drives along its local -Y axis with Z up.\n\nEach wheel is simulated with a raycast from its `wheel_offset` down the suspension every physics step, and its `translation` and `rotation` are set to its world transform, so the wheel entities should not have a `parent`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle wheels"] , Description ["The wheels of this vehicle, which are entities with the `VehicleWheel` concept. The vehicle itself must be a dynamic physics object (with a collider, `dynamic` and `physics_controlled`), which drives along its local -Y axis with Z up.\nEach wheel is simulated with a raycast from its `wheel_offset` down the suspension every physics step, and its `translation` and `rotation` are set to its world transform, so the wheel entities should not have a `parent`."]] vehicle_wheels : Vec<EntityId> , # [doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Visualize collider"] , Description ["If attached, the collider will be rendered.\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n"]] visualize_collider : () , # [doc = "**Wheel angular velocity**: How fast this wheel is spinning around its axle, in radians/second. Positive values roll the vehicle forward.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel angular velocity"] , Description ["How fast this wheel is spinning around its axle, in radians/second. Positive values roll the vehicle forward."]] wheel_angular_velocity : f32 , # [doc = "**Wheel brake torque**: The torque (in N·m) that stops this wheel from spinning when its vehicle is braking fully (see `vehicle_brake`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel brake torque"] , Description ["The torque (in N·m) that stops this wheel from spinning when its vehicle is braking fully (see `vehicle_brake`)."]] wheel_brake_torque : f32 , # [doc = "**Wheel contact entity**: The entity that this wheel is touching, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel contact entity"] , Description ["The entity that this wheel is touching, while `wheel_in_contact` is true."]] wheel_contact_entity : EntityId , # [doc = "**Wheel contact normal**: The normal of the surface that this wheel is touching, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel contact normal"] , Description ["The normal of the surface that this wheel is touching, while `wheel_in_contact` is true."]] wheel_contact_normal : Vec3 , # [doc = "**Wheel contact point**: The point (in world space) where this wheel is touching the ground, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel contact point"] , Description ["The point (in world space) where this wheel is touching the ground, while `wheel_in_contact` is true."]] wheel_contact_point : Vec3 , # [doc = "**Wheel drive torque**: The torque (in N·m) that drives this wheel when its vehicle is at full throttle (see `vehicle_throttle`). Wheels with a drive torque of 0 are not driven.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel drive torque"] , Description ["The torque (in N·m) that drives this wheel when its vehicle is at full throttle (see `vehicle_throttle`). Wheels with a drive torque of 0 are not driven."]] wheel_drive_torque : f32 , # [doc = "**Wheel friction**: The peak friction coefficient of this wheel's tire, which it has while its `wheel_slip` is below `wheel_peak_slip`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel friction"] , Description ["The peak friction coefficient of this wheel's tire, which it has while its `wheel_slip` is below `wheel_peak_slip`."]] wheel_friction : f32 , # [doc = "**Wheel in contact**: Whether this wheel is touching the ground.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel in contact"] , Description ["Whether this wheel is touching the ground."]] wheel_in_contact : bool , # [doc = "**Wheel inertia**: The moment of inertia (in kg·m²) of this wheel around its axle. Lighter wheels spin up and lock up more easily.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel inertia"] , Description ["The moment of inertia (in kg·m²) of this wheel around its axle. Lighter wheels spin up and lock up more easily."]] wheel_inertia : f32 , # [doc = "**Wheel max steering angle**: The angle (in radians) that this wheel is turned by when its vehicle is steering fully (see `vehicle_steering`). Use negative values for wheels that steer the opposite way, and 0 for wheels that don't steer.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel max steering angle"] , Description ["The angle (in radians) that this wheel is turned by when its vehicle is steering fully (see `vehicle_steering`). Use negative values for wheels that steer the opposite way, and 0 for wheels that don't steer."]] wheel_max_steering_angle : f32 , # [doc = "**Wheel offset**: The top of this wheel's suspension, relative to its vehicle. At full extension, the center of the wheel is `wheel_suspension_length` below it.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel offset"] , Description ["The top of this wheel's suspension, relative to its vehicle. At full extension, the center of the wheel is `wheel_suspension_length` below it."]] wheel_offset : Vec3 , # [doc = "**Wheel peak slip**: How fast (in meters/second) this wheel's tire can slide over the ground before it loses grip. Above this, its friction falls from `wheel_friction` to `wheel_sliding_friction`, which it reaches at twice this speed.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel peak slip"] , Description ["How fast (in meters/second) this wheel's tire can slide over the ground before it loses grip. Above this, its friction falls from `wheel_friction` to `wheel_sliding_friction`, which it reaches at twice this speed."]] wheel_peak_slip : f32 , # [doc = "**Wheel radius**: The radius of this wheel.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel radius"] , Description ["The radius of this wheel."]] wheel_radius : f32 , # [doc = "**Wheel sliding friction**: The friction coefficient of this wheel's tire while it is sliding over the ground (see `wheel_peak_slip`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel sliding friction"] , Description ["The friction coefficient of this wheel's tire while it is sliding over the ground (see `wheel_peak_slip`)."]] wheel_sliding_friction : f32 , # [doc = "**Wheel slip**: How fast (in meters/second) this wheel's tire is sliding over the ground, as `(longitudinal, lateral)`. The longitudinal slip is positive when the wheel is spinning faster than the ground, and negative when it's skidding.\n\nThis is useful for tire smoke and skid marks.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel slip"] , Description ["How fast (in meters/second) this wheel's tire is sliding over the ground, as `(longitudinal, lateral)`. The longitudinal slip is positive when the wheel is spinning faster than the ground, and negative when it's skidding.\nThis is useful for tire smoke and skid marks."]] wheel_slip : Vec2 , # [doc = "**Wheel steering angle**: The angle (in radians) that this wheel is currently turned by.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel steering angle"] , Description ["The angle (in radians) that this wheel is currently turned by."]] wheel_steering_angle : f32 , # [doc = "**Wheel suspension compression**: How far (in meters) this wheel's suspension is compressed, from 0 at full extension to `wheel_suspension_length`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Wheel suspension compression"] , Description ["How far (in meters) this wheel's suspension is compressed, from 0 at full extension to `wheel_suspension_length`."]] wheel_suspension_compression : f32 , # [doc = "**Wheel suspension damping**: The damping (in N·s/m) of this wheel's suspension, which stops its vehicle from bouncing.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel suspension damping"] , Description ["The damping (in N·s/m) of this wheel's suspension, which stops its vehicle from bouncing."]] wheel_suspension_damping : f32 , # [doc = "**Wheel suspension length**: How far (in meters) this wheel's suspension can travel.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel suspension length"] , Description ["How far (in meters) this wheel's suspension can travel."]] wheel_suspension_length : f32 , # [doc = "**Wheel suspension stiffness**: The stiffness (in N/m) of this wheel's suspension.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wheel suspension stiffness"] , Description ["The stiffness (in N/m) of this wheel's suspension."]] wheel_suspension_stiffness : f32 , });
+            }
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use ambient_package_rt::message_serde::*;
+                use serde;
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**CombineMode**: How the material properties of two touching colliders are combined."]
+                pub enum CombineMode {
+                    #[default]
+                    #[doc = "Use the average of the two values."]
+                    Average,
+                    #[doc = "Use the smaller of the two values."]
+                    Min,
+                    #[doc = "Multiply the two values."]
+                    Multiply,
+                    #[doc = "Use the larger of the two values."]
+                    Max,
+                }
+                impl crate::EnumComponent for CombineMode {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Average => CombineMode::Average as u32,
+                            Self::Min => CombineMode::Min as u32,
+                            Self::Multiply => CombineMode::Multiply as u32,
+                            Self::Max => CombineMode::Max as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == CombineMode::Average as u32 {
+                            return Some(Self::Average);
+                        }
+                        if value == CombineMode::Min as u32 {
+                            return Some(Self::Min);
+                        }
+                        if value == CombineMode::Multiply as u32 {
+                            return Some(Self::Multiply);
+                        }
+                        if value == CombineMode::Max as u32 {
+                            return Some(Self::Max);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for CombineMode {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod player {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -747,8 +820,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -756,15 +829,15 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("prefab" , { # [doc = "**Prefab from URL**: Load and attach a prefab from a URL or relative path.\n\nWhen loaded, the components from this prefab will add to or replace the existing components for the entity.\n\n*Attributes*: Debuggable, Store"] @ [Debuggable , Store , Name ["Prefab from URL"] , Description ["Load and attach a prefab from a URL or relative path.\nWhen loaded, the components from this prefab will add to or replace the existing components for the entity."]] prefab_from_url : String , # [doc = "**Spawned**: If attached, this entity was built from a prefab that has finished spawning.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Spawned"] , Description ["If attached, this entity was built from a prefab that has finished spawning."]] spawned : () , });
+                components ! ("prefab" , { # [doc = "**Prefab from URL**: Load and attach a prefab from a URL or relative path.\n\nWhen loaded, the components from this prefab will add to or replace the existing components for the entity.\n\n*Attributes*: Debuggable, Store"] @ [Debuggable , Store , Name ["Prefab from URL"] , Description ["Load and attach a prefab from a URL or relative path.\nWhen loaded, the components from this prefab will add to or replace the existing components for the entity."]] prefab_from_url : String , # [doc = "**Spawned**: If attached, this entity was built from a prefab that has finished spawning.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Spawned"] , Description ["If attached, this entity was built from a prefab that has finished spawning."]] spawned : () , # [doc = "**Prefab overrides**: The paths of components (e.g. `ambient_core::transform::translation`) that this prefab instance overrides.\n\nWhen the prefab is applied, these components keep the instance's values instead of being replaced by the prefab's.\n\nAll other components continue to follow the source prefab whenever it is re-applied (i.e. when `prefab_from_url` changes).\n\n*Attributes*: Debuggable, Store"] @ [Debuggable , Store , Name ["Prefab overrides"] , Description ["The paths of components (e.g. `ambient_core::transform::translation`) that this prefab instance overrides.\nWhen the prefab is applied, these components keep the instance's values instead of being replaced by the prefab's.\nAll other components continue to follow the source prefab whenever it is re-applied (i.e. when `prefab_from_url` changes)."]] prefab_overrides : Vec :: < String > , # [doc = "**Prefab root**: The prefab instance that spawned this entity as part of its hierarchy.\n\nThese entities are despawned and respawned when the prefab instance is re-applied.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Prefab root"] , Description ["The prefab instance that spawned this entity as part of its hierarchy.\nThese entities are despawned and respawned when the prefab instance is re-applied."]] prefab_root : EntityId , });
             }
         }
         pub mod primitives {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -779,8 +852,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -795,8 +868,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -811,8 +884,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -827,8 +900,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -934,8 +1007,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -950,8 +1023,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -1008,8 +1081,8 @@ mod raw {
             #[doc = r" Auto-generated component definitions."]
             pub mod components {
                 use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
+                    components, Debuggable, Description, EntityId, Enum, Indexed, MaybeResource,
+                    Name, Networked, Relationship, Resource, Store,
                 };
                 use ambient_shared_types::{
                     ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
@@ -1123,6 +1196,72 @@ mod raw {
             }
             impl RuntimeMessage for ColliderLoads {}
             #[derive(Clone, Debug)]
+            #[doc = "**TriggerEnter**: Sent when an entity enters a trigger area collider (a collider whose type is `TriggerArea`)."]
+            pub struct TriggerEnter {
+                pub trigger: EntityId,
+                pub other: EntityId,
+            }
+            impl TriggerEnter {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(trigger: impl Into<EntityId>, other: impl Into<EntityId>) -> Self {
+                    Self {
+                        trigger: trigger.into(),
+                        other: other.into(),
+                    }
+                }
+            }
+            impl Message for TriggerEnter {
+                fn id() -> &'static str {
+                    "ambient_core::TriggerEnter"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.other.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        trigger: EntityId::deserialize_message_part(&mut input)?,
+                        other: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for TriggerEnter {}
+            #[derive(Clone, Debug)]
+            #[doc = "**TriggerExit**: Sent when an entity leaves a trigger area collider (a collider whose type is `TriggerArea`), or is despawned while inside it."]
+            pub struct TriggerExit {
+                pub trigger: EntityId,
+                pub other: EntityId,
+            }
+            impl TriggerExit {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(trigger: impl Into<EntityId>, other: impl Into<EntityId>) -> Self {
+                    Self {
+                        trigger: trigger.into(),
+                        other: other.into(),
+                    }
+                }
+            }
+            impl Message for TriggerExit {
+                fn id() -> &'static str {
+                    "ambient_core::TriggerExit"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.other.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        trigger: EntityId::deserialize_message_part(&mut input)?,
+                        other: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for TriggerExit {}
+            #[derive(Clone, Debug)]
             #[doc = "**ModuleLoad**: Sent to a module when it loads."]
             pub struct ModuleLoad;
             impl ModuleLoad {
@@ -1453,12 +1592,425 @@ mod raw {
             }
             impl RuntimeMessage for WindowCursorLockChange {}
             #[derive(Clone, Debug)]
-            #[doc = "**HttpResponse**: Sent when an HTTP response is received."]
+            #[doc = "**GamepadConnected**: Sent when a gamepad is connected. Gamepads that are already connected when the client starts are reported as well."]
+            pub struct GamepadConnected {
+                pub gamepad_id: u32,
+                pub name: String,
+            }
+            impl GamepadConnected {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(gamepad_id: impl Into<u32>, name: impl Into<String>) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        name: name.into(),
+                    }
+                }
+            }
+            impl Message for GamepadConnected {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadConnected"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.name.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        name: String::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadConnected {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadDisconnected**: Sent when a gamepad is disconnected."]
+            pub struct GamepadDisconnected {
+                pub gamepad_id: u32,
+            }
+            impl GamepadDisconnected {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(gamepad_id: impl Into<u32>) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                    }
+                }
+            }
+            impl Message for GamepadDisconnected {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadDisconnected"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadDisconnected {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadButtonInput**: Sent when a button on a gamepad is pressed or released."]
+            pub struct GamepadButtonInput {
+                pub gamepad_id: u32,
+                pub button: u32,
+                pub pressed: bool,
+            }
+            impl GamepadButtonInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    button: impl Into<u32>,
+                    pressed: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        button: button.into(),
+                        pressed: pressed.into(),
+                    }
+                }
+            }
+            impl Message for GamepadButtonInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadButtonInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.button.serialize_message_part(&mut output)?;
+                    self.pressed.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        button: u32::deserialize_message_part(&mut input)?,
+                        pressed: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadButtonInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadStickInput**: Sent when an analog stick on a gamepad moves. `stick` is 0 for the left stick and 1 for the right stick. `value` is the raw position of the stick, before the deadzone is applied, with +Y pointing up."]
+            pub struct GamepadStickInput {
+                pub gamepad_id: u32,
+                pub stick: u32,
+                pub value: Vec2,
+            }
+            impl GamepadStickInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    stick: impl Into<u32>,
+                    value: impl Into<Vec2>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        stick: stick.into(),
+                        value: value.into(),
+                    }
+                }
+            }
+            impl Message for GamepadStickInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadStickInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.stick.serialize_message_part(&mut output)?;
+                    self.value.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        stick: u32::deserialize_message_part(&mut input)?,
+                        value: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadStickInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadTriggerInput**: Sent when an analog trigger on a gamepad moves. `trigger` is 0 for the left trigger and 1 for the right trigger. `value` is the raw position of the trigger between 0 and 1, before the deadzone is applied."]
+            pub struct GamepadTriggerInput {
+                pub gamepad_id: u32,
+                pub trigger: u32,
+                pub value: f32,
+            }
+            impl GamepadTriggerInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    trigger: impl Into<u32>,
+                    value: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        trigger: trigger.into(),
+                        value: value.into(),
+                    }
+                }
+            }
+            impl Message for GamepadTriggerInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadTriggerInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.value.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        trigger: u32::deserialize_message_part(&mut input)?,
+                        value: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadTriggerInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WindowTouchInput**: Sent when the window receives a touch input. `phase` is 0 when the touch starts, 1 when it moves, 2 when it ends and 3 when it is cancelled. `position` is in logical pixels, and `pressure` is from 0 to 1, or 1 if the device doesn't report pressure."]
+            pub struct WindowTouchInput {
+                pub touch_id: u64,
+                pub phase: u32,
+                pub position: Vec2,
+                pub pressure: f32,
+            }
+            impl WindowTouchInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    touch_id: impl Into<u64>,
+                    phase: impl Into<u32>,
+                    position: impl Into<Vec2>,
+                    pressure: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        touch_id: touch_id.into(),
+                        phase: phase.into(),
+                        position: position.into(),
+                        pressure: pressure.into(),
+                    }
+                }
+            }
+            impl Message for WindowTouchInput {
+                fn id() -> &'static str {
+                    "ambient_core::WindowTouchInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.touch_id.serialize_message_part(&mut output)?;
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.position.serialize_message_part(&mut output)?;
+                    self.pressure.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        touch_id: u64::deserialize_message_part(&mut input)?,
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                        pressure: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WindowTouchInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureTap**: Sent when one finger briefly touches the screen without moving."]
+            pub struct GestureTap {
+                pub position: Vec2,
+            }
+            impl GestureTap {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(position: impl Into<Vec2>) -> Self {
+                    Self {
+                        position: position.into(),
+                    }
+                }
+            }
+            impl Message for GestureTap {
+                fn id() -> &'static str {
+                    "ambient_core::GestureTap"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.position.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureTap {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureLongPress**: Sent when one finger has touched the screen without moving for long enough to be a long press. The touch does not produce a tap when it ends."]
+            pub struct GestureLongPress {
+                pub position: Vec2,
+            }
+            impl GestureLongPress {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(position: impl Into<Vec2>) -> Self {
+                    Self {
+                        position: position.into(),
+                    }
+                }
+            }
+            impl Message for GestureLongPress {
+                fn id() -> &'static str {
+                    "ambient_core::GestureLongPress"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.position.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureLongPress {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GesturePan**: Sent while one finger is dragged across the screen. `phase` is 0 when the pan starts, 1 while it moves, 2 when it ends and 3 when it is cancelled. `delta` is the movement since the previous `GesturePan`."]
+            pub struct GesturePan {
+                pub phase: u32,
+                pub position: Vec2,
+                pub delta: Vec2,
+            }
+            impl GesturePan {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    position: impl Into<Vec2>,
+                    delta: impl Into<Vec2>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        position: position.into(),
+                        delta: delta.into(),
+                    }
+                }
+            }
+            impl Message for GesturePan {
+                fn id() -> &'static str {
+                    "ambient_core::GesturePan"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.position.serialize_message_part(&mut output)?;
+                    self.delta.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                        delta: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GesturePan {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GesturePinch**: Sent while two fingers move toward or away from each other. `phase` is as for `GesturePan`. `center` is the midpoint between the fingers, and `scale` is the ratio between the distance between them and the distance at the previous `GesturePinch`."]
+            pub struct GesturePinch {
+                pub phase: u32,
+                pub center: Vec2,
+                pub scale: f32,
+            }
+            impl GesturePinch {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    center: impl Into<Vec2>,
+                    scale: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        center: center.into(),
+                        scale: scale.into(),
+                    }
+                }
+            }
+            impl Message for GesturePinch {
+                fn id() -> &'static str {
+                    "ambient_core::GesturePinch"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.center.serialize_message_part(&mut output)?;
+                    self.scale.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        center: Vec2::deserialize_message_part(&mut input)?,
+                        scale: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GesturePinch {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureRotate**: Sent while two fingers rotate around each other. `phase` is as for `GesturePan`. `center` is the midpoint between the fingers, and `angle` is how far the line between them has turned since the previous `GestureRotate`, in radians, clockwise on screen."]
+            pub struct GestureRotate {
+                pub phase: u32,
+                pub center: Vec2,
+                pub angle: f32,
+            }
+            impl GestureRotate {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    center: impl Into<Vec2>,
+                    angle: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        center: center.into(),
+                        angle: angle.into(),
+                    }
+                }
+            }
+            impl Message for GestureRotate {
+                fn id() -> &'static str {
+                    "ambient_core::GestureRotate"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.center.serialize_message_part(&mut output)?;
+                    self.angle.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        center: Vec2::deserialize_message_part(&mut input)?,
+                        angle: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureRotate {}
+            #[derive(Clone, Debug)]
+            #[doc = "**HttpResponse**: Sent when an HTTP response is received. For streaming requests, this is sent as soon as the headers are received with an empty `body`, which is then delivered through `HttpResponseChunk` messages."]
             pub struct HttpResponse {
                 pub response_id: u64,
                 pub status: u32,
                 pub body: Vec<u8>,
                 pub error: Option<String>,
+                pub headers: std::collections::HashMap<String, String>,
             }
             impl HttpResponse {
                 #[allow(clippy::too_many_arguments)]
@@ -1467,12 +2019,14 @@ mod raw {
                     status: impl Into<u32>,
                     body: impl Into<Vec<u8>>,
                     error: impl Into<Option<String>>,
+                    headers: impl Into<std::collections::HashMap<String, String>>,
                 ) -> Self {
                     Self {
                         response_id: response_id.into(),
                         status: status.into(),
                         body: body.into(),
                         error: error.into(),
+                        headers: headers.into(),
                     }
                 }
             }
@@ -1486,6 +2040,7 @@ mod raw {
                     self.status.serialize_message_part(&mut output)?;
                     self.body.serialize_message_part(&mut output)?;
                     self.error.serialize_message_part(&mut output)?;
+                    self.headers.serialize_message_part(&mut output)?;
                     Ok(output)
                 }
                 fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
@@ -1494,11 +2049,167 @@ mod raw {
                         status: u32::deserialize_message_part(&mut input)?,
                         body: Vec::<u8>::deserialize_message_part(&mut input)?,
                         error: Option::<String>::deserialize_message_part(&mut input)?,
+                        headers:
+                            std::collections::HashMap::<String, String>::deserialize_message_part(
+                                &mut input,
+                            )?,
                     })
                 }
             }
             impl RuntimeMessage for HttpResponse {}
             #[derive(Clone, Debug)]
+            #[doc = "**HttpResponseChunk**: Sent for each chunk of the body of a streaming HTTP response. The last chunk has `end` set, along with `error` if the body could not be read in full."]
+            pub struct HttpResponseChunk {
+                pub response_id: u64,
+                pub data: Vec<u8>,
+                pub end: bool,
+                pub error: Option<String>,
+            }
+            impl HttpResponseChunk {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    response_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    end: impl Into<bool>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        response_id: response_id.into(),
+                        data: data.into(),
+                        end: end.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for HttpResponseChunk {
+                fn id() -> &'static str {
+                    "ambient_core::HttpResponseChunk"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.response_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.end.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        response_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        end: bool::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for HttpResponseChunk {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketOpen**: Sent when an outbound WebSocket connection has been established."]
+            pub struct WebSocketOpen {
+                pub connection_id: u64,
+            }
+            impl WebSocketOpen {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(connection_id: impl Into<u64>) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketOpen {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketOpen"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketOpen {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketMessage**: Sent when a message is received on an outbound WebSocket connection. `is_text` is set if it was a text message, in which case `data` is valid UTF-8."]
+            pub struct WebSocketMessage {
+                pub connection_id: u64,
+                pub data: Vec<u8>,
+                pub is_text: bool,
+            }
+            impl WebSocketMessage {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    connection_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    is_text: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                        data: data.into(),
+                        is_text: is_text.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketMessage {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketMessage"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.is_text.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        is_text: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketMessage {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketClose**: Sent when an outbound WebSocket connection is closed, whether by the remote end, the module or an error. `error` is set if the connection failed, including if it could not be established."]
+            pub struct WebSocketClose {
+                pub connection_id: u64,
+                pub error: Option<String>,
+            }
+            impl WebSocketClose {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    connection_id: impl Into<u64>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketClose {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketClose"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketClose {}
+            #[derive(Clone, Debug)]
             #[doc = "**WasmRebuild**: Sent when a request for WASM rebuilding is completed."]
             pub struct WasmRebuild {
                 pub error: Option<String>,
@@ -1527,6 +2238,47 @@ mod raw {
                 }
             }
             impl RuntimeMessage for WasmRebuild {}
+            #[derive(Clone, Debug)]
+            #[doc = "**AssetReadResponse**: Sent when a request to read an asset on the server has completed."]
+            pub struct AssetReadResponse {
+                pub request_id: u64,
+                pub data: Vec<u8>,
+                pub error: Option<String>,
+            }
+            impl AssetReadResponse {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    request_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        request_id: request_id.into(),
+                        data: data.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for AssetReadResponse {
+                fn id() -> &'static str {
+                    "ambient_core::AssetReadResponse"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.request_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        request_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for AssetReadResponse {}
         }
         #[doc = r" Auto-generated type definitions."]
         pub mod types {
@@ -1543,12 +2295,21 @@ mod raw {
                 Get,
                 #[doc = "POST"]
                 Post,
+                #[doc = "PUT"]
+                Put,
+                #[doc = "DELETE"]
+                Delete,
+                #[doc = "PATCH"]
+                Patch,
             }
             impl crate::EnumComponent for HttpMethod {
                 fn to_u32(&self) -> u32 {
                     match self {
                         Self::Get => HttpMethod::Get as u32,
                         Self::Post => HttpMethod::Post as u32,
+                        Self::Put => HttpMethod::Put as u32,
+                        Self::Delete => HttpMethod::Delete as u32,
+                        Self::Patch => HttpMethod::Patch as u32,
                     }
                 }
                 fn from_u32(value: u32) -> Option<Self> {
@@ -1558,6 +2319,15 @@ mod raw {
                     if value == HttpMethod::Post as u32 {
                         return Some(Self::Post);
                     }
+                    if value == HttpMethod::Put as u32 {
+                        return Some(Self::Put);
+                    }
+                    if value == HttpMethod::Delete as u32 {
+                        return Some(Self::Delete);
+                    }
+                    if value == HttpMethod::Patch as u32 {
+                        return Some(Self::Patch);
+                    }
                     None
                 }
             }
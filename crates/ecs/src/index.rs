@@ -3,18 +3,51 @@ use std::{
     collections::{btree_set::Range, BTreeSet, HashMap},
     fmt::Debug,
     ops::RangeBounds,
+    time::Duration,
 };
 
 use itertools::Itertools;
 
 use crate::{
     ArchetypeFilter, Component, ComponentDesc, ComponentEntry, ComponentValue, EntityId, FnSystem,
-    Query, SystemGroup, World,
+    PrimitiveComponentType, Query, SystemGroup, World,
 };
 
+/// Orders two values of the same component in an [Index]
+pub type IndexComparator = fn(&ComponentEntry, &ComponentEntry) -> Ordering;
+
+fn compare<T: ComponentValue + Ord>(a: &ComponentEntry, b: &ComponentEntry) -> Ordering {
+    a.downcast_ref::<T>().cmp(b.downcast_ref::<T>())
+}
+
+/// Returns the comparator for a primitive component type, or `None` if the type has no order.
+///
+/// Floats are ordered with `total_cmp`, so `NaN`s are sorted after all other values.
+pub fn primitive_comparator(ty: PrimitiveComponentType) -> Option<IndexComparator> {
+    type P = PrimitiveComponentType;
+    let comparator: IndexComparator = match ty {
+        P::Bool => compare::<bool>,
+        P::EntityId => compare::<EntityId>,
+        P::F32 => |a, b| a.downcast_ref::<f32>().total_cmp(b.downcast_ref::<f32>()),
+        P::F64 => |a, b| a.downcast_ref::<f64>().total_cmp(b.downcast_ref::<f64>()),
+        P::String => compare::<String>,
+        P::U8 => compare::<u8>,
+        P::U16 => compare::<u16>,
+        P::U32 => compare::<u32>,
+        P::U64 => compare::<u64>,
+        P::I8 => compare::<i8>,
+        P::I16 => compare::<i16>,
+        P::I32 => compare::<i32>,
+        P::I64 => compare::<i64>,
+        P::Duration => compare::<Duration>,
+        _ => return None,
+    };
+    Some(comparator)
+}
+
 #[derive(Clone)]
 pub struct IndexColumns {
-    comparators: Vec<fn(&ComponentEntry, &ComponentEntry) -> Ordering>,
+    comparators: Vec<IndexComparator>,
     components: Vec<ComponentDesc>,
 }
 
@@ -35,10 +68,19 @@ impl IndexColumns {
         }
     }
 
-    pub fn add_column<T: ComponentValue + Ord>(mut self, component: Component<T>) -> Self {
-        self.comparators
-            .push(|a, b| a.downcast_ref::<T>().cmp(b.downcast_ref::<T>()));
-        self.components.push(component.desc());
+    pub fn add_column<T: ComponentValue + Ord>(self, component: Component<T>) -> Self {
+        self.add_column_with_comparator(component.desc(), compare::<T>)
+    }
+
+    /// Adds a column for a component whose type is only known at runtime, such as one
+    /// defined by a package. `comparator` must accept the component's values.
+    pub fn add_column_with_comparator(
+        mut self,
+        component: ComponentDesc,
+        comparator: IndexComparator,
+    ) -> Self {
+        self.comparators.push(comparator);
+        self.components.push(component);
         self
     }
 
@@ -104,6 +146,14 @@ impl Index {
         }
     }
 
+    /// Re-inserts `id` with its current values, or removes it if it no longer has all the columns
+    pub fn sync_entity(&mut self, world: &World, id: EntityId) {
+        self.remove(id);
+        if let Some(key) = self.columns.try_key_from_entity(world, id) {
+            self.insert(key);
+        }
+    }
+
     /// Query the set between the specified ordered range
     pub fn range<R>(&self, range: R) -> Range<'_, IndexKey>
     where
@@ -188,15 +238,16 @@ impl IndexField {
 }
 #[derive(Clone)]
 pub struct IndexFieldValue {
-    comparator: fn(&ComponentEntry, &ComponentEntry) -> Ordering,
+    comparator: IndexComparator,
     value: ComponentEntry,
 }
 impl IndexFieldValue {
     pub fn new<T: ComponentValue + Ord>(component: Component<T>, value: T) -> Self {
-        Self {
-            comparator: |a, b| a.downcast_ref::<T>().cmp(b.downcast_ref::<T>()),
-            value: ComponentEntry::new(component, value),
-        }
+        Self::from_entry(ComponentEntry::new(component, value), compare::<T>)
+    }
+    /// Creates a field value from an untyped entry, which must be accepted by `comparator`
+    pub fn from_entry(value: ComponentEntry, comparator: IndexComparator) -> Self {
+        Self { comparator, value }
     }
 }
 impl Debug for IndexFieldValue {
@@ -39,6 +39,7 @@ mod helpers;
 mod index;
 mod location;
 mod message_serde;
+mod observer;
 mod primitive_component;
 mod query;
 mod serialization;
@@ -56,6 +57,7 @@ pub use helpers::*;
 pub use index::*;
 pub use location::*;
 pub use message_serde::*;
+pub use observer::*;
 pub use primitive_component::*;
 pub use query::*;
 pub use serialization::*;
@@ -152,6 +154,7 @@ pub struct World {
     /// Used for reset_events. Prevents change events in queries when you use reset_events
     ignore_query_inits: bool,
     query_ticker: CloneableAtomicU64,
+    observers: observer::Observers,
 }
 impl World {
     pub fn new_unknown(name: &'static str) -> Self {
@@ -178,6 +181,7 @@ impl World {
             shape_change_events: None,
             ignore_query_inits: false,
             query_ticker: CloneableAtomicU64::new(0),
+            observers: observer::Observers::new(),
         };
         if resources {
             world.spawn_with_id(EntityId::resources(), Entity::new());
@@ -233,6 +237,7 @@ impl World {
     pub fn spawn_with_id(&mut self, entity_id: EntityId, entity_data: Entity) -> bool {
         if let std::collections::hash_map::Entry::Vacant(e) = self.locs.entry(entity_id) {
             e.insert(EntityLocation::empty());
+            self.observers.spawned(&[entity_id], &entity_data);
             let version = self.inc_version();
             self.batch_spawn_with_ids_internal(
                 EntityMoveData::from_entity_data(entity_data, version),
@@ -250,6 +255,7 @@ impl World {
                     .map(|id| WorldChange::Spawn(*id, entity_data.clone())),
             );
         }
+        self.observers.spawned(&ids, &entity_data);
         let version = self.inc_version();
         self.batch_spawn_with_ids_internal(
             EntityMoveData::from_entity_data(entity_data, version),
@@ -292,7 +298,9 @@ impl World {
                 self.locs.get_mut(&last_entity_in_arch).unwrap().index = loc.index;
                 self.loc_changed.add_event(last_entity_in_arch);
             }
-            Some(arch.moveout(loc.index, entity_id, version).into())
+            let entity: Entity = arch.moveout(loc.index, entity_id, version).into();
+            self.observers.despawned(entity_id, &entity);
+            Some(entity)
         } else {
             None
         }
@@ -323,8 +331,17 @@ impl World {
         component: crate::component::Component<T>,
         value: T,
     ) -> Result<T, ECSError> {
+        let new = self
+            .observers
+            .observes(component.desc())
+            .then(|| ComponentEntry::new(component, value.clone()));
         let p = self.get_mut(entity_id, component)?;
-        Ok(std::mem::replace(p, value))
+        let old = std::mem::replace(p, value);
+        if let Some(new) = new {
+            self.observers
+                .changed(entity_id, ComponentEntry::new(component, old.clone()), &new);
+        }
+        Ok(old)
     }
 
     pub fn set_entry(
@@ -338,7 +355,12 @@ impl World {
                 .archetypes
                 .get_mut(loc.archetype)
                 .expect("Archetype doesn't exist");
-            arch.replace_with_entry(entity_id, loc.index, entry, version)
+            let new = self.observers.observes(entry.desc()).then(|| entry.clone());
+            let old = arch.replace_with_entry(entity_id, loc.index, entry, version)?;
+            if let Some(new) = new {
+                self.observers.changed(entity_id, old.clone(), &new);
+            }
+            Ok(old)
         } else {
             Err(ECSError::NoSuchEntity { entity_id })
         }
@@ -352,7 +374,11 @@ impl World {
                 .get_mut(loc.archetype)
                 .expect("Archetype doesn't exist");
             for entry in data {
-                arch.replace_with_entry(entity_id, loc.index, entry, version)?;
+                let new = self.observers.observes(entry.desc()).then(|| entry.clone());
+                let old = arch.replace_with_entry(entity_id, loc.index, entry, version)?;
+                if let Some(new) = new {
+                    self.observers.changed(entity_id, old, &new);
+                }
             }
             Ok(())
         } else {
@@ -538,6 +564,7 @@ impl World {
                 removes: HashSet::new(),
                 active_components: prev_comps.clone(),
             });
+            self.observe_mapping(entity_id, &loc, &mapping);
 
             if mapping.active_components == prev_comps {
                 assert_eq!(mapping.removes.len(), 0);
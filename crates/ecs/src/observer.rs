@@ -0,0 +1,273 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    Component, ComponentDesc, ComponentEntry, ComponentSet, ComponentValue, Entity, EntityId,
+    EntityLocation, MapEntity, World,
+};
+
+/// The most changes a single [World::flush_observers] will dispatch. Observers that keep
+/// triggering each other are cut off here, and the remaining changes are left for the next flush.
+const MAX_CHANGES_PER_FLUSH: usize = 100_000;
+
+/// What happened to a component for an observer to be notified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObserverEvent {
+    /// The component was added to an entity, including by spawning the entity.
+    Added,
+    /// The component was removed from an entity, including by despawning the entity.
+    Removed,
+    /// The component was overwritten with a new value with `set`, `set_entry`,
+    /// `set_components` or `add_components`.
+    ///
+    /// Writes through `get_mut` or mutable queries are not observed; use change queries for those.
+    Changed,
+}
+
+/// A change to a single component of a single entity, as passed to observers.
+#[derive(Debug, Clone)]
+pub struct ObservedChange {
+    pub event: ObserverEvent,
+    pub id: EntityId,
+    pub component: ComponentDesc,
+    /// The value before the change. `None` for [ObserverEvent::Added].
+    pub old: Option<ComponentEntry>,
+    /// The value after the change. `None` for [ObserverEvent::Removed].
+    pub new: Option<ComponentEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+pub type ObserverFn = Arc<dyn Fn(&mut World, &ObservedChange) + Sync + Send>;
+
+#[derive(Clone)]
+struct Observer {
+    id: ObserverId,
+    component: ComponentDesc,
+    event: ObserverEvent,
+    callback: ObserverFn,
+}
+
+/// The observers registered on a [World], and the changes waiting to be dispatched to them.
+#[derive(Clone)]
+pub(crate) struct Observers {
+    next_id: u64,
+    observers: Vec<Observer>,
+    /// All the components that have at least one observer, so that unobserved changes can be
+    /// skipped without looking at the observers
+    observed: ComponentSet,
+    pending: VecDeque<ObservedChange>,
+}
+impl Observers {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 0,
+            observers: Vec::new(),
+            observed: ComponentSet::new(),
+            pending: VecDeque::new(),
+        }
+    }
+    #[inline]
+    pub(crate) fn observes(&self, component: ComponentDesc) -> bool {
+        !self.observers.is_empty() && self.observed.contains(component)
+    }
+    fn push(
+        &mut self,
+        event: ObserverEvent,
+        id: EntityId,
+        old: Option<ComponentEntry>,
+        new: Option<ComponentEntry>,
+    ) {
+        let component = old.as_ref().or(new.as_ref()).unwrap().desc();
+        self.pending.push_back(ObservedChange {
+            event,
+            id,
+            component,
+            old,
+            new,
+        });
+    }
+    pub(crate) fn spawned(&mut self, ids: &[EntityId], data: &Entity) {
+        if self.observers.is_empty() {
+            return;
+        }
+        for &id in ids {
+            for entry in data.iter() {
+                if self.observed.contains(entry.desc()) {
+                    self.push(ObserverEvent::Added, id, None, Some(entry.clone()));
+                }
+            }
+        }
+    }
+    pub(crate) fn despawned(&mut self, id: EntityId, data: &Entity) {
+        if self.observers.is_empty() {
+            return;
+        }
+        for entry in data.iter() {
+            if self.observed.contains(entry.desc()) {
+                self.push(ObserverEvent::Removed, id, Some(entry.clone()), None);
+            }
+        }
+    }
+    pub(crate) fn changed(&mut self, id: EntityId, old: ComponentEntry, new: &ComponentEntry) {
+        if self.observes(new.desc()) {
+            self.push(ObserverEvent::Changed, id, Some(old), Some(new.clone()));
+        }
+    }
+}
+
+impl World {
+    /// Calls `callback` for every `event` on `component` from now on.
+    ///
+    /// Changes are not dispatched immediately; they are queued, and the observers are called in
+    /// order by [Self::flush_observers]. Systems run in a [crate::SystemGroup] flush after each
+    /// system, so the observers have run before the next system starts. Changes made by the
+    /// observers themselves are dispatched in the same flush.
+    pub fn observe(
+        &mut self,
+        component: impl Into<ComponentDesc>,
+        event: ObserverEvent,
+        callback: impl Fn(&mut World, &ObservedChange) + Sync + Send + 'static,
+    ) -> ObserverId {
+        let observers = &mut self.observers;
+        let id = ObserverId(observers.next_id);
+        observers.next_id += 1;
+        let component = component.into();
+        observers.observed.insert(component);
+        observers.observers.push(Observer {
+            id,
+            component,
+            event,
+            callback: Arc::new(callback),
+        });
+        id
+    }
+    /// Calls `callback` with the new value whenever `component` is added to an entity.
+    pub fn on_added<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        callback: impl Fn(&mut World, EntityId, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverEvent::Added, move |world, change| {
+            callback(
+                world,
+                change.id,
+                change.new.as_ref().unwrap().downcast_ref(),
+            )
+        })
+    }
+    /// Calls `callback` with the old value whenever `component` is removed from an entity.
+    pub fn on_removed<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        callback: impl Fn(&mut World, EntityId, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverEvent::Removed, move |world, change| {
+            callback(
+                world,
+                change.id,
+                change.old.as_ref().unwrap().downcast_ref(),
+            )
+        })
+    }
+    /// Calls `callback` with the old and new values whenever `component` is overwritten.
+    pub fn on_changed<T: ComponentValue>(
+        &mut self,
+        component: Component<T>,
+        callback: impl Fn(&mut World, EntityId, &T, &T) + Sync + Send + 'static,
+    ) -> ObserverId {
+        self.observe(component, ObserverEvent::Changed, move |world, change| {
+            callback(
+                world,
+                change.id,
+                change.old.as_ref().unwrap().downcast_ref(),
+                change.new.as_ref().unwrap().downcast_ref(),
+            )
+        })
+    }
+    /// Removes the observer. Returns false if it was already removed.
+    pub fn unobserve(&mut self, id: ObserverId) -> bool {
+        let observers = &mut self.observers;
+        let Some(index) = observers.observers.iter().position(|o| o.id == id) else {
+            return false;
+        };
+        let component = observers.observers.remove(index).component;
+        if !observers.observers.iter().any(|o| o.component == component) {
+            observers.observed.remove(component);
+            observers
+                .pending
+                .retain(|change| change.component != component);
+        }
+        true
+    }
+
+    /// Calls the observers for all the changes made since the last flush.
+    pub fn flush_observers(&mut self) {
+        for _ in 0..MAX_CHANGES_PER_FLUSH {
+            let Some(change) = self.observers.pending.pop_front() else {
+                return;
+            };
+            let callbacks = self
+                .observers
+                .observers
+                .iter()
+                .filter(|o| o.component == change.component && o.event == change.event)
+                .map(|o| o.callback.clone())
+                .collect::<Vec<_>>();
+            for callback in callbacks {
+                callback(self, &change);
+            }
+        }
+        if !self.observers.pending.is_empty() {
+            tracing::warn!(
+                "Observers made more than {MAX_CHANGES_PER_FLUSH} changes in one flush; do they trigger each other? The remaining {} changes will be dispatched in the next flush",
+                self.observers.pending.len()
+            );
+        }
+    }
+
+    /// Records the observed changes that `mapping` is about to make to the entity.
+    pub(crate) fn observe_mapping(
+        &mut self,
+        id: EntityId,
+        loc: &EntityLocation,
+        mapping: &MapEntity,
+    ) {
+        if self.observers.observers.is_empty() {
+            return;
+        }
+        let arch = &self.archetypes[loc.archetype];
+        for entry in mapping.sets.values() {
+            if !self.observers.observed.contains(entry.desc()) {
+                continue;
+            }
+            match arch.get_component_buffer_untyped(entry.desc()) {
+                Some(buffer) => self.observers.push(
+                    ObserverEvent::Changed,
+                    id,
+                    Some(buffer.clone_value_boxed(loc.index)),
+                    Some(entry.clone()),
+                ),
+                None => self
+                    .observers
+                    .push(ObserverEvent::Added, id, None, Some(entry.clone())),
+            }
+        }
+        for &index in &mapping.removes {
+            let Some(component) = arch.components.get(index as usize) else {
+                continue;
+            };
+            if self.observers.observed.contains(component.component) {
+                let buffer = arch
+                    .get_component_buffer_untyped(component.component)
+                    .unwrap();
+                self.observers.push(
+                    ObserverEvent::Removed,
+                    id,
+                    Some(buffer.clone_value_boxed(loc.index)),
+                    None,
+                );
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use paste::paste;
 
 use crate::{
     AttributeConstructor, AttributeStore, ComponentDesc, ComponentRegistry, ComponentVTable,
-    Description, EntityId, ExternalComponentAttributes, Name,
+    Description, EntityId, ExternalComponentAttributes, Name, StructFields,
 };
 
 use ambient_shared_types::primitive_component_definitions;
@@ -44,23 +44,36 @@ macro_rules! make_primitive_component {
             #[derive(serde::Serialize, serde::Deserialize)]
             pub enum PrimitiveComponentContainerType {
                 Vec,
-                Option
+                Option,
+                /// A map with keys of the given type.
+                Map(PrimitiveComponentMapKeyType),
             }
             impl PrimitiveComponentContainerType {
                 pub fn as_str(&self) -> &'static str {
                     match self {
                         Self::Vec => "Vec",
                         Self::Option => "Option",
+                        Self::Map(_) => "Map",
                     }
                 }
             }
 
+            /// The types that can be used as the keys of a map component.
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[derive(serde::Serialize, serde::Deserialize)]
+            pub enum PrimitiveComponentMapKeyType {
+                String,
+                U32,
+            }
+
             #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
             #[derive(serde::Serialize, serde::Deserialize)]
             pub enum PrimitiveComponentType {
                 $($value), *,
                 $([< Vec $value >]), *,
                 $([< Option$value >]), *,
+                $([< MapString $value >]), *,
+                $([< MapU32 $value >]), *,
             }
 
             impl TryFrom<&str> for PrimitiveComponentType {
@@ -69,7 +82,7 @@ macro_rules! make_primitive_component {
                 fn try_from(value: &str) -> Result<Self, Self::Error> {
                     match value {
                         $(stringify!($value) => Ok(Self::$value),)*
-                        "Vec" | "Option" => Err("The specified type is a container type, not primitive"),
+                        "Vec" | "Option" | "Map" => Err("The specified type is a container type, not primitive"),
                         _ => Err("Unsupported type")
                     }
                 }
@@ -104,16 +117,30 @@ macro_rules! make_primitive_component {
                     }
                 }
 
+                pub fn to_map_type(&self, key: PrimitiveComponentMapKeyType) -> Option<Self> {
+                    match (key, self) {
+                        $((PrimitiveComponentMapKeyType::String, Self::$value) => Some(Self::[<MapString $value>]),)*
+                        $((PrimitiveComponentMapKeyType::U32, Self::$value) => Some(Self::[<MapU32 $value>]),)*
+                        _ => None
+                    }
+                }
+
                 pub fn decompose_container_type(&self) -> Option<(PrimitiveComponentContainerType, Self)> {
+                    use PrimitiveComponentMapKeyType as K;
                     match self {
                         $(Self::[<Vec $value>] => Some((PrimitiveComponentContainerType::Vec, Self::$value)),)*
                         $(Self::[<Option $value>] => Some((PrimitiveComponentContainerType::Option, Self::$value)),)*
+                        $(Self::[<MapString $value>] => Some((PrimitiveComponentContainerType::Map(K::String), Self::$value)),)*
+                        $(Self::[<MapU32 $value>] => Some((PrimitiveComponentContainerType::Map(K::U32), Self::$value)),)*
                         _ => None
                     }
                 }
 
-                pub(crate) fn register(&self, reg: &mut ComponentRegistry, path: &str, name: Option<&str>, description: Option<&str>, attributes: ExternalComponentAttributes) {
+                pub(crate) fn register(&self, reg: &mut ComponentRegistry, path: &str, name: Option<&str>, description: Option<&str>, attributes: ExternalComponentAttributes, struct_fields: Option<Vec<PrimitiveComponentType>>) {
                     let mut store = AttributeStore::new();
+                    if let Some(fields) = struct_fields {
+                        store.set(StructFields(fields));
+                    }
                     let vtable = match self {
                         $(
                             PrimitiveComponentType::$value => {
@@ -125,6 +152,12 @@ macro_rules! make_primitive_component {
                             PrimitiveComponentType::[< Option $value >] => {
                                 build_attribute_registration!(Option<$type>, store, name, description, attributes)
                             },
+                            PrimitiveComponentType::[< MapString $value >] => {
+                                build_attribute_registration!(HashMap<String, $type>, store, name, description, attributes)
+                            },
+                            PrimitiveComponentType::[< MapU32 $value >] => {
+                                build_attribute_registration!(HashMap<u32, $type>, store, name, description, attributes)
+                            },
                         )*
                     };
 
@@ -142,6 +175,8 @@ macro_rules! make_primitive_component {
                     $((TypeId::of::<$type>(), PrimitiveComponentType::$value),)*
                     $((TypeId::of::<Vec<$type>>(), PrimitiveComponentType::[<Vec $value>]),)*
                     $((TypeId::of::<Option<$type>>(), PrimitiveComponentType::[<Option $value>]),)*
+                    $((TypeId::of::<HashMap<String, $type>>(), PrimitiveComponentType::[<MapString $value>]),)*
+                    $((TypeId::of::<HashMap<u32, $type>>(), PrimitiveComponentType::[<MapU32 $value>]),)*
                 ])
             });
         }
@@ -1097,6 +1097,7 @@ impl<E> System<E> for SystemGroup<E> {
         let _span = tracing::debug_span!("SystemGroup::run", "{}", &self.0).entered();
         for system in self.1.iter_mut() {
             system.run(world, event);
+            world.flush_observers();
         }
     }
 }
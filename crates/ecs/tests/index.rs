@@ -1,13 +1,15 @@
 use std::ops::Bound;
 
 use ambient_ecs::{
-    components, index_system, ArchetypeFilter, Entity, FrameEvent, Index, IndexColumns, IndexField,
-    IndexKey, System, World,
+    components, index_system, primitive_comparator, ArchetypeFilter, ComponentEntry, Entity,
+    FrameEvent, Index, IndexColumns, IndexField, IndexFieldValue, IndexKey, PrimitiveComponentType,
+    System, World,
 };
 use itertools::Itertools;
 
 components!("test", {
     a: i32,
+    score: f32,
     test_index: Index,
 });
 
@@ -210,3 +212,46 @@ fn multiple_for_exact_value() {
         vec![y, z].into_iter().sorted().collect_vec()
     );
 }
+
+#[test]
+fn float_column() {
+    init();
+    let mut world = World::new_unknown("float_column");
+    let comparator = primitive_comparator(PrimitiveComponentType::F32).unwrap();
+    let mut index =
+        Index::new(IndexColumns::new().add_column_with_comparator(score().desc(), comparator));
+
+    let x = world.spawn(Entity::new().with(score(), 2.5));
+    let y = world.spawn(Entity::new().with(score(), -1.0));
+    let z = world.spawn(Entity::new().with(score(), 10.0));
+    for id in [x, y, z] {
+        index.sync_entity(&world, id);
+    }
+
+    let field = |value: f32| {
+        vec![IndexField::Exact(IndexFieldValue::from_entry(
+            ComponentEntry::new(score(), value),
+            comparator,
+        ))]
+    };
+    assert_eq!(
+        index.range(..).map(|x| x.id().unwrap()).collect_vec(),
+        vec![y, x, z]
+    );
+    assert_eq!(
+        index
+            .range(..IndexKey::min(field(10.0)))
+            .map(|x| x.id().unwrap())
+            .collect_vec(),
+        vec![y, x]
+    );
+
+    world.set(y, score(), 20.0).unwrap();
+    index.sync_entity(&world, y);
+    world.despawn(x);
+    index.sync_entity(&world, x);
+    assert_eq!(
+        index.range(..).map(|x| x.id().unwrap()).collect_vec(),
+        vec![z, y]
+    );
+}
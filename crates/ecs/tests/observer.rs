@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex};
+
+use ambient_ecs::{
+    components, Entity, FnSystem, FrameEvent, ObserverEvent, System, SystemGroup, World,
+};
+
+components!("test", {
+    a: f32,
+    b: f32,
+});
+
+fn init() {
+    init_components();
+}
+
+#[test]
+fn added_changed_removed() {
+    init();
+    let mut world = World::new_unknown("observers");
+    let log = Arc::new(Mutex::new(Vec::new()));
+    {
+        let log = log.clone();
+        world.on_added(a(), move |_, id, value| {
+            log.lock().unwrap().push((id, "added", None, Some(*value)))
+        });
+    }
+    {
+        let log = log.clone();
+        world.on_changed(a(), move |_, id, old, new| {
+            log.lock()
+                .unwrap()
+                .push((id, "changed", Some(*old), Some(*new)))
+        });
+    }
+    {
+        let log = log.clone();
+        world.on_removed(a(), move |_, id, value| {
+            log.lock()
+                .unwrap()
+                .push((id, "removed", Some(*value), None))
+        });
+    }
+
+    let id = world.spawn(Entity::new().with(a(), 1.));
+    world.set(id, a(), 2.).unwrap();
+    world.add_component(id, a(), 3.).unwrap();
+    world.add_component(id, b(), 0.).unwrap();
+    world.remove_component(id, a()).unwrap();
+    world.add_component(id, a(), 4.).unwrap();
+    world.despawn(id);
+
+    // Nothing is dispatched until the flush
+    assert!(log.lock().unwrap().is_empty());
+    world.flush_observers();
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            (id, "added", None, Some(1.)),
+            (id, "changed", Some(1.), Some(2.)),
+            (id, "changed", Some(2.), Some(3.)),
+            (id, "removed", Some(3.), None),
+            (id, "added", None, Some(4.)),
+            (id, "removed", Some(4.), None),
+        ]
+    );
+}
+
+#[test]
+fn unobserve() {
+    init();
+    let mut world = World::new_unknown("unobserve");
+    let count = Arc::new(Mutex::new(0));
+    let observer = {
+        let count = count.clone();
+        world.observe(a(), ObserverEvent::Added, move |_, _| {
+            *count.lock().unwrap() += 1
+        })
+    };
+    world.spawn(Entity::new().with(a(), 1.));
+    world.flush_observers();
+    assert!(world.unobserve(observer));
+    assert!(!world.unobserve(observer));
+    world.spawn(Entity::new().with(a(), 1.));
+    world.flush_observers();
+    assert_eq!(*count.lock().unwrap(), 1);
+}
+
+#[test]
+fn observers_run_between_systems() {
+    init();
+    let mut world = World::new_unknown("observer_systems");
+    // Observers can change the world, and their own changes are dispatched in the same flush
+    world.on_added(a(), |world, id, value| {
+        world.add_component(id, b(), *value * 2.).unwrap();
+    });
+    let seen = Arc::new(Mutex::new(None));
+    world.on_added(b(), {
+        let seen = seen.clone();
+        move |_, _, value| *seen.lock().unwrap() = Some(*value)
+    });
+
+    let mut systems = SystemGroup::new(
+        "observer_systems",
+        vec![
+            Box::new(FnSystem::new(|world, _| {
+                world.spawn(Entity::new().with(a(), 1.));
+            })),
+            Box::new(FnSystem::new({
+                let seen = seen.clone();
+                move |_, _| assert_eq!(*seen.lock().unwrap(), Some(2.))
+            })),
+        ],
+    );
+    systems.run(&mut world, &FrameEvent);
+}
@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use ambient_core::{
     name, runtime, snap_to_ground, tags,
@@ -288,6 +288,30 @@ fn EntityComponentsEditor(
                 PrimitiveComponentType::Vec4 => {
                     register_dynamic_component::<Vec4>(t, &display_name, desc)
                 }
+                PrimitiveComponentType::MapStringF32 => {
+                    register_dynamic_component::<HashMap<String, f32>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapStringI32 => {
+                    register_dynamic_component::<HashMap<String, i32>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapStringString => {
+                    register_dynamic_component::<HashMap<String, String>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapStringU32 => {
+                    register_dynamic_component::<HashMap<String, u32>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapU32F32 => {
+                    register_dynamic_component::<HashMap<u32, f32>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapU32I32 => {
+                    register_dynamic_component::<HashMap<u32, i32>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapU32String => {
+                    register_dynamic_component::<HashMap<u32, String>>(t, &display_name, desc)
+                }
+                PrimitiveComponentType::MapU32U32 => {
+                    register_dynamic_component::<HashMap<u32, u32>>(t, &display_name, desc)
+                }
                 _ => None,
             };
 
@@ -0,0 +1,289 @@
+//! Input actions.
+//!
+//! Packages declare named actions with default bindings, either in the `[actions]` section of
+//! their manifest (stored on the package entity as `package::input_actions`) or at runtime
+//! (stored in the [ActionRegistry]). Players can rebind any action; their rebinds are saved in
+//! the client [Settings](ambient_settings::Settings).
+use std::collections::{BTreeMap, HashMap};
+
+use ambient_core::asset_cache;
+use ambient_ecs::{generated::package::components as package, EntityId, World};
+use ambient_native_std::asset_cache::SyncAssetKeyExt;
+use ambient_settings::{InputSettings, SettingsKey};
+use ambient_shared_types::{ActionBinding, InputSource};
+
+use crate::{action_registry, PlayerRawInput};
+
+/// The actions registered at runtime, and the player's rebinds of all actions.
+#[derive(Clone, Default, Debug)]
+pub struct ActionRegistry {
+    /// Default bindings of the actions registered at runtime, by package ID and then action name.
+    registered: HashMap<String, HashMap<String, Vec<ActionBinding>>>,
+    /// The player's rebinds, by package ID and then action name.
+    overrides: HashMap<String, HashMap<String, Vec<ActionBinding>>>,
+}
+impl ActionRegistry {
+    /// Creates a registry with the rebinds saved in `settings`. Rebinds that can't be parsed are
+    /// ignored.
+    pub fn from_settings(settings: &InputSettings) -> Self {
+        let overrides = settings
+            .bindings
+            .iter()
+            .map(|(package_id, actions)| {
+                let actions = actions
+                    .iter()
+                    .filter_map(|(name, bindings)| {
+                        match bindings.iter().map(|b| b.parse()).collect() {
+                            Ok(bindings) => Some((name.clone(), bindings)),
+                            Err(err) => {
+                                tracing::warn!(
+                                    "Ignoring rebind of action {name:?} of package {package_id}: {err}"
+                                );
+                                None
+                            }
+                        }
+                    })
+                    .collect();
+                (package_id.clone(), actions)
+            })
+            .collect();
+
+        Self {
+            registered: HashMap::new(),
+            overrides,
+        }
+    }
+
+    fn to_settings(&self) -> InputSettings {
+        InputSettings {
+            bindings: self
+                .overrides
+                .iter()
+                .filter(|(_, actions)| !actions.is_empty())
+                .map(|(package_id, actions)| {
+                    let actions = actions
+                        .iter()
+                        .map(|(name, bindings)| {
+                            (
+                                name.clone(),
+                                bindings.iter().map(|b| b.to_string()).collect(),
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>();
+                    (package_id.clone(), actions)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An action of a package.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    pub name: String,
+    /// The bindings in use: the player's rebinds if there are any, otherwise the defaults.
+    pub bindings: Vec<ActionBinding>,
+    pub default_bindings: Vec<ActionBinding>,
+}
+
+/// Registers the action `name` of `package` with `default_bindings`, replacing the defaults of
+/// any action with the same name, including those declared in the package's manifest.
+pub fn register_action(
+    world: &mut World,
+    package: EntityId,
+    name: String,
+    default_bindings: Vec<ActionBinding>,
+) {
+    let Some(package_id) = package_id(world, package) else {
+        return;
+    };
+    world
+        .resource_mut(action_registry())
+        .registered
+        .entry(package_id)
+        .or_default()
+        .insert(name, default_bindings);
+}
+
+/// Returns all of the actions of `package`, sorted by name.
+pub fn actions(world: &World, package: EntityId) -> Vec<Action> {
+    let mut names = manifest_actions(world, package)
+        .into_keys()
+        .chain(
+            package_id(world, package)
+                .and_then(|id| world.resource(action_registry()).registered.get(&id))
+                .into_iter()
+                .flat_map(|actions| actions.keys().cloned()),
+        )
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    names
+        .iter()
+        .filter_map(|name| action(world, package, name))
+        .collect()
+}
+
+/// Returns the action `name` of `package`, if it has been declared in the package's manifest or
+/// registered at runtime.
+pub fn action(world: &World, package: EntityId, name: &str) -> Option<Action> {
+    let package_id = package_id(world, package)?;
+    let registry = world.resource(action_registry());
+
+    let default_bindings = registry
+        .registered
+        .get(&package_id)
+        .and_then(|actions| actions.get(name))
+        .cloned()
+        .or_else(|| manifest_actions(world, package).remove(name))?;
+    let bindings = registry
+        .overrides
+        .get(&package_id)
+        .and_then(|actions| actions.get(name))
+        .cloned()
+        .unwrap_or_else(|| default_bindings.clone());
+
+    Some(Action {
+        name: name.to_string(),
+        bindings,
+        default_bindings,
+    })
+}
+
+/// Sets the player's bindings for the action `name` of `package`, or restores the defaults if
+/// `bindings` is `None`, and saves the change to the settings.
+pub fn rebind_action(
+    world: &mut World,
+    package: EntityId,
+    name: &str,
+    bindings: Option<Vec<ActionBinding>>,
+) {
+    let Some(package_id) = package_id(world, package) else {
+        return;
+    };
+
+    let registry = world.resource_mut(action_registry());
+    let overrides = registry.overrides.entry(package_id).or_default();
+    match bindings {
+        Some(bindings) => {
+            overrides.insert(name.to_string(), bindings);
+        }
+        None => {
+            overrides.remove(name);
+        }
+    }
+    let input_settings = registry.to_settings();
+
+    let assets = world.resource(asset_cache());
+    let mut settings = SettingsKey.get(assets);
+    settings.input = input_settings;
+    #[cfg(not(target_os = "unknown"))]
+    if let Err(err) = settings.write_to_file(None) {
+        tracing::warn!("Failed to save input bindings: {err:?}");
+    }
+    SettingsKey.insert(assets, settings);
+}
+
+/// Returns the value of an action with `bindings` for `input`, from -1 to 1.
+///
+/// Each binding contributes 1 while its key or button is held, or the value of its axis, and
+/// the contributions are summed.
+pub fn action_value(bindings: &[ActionBinding], input: &PlayerRawInput) -> f32 {
+    let held = |held: bool| if held { 1.0 } else { 0.0 };
+
+    bindings
+        .iter()
+        .map(|binding| {
+            let value = match binding.source {
+                InputSource::Key(key) => held(input.keys.contains(&key)),
+                InputSource::MouseButton(button) => held(input.mouse_buttons.contains(&button)),
+                InputSource::GamepadButton(button) => held(
+                    input
+                        .gamepads
+                        .iter()
+                        .any(|gamepad| gamepad.buttons.contains(&button)),
+                ),
+                // Use whichever gamepad is pushing the axis the furthest
+                InputSource::GamepadAxis(axis) => input
+                    .gamepads
+                    .iter()
+                    .map(|gamepad| gamepad.axis(axis))
+                    .fold(0.0, |a: f32, b| if b.abs() > a.abs() { b } else { a }),
+            };
+            if binding.negative {
+                -value
+            } else {
+                value
+            }
+        })
+        .sum::<f32>()
+        .clamp(-1.0, 1.0)
+}
+
+fn package_id(world: &World, package: EntityId) -> Option<String> {
+    world.get_cloned(package, package::id()).ok()
+}
+
+fn manifest_actions(world: &World, package: EntityId) -> HashMap<String, Vec<ActionBinding>> {
+    let Ok(actions) = world.get_ref(package, package::input_actions()) else {
+        return HashMap::new();
+    };
+
+    actions
+        .iter()
+        .filter_map(
+            |(name, bindings)| match ActionBinding::parse_list(bindings) {
+                Ok(bindings) => Some((name.clone(), bindings)),
+                Err(err) => {
+                    tracing::warn!("Ignoring action {name:?} with invalid bindings: {err}");
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_shared_types::{GamepadAxis, GamepadButton, VirtualKeyCode};
+    use glam::vec2;
+
+    use super::*;
+    use crate::gamepad::Gamepad;
+
+    #[test]
+    fn action_values() {
+        let steer = [
+            ActionBinding::from(VirtualKeyCode::D),
+            ActionBinding::from(VirtualKeyCode::A).negated(),
+            ActionBinding::from(GamepadAxis::LeftStickX),
+        ];
+        let boost = [
+            ActionBinding::from(VirtualKeyCode::Space),
+            ActionBinding::from(GamepadButton::South),
+        ];
+
+        let mut input = PlayerRawInput::default();
+        assert_eq!(action_value(&steer, &input), 0.0);
+        assert_eq!(action_value(&boost, &input), 0.0);
+
+        input.keys.insert(VirtualKeyCode::A);
+        assert_eq!(action_value(&steer, &input), -1.0);
+        input.keys.insert(VirtualKeyCode::D);
+        assert_eq!(action_value(&steer, &input), 0.0);
+        input.keys.clear();
+
+        let mut gamepad = Gamepad::new(0, "Test".to_string());
+        gamepad.left_stick = vec2(0.5, 0.0);
+        gamepad.buttons.insert(GamepadButton::South);
+        input.gamepads.push(gamepad);
+        assert_eq!(action_value(&steer, &input), 0.5);
+        assert_eq!(action_value(&boost, &input), 1.0);
+
+        input.keys.insert(VirtualKeyCode::D);
+        input.keys.insert(VirtualKeyCode::Space);
+        assert_eq!(action_value(&steer, &input), 1.0);
+        assert_eq!(action_value(&boost, &input), 1.0);
+    }
+}
@@ -0,0 +1,364 @@
+//! Gamepad support.
+//!
+//! Gamepads are polled on a background thread, and their input is sent to the world as
+//! runtime messages (`GamepadConnected`, `GamepadButtonInput`, etc). The client turns these into
+//! the [Gamepad]s in [PlayerRawInput](crate::PlayerRawInput), applying the [GamepadDeadzones].
+use std::{collections::HashSet, time::Duration};
+
+use ambient_ecs::{generated::messages, world_events, FnSystem, System, WorldEventsExt};
+use ambient_shared_types::{GamepadAxis, GamepadButton, GamepadStick, GamepadTrigger};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// The state of a connected gamepad.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Gamepad {
+    pub id: u32,
+    pub name: String,
+    pub buttons: HashSet<GamepadButton>,
+    /// The position of the left stick, with the deadzone applied. +Y is up.
+    pub left_stick: Vec2,
+    /// The position of the right stick, with the deadzone applied. +Y is up.
+    pub right_stick: Vec2,
+    /// How far the left trigger is pressed, from 0 to 1, with the deadzone applied.
+    pub left_trigger: f32,
+    /// How far the right trigger is pressed, from 0 to 1, with the deadzone applied.
+    pub right_trigger: f32,
+}
+impl Gamepad {
+    pub fn new(id: u32, name: String) -> Self {
+        Self {
+            id,
+            name,
+            buttons: HashSet::new(),
+            left_stick: Vec2::ZERO,
+            right_stick: Vec2::ZERO,
+            left_trigger: 0.0,
+            right_trigger: 0.0,
+        }
+    }
+
+    pub fn stick_mut(&mut self, stick: GamepadStick) -> &mut Vec2 {
+        match stick {
+            GamepadStick::Left => &mut self.left_stick,
+            GamepadStick::Right => &mut self.right_stick,
+        }
+    }
+
+    pub fn trigger_mut(&mut self, trigger: GamepadTrigger) -> &mut f32 {
+        match trigger {
+            GamepadTrigger::Left => &mut self.left_trigger,
+            GamepadTrigger::Right => &mut self.right_trigger,
+        }
+    }
+
+    /// Returns the current value of `axis`.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        match axis {
+            GamepadAxis::LeftStickX => self.left_stick.x,
+            GamepadAxis::LeftStickY => self.left_stick.y,
+            GamepadAxis::RightStickX => self.right_stick.x,
+            GamepadAxis::RightStickY => self.right_stick.y,
+            GamepadAxis::LeftTrigger => self.left_trigger,
+            GamepadAxis::RightTrigger => self.right_trigger,
+        }
+    }
+
+    /// Releases all buttons and recenters the sticks and triggers.
+    pub fn clear(&mut self) {
+        *self = Self::new(self.id, std::mem::take(&mut self.name));
+    }
+}
+
+/// How far the sticks and triggers have to move before they register as input. This hides the
+/// small amount of drift that most gamepads have at rest.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GamepadDeadzones {
+    /// The radius of the deadzone around the center of each stick, from 0 to 1.
+    pub stick: f32,
+    /// The deadzone at the start of each trigger's travel, from 0 to 1.
+    pub trigger: f32,
+}
+impl Default for GamepadDeadzones {
+    fn default() -> Self {
+        Self {
+            stick: 0.15,
+            trigger: 0.05,
+        }
+    }
+}
+
+/// Applies a radial deadzone to a stick position. Positions within the deadzone become zero, and
+/// the rest of the range is rescaled so that the output still goes smoothly from 0 to 1.
+pub fn apply_stick_deadzone(value: Vec2, deadzone: f32) -> Vec2 {
+    let deadzone = deadzone.clamp(0.0, 0.99);
+    let length = value.length();
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+
+    let scaled = ((length - deadzone) / (1.0 - deadzone)).min(1.0);
+    value * (scaled / length)
+}
+
+/// Applies a deadzone to a trigger position, rescaling the rest of its travel to 0 to 1.
+pub fn apply_trigger_deadzone(value: f32, deadzone: f32) -> f32 {
+    let deadzone = deadzone.clamp(0.0, 0.99);
+    if value <= deadzone {
+        return 0.0;
+    }
+
+    ((value - deadzone) / (1.0 - deadzone)).min(1.0)
+}
+
+/// Allows controlling the gamepads from afar.
+#[derive(Debug, Clone)]
+pub enum GamepadCtl {
+    /// Vibrates the gamepad for `duration`, replacing any vibration that is already playing.
+    /// `strong` and `weak` are the magnitudes of the low- and high-frequency motors, from 0 to 1.
+    Rumble {
+        gamepad_id: u32,
+        strong: f32,
+        weak: f32,
+        duration: Duration,
+    },
+}
+
+#[derive(Debug)]
+enum GamepadEvent {
+    Connected(messages::GamepadConnected),
+    Disconnected(messages::GamepadDisconnected),
+    Button(messages::GamepadButtonInput),
+    Stick(messages::GamepadStickInput),
+    Trigger(messages::GamepadTriggerInput),
+}
+
+/// Starts polling the gamepads, and returns a system that sends their input to the world as
+/// runtime messages.
+pub fn gamepad_system(
+    gamepad_ctl_rx: flume::Receiver<GamepadCtl>,
+) -> Box<dyn System + Send + Sync> {
+    let (events_tx, events_rx) = flume::unbounded();
+
+    #[cfg(not(target_os = "unknown"))]
+    if let Err(err) = std::thread::Builder::new()
+        .name("gamepads".to_string())
+        .spawn(move || poll_gamepads(events_tx, gamepad_ctl_rx))
+    {
+        tracing::warn!("Failed to start gamepad thread: {err}");
+    }
+
+    // TODO: support gamepads on the web. gilrs only polls native backends, so web clients
+    // currently report no gamepads and ignore rumble.
+    #[cfg(target_os = "unknown")]
+    let _ = (events_tx, gamepad_ctl_rx);
+
+    Box::new(FnSystem::new(move |world, _event| {
+        let events = world.resource_mut(world_events());
+        for event in events_rx.drain() {
+            match event {
+                GamepadEvent::Connected(msg) => events.add_message(msg),
+                GamepadEvent::Disconnected(msg) => events.add_message(msg),
+                GamepadEvent::Button(msg) => events.add_message(msg),
+                GamepadEvent::Stick(msg) => events.add_message(msg),
+                GamepadEvent::Trigger(msg) => events.add_message(msg),
+            }
+        }
+    }))
+}
+
+#[cfg(not(target_os = "unknown"))]
+fn poll_gamepads(events: flume::Sender<GamepadEvent>, ctl: flume::Receiver<GamepadCtl>) {
+    use std::{collections::HashMap, time::Instant};
+
+    use gilrs::{ff, Axis, Button, EventType, Gilrs};
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(4);
+
+    let mut gilrs = match Gilrs::new() {
+        Ok(gilrs) => gilrs,
+        Err(err) => {
+            tracing::warn!("Failed to initialize gamepad support: {err}");
+            return;
+        }
+    };
+
+    let to_id = |id: gilrs::GamepadId| usize::from(id) as u32;
+    let to_button = |button: Button| {
+        Some(match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::North => GamepadButton::North,
+            Button::West => GamepadButton::West,
+            Button::LeftTrigger => GamepadButton::LeftBumper,
+            Button::RightTrigger => GamepadButton::RightBumper,
+            Button::LeftThumb => GamepadButton::LeftStick,
+            Button::RightThumb => GamepadButton::RightStick,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::Mode => GamepadButton::Mode,
+            Button::DPadUp => GamepadButton::DPadUp,
+            Button::DPadDown => GamepadButton::DPadDown,
+            Button::DPadLeft => GamepadButton::DPadLeft,
+            Button::DPadRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    };
+
+    for (id, gamepad) in gilrs.gamepads() {
+        let connected = messages::GamepadConnected::new(to_id(id), gamepad.name().to_string());
+        if events.send(GamepadEvent::Connected(connected)).is_err() {
+            return;
+        }
+    }
+
+    // Effects stop playing when they're dropped, so they're kept until they've finished
+    let mut effects: HashMap<u32, (Instant, ff::Effect)> = HashMap::new();
+    loop {
+        if let Some(gilrs::Event { id, event, .. }) = gilrs.next_event_blocking(Some(POLL_INTERVAL))
+        {
+            let gamepad_id = to_id(id);
+            let event = match event {
+                EventType::Connected => GamepadEvent::Connected(messages::GamepadConnected::new(
+                    gamepad_id,
+                    gilrs.gamepad(id).name().to_string(),
+                )),
+                EventType::Disconnected => {
+                    effects.remove(&gamepad_id);
+                    GamepadEvent::Disconnected(messages::GamepadDisconnected::new(gamepad_id))
+                }
+                EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                    let Some(button) = to_button(button) else {
+                        continue;
+                    };
+                    GamepadEvent::Button(messages::GamepadButtonInput::new(
+                        gamepad_id,
+                        u32::from(button),
+                        matches!(event, EventType::ButtonPressed(..)),
+                    ))
+                }
+                EventType::ButtonChanged(button, value, _) => {
+                    let trigger = match button {
+                        Button::LeftTrigger2 => GamepadTrigger::Left,
+                        Button::RightTrigger2 => GamepadTrigger::Right,
+                        _ => continue,
+                    };
+                    GamepadEvent::Trigger(messages::GamepadTriggerInput::new(
+                        gamepad_id,
+                        u32::from(trigger),
+                        value,
+                    ))
+                }
+                EventType::AxisChanged(axis, _, _) => {
+                    let (stick, x, y) = match axis {
+                        Axis::LeftStickX | Axis::LeftStickY => {
+                            (GamepadStick::Left, Axis::LeftStickX, Axis::LeftStickY)
+                        }
+                        Axis::RightStickX | Axis::RightStickY => {
+                            (GamepadStick::Right, Axis::RightStickX, Axis::RightStickY)
+                        }
+                        _ => continue,
+                    };
+                    // Send both axes so that the deadzone can be applied to the stick as a whole
+                    let gamepad = gilrs.gamepad(id);
+                    GamepadEvent::Stick(messages::GamepadStickInput::new(
+                        gamepad_id,
+                        u32::from(stick),
+                        Vec2::new(gamepad.value(x), gamepad.value(y)),
+                    ))
+                }
+                _ => continue,
+            };
+
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+
+        for ctl in ctl.try_iter() {
+            match ctl {
+                GamepadCtl::Rumble {
+                    gamepad_id,
+                    strong,
+                    weak,
+                    duration,
+                } => {
+                    effects.remove(&gamepad_id);
+                    if (strong <= 0.0 && weak <= 0.0) || duration.is_zero() {
+                        continue;
+                    }
+
+                    let Some((id, _)) = gilrs.gamepads().find(|(id, gamepad)| {
+                        to_id(*id) == gamepad_id && gamepad.is_ff_supported()
+                    }) else {
+                        continue;
+                    };
+
+                    let play_for = ff::Ticks::from_ms(duration.as_millis() as u32);
+                    let scheduling = ff::Replay {
+                        play_for,
+                        ..Default::default()
+                    };
+                    let magnitude = |value: f32| (value.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+                    let effect = ff::EffectBuilder::new()
+                        .add_effect(ff::BaseEffect {
+                            kind: ff::BaseEffectType::Strong {
+                                magnitude: magnitude(strong),
+                            },
+                            scheduling,
+                            ..Default::default()
+                        })
+                        .add_effect(ff::BaseEffect {
+                            kind: ff::BaseEffectType::Weak {
+                                magnitude: magnitude(weak),
+                            },
+                            scheduling,
+                            ..Default::default()
+                        })
+                        .repeat(ff::Repeat::For(play_for))
+                        .gamepads(&[id])
+                        .finish(&mut gilrs)
+                        .and_then(|effect| effect.play().map(|_| effect));
+
+                    match effect {
+                        Ok(effect) => {
+                            effects.insert(gamepad_id, (Instant::now() + duration, effect));
+                        }
+                        Err(err) => tracing::warn!("Failed to rumble gamepad {gamepad_id}: {err}"),
+                    }
+                }
+            }
+        }
+
+        let now = Instant::now();
+        effects.retain(|_, (until, _)| *until > now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::vec2;
+
+    use super::*;
+
+    #[test]
+    fn stick_deadzone() {
+        assert_eq!(apply_stick_deadzone(vec2(0.1, 0.05), 0.15), Vec2::ZERO);
+        assert_eq!(apply_stick_deadzone(vec2(1.0, 0.0), 0.15), vec2(1.0, 0.0));
+
+        // The output is rescaled, and keeps the direction of the input
+        let value = apply_stick_deadzone(vec2(0.0, -0.575), 0.15);
+        assert!((value - vec2(0.0, -0.5)).length() < 1e-5);
+
+        // Corners can go past 1 on some gamepads
+        assert!((apply_stick_deadzone(vec2(1.0, 1.0), 0.15).length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn trigger_deadzone() {
+        assert_eq!(apply_trigger_deadzone(0.04, 0.05), 0.0);
+        assert_eq!(apply_trigger_deadzone(1.0, 0.05), 1.0);
+        assert!((apply_trigger_deadzone(0.525, 0.05) - 0.5).abs() < 1e-5);
+        assert_eq!(apply_trigger_deadzone(0.5, 0.0), 0.5);
+    }
+}
@@ -1,9 +1,11 @@
 use std::collections::HashSet;
 
+use ambient_core::window::window_scale_factor;
 use ambient_ecs::{
-    components, generated::messages, world_events, Debuggable, Entity, FnSystem, Resource, System,
-    SystemGroup, WorldEventsExt,
+    components, generated::messages, world_events, Debuggable, Description, Entity, FnSystem, Name,
+    Resource, System, SystemGroup, WorldEventsExt,
 };
+use ambient_sys::time::Instant;
 use glam::{vec2, Vec2};
 use serde::{Deserialize, Serialize};
 use winit::event::ModifiersState;
@@ -12,7 +14,14 @@ pub use winit::event::{
     WindowEvent,
 };
 
+pub mod action;
+pub mod gamepad;
 pub mod picking;
+pub mod touch;
+
+use action::ActionRegistry;
+use gamepad::{Gamepad, GamepadCtl, GamepadDeadzones};
+use touch::{GestureRecognizer, Touch};
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
 pub struct PlayerRawInput {
@@ -21,6 +30,10 @@ pub struct PlayerRawInput {
     pub mouse_delta: Vec2,
     pub mouse_wheel: f32,
     pub mouse_buttons: HashSet<ambient_shared_types::MouseButton>,
+    /// The connected gamepads, in the order they were connected.
+    pub gamepads: Vec<Gamepad>,
+    /// The fingers touching the screen, in the order they started touching it.
+    pub touches: Vec<Touch>,
 }
 impl PlayerRawInput {
     pub fn clear(&mut self) {
@@ -28,6 +41,19 @@ impl PlayerRawInput {
         self.mouse_delta = vec2(0.0, 0.0);
         self.mouse_wheel = 0.0;
         self.mouse_buttons.clear();
+        // Gamepads stay connected, but their input is released
+        for gamepad in &mut self.gamepads {
+            gamepad.clear();
+        }
+        self.touches.clear();
+    }
+
+    pub fn gamepad_mut(&mut self, id: u32) -> Option<&mut Gamepad> {
+        self.gamepads.iter_mut().find(|gamepad| gamepad.id == id)
+    }
+
+    pub fn touch_mut(&mut self, id: u64) -> Option<&mut Touch> {
+        self.touches.iter_mut().find(|touch| touch.id == id)
     }
 }
 
@@ -38,6 +64,12 @@ components!("input", {
     player_raw_input: PlayerRawInput,
     @[Debuggable, Resource]
     player_prev_raw_input: PlayerRawInput,
+    @[Debuggable, Resource]
+    gamepad_deadzones: GamepadDeadzones,
+    @[Debuggable, Resource]
+    action_registry: ActionRegistry,
+    @[Resource, Name["Gamepad Control"], Description["Allows controlling the gamepads from afar."]]
+    gamepad_ctl: flume::Sender<GamepadCtl>,
 });
 
 pub fn init_all_components() {
@@ -63,12 +95,16 @@ pub fn resources() -> Entity {
     Entity::new()
         .with(player_raw_input(), Default::default())
         .with(player_prev_raw_input(), Default::default())
+        .with(gamepad_deadzones(), Default::default())
+        .with(action_registry(), Default::default())
 }
 
 #[derive(Debug)]
 pub struct InputSystem {
     modifiers: ModifiersState,
     is_focused: bool,
+    gestures: GestureRecognizer,
+    start_time: Instant,
 }
 
 impl InputSystem {
@@ -76,12 +112,19 @@ impl InputSystem {
         Self {
             modifiers: ModifiersState::empty(),
             is_focused: true,
+            gestures: GestureRecognizer::new(),
+            start_time: Instant::now(),
         }
     }
 }
 
 impl System<Event<'static, ()>> for InputSystem {
     fn run(&mut self, world: &mut ambient_ecs::World, event: &Event<'static, ()>) {
+        // Long presses are recognized by waiting, so check for them on every event
+        for gesture in self.gestures.update(self.start_time.elapsed()) {
+            gesture.send(world);
+        }
+
         match event {
             Event::WindowEvent { event, .. } => match event {
                 &WindowEvent::Focused(focused) => {
@@ -89,6 +132,11 @@ impl System<Event<'static, ()>> for InputSystem {
                     world
                         .resource_mut(world_events())
                         .add_message(messages::WindowFocusChange::new(focused));
+                    if !focused {
+                        for gesture in self.gestures.cancel() {
+                            gesture.send(world);
+                        }
+                    }
                 }
                 WindowEvent::ReceivedCharacter(c) => {
                     // HACK: Drop the following characters as they will be produced
@@ -164,6 +212,27 @@ impl System<Event<'static, ()>> for InputSystem {
                     );
                 }
 
+                WindowEvent::Touch(touch) => {
+                    let scale_factor = world
+                        .resource_opt(window_scale_factor())
+                        .copied()
+                        .unwrap_or(1.0);
+                    let position = vec2(touch.location.x as f32, touch.location.y as f32)
+                        / scale_factor as f32;
+                    let phase = ambient_shared_types::TouchPhase::from(touch.phase);
+                    let pressure = touch.force.map_or(1.0, |force| force.normalized() as f32);
+
+                    world.resource_mut(world_events()).add_message(
+                        messages::WindowTouchInput::new(touch.id, phase, position, pressure),
+                    );
+                    for gesture in
+                        self.gestures
+                            .touch(touch.id, phase, position, self.start_time.elapsed())
+                    {
+                        gesture.send(world);
+                    }
+                }
+
                 _ => {}
             },
 
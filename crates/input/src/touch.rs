@@ -0,0 +1,439 @@
+//! Touch input and gestures.
+//!
+//! Touches are sent to the world as `WindowTouchInput` runtime messages, which the client turns
+//! into the [Touch]es in [PlayerRawInput](crate::PlayerRawInput). The [GestureRecognizer]
+//! recognizes taps, long presses, pans, pinches and rotations from the same touches, and sends
+//! them as `GestureTap`, `GestureLongPress`, `GesturePan`, `GesturePinch` and `GestureRotate`
+//! messages.
+use std::{f32::consts::PI, time::Duration};
+
+use ambient_ecs::{generated::messages, world_events, World, WorldEventsExt};
+use ambient_shared_types::TouchPhase;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// How long a touch can last and still be a tap.
+const TAP_MAX_DURATION: Duration = Duration::from_millis(300);
+/// How long a touch has to be held without moving to be a long press.
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// How far a touch can move, in logical pixels, before it is considered to be dragged.
+const DRAG_THRESHOLD: f32 = 10.0;
+
+/// A finger touching the screen.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Touch {
+    /// Identifies the touch while it lasts. IDs can be reused once a touch ends.
+    pub id: u64,
+    /// The position of the touch, in logical pixels.
+    pub position: Vec2,
+    /// How hard the screen is being pressed, from 0 to 1. 1 if the device doesn't report pressure.
+    pub pressure: f32,
+    /// `Started` on the frame the touch started, `Ended` or `Cancelled` on the frame it finished,
+    /// and `Moved` in between. Finished touches are removed at the end of the frame.
+    pub phase: TouchPhase,
+}
+
+/// A gesture recognized from touches.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gesture {
+    Tap {
+        position: Vec2,
+    },
+    LongPress {
+        position: Vec2,
+    },
+    Pan {
+        phase: TouchPhase,
+        position: Vec2,
+        delta: Vec2,
+    },
+    Pinch {
+        phase: TouchPhase,
+        center: Vec2,
+        scale: f32,
+    },
+    Rotate {
+        phase: TouchPhase,
+        center: Vec2,
+        angle: f32,
+    },
+}
+impl Gesture {
+    /// Sends this gesture to `world` as a runtime message.
+    pub fn send(self, world: &mut World) {
+        let events = world.resource_mut(world_events());
+        match self {
+            Gesture::Tap { position } => events.add_message(messages::GestureTap::new(position)),
+            Gesture::LongPress { position } => {
+                events.add_message(messages::GestureLongPress::new(position))
+            }
+            Gesture::Pan {
+                phase,
+                position,
+                delta,
+            } => events.add_message(messages::GesturePan::new(phase, position, delta)),
+            Gesture::Pinch {
+                phase,
+                center,
+                scale,
+            } => events.add_message(messages::GesturePinch::new(phase, center, scale)),
+            Gesture::Rotate {
+                phase,
+                center,
+                angle,
+            } => events.add_message(messages::GestureRotate::new(phase, center, angle)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TrackedTouch {
+    id: u64,
+    start_position: Vec2,
+    start_time: Duration,
+    position: Vec2,
+    /// Set once the touch has moved further than [DRAG_THRESHOLD], or was joined by another
+    /// touch. Such touches can't be taps or long presses anymore.
+    dragged: bool,
+    long_pressed: bool,
+}
+
+/// Recognizes gestures from a stream of touches.
+///
+/// One finger makes taps, long presses and pans; two fingers make pinches and rotations, which
+/// are always reported together. Touches beyond the first two are ignored by gestures.
+#[derive(Clone, Debug, Default)]
+pub struct GestureRecognizer {
+    touches: Vec<TrackedTouch>,
+    panning: bool,
+    /// The distance and angle between the two fingers at the last pinch and rotation.
+    two_finger: Option<(f32, f32)>,
+}
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the recognizer with a touch event that happened at `time`, and returns the
+    /// gestures it completes or continues.
+    pub fn touch(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2,
+        time: Duration,
+    ) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match phase {
+            TouchPhase::Started => {
+                self.touches.push(TrackedTouch {
+                    id,
+                    start_position: position,
+                    start_time: time,
+                    position,
+                    dragged: false,
+                    long_pressed: false,
+                });
+                if self.touches.len() == 2 {
+                    self.end_pan(TouchPhase::Cancelled, &mut gestures);
+                    for touch in &mut self.touches {
+                        touch.dragged = true;
+                    }
+                    let (center, distance, angle) = self.two_finger_state();
+                    self.two_finger = Some((distance, angle));
+                    gestures.push(Gesture::Pinch {
+                        phase: TouchPhase::Started,
+                        center,
+                        scale: 1.0,
+                    });
+                    gestures.push(Gesture::Rotate {
+                        phase: TouchPhase::Started,
+                        center,
+                        angle: 0.0,
+                    });
+                }
+            }
+            TouchPhase::Moved => {
+                let Some(index) = self.touches.iter().position(|t| t.id == id) else {
+                    return gestures;
+                };
+                let touch = &mut self.touches[index];
+                let delta = position - touch.position;
+                touch.position = position;
+                if touch.start_position.distance(position) > DRAG_THRESHOLD {
+                    touch.dragged = true;
+                }
+
+                if self.touches.len() == 1 {
+                    let touch = &self.touches[0];
+                    if touch.dragged && !touch.long_pressed {
+                        if !self.panning {
+                            self.panning = true;
+                            gestures.push(Gesture::Pan {
+                                phase: TouchPhase::Started,
+                                position,
+                                delta: position - touch.start_position,
+                            });
+                        } else {
+                            gestures.push(Gesture::Pan {
+                                phase: TouchPhase::Moved,
+                                position,
+                                delta,
+                            });
+                        }
+                    }
+                } else if index < 2 {
+                    if let Some((prev_distance, prev_angle)) = self.two_finger {
+                        let (center, distance, angle) = self.two_finger_state();
+                        self.two_finger = Some((distance, angle));
+                        gestures.push(Gesture::Pinch {
+                            phase: TouchPhase::Moved,
+                            center,
+                            scale: if prev_distance > 0.0 {
+                                distance / prev_distance
+                            } else {
+                                1.0
+                            },
+                        });
+                        gestures.push(Gesture::Rotate {
+                            phase: TouchPhase::Moved,
+                            center,
+                            angle: wrap_angle(angle - prev_angle),
+                        });
+                    }
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let Some(index) = self.touches.iter().position(|t| t.id == id) else {
+                    return gestures;
+                };
+                let touch = &self.touches[index];
+
+                if phase == TouchPhase::Ended
+                    && self.touches.len() == 1
+                    && !touch.dragged
+                    && !touch.long_pressed
+                    && time.saturating_sub(touch.start_time) <= TAP_MAX_DURATION
+                {
+                    gestures.push(Gesture::Tap { position });
+                }
+
+                if self.panning && self.touches.len() == 1 {
+                    self.panning = false;
+                    gestures.push(Gesture::Pan {
+                        phase,
+                        position,
+                        delta: position - touch.position,
+                    });
+                }
+
+                if index < 2 && self.two_finger.is_some() {
+                    let (center, _, _) = self.two_finger_state();
+                    self.two_finger = None;
+                    gestures.push(Gesture::Pinch {
+                        phase,
+                        center,
+                        scale: 1.0,
+                    });
+                    gestures.push(Gesture::Rotate {
+                        phase,
+                        center,
+                        angle: 0.0,
+                    });
+                }
+
+                self.touches.remove(index);
+            }
+        }
+
+        gestures
+    }
+
+    /// Returns the gestures that complete by waiting until `time`, i.e. long presses.
+    pub fn update(&mut self, time: Duration) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        if let [touch] = self.touches.as_mut_slice() {
+            if !touch.dragged
+                && !touch.long_pressed
+                && time.saturating_sub(touch.start_time) >= LONG_PRESS_DURATION
+            {
+                touch.long_pressed = true;
+                gestures.push(Gesture::LongPress {
+                    position: touch.position,
+                });
+            }
+        }
+        gestures
+    }
+
+    /// Ends all gestures in progress, e.g. when the window loses focus.
+    pub fn cancel(&mut self) -> Vec<Gesture> {
+        let ids = self
+            .touches
+            .iter()
+            .map(|t| (t.id, t.position))
+            .collect::<Vec<_>>();
+        let mut gestures = Vec::new();
+        for (id, position) in ids {
+            gestures.extend(self.touch(id, TouchPhase::Cancelled, position, Duration::ZERO));
+        }
+        gestures
+    }
+
+    fn end_pan(&mut self, phase: TouchPhase, gestures: &mut Vec<Gesture>) {
+        if self.panning {
+            self.panning = false;
+            gestures.push(Gesture::Pan {
+                phase,
+                position: self.touches[0].position,
+                delta: Vec2::ZERO,
+            });
+        }
+    }
+
+    /// Returns the center of, distance between and angle between the first two touches.
+    fn two_finger_state(&self) -> (Vec2, f32, f32) {
+        let (a, b) = (self.touches[0].position, self.touches[1].position);
+        let offset = b - a;
+        ((a + b) / 2.0, offset.length(), offset.y.atan2(offset.x))
+    }
+}
+
+/// Wraps `angle` to the range -PI to PI.
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn taps_and_long_presses() {
+        let mut recognizer = GestureRecognizer::new();
+        let position = vec2(100.0, 100.0);
+
+        assert!(recognizer
+            .touch(0, TouchPhase::Started, position, ms(0))
+            .is_empty());
+        assert_eq!(
+            recognizer.touch(0, TouchPhase::Ended, position, ms(100)),
+            vec![Gesture::Tap { position }]
+        );
+
+        // Held too long for a tap
+        recognizer.touch(0, TouchPhase::Started, position, ms(1000));
+        assert!(recognizer.update(ms(1200)).is_empty());
+        assert_eq!(
+            recognizer.update(ms(1500)),
+            vec![Gesture::LongPress { position }]
+        );
+        assert!(recognizer.update(ms(1600)).is_empty());
+        assert!(recognizer
+            .touch(0, TouchPhase::Ended, position, ms(1700))
+            .is_empty());
+    }
+
+    #[test]
+    fn pans() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.touch(0, TouchPhase::Started, vec2(0.0, 0.0), ms(0));
+        // Within the drag threshold, so this could still be a tap
+        assert!(recognizer
+            .touch(0, TouchPhase::Moved, vec2(5.0, 0.0), ms(10))
+            .is_empty());
+        assert_eq!(
+            recognizer.touch(0, TouchPhase::Moved, vec2(20.0, 0.0), ms(20)),
+            vec![Gesture::Pan {
+                phase: TouchPhase::Started,
+                position: vec2(20.0, 0.0),
+                delta: vec2(20.0, 0.0),
+            }]
+        );
+        assert_eq!(
+            recognizer.touch(0, TouchPhase::Moved, vec2(30.0, 5.0), ms(30)),
+            vec![Gesture::Pan {
+                phase: TouchPhase::Moved,
+                position: vec2(30.0, 5.0),
+                delta: vec2(10.0, 5.0),
+            }]
+        );
+        assert_eq!(
+            recognizer.touch(0, TouchPhase::Ended, vec2(30.0, 5.0), ms(40)),
+            vec![Gesture::Pan {
+                phase: TouchPhase::Ended,
+                position: vec2(30.0, 5.0),
+                delta: vec2(0.0, 0.0),
+            }]
+        );
+        assert!(recognizer.update(ms(2000)).is_empty());
+    }
+
+    #[test]
+    fn pinches_and_rotations() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.touch(0, TouchPhase::Started, vec2(0.0, 0.0), ms(0));
+        let gestures = recognizer.touch(1, TouchPhase::Started, vec2(10.0, 0.0), ms(0));
+        assert_eq!(
+            gestures,
+            vec![
+                Gesture::Pinch {
+                    phase: TouchPhase::Started,
+                    center: vec2(5.0, 0.0),
+                    scale: 1.0,
+                },
+                Gesture::Rotate {
+                    phase: TouchPhase::Started,
+                    center: vec2(5.0, 0.0),
+                    angle: 0.0,
+                },
+            ]
+        );
+
+        // Spread the fingers apart and turn them a quarter turn clockwise
+        let gestures = recognizer.touch(1, TouchPhase::Moved, vec2(0.0, 20.0), ms(10));
+        let [Gesture::Pinch {
+            phase: TouchPhase::Moved,
+            center,
+            scale,
+        }, Gesture::Rotate {
+            phase: TouchPhase::Moved,
+            angle,
+            ..
+        }] = gestures.as_slice()
+        else {
+            panic!("unexpected gestures: {gestures:?}");
+        };
+        assert_eq!(*center, vec2(0.0, 10.0));
+        assert!((scale - 2.0).abs() < 1e-5);
+        assert!((angle - PI / 2.0).abs() < 1e-5);
+
+        // Neither finger taps when the gesture ends
+        let gestures = recognizer.touch(0, TouchPhase::Ended, vec2(0.0, 0.0), ms(20));
+        assert!(matches!(
+            gestures.as_slice(),
+            [
+                Gesture::Pinch {
+                    phase: TouchPhase::Ended,
+                    ..
+                },
+                Gesture::Rotate {
+                    phase: TouchPhase::Ended,
+                    ..
+                },
+            ]
+        ));
+        assert!(recognizer
+            .touch(1, TouchPhase::Ended, vec2(0.0, 20.0), ms(30))
+            .is_empty());
+    }
+}
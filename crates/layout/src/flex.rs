@@ -0,0 +1,141 @@
+/// A child of a `Flow` that is sized with the `flex_` components.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FlexItem {
+    pub basis: f32,
+    pub grow: f32,
+    pub shrink: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl FlexItem {
+    /// Limits `size` to the item's bounds. Unlike [f32::clamp], this does not panic if the
+    /// bounds are inverted or NaN; the max wins over the min.
+    fn clamp(&self, size: f32) -> f32 {
+        size.max(self.min).min(self.max)
+    }
+}
+
+/// Distributes `free_space` (which is negative if the items overflow) between the `items`, and
+/// returns their final sizes.
+///
+/// Items that would end up outside of their min/max bounds are clamped and take no further
+/// part in the distribution, so that the space they couldn't take goes to the other items.
+pub(crate) fn resolve_flex_sizes(items: &[FlexItem], free_space: f32) -> Vec<f32> {
+    let mut sizes = items
+        .iter()
+        .map(|item| item.clamp(item.basis))
+        .collect::<Vec<_>>();
+    let mut frozen = vec![false; items.len()];
+
+    for _ in 0..items.len() {
+        let remaining = free_space
+            - items
+                .iter()
+                .zip(&sizes)
+                .zip(&frozen)
+                .map(|((item, size), frozen)| if *frozen { size - item.basis } else { 0. })
+                .sum::<f32>();
+        let weight = |item: &FlexItem| {
+            if remaining >= 0. {
+                item.grow
+            } else {
+                item.shrink * item.basis
+            }
+        };
+        let total_weight = items
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, frozen)| !**frozen)
+            .map(|(item, _)| weight(item))
+            .sum::<f32>();
+
+        let mut violated = false;
+        for ((item, size), frozen) in items.iter().zip(&mut sizes).zip(&mut frozen) {
+            if *frozen {
+                continue;
+            }
+            let share = if total_weight > 0. {
+                remaining * weight(item) / total_weight
+            } else {
+                0.
+            };
+            let target = item.basis + share;
+            *size = item.clamp(target);
+            if *size != target {
+                *frozen = true;
+                violated = true;
+            }
+        }
+        if !violated {
+            break;
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(basis: f32, grow: f32, shrink: f32) -> FlexItem {
+        FlexItem {
+            basis,
+            grow,
+            shrink,
+            min: 0.,
+            max: f32::INFINITY,
+        }
+    }
+
+    #[test]
+    fn grows_by_weight() {
+        let sizes = resolve_flex_sizes(
+            &[item(0., 1., 0.), item(0., 3., 0.), item(50., 0., 0.)],
+            100.,
+        );
+        assert_eq!(sizes, vec![25., 75., 50.]);
+    }
+
+    #[test]
+    fn shrinks_by_weight_and_basis() {
+        let sizes = resolve_flex_sizes(&[item(100., 0., 1.), item(200., 0., 1.)], -60.);
+        assert_eq!(sizes, vec![80., 160.]);
+    }
+
+    #[test]
+    fn respects_min_and_max() {
+        let capped = FlexItem {
+            max: 20.,
+            ..item(0., 1., 0.)
+        };
+        let sizes = resolve_flex_sizes(&[capped, item(0., 1., 0.)], 100.);
+        assert_eq!(sizes, vec![20., 80.]);
+
+        let floored = FlexItem {
+            min: 90.,
+            ..item(100., 0., 1.)
+        };
+        let sizes = resolve_flex_sizes(&[floored, item(100., 0., 1.)], -40.);
+        assert_eq!(sizes, vec![90., 70.]);
+    }
+
+    #[test]
+    fn inverted_or_nan_bounds_do_not_panic() {
+        let inverted = FlexItem {
+            min: 50.,
+            max: 20.,
+            ..item(0., 1., 0.)
+        };
+        let sizes = resolve_flex_sizes(&[inverted, item(0., 1., 0.)], 100.);
+        assert_eq!(sizes, vec![20., 80.]);
+
+        let nan = FlexItem {
+            min: f32::NAN,
+            max: f32::NAN,
+            ..item(10., 0., 0.)
+        };
+        let sizes = resolve_flex_sizes(&[nan], 0.);
+        assert_eq!(sizes, vec![10.]);
+    }
+}
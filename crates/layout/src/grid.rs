@@ -0,0 +1,524 @@
+use std::collections::HashSet;
+
+use ambient_core::transform::{local_to_parent, translation};
+use ambient_ecs::{EntityId, World};
+use glam::{vec2, vec3, Vec2};
+use itertools::Itertools;
+
+use crate::{
+    align_horizontal, align_vertical, fit_horizontal, fit_vertical,
+    flex::{resolve_flex_sizes, FlexItem},
+    grid_column, grid_column_span, grid_columns, grid_gap, grid_row, grid_row_span, grid_rows,
+    height, invalidate_parent_layout, margin, min_height, min_width, padding, width, Align,
+    Borders, Fit, Orientation, Z_DELTA,
+};
+
+/// The most columns or rows that children can be placed in explicitly. Positions and spans beyond
+/// it are clamped, so that a stray value can't make the grid allocate an enormous number of tracks.
+const MAX_TRACKS: u32 = 256;
+
+/// Where a child of a grid asked to be placed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CellRequest {
+    column: Option<u32>,
+    row: Option<u32>,
+    column_span: u32,
+    row_span: u32,
+}
+impl CellRequest {
+    /// Keeps the requested cells within the first [MAX_TRACKS] columns and rows.
+    fn clamped(self) -> Self {
+        let column = self.column.map(|column| column.min(MAX_TRACKS - 1));
+        let row = self.row.map(|row| row.min(MAX_TRACKS - 1));
+        Self {
+            column,
+            row,
+            column_span: self.column_span.clamp(1, MAX_TRACKS - column.unwrap_or(0)),
+            row_span: self.row_span.clamp(1, MAX_TRACKS - row.unwrap_or(0)),
+        }
+    }
+}
+
+/// The cells a child of a grid ended up in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    column: u32,
+    row: u32,
+    column_span: u32,
+    row_span: u32,
+}
+
+/// The content of a grid along one axis: a child starting at track `start` that spans `span`
+/// tracks and wants `size` pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TrackItem {
+    start: usize,
+    span: usize,
+    size: f32,
+}
+
+#[profiling::function]
+pub(crate) fn grid_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
+    let self_padding: Borders = world
+        .get(id, padding())
+        .unwrap_or(Borders::ZERO.into())
+        .into();
+    let self_size = vec2(
+        world.get(id, width()).unwrap_or(0.),
+        world.get(id, height()).unwrap_or(0.),
+    );
+    let self_fit_horizontal = world.get(id, fit_horizontal()).unwrap_or(Fit::None);
+    let self_fit_vertical = world.get(id, fit_vertical()).unwrap_or(Fit::None);
+    let gap = world.get(id, grid_gap()).unwrap_or_default();
+    let mut column_tracks = world
+        .get_cloned(id, grid_columns())
+        .unwrap_or_else(|_| vec![vec2(0., 1.)]);
+    let mut row_tracks = world.get_cloned(id, grid_rows()).unwrap_or_default();
+
+    let children = children
+        .iter()
+        .filter(|id| world.has_component(**id, local_to_parent()))
+        .copied()
+        .collect_vec();
+    let requests = children
+        .iter()
+        .map(|&c| CellRequest {
+            column: world.get(c, grid_column()).ok(),
+            row: world.get(c, grid_row()).ok(),
+            column_span: world.get(c, grid_column_span()).unwrap_or(1),
+            row_span: world.get(c, grid_row_span()).unwrap_or(1),
+        })
+        .collect_vec();
+    let cells = place_cells(column_tracks.len().max(1) as u32, &requests);
+
+    // Children can be placed outside of the specified tracks; those tracks fit their content
+    let column_count = cells
+        .iter()
+        .map(|cell| cell.column.saturating_add(cell.column_span) as usize)
+        .max()
+        .unwrap_or(0);
+    let row_count = cells
+        .iter()
+        .map(|cell| cell.row.saturating_add(cell.row_span) as usize)
+        .max()
+        .unwrap_or(0);
+    if column_tracks.len() < column_count {
+        column_tracks.resize(column_count, Vec2::ZERO);
+    }
+    if row_tracks.len() < row_count {
+        row_tracks.resize(row_count, Vec2::ZERO);
+    }
+
+    let mut column_items = Vec::with_capacity(children.len());
+    let mut row_items = Vec::with_capacity(children.len());
+    for (&c, cell) in children.iter().zip(&cells) {
+        let child_margin: Borders = world
+            .get(c, margin())
+            .unwrap_or(Borders::ZERO.into())
+            .into();
+        // Children that fill their cell don't have a size of their own, other than their minimum
+        let child_width = if world.get(c, fit_horizontal()).unwrap_or(Fit::None) == Fit::Parent {
+            world.get(c, min_width()).unwrap_or(0.)
+        } else {
+            world.get(c, width()).unwrap_or(0.)
+        };
+        let child_height = if world.get(c, fit_vertical()).unwrap_or(Fit::None) == Fit::Parent {
+            world.get(c, min_height()).unwrap_or(0.)
+        } else {
+            world.get(c, height()).unwrap_or(0.)
+        };
+        column_items.push(TrackItem {
+            start: cell.column as usize,
+            span: cell.column_span as usize,
+            size: child_width + child_margin.get_horizontal(),
+        });
+        row_items.push(TrackItem {
+            start: cell.row as usize,
+            span: cell.row_span as usize,
+            size: child_height + child_margin.get_vertical(),
+        });
+    }
+
+    let column_sizes = resolve_tracks(
+        &column_tracks,
+        &column_items,
+        (self_fit_horizontal != Fit::Children).then(|| self_size.x - self_padding.get_horizontal()),
+        gap.x,
+    );
+    let row_sizes = resolve_tracks(
+        &row_tracks,
+        &row_items,
+        (self_fit_vertical != Fit::Children).then(|| self_size.y - self_padding.get_vertical()),
+        gap.y,
+    );
+    let column_offsets = track_offsets(&column_sizes, gap.x, self_padding.left);
+    let row_offsets = track_offsets(&row_sizes, gap.y, self_padding.top);
+
+    let align_horizontal = world.get(id, align_horizontal()).unwrap_or(Align::Begin);
+    let align_vertical = world.get(id, align_vertical()).unwrap_or(Align::Begin);
+    for (&c, cell) in children.iter().zip(&cells) {
+        let child_margin: Borders = world
+            .get(c, margin())
+            .unwrap_or(Borders::ZERO.into())
+            .into();
+        let cell_position = vec2(
+            column_offsets[cell.column as usize],
+            row_offsets[cell.row as usize],
+        ) + child_margin.offset();
+        let cell_size = vec2(
+            span_size(&column_sizes, cell.column, cell.column_span, gap.x),
+            span_size(&row_sizes, cell.row, cell.row_span, gap.y),
+        ) - child_margin.border_size();
+
+        let x = place_in_cell(
+            world,
+            c,
+            Orientation::Horizontal,
+            align_horizontal,
+            cell_position.x,
+            cell_size.x,
+        );
+        let y = place_in_cell(
+            world,
+            c,
+            Orientation::Vertical,
+            align_vertical,
+            cell_position.y,
+            cell_size.y,
+        );
+        world
+            .set_if_changed(c, translation(), vec3(x, y, Z_DELTA))
+            .ok();
+    }
+
+    let new_self_width = self_padding.get_horizontal()
+        + span_size(&column_sizes, 0, column_sizes.len() as u32, gap.x);
+    let new_self_height =
+        self_padding.get_vertical() + span_size(&row_sizes, 0, row_sizes.len() as u32, gap.y);
+    if self_fit_horizontal == Fit::Children && self_size.x != new_self_width {
+        world.set(id, width(), new_self_width).ok();
+        invalidate_parent_layout(world, id, Orientation::Horizontal);
+    }
+    if self_fit_vertical == Fit::Children && self_size.y != new_self_height {
+        world.set(id, height(), new_self_height).ok();
+        invalidate_parent_layout(world, id, Orientation::Vertical);
+    }
+}
+
+/// Stretches the child to fill its cell along `orientation` if it fits its parent, or aligns it
+/// in the cell otherwise. Returns the position of the child along that axis.
+fn place_in_cell(
+    world: &mut World,
+    child: EntityId,
+    orientation: Orientation,
+    align: Align,
+    cell_position: f32,
+    cell_size: f32,
+) -> f32 {
+    let (fit, size) = match orientation {
+        Orientation::Horizontal => (fit_horizontal(), width()),
+        Orientation::Vertical => (fit_vertical(), height()),
+    };
+    if world.get(child, fit).unwrap_or(Fit::None) == Fit::Parent {
+        world.set_if_changed(child, size, cell_size).ok();
+        return cell_position;
+    }
+    let child_size = world.get(child, size).unwrap_or(0.);
+    match align {
+        Align::Begin => cell_position,
+        Align::Center => cell_position + (cell_size - child_size) / 2.,
+        Align::End => cell_position + cell_size - child_size,
+    }
+}
+
+/// Places each child in the grid.
+///
+/// Children with both a column and a row are placed first. The rest are placed in order: children
+/// with only a column go in the first row that is free in that column, children with only a row
+/// go in the first free column of that row, and the others go in the next free cells, row by row.
+///
+/// Requests are clamped to [MAX_TRACKS] columns and rows first.
+fn place_cells(columns: u32, requests: &[CellRequest]) -> Vec<Cell> {
+    let columns = columns.clamp(1, MAX_TRACKS);
+    let requests = requests.iter().map(|r| r.clamped()).collect_vec();
+
+    let mut occupied = HashSet::new();
+    let is_free = |occupied: &HashSet<(u32, u32)>, cell: &Cell| {
+        (cell.column..cell.column.saturating_add(cell.column_span))
+            .cartesian_product(cell.row..cell.row.saturating_add(cell.row_span))
+            .all(|pos| !occupied.contains(&pos))
+    };
+    let occupy = |occupied: &mut HashSet<(u32, u32)>, cell: &Cell| {
+        occupied.extend(
+            (cell.column..cell.column.saturating_add(cell.column_span))
+                .cartesian_product(cell.row..cell.row.saturating_add(cell.row_span)),
+        );
+    };
+
+    let mut cells = vec![None; requests.len()];
+    for (request, cell) in requests.iter().zip(&mut cells) {
+        if let (Some(column), Some(row)) = (request.column, request.row) {
+            let placed = Cell {
+                column,
+                row,
+                column_span: request.column_span,
+                row_span: request.row_span,
+            };
+            occupy(&mut occupied, &placed);
+            *cell = Some(placed);
+        }
+    }
+
+    let mut cursor = (0, 0);
+    for (request, cell) in requests.iter().zip(&mut cells) {
+        if cell.is_some() {
+            continue;
+        }
+        let mut candidate = Cell {
+            column: request.column.unwrap_or(0),
+            row: request.row.unwrap_or(0),
+            column_span: request.column_span,
+            row_span: request.row_span,
+        };
+        match (request.column, request.row) {
+            (Some(_), None) => {
+                while !is_free(&occupied, &candidate) {
+                    candidate.row += 1;
+                }
+            }
+            (None, Some(_)) => {
+                while !is_free(&occupied, &candidate) {
+                    candidate.column += 1;
+                }
+            }
+            _ => {
+                (candidate.column, candidate.row) = cursor;
+                loop {
+                    // Children that are wider than the grid start at the first column
+                    let fits = candidate.column == 0
+                        || candidate.column.saturating_add(candidate.column_span) <= columns;
+                    if fits && is_free(&occupied, &candidate) {
+                        break;
+                    }
+                    candidate.column += 1;
+                    if candidate.column.saturating_add(candidate.column_span) > columns {
+                        candidate.column = 0;
+                        candidate.row += 1;
+                    }
+                }
+                cursor = (
+                    candidate.column.saturating_add(candidate.column_span),
+                    candidate.row,
+                );
+            }
+        }
+        occupy(&mut occupied, &candidate);
+        *cell = Some(candidate);
+    }
+
+    cells.into_iter().flatten().collect()
+}
+
+/// Computes the size of each track.
+///
+/// Tracks that fit their content are as large as the largest child in them. The remaining space
+/// is then shared between the fraction tracks. If there is no `available` size (because the grid
+/// fits its children), the fraction tracks fit their content too, while keeping their
+/// proportions.
+fn resolve_tracks(
+    tracks: &[Vec2],
+    items: &[TrackItem],
+    available: Option<f32>,
+    gap: f32,
+) -> Vec<f32> {
+    let is_fraction = |track: &Vec2| track.y > 0.;
+    let fits_content = |track: &Vec2| {
+        (track.x == 0. && track.y == 0.) || (is_fraction(track) && available.is_none())
+    };
+
+    let mut sizes = tracks.iter().map(|track| track.x).collect_vec();
+    for item in items.iter().sorted_by_key(|item| item.span) {
+        let range = item.start..item.start + item.span;
+        let current = sizes[range.clone()].iter().sum::<f32>() + gap * (item.span - 1) as f32;
+        let missing = item.size - current;
+        let growable = range
+            .clone()
+            .filter(|&i| fits_content(&tracks[i]))
+            .collect_vec();
+        if missing > 0. && !growable.is_empty() {
+            for &i in &growable {
+                sizes[i] += missing / growable.len() as f32;
+            }
+        }
+    }
+
+    match available {
+        None => {
+            let unit = tracks
+                .iter()
+                .zip(&sizes)
+                .filter(|(track, _)| is_fraction(track))
+                .map(|(track, size)| size / track.y)
+                .fold(0., f32::max);
+            for (track, size) in tracks.iter().zip(&mut sizes) {
+                if is_fraction(track) {
+                    *size = unit * track.y;
+                }
+            }
+        }
+        Some(available) => {
+            let fractions = tracks
+                .iter()
+                .filter(|track| is_fraction(track))
+                .map(|track| FlexItem {
+                    basis: 0.,
+                    grow: track.y,
+                    shrink: 0.,
+                    min: track.x,
+                    max: f32::INFINITY,
+                })
+                .collect_vec();
+            let fixed = tracks
+                .iter()
+                .zip(&sizes)
+                .filter(|(track, _)| !is_fraction(track))
+                .map(|(_, size)| size)
+                .sum::<f32>();
+            let free = available - fixed - gap * tracks.len().saturating_sub(1) as f32;
+            let mut fraction_sizes = resolve_flex_sizes(&fractions, free).into_iter();
+            for (track, size) in tracks.iter().zip(&mut sizes) {
+                if is_fraction(track) {
+                    *size = fraction_sizes.next().unwrap();
+                }
+            }
+        }
+    }
+    sizes
+}
+
+fn track_offsets(sizes: &[f32], gap: f32, start: f32) -> Vec<f32> {
+    sizes
+        .iter()
+        .scan(start, |offset, size| {
+            let current = *offset;
+            *offset += size + gap;
+            Some(current)
+        })
+        .collect()
+}
+
+fn span_size(sizes: &[f32], start: u32, span: u32, gap: f32) -> f32 {
+    let tracks = &sizes[start as usize..start.saturating_add(span) as usize];
+    tracks.iter().sum::<f32>() + gap * tracks.len().saturating_sub(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auto(column_span: u32, row_span: u32) -> CellRequest {
+        CellRequest {
+            column: None,
+            row: None,
+            column_span,
+            row_span,
+        }
+    }
+
+    fn cell(column: u32, row: u32, column_span: u32, row_span: u32) -> Cell {
+        Cell {
+            column,
+            row,
+            column_span,
+            row_span,
+        }
+    }
+
+    #[test]
+    fn places_cells() {
+        let explicit = CellRequest {
+            column: Some(1),
+            row: Some(0),
+            ..auto(1, 2)
+        };
+        let in_column = CellRequest {
+            column: Some(1),
+            ..auto(1, 1)
+        };
+        let cells = place_cells(
+            3,
+            &[auto(1, 1), explicit, auto(2, 1), in_column, auto(1, 1)],
+        );
+        assert_eq!(
+            cells,
+            vec![
+                cell(0, 0, 1, 1),
+                cell(1, 0, 1, 2),
+                cell(0, 2, 2, 1),
+                cell(1, 3, 1, 1),
+                cell(2, 2, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn clamps_huge_cells() {
+        let huge = CellRequest {
+            column: Some(u32::MAX),
+            row: Some(u32::MAX),
+            ..auto(u32::MAX, u32::MAX)
+        };
+        let in_column = CellRequest {
+            column: Some(u32::MAX),
+            ..auto(u32::MAX, 1)
+        };
+        let cells = place_cells(u32::MAX, &[huge, in_column, auto(u32::MAX, 1)]);
+        assert_eq!(
+            cells,
+            vec![
+                cell(MAX_TRACKS - 1, MAX_TRACKS - 1, 1, 1),
+                cell(MAX_TRACKS - 1, 0, 1, 1),
+                cell(0, 1, MAX_TRACKS, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_tracks() {
+        let tracks = [vec2(100., 0.), vec2(0., 1.), vec2(0., 0.), vec2(0., 3.)];
+        let items = [
+            TrackItem {
+                start: 2,
+                span: 1,
+                size: 30.,
+            },
+            TrackItem {
+                start: 1,
+                span: 1,
+                size: 50.,
+            },
+        ];
+        assert_eq!(
+            resolve_tracks(&tracks, &items, Some(280.), 10.),
+            vec![100., 30., 30., 90.]
+        );
+        assert_eq!(
+            resolve_tracks(&tracks, &items, None, 10.),
+            vec![100., 50., 30., 150.]
+        );
+    }
+
+    #[test]
+    fn spanning_items_grow_content_tracks() {
+        let tracks = [vec2(0., 0.), vec2(20., 0.), vec2(0., 0.)];
+        let items = [TrackItem {
+            start: 0,
+            span: 3,
+            size: 100.,
+        }];
+        assert_eq!(
+            resolve_tracks(&tracks, &items, Some(500.), 10.),
+            vec![30., 20., 30.]
+        );
+    }
+}
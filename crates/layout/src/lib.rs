@@ -2,7 +2,9 @@ use ambient_core::{
     hierarchy::{children, parent},
     transform::{local_to_parent, local_to_world, mesh_to_local, translation},
 };
-use ambient_ecs::{query, query_mut, DynSystem, EntityId, SystemGroup, World};
+use ambient_ecs::{
+    query, query_mut, Component, ComponentValue, DynSystem, EntityId, SystemGroup, World,
+};
 use ambient_gpu_ecs::{
     gpu_components, ComponentToGpuSystem, GpuComponentFormat, GpuWorldSyncEvent,
 };
@@ -12,16 +14,21 @@ use itertools::Itertools;
 
 pub use ambient_ecs::generated::layout::{
     components::{
-        align_horizontal, align_vertical, docking, fit_horizontal, fit_vertical, gpu_ui_size,
-        height, is_book_file, is_screen, layout, margin, max_height, max_width,
-        mesh_to_local_from_size, min_height, min_width, orientation, padding, space_between_items,
-        width,
+        align_horizontal, align_vertical, docking, fit_horizontal, fit_vertical, flex_basis,
+        flex_grow, flex_shrink, gpu_ui_size, grid_column, grid_column_span, grid_columns, grid_gap,
+        grid_row, grid_row_span, grid_rows, height, is_book_file, is_screen, layout, margin,
+        max_height, max_width, mesh_to_local_from_size, min_height, min_width, orientation,
+        padding, space_between_items, width,
     },
     types::{Align, Docking, Fit, Layout, Orientation},
 };
 use ambient_gpu::gpu::Gpu;
+use flex::{resolve_flex_sizes, FlexItem};
 use std::sync::Arc;
 
+mod flex;
+mod grid;
+
 gpu_components! {
     gpu_ui_size() => ui_size: GpuComponentFormat::Vec4,
 }
@@ -168,6 +175,16 @@ pub fn layout_systems() -> SystemGroup {
                         invalidate_parent_layout(world, id, Orientation::Vertical);
                     }
                 }),
+            relayout_on_change(grid_columns(), "layout/grid_columns", false),
+            relayout_on_change(grid_rows(), "layout/grid_rows", false),
+            relayout_on_change(grid_gap(), "layout/grid_gap", false),
+            relayout_on_change(grid_column(), "layout/grid_column", true),
+            relayout_on_change(grid_row(), "layout/grid_row", true),
+            relayout_on_change(grid_column_span(), "layout/grid_column_span", true),
+            relayout_on_change(grid_row_span(), "layout/grid_row_span", true),
+            relayout_on_change(flex_grow(), "layout/flex_grow", true),
+            relayout_on_change(flex_shrink(), "layout/flex_shrink", true),
+            relayout_on_change(flex_basis(), "layout/flex_basis", true),
             query((
                 width().changed(),
                 height().changed(),
@@ -195,6 +212,9 @@ pub fn layout_systems() -> SystemGroup {
                             Layout::WidthToChildren => {
                                 width_to_children(world, id, children);
                             }
+                            Layout::Grid => {
+                                grid::grid_layout(world, id, children);
+                            }
                         }
                     }
                     if !changed {
@@ -418,16 +438,32 @@ fn flow_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
         .filter(|id| world.has_component(**id, local_to_parent()))
         .copied()
         .collect_vec();
+    let main_axis_available = match orientation {
+        Orientation::Horizontal => (self_fit_horizontal != Fit::Children)
+            .then(|| self_size.x - self_padding.get_horizontal()),
+        Orientation::Vertical => {
+            (self_fit_vertical != Fit::Children).then(|| self_size.y - self_padding.get_vertical())
+        }
+    };
+    let flex_sizes = flex_layout(
+        world,
+        &children,
+        orientation,
+        main_axis_available,
+        space_between_items,
+    );
+    let is_flex = |i: usize| flex_sizes.as_ref().is_some_and(|sizes| sizes[i].is_some());
     let items = children
         .iter()
-        .map(|&c| {
+        .enumerate()
+        .map(|(i, &c)| {
             let child_margin: Borders = world
                 .get(c, margin())
                 .unwrap_or(Borders::ZERO.into())
                 .into();
 
-            let child_fit_horizontal = world.get(c, fit_horizontal()).unwrap_or(Fit::None);
-            let child_fit_vertical = world.get(c, fit_vertical()).unwrap_or(Fit::None);
+            let (child_fit_horizontal, child_fit_vertical) =
+                flow_child_fit(world, c, orientation, is_flex(i));
 
             let child_size = vec2(
                 if child_fit_horizontal == Fit::Parent {
@@ -441,10 +477,12 @@ fn flow_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
                     world.get(c, height()).unwrap_or(0.) + child_margin.top + child_margin.bottom
                 },
             );
-            let break_line = match orientation {
-                Orientation::Horizontal => offset.x + child_size.x >= self_max_width,
-                Orientation::Vertical => offset.y + child_size.y >= self_max_height,
-            };
+            // Flex children are sized to fit on a single line
+            let break_line = flex_sizes.is_none()
+                && match orientation {
+                    Orientation::Horizontal => offset.x + child_size.x >= self_max_width,
+                    Orientation::Vertical => offset.y + child_size.y >= self_max_height,
+                };
             if break_line {
                 match orientation {
                     Orientation::Horizontal => {
@@ -504,14 +542,14 @@ fn flow_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
         Align::End => new_self_height - children_height - self_padding.top,
     };
 
-    for (&c, pos) in children.iter().zip(items.into_iter()) {
+    for (i, (&c, pos)) in children.iter().zip(items.into_iter()).enumerate() {
         let child_margin: Borders = world
             .get(c, margin())
             .unwrap_or(Borders::ZERO.into())
             .into();
         let child_base_position = vec3(align_left, align_top, 0.) + pos;
-        let child_fit_horizontal = world.get(c, fit_horizontal()).unwrap_or(Fit::None);
-        let child_fit_vertical = world.get(c, fit_vertical()).unwrap_or(Fit::None);
+        let (child_fit_horizontal, child_fit_vertical) =
+            flow_child_fit(world, c, orientation, is_flex(i));
         let child_width = if child_fit_horizontal == Fit::Parent {
             let child_new_width =
                 new_self_width - child_base_position.x - child_margin.right - self_padding.right;
@@ -561,6 +599,93 @@ fn flow_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
     }
 }
 
+/// The fit of a child of a flow. Flex children are sized by the flow along its orientation, so
+/// they don't fit their parent along it.
+fn flow_child_fit(
+    world: &World,
+    child: EntityId,
+    orientation: Orientation,
+    is_flex: bool,
+) -> (Fit, Fit) {
+    let fit_horizontal = world.get(child, fit_horizontal()).unwrap_or(Fit::None);
+    let fit_vertical = world.get(child, fit_vertical()).unwrap_or(Fit::None);
+    match orientation {
+        Orientation::Horizontal if is_flex => (Fit::None, fit_vertical),
+        Orientation::Vertical if is_flex => (fit_horizontal, Fit::None),
+        _ => (fit_horizontal, fit_vertical),
+    }
+}
+
+/// Sizes the children of a flow that have `flex_` components along the flow's orientation.
+///
+/// Returns the new size of each flex child, or `None` if there are no flex children.
+fn flex_layout(
+    world: &mut World,
+    children: &[EntityId],
+    orientation: Orientation,
+    available: Option<f32>,
+    space_between_items: f32,
+) -> Option<Vec<Option<f32>>> {
+    let is_flex = children
+        .iter()
+        .map(|&c| {
+            world.has_component(c, flex_grow())
+                || world.has_component(c, flex_shrink())
+                || world.has_component(c, flex_basis())
+        })
+        .collect_vec();
+    if !is_flex.contains(&true) {
+        return None;
+    }
+
+    let (fit, size, min, max) = match orientation {
+        Orientation::Horizontal => (fit_horizontal(), width(), min_width(), max_width()),
+        Orientation::Vertical => (fit_vertical(), height(), min_height(), max_height()),
+    };
+    let mut used = space_between_items * children.len().saturating_sub(1) as f32;
+    let mut items = Vec::new();
+    for (&c, &is_flex) in children.iter().zip(&is_flex) {
+        let child_margin: Borders = world
+            .get(c, margin())
+            .unwrap_or(Borders::ZERO.into())
+            .into();
+        used += match orientation {
+            Orientation::Horizontal => child_margin.get_horizontal(),
+            Orientation::Vertical => child_margin.get_vertical(),
+        };
+        if is_flex {
+            let item = FlexItem {
+                basis: world.get(c, flex_basis()).unwrap_or(0.),
+                grow: world.get(c, flex_grow()).unwrap_or(0.),
+                shrink: world.get(c, flex_shrink()).unwrap_or(0.),
+                min: world.get(c, min).unwrap_or(0.),
+                max: world.get(c, max).unwrap_or(f32::INFINITY),
+            };
+            used += item.basis;
+            items.push(item);
+        } else if world.get(c, fit).unwrap_or(Fit::None) != Fit::Parent {
+            used += world.get(c, size).unwrap_or(0.);
+        }
+    }
+
+    let free_space = available.map_or(0., |available| available - used);
+    let mut flex_sizes = resolve_flex_sizes(&items, free_space).into_iter();
+    Some(
+        children
+            .iter()
+            .zip(is_flex)
+            .map(|(&c, is_flex)| {
+                if !is_flex {
+                    return None;
+                }
+                let flex_size = flex_sizes.next().unwrap();
+                world.set_if_changed(c, size, flex_size).ok();
+                Some(flex_size)
+            })
+            .collect(),
+    )
+}
+
 #[profiling::function]
 fn bookcase_layout(world: &mut World, id: EntityId, files: Vec<EntityId>) {
     let orientation = world
@@ -665,6 +790,27 @@ fn invalidate_parent_layout(world: &mut World, id: EntityId, orientation: Orient
     }
 }
 
+/// Lays out the entity again when `component` changes, or its parent if `of_parent` is set.
+fn relayout_on_change<T: ComponentValue>(
+    component: Component<T>,
+    name: &'static str,
+    of_parent: bool,
+) -> DynSystem {
+    query((component.changed(),)).to_system_with_name(name, move |q, world, qs, _| {
+        for id in q.collect_ids(world, qs) {
+            let id = if of_parent {
+                match world.get(id, parent()) {
+                    Ok(parent) => parent,
+                    Err(_) => continue,
+                }
+            } else {
+                id
+            };
+            world.get_mut(id, width()).ok();
+        }
+    })
+}
+
 fn node_clickable_system() -> DynSystem {
     query_mut((mouse_pickable(),), (width().changed(), height().changed())).to_system_with_name(
         "layout/mouse_pickable",
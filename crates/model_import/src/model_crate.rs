@@ -8,8 +8,8 @@ use ambient_core::{
     transform::{local_to_parent, local_to_world, mesh_to_local, TransformSystem},
 };
 use ambient_ecs::{
-    query, query_mut, Component, ComponentValue, Entity, EntityId, FrameEvent, System, World,
-    WorldContext,
+    generated::animation::components::bind_id, query, query_mut, Component, ComponentValue, Entity,
+    EntityId, FrameEvent, System, World, WorldContext,
 };
 use ambient_model::{
     model_from_url, model_skin_ix, model_skins, pbr_renderer_primitives_from_url, Model,
@@ -29,6 +29,7 @@ use ambient_physics::{
     },
     mesh::PhysxGeometryFromUrl,
     physx::PhysicsKey,
+    ragdoll::{ragdoll_from_url, RagdollBoneLimits, RagdollDef, RagdollJoint},
 };
 use ambient_renderer::{
     double_sided,
@@ -125,6 +126,7 @@ pub struct ModelCrate {
     pub px_triangle_meshes: AssetMap<Vec<u8>>,
     pub px_convex_meshes: AssetMap<Vec<u8>>,
     pub colliders: AssetMap<ColliderFromUrls>,
+    pub ragdolls: AssetMap<RagdollDef>,
 }
 impl ModelCrate {
     pub fn new() -> Self {
@@ -142,6 +144,7 @@ impl ModelCrate {
             px_triangle_meshes: AssetMap::new("px_triangle_meshes", "pxtm", |v| v.clone()),
             px_convex_meshes: AssetMap::new("px_convex_meshes", "pxcm", |v| v.clone()),
             colliders: AssetMap::new("colliders", "json", |v| serde_json::to_vec(v).unwrap()),
+            ragdolls: AssetMap::new("ragdolls", "json", |v| serde_json::to_vec(v).unwrap()),
         }
     }
     pub async fn local_import(
@@ -202,6 +205,7 @@ impl ModelCrate {
             self.px_triangle_meshes.to_items().into_iter(),
             self.px_convex_meshes.to_items().into_iter(),
             self.colliders.to_items().into_iter(),
+            self.ragdolls.to_items().into_iter(),
         ]
         .into_iter()
         .flatten()
@@ -735,6 +739,65 @@ impl ModelCrate {
             .unwrap();
         Ok(())
     }
+    /// Generates a ragdoll from the bind pose of the model's skeleton (see [`RagdollDef::from_skeleton`]).
+    pub fn create_ragdoll(
+        &mut self,
+        bones: &[String],
+        radius: f32,
+        swing_limit: f32,
+        twist_limit: f32,
+        bone_limits: &HashMap<String, RagdollBoneLimits>,
+    ) -> anyhow::Result<()> {
+        self.update_transforms();
+        let world_transform = self.model().get_transform().unwrap_or_default();
+        let skin = self
+            .model()
+            .skins()
+            .and_then(|skins| skins.first())
+            .context("The model has no skeleton to generate a ragdoll from")?;
+        let world = self.model_world();
+        let parents: HashMap<EntityId, EntityId> = query(children())
+            .iter(world, None)
+            .flat_map(|(id, children)| children.iter().map(move |child| (*child, id)))
+            .collect();
+        let local_to_world_of = |id| world.get(id, local_to_world()).unwrap_or_default();
+        let joints = skin
+            .joints
+            .iter()
+            .map(|&id| {
+                let mut parent = parents.get(&id);
+                while let Some(p) = parent {
+                    if skin.joints.contains(p) {
+                        break;
+                    }
+                    parent = parents.get(p);
+                }
+                RagdollJoint {
+                    bind_id: world.get_cloned(id, bind_id()).unwrap_or_default(),
+                    name: world.get_cloned(id, name()).ok(),
+                    parent: parent.and_then(|p| skin.joints.iter().position(|j| j == p)),
+                    transform: world_transform * local_to_world_of(id),
+                    local_transform: match parents.get(&id) {
+                        Some(parent) => {
+                            local_to_world_of(*parent).inverse() * local_to_world_of(id)
+                        }
+                        None => local_to_world_of(id),
+                    },
+                }
+            })
+            .collect_vec();
+        let ragdoll = RagdollDef::from_skeleton(
+            &joints,
+            bones,
+            radius,
+            swing_limit,
+            twist_limit,
+            bone_limits,
+        )?;
+        let loc = self.ragdolls.insert(ModelCrate::MAIN, ragdoll);
+        self.add_component_to_prefab(ragdoll_from_url(), dotdot_path(loc.path).into());
+        Ok(())
+    }
 }
 pub struct AssetItem {
     pub path: RelativePathBuf,
@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
+use std::{collections::HashSet, marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
@@ -156,6 +156,58 @@ impl BytesFromUrl {
     }
 }
 
+/// Resolves asset keys (paths relative to the build root, which is served as the content root,
+/// e.g. `{package_id}/assets/model.glb`) to the URLs they are downloaded from.
+pub fn asset_key_download_urls(assets: &AssetCache, asset_keys: &[String]) -> HashSet<AbsAssetUrl> {
+    asset_keys
+        .iter()
+        .filter_map(|key| AbsAssetUrl::from_asset_key(key).ok())
+        .filter_map(|url| url.to_download_url(assets).ok())
+        .collect()
+}
+
+/// Whether `url` is downloaded from one of the `download_urls` (see [asset_key_download_urls]).
+pub fn is_downloaded_from(
+    assets: &AssetCache,
+    url: &AbsAssetUrl,
+    download_urls: &HashSet<AbsAssetUrl>,
+) -> bool {
+    url.to_download_url(assets)
+        .is_ok_and(|url| download_urls.contains(&url))
+}
+
+/// Evicts the assets with the given keys (see [asset_key_download_urls]) from memory and from
+/// the disk cache, so that they are downloaded again the next time they are used.
+///
+/// Loaded assets are evicted if their cache key refers to exactly one of the rebuilt URLs.
+pub fn evict_changed_assets(assets: &AssetCache, asset_keys: &[String]) {
+    let download_urls = asset_key_download_urls(assets, asset_keys);
+    assets.evict_loaded(|key| {
+        urls_in_cache_key(key).any(|url| is_downloaded_from(assets, &url, &download_urls))
+    });
+
+    #[cfg(not(target_os = "unknown"))]
+    for key in asset_keys {
+        let Ok(url) = AbsAssetUrl::from_asset_key(key) else {
+            continue;
+        };
+        let download_url = url.to_download_url(assets).ok();
+        for url in std::iter::once(url).chain(download_url) {
+            std::fs::remove_file(url.absolute_cache_path(assets)).ok();
+        }
+    }
+}
+
+/// The URLs in an asset cache key, which is the `Debug` representation of the asset key
+/// (e.g. `BytesFromUrl { url: http://host/content/a.glb, cache_on_disk: true }`).
+fn urls_in_cache_key(key: &str) -> impl Iterator<Item = AbsAssetUrl> + '_ {
+    key.split(|c: char| {
+        c.is_whitespace() || matches!(c, ',' | '"' | '(' | ')' | '{' | '}' | '[' | ']')
+    })
+    .filter_map(|token| url::Url::parse(token).ok())
+    .map(AbsAssetUrl)
+}
+
 /// Use [BytesFromUrl] unless you _really_ need uncached downloads
 pub async fn download_uncached_bytes(
     assets: &AssetCache,
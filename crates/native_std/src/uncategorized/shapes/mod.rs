@@ -1,4 +1,4 @@
-use glam::{vec3, DVec3, Mat4, Vec3};
+use glam::{vec3, DVec3, Mat4, Vec3, Vec4};
 
 mod culling;
 mod shape;
@@ -295,6 +295,32 @@ impl Frustum {
             ],
         })
     }
+    /// Extracts the planes directly from a projection-view matrix with a 0..1 depth range.
+    /// Unlike [Self::from_inv_projection_view], this also works for infinite projections,
+    /// where the far plane never culls anything.
+    ///
+    /// The plane normals point out of the frustum.
+    pub fn from_projection_view(projection_view: Mat4) -> Frustum {
+        let [r0, r1, r2, r3] = [0, 1, 2, 3].map(|i| projection_view.row(i));
+        let plane = |v: Vec4| {
+            let length = v.truncate().length();
+            if length > 0. {
+                Plane::new(-v.truncate() / length, -v.w / length)
+            } else {
+                Plane::new(Vec3::ZERO, -v.w)
+            }
+        };
+        Frustum {
+            planes: [
+                plane(r3 + r0), // left
+                plane(r3 - r0), // right
+                plane(r3 + r1), // bottom
+                plane(r3 - r1), // top
+                plane(r2),      // depth 0
+                plane(r3 - r2), // depth 1
+            ],
+        }
+    }
 }
 
 #[repr(C)]
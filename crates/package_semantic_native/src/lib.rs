@@ -9,8 +9,8 @@ use ambient_cb::Cb;
 use ambient_ecs::{
     components, generated::app::components::name as app_name,
     generated::wasm::components::module_enabled, query, ComponentRegistry, Entity, EntityId,
-    ExternalComponentAttributes, ExternalComponentDesc, Networked, PrimitiveComponentType,
-    Resource, SystemGroup, World,
+    ExternalComponentAttributes, ExternalComponentDesc, Networked, PrimitiveComponentMapKeyType,
+    PrimitiveComponentType, Resource, SystemGroup, World,
 };
 use ambient_native_std::asset_url::AbsAssetUrl;
 use ambient_network::ServerWorldExt;
@@ -19,8 +19,10 @@ use ambient_package_semantic::{
     Item, ItemId, ItemSource, LocalOrRemote, Package, PrimitiveType, RetrievableFile, Semantic,
     TypeInner,
 };
+use ambient_shared_types::ActionBinding;
 
 pub use ambient_ecs::generated::package::components::*;
+use anyhow::Context;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
@@ -215,6 +217,15 @@ fn sync_semantic_to_world(
         }
 
         let base_asset_url = match package.source.as_local_or_remote().unwrap() {
+            LocalOrRemote::Local(path)
+                if matches!(package.source, RetrievableFile::Deployment(_)) =>
+            {
+                // Vendored deployments are already built, so their assets are read from the vendor directory
+                AbsAssetUrl::from_directory_path(
+                    path.parent().context("vendored manifest has no parent")?,
+                )
+                .0
+            }
             LocalOrRemote::Local(_) => {
                 // HACK: assume that any local urls are in the build directory.
                 // I think this should generally be true, but something to watch out for.
@@ -250,6 +261,18 @@ fn sync_semantic_to_world(
         if let PackageContent::Mod { for_playables } = &manifest.package.content {
             entity.set(self::for_playables(), for_playables.clone());
         }
+        if !manifest.actions.is_empty() {
+            entity.set(
+                self::input_actions(),
+                manifest
+                    .actions
+                    .iter()
+                    .map(|(name, bindings)| {
+                        (name.to_string(), ActionBinding::format_list(bindings))
+                    })
+                    .collect(),
+            );
+        }
         let entity = entity.spawn(world);
         world
             .synced_resource_mut(package_id_to_package_entity())
@@ -346,16 +369,33 @@ fn all_defined_components(semantic: &Semantic) -> anyhow::Result<Vec<ExternalCom
                 type_map.insert(items.get_option_id(*type_id), ty.to_option_type().unwrap());
             }
         }
+        for ((key, value), map_id) in items.map_items() {
+            let key = match items.get(*key).inner.as_primitive() {
+                Some(PrimitiveType::String) => PrimitiveComponentMapKeyType::String,
+                Some(PrimitiveType::U32) => PrimitiveComponentMapKeyType::U32,
+                _ => continue,
+            };
+            if let Some(ty) = type_map.get(value).and_then(|ty| ty.to_map_type(key)) {
+                type_map.insert(*map_id, ty);
+            }
+        }
 
-        // Second pass: traverse the type graph and add all enums
+        // Second pass: traverse the type graph and add all enums and structs.
+        // Structs are stored as their serialized bytes.
         for package_id in semantic.packages.values() {
             let package = items.get(*package_id);
             let scope = items.get(package.scope_id);
             scope.visit_recursive(items, |scope| {
                 for type_id in scope.types.values() {
                     let type_ = items.get(*type_id);
-                    if let TypeInner::Enum { .. } = type_.inner {
-                        type_map.insert(*type_id, PrimitiveComponentType::U32);
+                    match type_.inner {
+                        TypeInner::Enum { .. } => {
+                            type_map.insert(*type_id, PrimitiveComponentType::U32);
+                        }
+                        TypeInner::Struct { .. } => {
+                            type_map.insert(*type_id, PrimitiveComponentType::VecU8);
+                        }
+                        _ => {}
                     }
                 }
                 Ok(())
@@ -391,19 +431,42 @@ fn all_defined_components(semantic: &Semantic) -> anyhow::Result<Vec<ExternalCom
                     })
                     .collect::<anyhow::Result<_>>()?;
 
+                let type_id = component.type_.as_resolved().unwrap_or_else(|| {
+                    panic!(
+                        "type id {:?} not resolved in component {:?}",
+                        component.type_, component
+                    )
+                });
+
+                // Struct components are passed to guests as their fields, so the host needs
+                // their layout. Structs that can't be flattened are passed as their bytes.
+                let struct_fields = items
+                    .get(type_id)
+                    .inner
+                    .as_struct()
+                    .and_then(|s| s.flattened_fields(items))
+                    .and_then(|fields| {
+                        fields
+                            .iter()
+                            .map(|id| type_map.get(id).copied())
+                            .collect::<Option<Vec<_>>>()
+                    });
+
                 components.push(ExternalComponentDesc {
                     path: items.fully_qualified_display_path(component, None, None),
-                    ty: type_map[&component.type_.as_resolved().unwrap_or_else(|| {
-                        panic!(
-                            "type id {:?} not resolved in component {:?}",
-                            component.type_, component
+                    ty: *type_map.get(&type_id).with_context(|| {
+                        format!(
+                            "component {} has type {}, which cannot be used as a component type",
+                            items.fully_qualified_display_path(component, None, None),
+                            items.fully_qualified_display_path(items.get(type_id), None, None)
                         )
-                    })],
+                    })?,
                     name: component.name.clone(),
                     description: component.description.clone(),
                     attributes: ExternalComponentAttributes::from_iter(
                         attributes.iter().map(|s| s.as_str()),
                     ),
+                    struct_fields,
                 });
             }
             Ok(())
@@ -7,8 +7,9 @@ use ambient_core::{
     transform::{get_world_position, rotation, scale, translation},
 };
 use ambient_ecs::{
-    components, query, Component, ComponentQuery, ComponentValueBase, Debuggable, Entity, EntityId,
-    MakeDefault, QueryEvent, QueryState, Store, SystemGroup, TypedReadQuery, World,
+    components, query, Component, ComponentQuery, ComponentValueBase, Debuggable, DynSystem,
+    Entity, EntityId, MakeDefault, QueryEvent, QueryState, Store, SystemGroup, TypedReadQuery,
+    World,
 };
 use ambient_model::model_from_url;
 use ambient_native_std::{
@@ -32,10 +33,13 @@ use physxx::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    collision_filter_data, collision_group, collision_query_filter_data,
+    helpers::update_ccd_flags,
     main_controller_manager,
     mesh::{PhysxGeometry, PhysxGeometryFromUrl},
     physx::{character_controller, physics, physics_shape, rigid_actor, Physics},
-    wood_physics_material, ColliderScene, PxActorUserData, PxShapeUserData, PxWoodMaterialKey,
+    wood_physics_material, ColliderScene, PxActorUserData, PxMaterialKey, PxShapeUserData,
+    PxWoodMaterialKey, DEFAULT_COLLISION_LAYER, DEFAULT_COLLISION_MASK,
 };
 
 pub use ambient_ecs::generated::physics::components::*;
@@ -302,6 +306,67 @@ pub fn server_systems() -> SystemGroup {
                         });
                     }
                 }),
+            query((collider_shapes().changed(),))
+                .optional_changed(static_friction())
+                .optional_changed(dynamic_friction())
+                .optional_changed(restitution())
+                .optional_changed(friction_combine_mode())
+                .optional_changed(restitution_combine_mode())
+                .to_system(|q, world, qs, _| {
+                    for id in q.iter(world, qs).map(|(id, _)| id).collect_vec() {
+                        if PxMaterialKey::from_entity(world, id).is_some() {
+                            update_collider_material(world, id);
+                        }
+                    }
+                }),
+            Box::new(SystemGroup::new(
+                "physics/collider/restore_default_material",
+                restore_default_material_systems(),
+            )),
+            query((collider_shapes().changed(),))
+                .optional_changed(collision_layer())
+                .optional_changed(collision_mask())
+                .optional_changed(ragdoll_from_url())
+                .to_system(|q, world, qs, _| {
+                    for (id, (shapes,)) in q.iter(world, qs) {
+                        let layer = world
+                            .get(id, collision_layer())
+                            .unwrap_or(DEFAULT_COLLISION_LAYER);
+                        let mask = world
+                            .get(id, collision_mask())
+                            .unwrap_or(DEFAULT_COLLISION_MASK);
+                        // Keeps the entity's own ragdoll from pushing against it
+                        let group = if world.has_component(id, ragdoll_from_url()) {
+                            collision_group(id)
+                        } else {
+                            0
+                        };
+                        let filter_data = collision_filter_data(layer, mask, group);
+                        let convex = world.get_ref(id, collider_shapes_convex()).ok();
+                        let mut actor = None;
+                        for shape in shapes.iter().chain(convex.into_iter().flatten()) {
+                            shape.set_simulation_filter_data(&filter_data);
+                            shape.set_query_filter_data(&collision_query_filter_data(layer));
+                            actor = actor.or_else(|| shape.get_actor());
+                        }
+                        // Pairs that were already filtered need to be filtered again
+                        if let Some(actor) = actor {
+                            if let Some(scene) = actor.get_scene() {
+                                scene.reset_filtering(&actor);
+                            }
+                        }
+                    }
+                }),
+            query((rigid_actor(),))
+                .optional_changed(ccd())
+                .optional_changed(speculative_ccd())
+                .to_system(|q, world, qs, _| {
+                    for (id, (actor,)) in q.iter(world, qs) {
+                        if let Some(body) = actor.to_rigid_body() {
+                            update_ccd_flags(world, id, &body);
+                        }
+                    }
+                }),
             query((
                 collider_shapes().changed(),
                 collider_shapes_convex().changed(),
@@ -352,7 +417,7 @@ pub fn server_systems() -> SystemGroup {
                         };
                         if let Some(actor) = actor.to_rigid_body() {
                             actor.set_rigid_body_flag(PxRigidBodyFlag::KINEMATIC, is_kinematic);
-                            actor.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, !is_kinematic);
+                            update_ccd_flags(world, id, &actor);
                         }
                         actor
                             .as_actor()
@@ -435,6 +500,49 @@ pub fn server_systems() -> SystemGroup {
     )
 }
 
+/// Sets the material of the entity's collider shapes from its material components, or to the
+/// default material if it has none of them.
+fn update_collider_material(world: &mut World, id: EntityId) {
+    let material = match PxMaterialKey::from_entity(world, id) {
+        Some(key) => key.get(world),
+        None => PxWoodMaterialKey.get(world.resource(asset_cache())),
+    };
+    let shapes = world.get_ref(id, collider_shapes()).into_iter().flatten();
+    let convex = world
+        .get_ref(id, collider_shapes_convex())
+        .into_iter()
+        .flatten();
+    for shape in shapes.chain(convex) {
+        shape.set_materials(&[&material]);
+    }
+}
+
+/// Updates the material of colliders when one of their material components is removed, so that
+/// removing all of them restores the default material.
+fn restore_default_material_systems() -> Vec<DynSystem> {
+    [
+        static_friction().desc(),
+        dynamic_friction().desc(),
+        restitution().desc(),
+        friction_combine_mode().desc(),
+        restitution_combine_mode().desc(),
+    ]
+    .into_iter()
+    .map(|component| {
+        query(())
+            .incl(component)
+            .despawned()
+            .to_system(|q, world, qs, _| {
+                for (id, _) in q.collect_cloned(world, qs) {
+                    if world.has_component(id, collider_shapes()) {
+                        update_collider_material(world, id);
+                    }
+                }
+            })
+    })
+    .collect()
+}
+
 fn one_value() -> f32 {
     1.
 }
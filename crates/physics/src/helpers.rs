@@ -9,23 +9,38 @@ use anyhow::{bail, Context};
 use glam::{vec3, Mat4, Vec3};
 use itertools::Itertools;
 use physxx::{
-    AsPxActor, AsPxRigidActor, PxActor, PxActorRef, PxActorTypeFlag, PxBase, PxBoxGeometry,
-    PxConvexMeshGeometry, PxForceMode, PxJoint, PxMeshScale, PxOverlapCallback, PxPhysicsRef,
-    PxQueryFilterData, PxQueryFlag, PxRevoluteJointRef, PxRigidActor, PxRigidActorRef, PxRigidBody,
-    PxRigidBodyFlag, PxRigidDynamicRef, PxRigidStaticRef, PxSceneRef, PxShape, PxSphereGeometry,
-    PxTransform, PxTriangleMeshGeometry, PxUserData,
+    AsPxActor, AsPxRigidActor, AsPxRigidBody, PxActor, PxActorRef, PxActorTypeFlag, PxBase,
+    PxBoxGeometry, PxConvexMeshGeometry, PxForceMode, PxJoint, PxMeshScale, PxOverlapCallback,
+    PxPhysicsRef, PxQueryFlag, PxRevoluteJointRef, PxRigidActor, PxRigidActorRef, PxRigidBody,
+    PxRigidBodyFlag, PxRigidBodyRef, PxRigidDynamicRef, PxRigidStaticRef, PxSceneRef, PxShape,
+    PxSphereGeometry, PxTransform, PxTriangleMeshGeometry, PxUserData,
 };
 
 use crate::{
-    collider::{collider_shapes_convex, collider_type, kinematic},
+    ccd,
+    collider::{collider_shapes_convex, collider_type, collision_layer, kinematic},
+    intersection::mask_filter_data,
     main_physics_scene,
     physx::{
-        physics, physics_controlled, physics_shape, revolute_joint, rigid_actor, rigid_dynamic,
-        rigid_static,
+        articulation_link, physics, physics_controlled, physics_shape, revolute_joint, rigid_actor,
+        rigid_dynamic, rigid_static,
     },
-    unit_mass, unit_velocity, ColliderScene, PxActorUserData, PxShapeUserData,
+    speculative_ccd, unit_mass, unit_velocity, ColliderScene, PxActorUserData, PxShapeUserData,
+    DEFAULT_COLLISION_LAYER,
 };
 
+/// Updates the continuous collision detection flags of `body` from the `ccd` and `speculative_ccd`
+/// components of `id`. Swept CCD is enabled unless turned off, but is ignored for kinematic bodies.
+pub fn update_ccd_flags(world: &World, id: EntityId, body: &dyn AsPxRigidBody) {
+    let is_kinematic = body
+        .get_rigid_body_flags()
+        .contains(PxRigidBodyFlag::KINEMATIC);
+    let swept = world.get(id, ccd()).unwrap_or(true) && !is_kinematic;
+    let speculative = world.get(id, speculative_ccd()).unwrap_or(false);
+    body.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, swept);
+    body.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_SPECULATIVE_CCD, speculative);
+}
+
 pub fn convert_rigid_static_to_dynamic(world: &mut World, id: EntityId) {
     convert_rigid_static_dynamic(world, id, true);
 }
@@ -59,7 +74,7 @@ pub fn convert_rigid_static_dynamic(world: &mut World, id: EntityId, to_dynamic:
     let new_actor = if to_dynamic {
         let actor = PxRigidDynamicRef::new(physics.physics, &old_actor.get_global_pose());
         actor.set_rigid_body_flag(PxRigidBodyFlag::KINEMATIC, is_kinematic);
-        actor.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, !is_kinematic);
+        update_ccd_flags(world, id, &actor);
         actor.as_rigid_actor()
     } else {
         PxRigidStaticRef::new(physics.physics, &old_actor.get_global_pose()).as_rigid_actor()
@@ -530,12 +545,22 @@ impl PhysicsObjectCollection {
         Self { actors, units }
     }
     pub fn from_radius(world: &World, position: Vec3, radius: f32) -> Self {
+        Self::from_radius_masked(world, position, radius, None)
+    }
+    /// Like [`Self::from_radius`], but only includes colliders on one of the `mask` collision
+    /// layers, and units whose `collision_layer` is in it.
+    pub fn from_radius_with_mask(world: &World, position: Vec3, radius: f32, mask: u32) -> Self {
+        Self::from_radius_masked(world, position, radius, Some(mask))
+    }
+    fn from_radius_masked(world: &World, position: Vec3, radius: f32, mask: Option<u32>) -> Self {
+        let mut res = Self::default();
+        let Some(mut filter_data) = mask_filter_data(mask) else {
+            return res;
+        };
+        filter_data.set_flags(PxQueryFlag::DYNAMIC);
         let scene = world.resource(main_physics_scene());
         let mut hit_call = PxOverlapCallback::new(1000);
-        let mut filter_data = PxQueryFilterData::new();
-        filter_data.set_flags(PxQueryFlag::DYNAMIC);
         let sphere = PxSphereGeometry::new(radius);
-        let mut res = Self::default();
         if scene.overlap(
             &sphere,
             PxTransform::from_translation(position),
@@ -549,7 +574,11 @@ impl PhysicsObjectCollection {
             .incl(unit_velocity())
             .iter(world, None)
             .filter_map(|(id, (&pos,))| {
-                if (pos - position).length() <= radius {
+                let layer = world
+                    .get(id, collision_layer())
+                    .unwrap_or(DEFAULT_COLLISION_LAYER);
+                let in_mask = mask.map_or(true, |mask| layer & mask != 0);
+                if in_mask && (pos - position).length() <= radius {
                     Some(id)
                 } else {
                     None
@@ -603,15 +632,24 @@ impl PhysicsObjectCollection {
     }
 }
 
+/// The dynamic body of `id`, which is either a rigid dynamic or the bone of a ragdoll.
+fn dynamic_body(world: &World, id: EntityId) -> anyhow::Result<PxRigidBodyRef> {
+    if let Ok(link) = world.get(id, articulation_link()) {
+        return Ok(link.as_rigid_body());
+    }
+    let shape = world.get_ref(id, physics_shape())?;
+    let actor = shape.get_actor().context("No actor for shape")?;
+    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    Ok(actor.as_rigid_body())
+}
+
 pub fn add_force(
     world: &World,
     id: EntityId,
     force: Vec3,
     mode: Option<PxForceMode>,
 ) -> anyhow::Result<()> {
-    let shape = world.get_ref(id, physics_shape())?;
-    let actor = shape.get_actor().context("No actor for shape")?;
-    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    let actor = dynamic_body(world, id)?;
     // Kinematic actors can't have force applied to them: https://github.com/OurMachinery/themachinery-public/issues/494
     if actor
         .get_rigid_body_flags()
@@ -630,9 +668,7 @@ pub fn add_force_at_position(
     position: Vec3,
     mode: Option<PxForceMode>,
 ) -> anyhow::Result<()> {
-    let shape = world.get_ref(id, physics_shape())?;
-    let actor = shape.get_actor().context("No actor for shape")?;
-    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    let actor = dynamic_body(world, id)?;
     // Kinematic actors can't have force applied to them: https://github.com/OurMachinery/themachinery-public/issues/494
     if actor
         .get_rigid_body_flags()
@@ -649,9 +685,7 @@ pub fn get_velocity_at_position(
     id: EntityId,
     position: Vec3,
 ) -> anyhow::Result<Vec3> {
-    let shape = world.get_ref(id, physics_shape())?;
-    let actor = shape.get_actor().context("No actor for shape")?;
-    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    let actor = dynamic_body(world, id)?;
     Ok(actor.get_velocity_at_pos(position))
 }
 
@@ -9,8 +9,9 @@ use glam::Vec3;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use physxx::{
-    PxConvexFlag, PxConvexMesh, PxConvexMeshDesc, PxConvexMeshGeometry, PxOverlapCallback,
-    PxQueryFilterData, PxRaycastCallback, PxRigidActor, PxShape, PxTransform, PxUserData,
+    PxConvexFlag, PxConvexMesh, PxConvexMeshDesc, PxConvexMeshGeometry, PxFilterData,
+    PxOverlapCallback, PxQueryFilterData, PxRaycastCallback, PxRigidActor, PxShape, PxTransform,
+    PxUserData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,16 +31,26 @@ pub fn get_entities_in_radius(world: &World, center: Vec3, radius: f32) -> Vec<E
 }
 
 pub fn raycast_first(world: &World, ray: Ray) -> Option<(EntityId, f32)> {
-    raycast_first_px(world, ray).and_then(|(shape, dist)| {
+    raycast_first_masked(world, ray, None)
+}
+
+/// Returns the first entity hit by the ray, only hitting colliders on one of the `mask` collision
+/// layers.
+pub fn raycast_first_with_mask(world: &World, ray: Ray, mask: u32) -> Option<(EntityId, f32)> {
+    raycast_first_masked(world, ray, Some(mask))
+}
+
+fn raycast_first_masked(world: &World, ray: Ray, mask: Option<u32>) -> Option<(EntityId, f32)> {
+    raycast_first_px(world, ray, mask).and_then(|(shape, dist)| {
         shape
             .get_user_data::<PxShapeUserData>()
             .map(|ud| (ud.entity, dist))
     })
 }
 
-fn raycast_first_px(world: &World, ray: Ray) -> Option<(PxShape, f32)> {
+fn raycast_first_px(world: &World, ray: Ray, mask: Option<u32>) -> Option<(PxShape, f32)> {
     (0..3)
-        .filter_map(|i| raycast_first_collider_type_px(world, ColliderScene::from_usize(i), ray))
+        .filter_map(|i| raycast_first_masked_px(world, ColliderScene::from_usize(i), ray, mask))
         .sorted_by_key(|x| OrderedFloat(x.1))
         .next()
 }
@@ -60,9 +71,17 @@ pub fn raycast_first_collider_type_px(
     collider_type: ColliderScene,
     ray: Ray,
 ) -> Option<(PxShape, f32)> {
+    raycast_first_masked_px(world, collider_type, ray, None)
+}
+fn raycast_first_masked_px(
+    world: &World,
+    collider_type: ColliderScene,
+    ray: Ray,
+    mask: Option<u32>,
+) -> Option<(PxShape, f32)> {
+    let filter_data = mask_filter_data(mask)?;
     let mut hit = PxRaycastCallback::new(0);
     let scene = collider_type.get_scene(world);
-    let filter_data = PxQueryFilterData::new();
     if scene.raycast(ray.origin, ray.dir, f32::MAX, &mut hit, None, &filter_data) {
         let block = hit.block().unwrap();
         if let Some(shape) = block.shape {
@@ -73,7 +92,17 @@ pub fn raycast_first_collider_type_px(
 }
 
 pub fn raycast(world: &World, ray: Ray) -> Vec<(EntityId, f32)> {
-    raycast_px(world, ray)
+    raycast_masked(world, ray, None)
+}
+
+/// Returns all entities hit by the ray, closest first, only hitting colliders on one of the `mask`
+/// collision layers.
+pub fn raycast_with_mask(world: &World, ray: Ray, mask: u32) -> Vec<(EntityId, f32)> {
+    raycast_masked(world, ray, Some(mask))
+}
+
+fn raycast_masked(world: &World, ray: Ray, mask: Option<u32>) -> Vec<(EntityId, f32)> {
+    raycast_px(world, ray, mask)
         .into_iter()
         .flat_map(|(shape, dist)| {
             shape
@@ -83,11 +112,9 @@ pub fn raycast(world: &World, ray: Ray) -> Vec<(EntityId, f32)> {
         .collect_vec()
 }
 
-fn raycast_px(world: &World, ray: Ray) -> Vec<(PxShape, f32)> {
+fn raycast_px(world: &World, ray: Ray, mask: Option<u32>) -> Vec<(PxShape, f32)> {
     (0..3)
-        .flat_map(|i| {
-            raycast_collider_type_px(world, ColliderScene::from_usize(i), ray).into_iter()
-        })
+        .flat_map(|i| raycast_masked_px(world, ColliderScene::from_usize(i), ray, mask).into_iter())
         .sorted_by_key(|x| OrderedFloat(x.1))
         .collect_vec()
 }
@@ -111,9 +138,19 @@ pub fn raycast_collider_type_px(
     collider_type: ColliderScene,
     ray: Ray,
 ) -> Vec<(PxShape, f32)> {
+    raycast_masked_px(world, collider_type, ray, None)
+}
+fn raycast_masked_px(
+    world: &World,
+    collider_type: ColliderScene,
+    ray: Ray,
+    mask: Option<u32>,
+) -> Vec<(PxShape, f32)> {
+    let Some(filter_data) = mask_filter_data(mask) else {
+        return Vec::new();
+    };
     let mut hit = PxRaycastCallback::new(100);
     let scene = collider_type.get_scene(world);
-    let filter_data = PxQueryFilterData::new();
     if scene.raycast(ray.origin, ray.dir, f32::MAX, &mut hit, None, &filter_data) {
         return hit
             .touches()
@@ -124,6 +161,18 @@ pub fn raycast_collider_type_px(
     Vec::new()
 }
 
+/// The query filter data for a scene query that only hits colliders on one of the `mask` collision
+/// layers. Returns `None` if the mask is empty, as nothing can be hit.
+pub(crate) fn mask_filter_data(mask: Option<u32>) -> Option<PxQueryFilterData> {
+    let mut filter_data = PxQueryFilterData::new();
+    match mask {
+        Some(0) => return None,
+        Some(mask) => filter_data.set_data(PxFilterData::new(mask, 0, 0, 0)),
+        None => {}
+    }
+    Some(filter_data)
+}
+
 pub fn intersect_frustum(world: &World, frustum_corners: &[Vec3; 8]) -> Vec<EntityId> {
     let mut hit_call = PxOverlapCallback::new(1000);
     let filter_data = PxQueryFilterData::new();
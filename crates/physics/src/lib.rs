@@ -1,15 +1,20 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use ambient_core::{asset_cache, FIXED_SERVER_TICK_TIME};
 use ambient_ecs::{
-    components, query, Debuggable, DynSystem, Entity, EntityId, FnSystem, Resource, SystemGroup,
-    World,
+    components, generated::physics::types::CombineMode, query, Debuggable, DynSystem, Entity,
+    EntityId, FnSystem, Resource, SystemGroup, World,
 };
 use ambient_native_std::asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt};
 use ambient_network::server::{ForkingEvent, ShutdownEvent};
-use collider::{collider_shapes, collider_shapes_convex};
+use collider::{collider_shapes, collider_shapes_convex, collider_type, ColliderType};
 use glam::{vec3, Mat4, Vec3};
 use helpers::release_px_scene;
+use intersection::mask_filter_data;
+use itertools::Itertools;
 use parking_lot::Mutex;
 use physx::{
     actor_aggregate, articulation_cache, articulation_link, articulation_reduce_coordinate,
@@ -17,8 +22,9 @@ use physx::{
     rigid_static,
 };
 use physxx::{
-    AsPxActor, PxContactPairHeader, PxContactPoint, PxControllerManagerRef, PxMaterial,
-    PxPvdSceneFlag, PxRigidActor, PxSceneDesc, PxSceneFlags, PxSceneRef, PxSimulationEventCallback,
+    AsPxActor, PxCombineMode, PxContactPairHeader, PxContactPoint, PxControllerManagerRef,
+    PxFilterData, PxMaterial, PxOverlapCallback, PxPvdSceneFlag, PxQueryFlag, PxReferenceCounted,
+    PxRigidActor, PxSceneDesc, PxSceneFlags, PxSceneRef, PxSimulationEventCallback, PxTransform,
     PxUserData,
 };
 use serde::{Deserialize, Serialize};
@@ -30,7 +36,9 @@ pub mod helpers;
 pub mod intersection;
 pub mod mesh;
 pub mod physx;
+pub mod ragdoll;
 pub mod rc_asset;
+pub mod vehicle;
 pub mod visualization;
 
 pub use ambient_ecs::generated::physics::components::*;
@@ -48,11 +56,27 @@ components!("physics", {
     wood_physics_material: PxMaterial,
     @[Debuggable, Resource]
     collisions: Arc<Mutex<Vec<(EntityId, EntityId, Vec<Vec3>, Vec<Vec3>)>>>,
+    @[Debuggable, Resource]
+    trigger_events: Vec<TriggerEvent>,
+    /// The materials created for [`PxMaterialKey`]s. They are released once no shape uses them.
+    @[Resource]
+    px_materials: HashMap<PxMaterialKey, PxMaterial>,
 });
+
+/// An entity entering or leaving a [`ColliderType::TriggerArea`] collider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEvent {
+    pub trigger: EntityId,
+    pub other: EntityId,
+    pub entered: bool,
+}
+
 pub fn init_all_components() {
     init_components();
     physx::init_components();
     collider::init_components();
+    ragdoll::init_components();
+    vehicle::init_components();
     visualization::init_components();
 }
 
@@ -65,7 +89,8 @@ pub fn create_server_resources(assets: &AssetCache, server_resources: &mut Entit
     main_scene_desc.set_cpu_dispatcher(&physics.dispatcher);
     main_scene_desc.set_gravity(vec3(0., 0., -GRAVITY));
     main_scene_desc.update_flags(|flags| flags | PxSceneFlags::ENABLE_CCD);
-    main_scene_desc.set_filter_shader(main_physx_scene_filter_shader, true);
+    // The default filter shader is skipped, as it would interpret our filter data as collision groups
+    main_scene_desc.set_filter_shader(main_physx_scene_filter_shader, false);
     let collisions = Arc::new(Mutex::new(Vec::new()));
     {
         let collisions = collisions.clone();
@@ -100,6 +125,8 @@ pub fn create_server_resources(assets: &AssetCache, server_resources: &mut Entit
     }
     let main_scene = PxSceneRef::new(&physics.physics, &main_scene_desc);
     server_resources.set(self::collisions(), collisions);
+    server_resources.set(self::trigger_events(), vec![]);
+    server_resources.set(self::px_materials(), HashMap::new());
     server_resources.set(self::collider_loads(), vec![]);
 
     main_scene.get_scene_pvd_client().set_scene_pvd_flags(
@@ -191,10 +218,142 @@ impl SyncAssetKey<PxMaterial> for PxWoodMaterialKey {
     }
 }
 
+/// The properties of a [`PxMaterial`]; colliders with the same properties share a material.
+///
+/// The friction and restitution are rounded to the nearest multiple of [`Self::PRECISION`], so
+/// that values which only differ slightly (e.g. while they are being animated) share a material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PxMaterialKey {
+    static_friction: i32,
+    dynamic_friction: i32,
+    restitution: i32,
+    friction_combine_mode: CombineMode,
+    restitution_combine_mode: CombineMode,
+}
+impl std::hash::Hash for PxMaterialKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.static_friction.hash(state);
+        self.dynamic_friction.hash(state);
+        self.restitution.hash(state);
+        (self.friction_combine_mode as u8).hash(state);
+        (self.restitution_combine_mode as u8).hash(state);
+    }
+}
+impl PxMaterialKey {
+    pub const PRECISION: f32 = 0.001;
+
+    pub fn new(
+        static_friction: f32,
+        dynamic_friction: f32,
+        restitution: f32,
+        friction_combine_mode: CombineMode,
+        restitution_combine_mode: CombineMode,
+    ) -> Self {
+        let quantize = |value: f32| (value / Self::PRECISION).round() as i32;
+        Self {
+            static_friction: quantize(static_friction),
+            dynamic_friction: quantize(dynamic_friction),
+            restitution: quantize(restitution),
+            friction_combine_mode,
+            restitution_combine_mode,
+        }
+    }
+    /// Reads the material components of an entity, using the default material's values for the
+    /// missing ones. Returns `None` if the entity has none of them.
+    pub fn from_entity(world: &World, id: EntityId) -> Option<Self> {
+        let key = Self::new(
+            world.get(id, static_friction()).unwrap_or(0.5),
+            world.get(id, dynamic_friction()).unwrap_or(0.5),
+            world.get(id, restitution()).unwrap_or(0.6),
+            world.get(id, friction_combine_mode()).unwrap_or_default(),
+            world
+                .get(id, restitution_combine_mode())
+                .unwrap_or_default(),
+        );
+        let has_any = world.has_component(id, static_friction())
+            || world.has_component(id, dynamic_friction())
+            || world.has_component(id, restitution())
+            || world.has_component(id, friction_combine_mode())
+            || world.has_component(id, restitution_combine_mode());
+        has_any.then_some(key)
+    }
+    /// Returns the material with these properties from [`px_materials`], creating it if needed.
+    pub fn get(&self, world: &mut World) -> PxMaterial {
+        let physics = world.resource(crate::physx::physics()).physics;
+        world
+            .resource_mut(px_materials())
+            .entry(*self)
+            .or_insert_with(|| {
+                let value = |value: i32| value as f32 * Self::PRECISION;
+                let material = PxMaterial::new(
+                    physics,
+                    value(self.static_friction),
+                    value(self.dynamic_friction),
+                    value(self.restitution),
+                );
+                material.set_friction_combine_mode(px_combine_mode(self.friction_combine_mode));
+                material
+                    .set_restitution_combine_mode(px_combine_mode(self.restitution_combine_mode));
+                material
+            })
+            .clone()
+    }
+}
+
+fn px_combine_mode(mode: CombineMode) -> PxCombineMode {
+    match mode {
+        CombineMode::Average => PxCombineMode::Average,
+        CombineMode::Min => PxCombineMode::Min,
+        CombineMode::Multiply => PxCombineMode::Multiply,
+        CombineMode::Max => PxCombineMode::Max,
+    }
+}
+
+/// The collision layers of colliders without a `collision_layer`.
+pub const DEFAULT_COLLISION_LAYER: u32 = 1;
+/// The collision layers that colliders without a `collision_mask` collide with.
+pub const DEFAULT_COLLISION_MASK: u32 = u32::MAX;
+/// Set in `word2` of a shape's simulation filter data once its collision layers have been applied;
+/// shapes without it (e.g. before the first update) use the defaults.
+const COLLISION_FILTER_DATA_SET: u32 = 1;
+
+/// The simulation filter data for a shape on the `layer` collision layers that collides with the
+/// `mask` collision layers. Shapes in the same non-zero collision `group` never collide with each other.
+pub fn collision_filter_data(layer: u32, mask: u32, group: u32) -> PxFilterData {
+    PxFilterData::new(layer, mask, COLLISION_FILTER_DATA_SET, group)
+}
+
+/// The collision group shared by the shapes of an entity and the bones of its ragdoll.
+pub fn collision_group(id: EntityId) -> u32 {
+    (id.0 as u32).max(1)
+}
+
+/// The query filter data for a shape on the `layer` collision layers. Scene queries with a mask
+/// only hit shapes whose query filter data has a bit in common with it.
+pub fn collision_query_filter_data(layer: u32) -> PxFilterData {
+    PxFilterData::new(layer, 0, 0, 0)
+}
+
 unsafe extern "C" fn main_physx_scene_filter_shader(
     info: *mut physxx::sys::FilterShaderCallbackInfo,
 ) -> u16 {
-    (*(*info).pairFlags).mBits |= (physxx::sys::PxPairFlag::eSOLVE_CONTACT
+    let layers = |data: &physxx::sys::PxFilterData| {
+        if data.word2 & COLLISION_FILTER_DATA_SET != 0 {
+            (data.word0, data.word1)
+        } else {
+            (DEFAULT_COLLISION_LAYER, DEFAULT_COLLISION_MASK)
+        }
+    };
+    let ((layer0, mask0), (layer1, mask1)) =
+        (layers(&(*info).filterData0), layers(&(*info).filterData1));
+    if layer0 & mask1 == 0 || layer1 & mask0 == 0 {
+        return (physxx::sys::PxFilterFlag::eSUPPRESS) as u16;
+    }
+    let group = (*info).filterData0.word3;
+    if group != 0 && group == (*info).filterData1.word3 {
+        return (physxx::sys::PxFilterFlag::eSUPPRESS) as u16;
+    }
+    (*(*info).pairFlags).mBits = (physxx::sys::PxPairFlag::eSOLVE_CONTACT
         | physxx::sys::PxPairFlag::eDETECT_DISCRETE_CONTACT
         | physxx::sys::PxPairFlag::eDETECT_CCD_CONTACT
         | physxx::sys::PxPairFlag::eCONTACT_DEFAULT
@@ -203,6 +362,157 @@ unsafe extern "C" fn main_physx_scene_filter_shader(
     (physxx::sys::PxFilterFlag::eDEFAULT) as u16
 }
 
+/// How many overlaps are first recorded for each shape of a trigger. The buffer grows if it fills up.
+const TRIGGER_OVERLAP_CAPACITY: usize = 256;
+
+/// Finds the entities inside each [`ColliderType::TriggerArea`] collider by overlapping its shapes
+/// with the main physics scene, and records the ones that entered or left it in [`trigger_events`].
+fn update_entities_in_trigger(world: &mut World) {
+    let scene = *world.resource(main_physics_scene());
+    let mut events = Vec::new();
+    for (trigger, (&kind, actor, shapes)) in
+        query((collider_type(), rigid_actor(), collider_shapes_convex())).iter(world, None)
+    {
+        if kind != ColliderType::TriggerArea {
+            continue;
+        }
+        // A trigger with an empty mask overlaps nothing, so everything inside it leaves
+        let mut current = Vec::new();
+        if let Some(mut filter_data) = mask_filter_data(world.get(trigger, collision_mask()).ok()) {
+            filter_data.set_flags(PxQueryFlag::DYNAMIC);
+            let actor_pose = actor.get_global_pose();
+            for shape in shapes {
+                let local_pose = shape.get_local_pose();
+                let pose = PxTransform::new(
+                    actor_pose.translation() + actor_pose.rotation() * local_pose.translation(),
+                    actor_pose.rotation() * local_pose.rotation(),
+                );
+                let touches = overlap_all(|capacity| {
+                    let mut hit_call = PxOverlapCallback::new(capacity);
+                    if scene.overlap(&shape.get_geometry(), pose, &mut hit_call, &filter_data) {
+                        hit_call.touches()
+                    } else {
+                        Vec::new()
+                    }
+                });
+                current.extend(touches.iter().filter_map(|hit| {
+                    hit.shape
+                        .get_user_data::<PxShapeUserData>()
+                        .map(|ud| ud.entity)
+                }));
+            }
+        }
+        let previous = world
+            .get_ref(trigger, entities_in_trigger())
+            .map(|inside| inside.as_slice())
+            .unwrap_or_default();
+        events.push((
+            trigger,
+            diff_entities_in_trigger(trigger, previous, current),
+        ));
+    }
+
+    let updated = events.iter().map(|(trigger, _)| *trigger).collect();
+    let mut all_events = exit_stale_triggers(world, &updated);
+    for (trigger, (inside, changes)) in events {
+        if !changes.is_empty() || !world.has_component(trigger, entities_in_trigger()) {
+            world
+                .add_component(trigger, entities_in_trigger(), inside)
+                .unwrap();
+        }
+        all_events.extend(changes);
+    }
+    *world.resource_mut(trigger_events()) = all_events;
+}
+
+/// Runs a scene query with a touch buffer of `capacity`, growing it until it doesn't fill up,
+/// as PhysX stops reporting touches once the buffer is full.
+fn overlap_all<T>(mut overlap: impl FnMut(usize) -> Vec<T>) -> Vec<T> {
+    let mut capacity = TRIGGER_OVERLAP_CAPACITY;
+    loop {
+        let touches = overlap(capacity);
+        if touches.len() < capacity {
+            return touches;
+        }
+        capacity *= 2;
+    }
+}
+
+/// Empties the entities that have [`entities_in_trigger`] but are not trigger areas anymore, such
+/// as colliders whose type changed or that lost their actor, and returns an exit event for each
+/// entity that was inside them.
+fn exit_stale_triggers(world: &mut World, updated: &HashSet<EntityId>) -> Vec<TriggerEvent> {
+    let stale = query(entities_in_trigger())
+        .iter(world, None)
+        .filter(|(id, _)| !updated.contains(id))
+        .map(|(id, inside)| (id, inside.clone()))
+        .collect_vec();
+
+    let mut events = Vec::new();
+    for (trigger, inside) in stale {
+        world
+            .remove_component(trigger, entities_in_trigger())
+            .unwrap();
+        events.extend(diff_entities_in_trigger(trigger, &inside, []).1);
+    }
+    events
+}
+
+/// Sends an exit event for each entity that was inside a trigger area when it was despawned.
+fn exit_despawned_triggers_system() -> DynSystem {
+    query(entities_in_trigger())
+        .despawned()
+        .to_system(|q, world, qs, _| {
+            let events = q
+                .iter(world, qs)
+                .flat_map(|(trigger, inside)| diff_entities_in_trigger(trigger, inside, []).1)
+                .collect_vec();
+            world.resource_mut(trigger_events()).extend(events);
+        })
+}
+
+/// Compares the entities that were inside `trigger` with the entities that overlap it now (which
+/// may contain the same entity more than once, if several of its shapes overlap), and returns the
+/// entities inside it now along with an event for each entity that entered or left it.
+///
+/// Entities that stay inside keep their place, and entities that entered are added at the end.
+pub fn diff_entities_in_trigger(
+    trigger: EntityId,
+    previous: &[EntityId],
+    current: impl IntoIterator<Item = EntityId>,
+) -> (Vec<EntityId>, Vec<TriggerEvent>) {
+    let mut seen = HashSet::new();
+    let current = current
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect_vec();
+
+    let mut inside = Vec::with_capacity(current.len());
+    let mut events = Vec::new();
+    for &other in previous {
+        if seen.contains(&other) {
+            inside.push(other);
+        } else {
+            events.push(TriggerEvent {
+                trigger,
+                other,
+                entered: false,
+            });
+        }
+    }
+    for other in current {
+        if !previous.contains(&other) {
+            inside.push(other);
+            events.push(TriggerEvent {
+                trigger,
+                other,
+                entered: true,
+            });
+        }
+    }
+    (inside, events)
+}
+
 pub fn server_systems() -> SystemGroup {
     SystemGroup::new(
         "physics",
@@ -233,7 +543,22 @@ pub fn server_systems() -> SystemGroup {
                         controller.release();
                     }
                 }),
+            Box::new(FnSystem::new(|world, _| {
+                profiling::scope!("release_unused_materials");
+                // The materials that only the cache holds on to aren't used by any shape
+                world
+                    .resource_mut(px_materials())
+                    .retain(|_, material| material.get_reference_count() > 1);
+            })),
+            Box::new(FnSystem::new(|world, _| {
+                profiling::scope!("update_entities_in_trigger");
+                update_entities_in_trigger(world);
+            })),
+            // Runs after the triggers are updated, as that replaces the events
+            exit_despawned_triggers_system(),
             Box::new(collider::server_systems()),
+            Box::new(ragdoll::server_systems()),
+            Box::new(vehicle::server_systems()),
             Box::new(visualization::server_systems()),
         ],
     )
@@ -351,3 +676,156 @@ pub fn on_shutdown_systems() -> SystemGroup<ShutdownEvent> {
         }))],
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::{FrameEvent, WorldContext};
+
+    use super::*;
+
+    fn exits(trigger: EntityId, ids: &[EntityId]) -> Vec<TriggerEvent> {
+        ids.iter()
+            .map(|&other| TriggerEvent {
+                trigger,
+                other,
+                entered: false,
+            })
+            .collect()
+    }
+
+    fn enters(trigger: EntityId, ids: &[EntityId]) -> Vec<TriggerEvent> {
+        ids.iter()
+            .map(|&other| TriggerEvent {
+                trigger,
+                other,
+                entered: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn material_keys_are_quantized() {
+        let key = |friction: f32| {
+            PxMaterialKey::new(
+                friction,
+                0.5,
+                0.6,
+                CombineMode::Average,
+                CombineMode::Average,
+            )
+        };
+        assert_eq!(key(0.3), key(0.3001));
+        assert_ne!(key(0.3), key(0.302));
+        assert_ne!(
+            key(0.3),
+            PxMaterialKey::new(0.3, 0.5, 0.6, CombineMode::Max, CombineMode::Average)
+        );
+
+        let keys: HashSet<_> = (0..10_000).map(|i| key(0.3 + i as f32 * 4e-8)).collect();
+        assert_eq!(keys.len(), 1);
+    }
+
+    #[test]
+    fn entities_entering_and_leaving() {
+        let trigger = EntityId::new();
+        let [a, b, c] = [EntityId::new(), EntityId::new(), EntityId::new()];
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &[], [a, b]);
+        assert_eq!(inside, vec![a, b]);
+        assert_eq!(events, enters(trigger, &[a, b]));
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &inside, [c, b]);
+        assert_eq!(inside, vec![b, c]);
+        assert_eq!(
+            events,
+            [exits(trigger, &[a]), enters(trigger, &[c])].concat()
+        );
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &inside, []);
+        assert!(inside.is_empty());
+        assert_eq!(events, exits(trigger, &[b, c]));
+    }
+
+    #[test]
+    fn overlaps_grow_until_the_buffer_does_not_fill() {
+        let overlap = |count: usize| move |capacity: usize| (0..count.min(capacity)).collect_vec();
+        assert_eq!(overlap_all(overlap(3)).len(), 3);
+        assert_eq!(
+            overlap_all(overlap(TRIGGER_OVERLAP_CAPACITY)).len(),
+            TRIGGER_OVERLAP_CAPACITY
+        );
+        assert_eq!(overlap_all(overlap(5000)).len(), 5000);
+    }
+
+    fn trigger_world(name: &'static str) -> World {
+        ambient_ecs::init_components();
+        init_components();
+        let mut world = World::new(name, WorldContext::Server);
+        world.add_resource(trigger_events(), Vec::new());
+        world
+    }
+
+    #[test]
+    fn stale_triggers_send_exits() {
+        let mut world = trigger_world("stale_triggers_send_exits");
+        let [a, b] = [EntityId::new(), EntityId::new()];
+        let active = Entity::new()
+            .with(entities_in_trigger(), vec![a])
+            .spawn(&mut world);
+        // E.g. a collider whose type changed, or that lost its actor
+        let stale = Entity::new()
+            .with(entities_in_trigger(), vec![a, b])
+            .spawn(&mut world);
+
+        let events = exit_stale_triggers(&mut world, &HashSet::from([active]));
+        assert_eq!(events, exits(stale, &[a, b]));
+        assert!(!world.has_component(stale, entities_in_trigger()));
+        assert_eq!(
+            world.get_ref(active, entities_in_trigger()).unwrap(),
+            &vec![a]
+        );
+
+        // Once emptied, they send nothing more
+        assert!(exit_stale_triggers(&mut world, &HashSet::from([active])).is_empty());
+    }
+
+    #[test]
+    fn despawned_triggers_send_exits() {
+        let mut world = trigger_world("despawned_triggers_send_exits");
+        let mut system = exit_despawned_triggers_system();
+        let [a, b] = [EntityId::new(), EntityId::new()];
+        let trigger = Entity::new()
+            .with(entities_in_trigger(), vec![a, b])
+            .spawn(&mut world);
+        system.run(&mut world, &FrameEvent);
+        assert!(world.resource(trigger_events()).is_empty());
+
+        world.despawn(trigger);
+        system.run(&mut world, &FrameEvent);
+        assert_eq!(world.resource(trigger_events()), &exits(trigger, &[a, b]));
+    }
+
+    #[test]
+    fn entities_staying_inside_send_nothing() {
+        let trigger = EntityId::new();
+        let [a, b] = [EntityId::new(), EntityId::new()];
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &[a, b], [b, a]);
+        assert_eq!(inside, vec![a, b]);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn entities_with_several_shapes_enter_once() {
+        let trigger = EntityId::new();
+        let a = EntityId::new();
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &[], [a, a, a]);
+        assert_eq!(inside, vec![a]);
+        assert_eq!(events, enters(trigger, &[a]));
+
+        let (inside, events) = diff_entities_in_trigger(trigger, &inside, [a, a]);
+        assert_eq!(inside, vec![a]);
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,994 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
+
+use ambient_animation::sample_joint_transform;
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    runtime,
+    transform::{get_world_transform, rotation, translation},
+};
+use ambient_ecs::{
+    components, generated::animation::components::apply_animation_player, query, Entity, EntityId,
+    SystemGroup, World,
+};
+use ambient_native_std::{
+    asset_cache::{AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::JsonFromUrl,
+};
+use anyhow::Context;
+use glam::{Mat3, Mat4, Quat, Vec3};
+use itertools::Itertools;
+use physxx::{
+    articulation_reduced_coordinate::{PxArticulationJointRef, PxArticulationRef},
+    PxArticulationAxis, PxArticulationBase, PxArticulationJointBase, PxArticulationJointType,
+    PxArticulationLinkRef, PxArticulationMotion, PxCapsuleGeometry, PxRigidActor, PxRigidBody,
+    PxShape, PxShapeFlag, PxTransform, PxUserData,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    collision_filter_data, collision_group, collision_query_filter_data, main_physics_scene,
+    physx::{articulation_link, articulation_reduce_coordinate, character_controller, physics},
+    PxMaterialKey, PxShapeUserData, PxWoodMaterialKey, DEFAULT_COLLISION_LAYER,
+    DEFAULT_COLLISION_MASK,
+};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+components!("physics", {
+    ragdoll_def: Arc<RagdollDef>,
+});
+
+/// PhysX articulations can't have more links than this.
+pub const MAX_RAGDOLL_BONES: usize = 64;
+/// Bones whose descendants are all closer than this (relative to the size of the skeleton) are
+/// left out of generated ragdolls, which skips fingers, toes and end bones.
+const MIN_BONE_EXTENT: f32 = 0.05;
+/// Limbs shorter than this (relative to the size of the skeleton) don't get a hinge-like joint,
+/// which skips necks and tails.
+const MIN_LIMB_LENGTH: f32 = 0.2;
+/// How far (in radians) hinge-like joints bend by default.
+const HINGE_BEND_LIMIT: f32 = 2.4;
+/// How far (in radians) hinge-like joints bend the other way, if the bind pose shows which way
+/// they bend.
+const HINGE_HYPEREXTENSION: f32 = 0.1;
+/// How far (in radians) hinge-like joints bend sideways.
+const HINGE_SIDE_LIMIT: f32 = 0.05;
+/// How far (in radians) hinge-like joints twist by default.
+const HINGE_TWIST_LIMIT: f32 = 0.15;
+/// Bones that are bent by less than this (in radians) from their parent bone in the bind pose
+/// are considered straight.
+const MIN_BIND_BEND: f32 = 0.02;
+
+/// A ragdoll generated from the bind pose of a model's skeleton, with a capsule per bone. It's
+/// spawned in the pose that the skeleton has at that time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagdollDef {
+    /// Parents come before their children, and the first bone is the root.
+    pub bones: Vec<RagdollBone>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagdollBone {
+    pub bind_id: String,
+    /// The index of the parent bone in [`RagdollDef::bones`].
+    pub parent: Option<usize>,
+    /// The bind pose of the bone, relative to the model.
+    pub position: Vec3,
+    pub rotation: Quat,
+    /// The center of the capsule, relative to the bone.
+    pub shape_offset: Vec3,
+    /// The rotation of the capsule relative to the bone. The capsule's axis is along X.
+    pub shape_rotation: Quat,
+    pub radius: f32,
+    pub half_height: f32,
+    /// The rotation of the joint to the parent bone, relative to the bone. The joint twists
+    /// around X, and hinge-like joints bend around Z.
+    pub joint_rotation: Quat,
+    pub limits: RagdollJointLimits,
+    /// The joints from the parent bone (or the model) down to this bone, including it.
+    pub chain: Vec<RagdollChainJoint>,
+}
+
+/// The limits (in radians) of the joint between a ragdoll bone and its parent bone, relative to
+/// the pose that the ragdoll was spawned in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RagdollJointLimits {
+    /// How far the bone can twist around itself, in either direction.
+    pub twist: f32,
+    /// The range that the bone can swing in around the joint's Y axis.
+    pub swing_y: (f32, f32),
+    /// The range that the bone can swing in around the joint's Z axis.
+    pub swing_z: (f32, f32),
+}
+impl RagdollJointLimits {
+    fn ball(swing: f32, twist: f32) -> Self {
+        Self {
+            twist,
+            swing_y: (-swing, swing),
+            swing_z: (-swing, swing),
+        }
+    }
+}
+
+/// Overrides the limits of the joint between a ragdoll bone and its parent bone.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RagdollBoneLimits {
+    /// Whether the joint bends around a single axis, like a knee or an elbow. By default, the
+    /// joint in the middle of each limb does.
+    pub hinge: Option<bool>,
+    /// How far (in radians) a hinge-like joint bends, or how far the bone swings away otherwise.
+    pub swing_limit: Option<f32>,
+    /// How far (in radians) the bone twists around itself.
+    pub twist_limit: Option<f32>,
+}
+
+/// A joint of the skeleton between a ragdoll bone and its parent bone, used to pose the ragdoll
+/// like the animated model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagdollChainJoint {
+    pub bind_id: String,
+    /// The transform from the previous joint in the chain (or the model) to the parent node of
+    /// this joint.
+    pub offset: Mat4,
+    /// The bind pose of the joint, relative to its parent node.
+    pub local_transform: Mat4,
+}
+
+/// A joint of a model's skeleton, used to generate a [`RagdollDef`].
+#[derive(Debug, Clone)]
+pub struct RagdollJoint {
+    pub bind_id: String,
+    pub name: Option<String>,
+    /// The index of the parent joint, if it's part of the skeleton.
+    pub parent: Option<usize>,
+    /// The bind pose of the joint, relative to the model.
+    pub transform: Mat4,
+    /// The bind pose of the joint, relative to its parent node.
+    pub local_transform: Mat4,
+}
+
+impl RagdollDef {
+    /// Generates a ragdoll from the bind pose of a skeleton.
+    ///
+    /// `bones` are the names or bind ids of the joints to simulate; if it's empty, all joints
+    /// except for the small ones at the extremities are used. Each capsule goes from its bone to
+    /// the bone's children, with a radius of `radius` times its length.
+    ///
+    /// The joint in the middle of each limb (a chain of bones that branches off the skeleton and
+    /// ends without branching again, e.g. an arm or a leg) bends around a single axis, like a knee
+    /// or an elbow. Its axis and direction are taken from how it's bent in the bind pose, or it
+    /// bends both ways if it's straight. The other joints can swing `swing_limit` and twist
+    /// `twist_limit` in any direction. `bone_limits` overrides the limits of bones by their name
+    /// or bind id.
+    pub fn from_skeleton(
+        joints: &[RagdollJoint],
+        bones: &[String],
+        radius: f32,
+        swing_limit: f32,
+        twist_limit: f32,
+        bone_limits: &HashMap<String, RagdollBoneLimits>,
+    ) -> anyhow::Result<Self> {
+        let transforms = joints
+            .iter()
+            .map(|joint| {
+                let (_, rotation, position) = joint.transform.to_scale_rotation_translation();
+                (position, rotation)
+            })
+            .collect_vec();
+        let mut children = vec![Vec::new(); joints.len()];
+        for (index, joint) in joints.iter().enumerate() {
+            if let Some(parent) = joint.parent {
+                children[parent].push(index);
+            }
+        }
+
+        // Parents first
+        let mut order = (0..joints.len())
+            .filter(|index| joints[*index].parent.is_none())
+            .collect_vec();
+        let mut next = 0;
+        while next < order.len() {
+            order.extend(children[order[next]].iter().copied());
+            next += 1;
+        }
+
+        let (min, max) = transforms.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), (position, _)| (min.min(*position), max.max(*position)),
+        );
+        let size = (max - min).max_element();
+        let find_joint = |bone: &String| {
+            joints
+                .iter()
+                .position(|joint| &joint.bind_id == bone || joint.name.as_ref() == Some(bone))
+                .with_context(|| format!("The model has no bone named {bone:?}"))
+        };
+
+        let selected: HashSet<usize> = if bones.is_empty() {
+            let min_extent = size * MIN_BONE_EXTENT;
+            let mut extents = vec![0f32; joints.len()];
+            for &index in order.iter().rev() {
+                for &child in &children[index] {
+                    let distance = transforms[child].0.distance(transforms[index].0);
+                    extents[index] = extents[index].max(distance + extents[child]);
+                }
+            }
+            order
+                .iter()
+                .copied()
+                .filter(|index| extents[*index] >= min_extent)
+                .collect()
+        } else {
+            bones
+                .iter()
+                .map(find_joint)
+                .collect::<anyhow::Result<_>>()?
+        };
+
+        let mut def = Self { bones: Vec::new() };
+        // The length and the model-space direction of each bone
+        let mut lengths = Vec::new();
+        let mut directions = Vec::new();
+        let mut bone_indices = vec![None; joints.len()];
+        for &index in order.iter().filter(|index| selected.contains(index)) {
+            let mut ancestor = joints[index].parent;
+            while let Some(i) = ancestor {
+                if bone_indices[i].is_some() {
+                    break;
+                }
+                ancestor = joints[i].parent;
+            }
+            let parent = ancestor.and_then(|i| bone_indices[i]);
+            let mut chain = Vec::new();
+            let mut joint = Some(index);
+            while let Some(i) = joint.filter(|joint| Some(*joint) != ancestor) {
+                let parent_transform = joints[i]
+                    .parent
+                    .map_or(Mat4::IDENTITY, |parent| joints[parent].transform);
+                chain.push(RagdollChainJoint {
+                    bind_id: joints[i].bind_id.clone(),
+                    offset: parent_transform.inverse()
+                        * joints[i].transform
+                        * joints[i].local_transform.inverse(),
+                    local_transform: joints[i].local_transform,
+                });
+                joint = joints[i].parent;
+            }
+            chain.reverse();
+            if parent.is_none() && !def.bones.is_empty() {
+                if bones.is_empty() {
+                    continue;
+                }
+                anyhow::bail!(
+                    "The ragdoll bones need to form a single hierarchy, but {:?} isn't below {:?}",
+                    joints[index].bind_id,
+                    def.bones[0].bind_id
+                );
+            }
+            if def.bones.len() == MAX_RAGDOLL_BONES {
+                if bones.is_empty() {
+                    break;
+                }
+                anyhow::bail!("A ragdoll can't have more than {MAX_RAGDOLL_BONES} bones");
+            }
+
+            let (position, rotation) = transforms[index];
+            let offsets = if children[index].is_empty() {
+                // Leaves continue in the direction of their parent bone
+                match joints[index].parent {
+                    Some(parent) => vec![(position - transforms[parent].0) * 0.5],
+                    None => vec![],
+                }
+            } else {
+                children[index]
+                    .iter()
+                    .map(|child| transforms[*child].0 - position)
+                    .collect_vec()
+            };
+            let length = offsets.iter().map(|offset| offset.length()).sum::<f32>()
+                / offsets.len().max(1) as f32;
+            // Bones that branch out symmetrically (e.g. hips) point towards their first child
+            let mut direction = offsets.iter().sum::<Vec3>();
+            if direction.length() < length * 0.25 {
+                direction = offsets.first().copied().unwrap_or_default();
+            }
+            let direction = (rotation.inverse() * direction).normalize_or_zero();
+            let (shape_rotation, radius, half_height) = if length > f32::EPSILON {
+                (
+                    Quat::from_rotation_arc(Vec3::X, direction),
+                    length * radius,
+                    (length / 2. - length * radius).max(0.),
+                )
+            } else {
+                // A lone bone has nothing to size its capsule by
+                (Quat::IDENTITY, 0.05, 0.)
+            };
+            bone_indices[index] = Some(def.bones.len());
+            lengths.push(length);
+            directions.push(rotation * direction);
+            def.bones.push(RagdollBone {
+                bind_id: joints[index].bind_id.clone(),
+                parent,
+                position,
+                rotation,
+                shape_offset: direction * length / 2.,
+                shape_rotation,
+                radius,
+                half_height,
+                joint_rotation: shape_rotation,
+                limits: RagdollJointLimits::ball(swing_limit, twist_limit),
+                chain,
+            });
+        }
+        anyhow::ensure!(!def.bones.is_empty(), "The ragdoll has no bones");
+
+        let mut overrides = vec![RagdollBoneLimits::default(); def.bones.len()];
+        for (bone, limits) in bone_limits {
+            let index = bone_indices[find_joint(bone)?]
+                .with_context(|| format!("{bone:?} isn't one of the ragdoll's bones"))?;
+            overrides[index] = *limits;
+        }
+        let hinges = limb_hinges(&def.bones, &lengths, size);
+        let root_position = def.bones[0].position;
+        for (index, bone) in def.bones.iter_mut().enumerate() {
+            let Some(parent) = bone.parent else {
+                continue;
+            };
+            let overrides = overrides[index];
+            let direction = directions[index];
+            let hinge = overrides.hinge.unwrap_or(hinges.contains(&index));
+            if !hinge || direction == Vec3::ZERO {
+                bone.limits = RagdollJointLimits::ball(
+                    overrides.swing_limit.unwrap_or(swing_limit),
+                    overrides.twist_limit.unwrap_or(twist_limit),
+                );
+                continue;
+            }
+
+            // The joint bends the way it's bent in the bind pose. Straight joints bend around
+            // the axis that points away from the root, e.g. sideways for a leg below the hips.
+            let bend = directions[parent].cross(direction);
+            let bent = bend.length() > MIN_BIND_BEND.sin();
+            let axis = if bent {
+                bend
+            } else {
+                let away = (bone.position - root_position).reject_from_normalized(direction);
+                if away.length() > f32::EPSILON {
+                    away
+                } else {
+                    direction.any_orthonormal_vector()
+                }
+            };
+            let x = bone.rotation.inverse() * direction;
+            let z = (bone.rotation.inverse() * axis)
+                .reject_from_normalized(x)
+                .normalize();
+            bone.joint_rotation = Quat::from_mat3(&Mat3::from_cols(x, z.cross(x), z));
+
+            let bend_limit = overrides.swing_limit.unwrap_or(HINGE_BEND_LIMIT);
+            bone.limits = RagdollJointLimits {
+                twist: overrides.twist_limit.unwrap_or(HINGE_TWIST_LIMIT),
+                swing_y: (-HINGE_SIDE_LIMIT, HINGE_SIDE_LIMIT),
+                swing_z: if bent {
+                    (-HINGE_HYPEREXTENSION, bend_limit)
+                } else {
+                    (-bend_limit, bend_limit)
+                },
+            };
+        }
+        Ok(def)
+    }
+
+    /// The pose of each bone in world space, for a model at `transform`. `local_transform` gets
+    /// the bind id and the bind pose of a joint relative to its parent node, and returns its
+    /// current pose.
+    pub fn poses(
+        &self,
+        transform: Mat4,
+        local_transform: impl Fn(&str, Mat4) -> Mat4,
+    ) -> Vec<Mat4> {
+        let mut poses: Vec<Mat4> = Vec::with_capacity(self.bones.len());
+        for bone in &self.bones {
+            let parent = bone.parent.map_or(transform, |parent| poses[parent]);
+            poses.push(bone.chain.iter().fold(parent, |pose, joint| {
+                pose * joint.offset * local_transform(&joint.bind_id, joint.local_transform)
+            }));
+        }
+        poses
+    }
+}
+
+/// Finds the bone in the middle of each limb, whose joint bends like a knee or an elbow.
+///
+/// A limb is a chain of bones without branches that starts where the skeleton branches out (e.g.
+/// at the hips) and ends at a bone without children, and is at least [MIN_LIMB_LENGTH] long.
+/// Chains that end where the skeleton branches out again, like a spine, aren't limbs.
+fn limb_hinges(bones: &[RagdollBone], lengths: &[f32], size: f32) -> HashSet<usize> {
+    let mut children = vec![Vec::new(); bones.len()];
+    for (index, bone) in bones.iter().enumerate() {
+        if let Some(parent) = bone.parent {
+            children[parent].push(index);
+        }
+    }
+
+    let mut hinges = HashSet::new();
+    for (start, bone) in bones.iter().enumerate() {
+        if bone
+            .parent
+            .map_or(true, |parent| children[parent].len() < 2)
+        {
+            continue;
+        }
+        let mut chain = vec![start];
+        while let [child] = children[chain[chain.len() - 1]].as_slice() {
+            chain.push(*child);
+        }
+        let end = chain[chain.len() - 1];
+        if chain.len() < 2 || !children[end].is_empty() {
+            continue;
+        }
+
+        // How far along the limb each bone starts
+        let mut starts = vec![0.];
+        for pair in chain.windows(2) {
+            let distance = bones[pair[0]].position.distance(bones[pair[1]].position);
+            starts.push(starts[starts.len() - 1] + distance);
+        }
+        let length = starts[starts.len() - 1] + lengths[end];
+        if length < size * MIN_LIMB_LENGTH {
+            continue;
+        }
+        hinges.extend(
+            chain
+                .iter()
+                .zip(&starts)
+                .skip(1)
+                .min_by(|(_, a), (_, b)| {
+                    (*a - length / 2.)
+                        .abs()
+                        .total_cmp(&(*b - length / 2.).abs())
+                })
+                .map(|(index, _)| *index),
+        );
+    }
+    hinges
+}
+
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/ragdoll/server",
+        vec![
+            query(ragdoll_from_url().changed()).to_system(|q, world, qs, _| {
+                let assets = world.resource(asset_cache()).clone();
+                let runtime = world.resource(runtime()).clone();
+                let async_run = world.resource(async_run()).clone();
+                for (id, url) in q.collect_cloned(world, qs) {
+                    let key = match AbsAssetUrl::from_str(&url) {
+                        Ok(url) => JsonFromUrl::<RagdollDef>::new(url, true),
+                        Err(err) => {
+                            tracing::warn!("Invalid ragdoll url {url}: {err:?}");
+                            continue;
+                        }
+                    };
+                    let assets = assets.clone();
+                    let async_run = async_run.clone();
+                    runtime.spawn(async move {
+                        let def = match key.get(&assets).await {
+                            Ok(def) => def,
+                            Err(err) => {
+                                tracing::warn!("Failed to load ragdoll from {url}: {err:?}");
+                                return;
+                            }
+                        };
+                        async_run.run(move |world| {
+                            if world.get_ref(id, ragdoll_from_url()).ok() != Some(&url) {
+                                return;
+                            }
+                            remove_ragdoll(world, id);
+                            world.add_component(id, ragdoll_def(), def).unwrap();
+                        });
+                    });
+                }
+            }),
+            query(ragdoll_from_url())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        if world.exists(id) {
+                            world.remove_component(id, ragdoll_def()).ok();
+                        }
+                    }
+                }),
+            query((ragdoll_def().changed(), ragdoll_blend().changed())).to_system(
+                |q, world, qs, _| {
+                    for (id, (def, blend)) in q.collect_cloned(world, qs) {
+                        let active = world.has_component(id, articulation_reduce_coordinate());
+                        if blend > 0. && !active {
+                            spawn_ragdoll(world, id, &def);
+                        } else if blend <= 0. && active {
+                            remove_ragdoll(world, id);
+                        }
+                    }
+                },
+            ),
+            query((ragdoll_def(), ragdoll_blend()))
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        if world.exists(id) {
+                            remove_ragdoll(world, id);
+                        }
+                    }
+                }),
+            query((articulation_reduce_coordinate(), ragdoll_bones()))
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, (articulation, bones)) in q.collect_cloned(world, qs) {
+                        // Ragdolls of entities that still exist are removed by `remove_ragdoll`
+                        if world.exists(id) {
+                            continue;
+                        }
+                        release_ragdoll(world, articulation, &bones);
+                    }
+                }),
+        ],
+    )
+}
+
+/// Builds the ragdoll of `id` in the pose that its animation player is in, or in its bind pose if
+/// it isn't animated.
+fn spawn_ragdoll(world: &mut World, id: EntityId, def: &RagdollDef) {
+    // Clears out the bones of a ragdoll whose articulation is gone (e.g. after forking)
+    remove_ragdoll(world, id);
+
+    let physics = world.resource(physics()).clone();
+    let scene = *world.resource(main_physics_scene());
+    let assets = world.resource(asset_cache()).clone();
+    let transform = get_world_transform(world, id).unwrap_or_default();
+    let (scale, _, _) = transform.to_scale_rotation_translation();
+    let size = scale.abs().max_element();
+    // The server has no skeleton, so the joints are posed from the animation player's output
+    let player = world.get(id, apply_animation_player()).ok();
+    let bone_poses = def.poses(transform, |bind_id, bind_pose| match player {
+        Some(player) => sample_joint_transform(world, player, bind_id, bind_pose),
+        None => bind_pose,
+    });
+    let material = match PxMaterialKey::from_entity(world, id) {
+        Some(key) => key.get(world),
+        None => PxWoodMaterialKey.get(&assets),
+    };
+    let density = world.get(id, density()).unwrap_or(1.);
+    let layer = world
+        .get(id, collision_layer())
+        .unwrap_or(DEFAULT_COLLISION_LAYER);
+    let mask = world
+        .get(id, collision_mask())
+        .unwrap_or(DEFAULT_COLLISION_MASK);
+    let filter_data = collision_filter_data(layer, mask, collision_group(id));
+    let query_filter_data = collision_query_filter_data(layer);
+    // Scene queries would hit the bones when moving the character controller, so it's used for
+    // them instead
+    let is_query_shape = !world.has_component(id, character_controller());
+
+    let articulation = PxArticulationRef::new(&physics.physics);
+    let mut links: Vec<PxArticulationLinkRef> = Vec::with_capacity(def.bones.len());
+    let mut poses: Vec<PxTransform> = Vec::with_capacity(def.bones.len());
+    let mut bone_ids = Vec::with_capacity(def.bones.len());
+    for (bone, bone_pose) in def.bones.iter().zip(bone_poses) {
+        let (_, bone_rotation, bone_position) = bone_pose.to_scale_rotation_translation();
+        let pose = PxTransform::new(bone_position, bone_rotation);
+        let link = PxArticulationLinkRef::new(
+            &articulation,
+            bone.parent.map(|parent| &links[parent]),
+            &pose,
+        );
+        let bone_id = Entity::new()
+            .with(articulation_link(), link)
+            .with(translation(), pose.translation())
+            .with(rotation(), pose.rotation())
+            .with(physics_controlled(), ())
+            .with(ragdoll_bone(), bone.bind_id.clone())
+            .spawn(world);
+
+        let geometry = PxCapsuleGeometry::new(bone.radius * size, bone.half_height * size);
+        let shape = PxShape::new(physics.physics, &geometry, &[&material], Some(true), None);
+        shape.set_local_pose(&PxTransform::new(
+            bone.shape_offset * size,
+            bone.shape_rotation,
+        ));
+        shape.set_user_data(PxShapeUserData {
+            entity: bone_id,
+            density,
+            base_pose: Mat4::from_rotation_translation(bone.shape_rotation, bone.shape_offset),
+        });
+        shape.set_simulation_filter_data(&filter_data);
+        shape.set_query_filter_data(&query_filter_data);
+        shape.set_flag(PxShapeFlag::SCENE_QUERY_SHAPE, is_query_shape);
+        shape.set_flag(PxShapeFlag::VISUALIZATION, false);
+        link.attach_shape(&shape);
+        link.update_mass_and_inertia(vec![density], None, None);
+
+        if let (Some(parent), Some(mut joint)) =
+            (bone.parent, PxArticulationJointRef::from_link(&link))
+        {
+            // The joint's twist axis runs along the capsule, and its limits are centered on the
+            // pose that the ragdoll is spawned in
+            let parent_pose = &poses[parent];
+            let frame = pose.rotation() * bone.joint_rotation;
+            let parent_inv = parent_pose.rotation().inverse();
+            joint.set_joint_type(PxArticulationJointType::Spherical);
+            joint.set_parent_pose(&PxTransform::new(
+                parent_inv * (pose.translation() - parent_pose.translation()),
+                parent_inv * frame,
+            ));
+            joint.set_child_pose(&PxTransform::from_rotation(bone.joint_rotation));
+            let limits = &bone.limits;
+            for (axis, (low, high)) in [
+                (PxArticulationAxis::Twist, (-limits.twist, limits.twist)),
+                (PxArticulationAxis::Swing1, limits.swing_y),
+                (PxArticulationAxis::Swing2, limits.swing_z),
+            ] {
+                joint.set_motion(axis, PxArticulationMotion::Limited);
+                joint.set_limit(axis, low, high);
+            }
+        }
+        links.push(link);
+        poses.push(pose);
+        bone_ids.push(bone_id);
+    }
+    articulation.set_solver_iteration_counts(16, 4);
+    scene.add_articulation(&articulation);
+    world
+        .add_components(
+            id,
+            Entity::new()
+                .with(articulation_reduce_coordinate(), articulation)
+                .with(ragdoll_bones(), bone_ids),
+        )
+        .unwrap();
+}
+
+/// Removes the ragdoll of `id`, if it has one.
+fn remove_ragdoll(world: &mut World, id: EntityId) {
+    let articulation = world.get(id, articulation_reduce_coordinate()).ok();
+    let bones = world.get_cloned(id, ragdoll_bones()).unwrap_or_default();
+    world
+        .remove_component(id, articulation_reduce_coordinate())
+        .ok();
+    world.remove_component(id, ragdoll_bones()).ok();
+    if let Some(articulation) = articulation {
+        release_ragdoll(world, articulation, &bones);
+    } else {
+        for bone in bones {
+            world.despawn(bone);
+        }
+    }
+}
+
+fn release_ragdoll(world: &mut World, mut articulation: PxArticulationRef, bones: &[EntityId]) {
+    let scene = *world.resource(main_physics_scene());
+    scene.remove_articulation(&articulation, true);
+    articulation.release();
+    for bone in bones {
+        world.despawn(*bone);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec3;
+
+    use super::*;
+
+    /// A skeleton from `(bind_id, parent, translation relative to the parent)`, whose roots are
+    /// below a node at `root`.
+    fn skeleton(root: Mat4, nodes: &[(&str, Option<usize>, Vec3)]) -> Vec<RagdollJoint> {
+        let mut joints: Vec<RagdollJoint> = Vec::new();
+        for &(bind_id, parent, position) in nodes {
+            let local_transform = Mat4::from_translation(position);
+            let parent_transform = parent.map_or(root, |parent| joints[parent].transform);
+            joints.push(RagdollJoint {
+                bind_id: bind_id.to_string(),
+                name: Some(bind_id.to_uppercase()),
+                parent,
+                transform: parent_transform * local_transform,
+                local_transform,
+            });
+        }
+        joints
+    }
+
+    fn humanoid() -> Vec<RagdollJoint> {
+        skeleton(
+            Mat4::from_translation(vec3(0., 0., 1.)),
+            &[
+                ("hips", None, vec3(0., 1., 0.)),
+                ("spine", Some(0), vec3(0., 0.5, 0.)),
+                ("head", Some(1), vec3(0., 0.5, 0.)),
+                ("leg_l", Some(0), vec3(-0.2, -0.5, 0.)),
+                ("leg_r", Some(0), vec3(0.2, -0.5, 0.)),
+                ("head_end", Some(2), vec3(0., 0.3, 0.)),
+                ("foot_l", Some(3), vec3(0., -0.5, 0.)),
+                ("foot_r", Some(4), vec3(0., -0.5, 0.)),
+                ("toe_l", Some(6), vec3(0., 0., 0.02)),
+            ],
+        )
+    }
+
+    /// The left half of a body, with a bent knee and a straight arm.
+    fn half_body() -> Vec<RagdollJoint> {
+        skeleton(
+            Mat4::IDENTITY,
+            &[
+                ("hips", None, vec3(0., 1., 0.)),
+                ("spine", Some(0), vec3(0., 0.3, 0.)),
+                ("chest", Some(1), vec3(0., 0.3, 0.)),
+                ("neck", Some(2), vec3(0., 0.2, 0.)),
+                ("head", Some(3), vec3(0., 0.1, 0.)),
+                ("head_end", Some(4), vec3(0., 0.2, 0.)),
+                ("arm_l", Some(2), vec3(-0.2, 0.1, 0.)),
+                ("forearm_l", Some(6), vec3(-0.3, 0., 0.)),
+                ("hand_l", Some(7), vec3(-0.25, 0., 0.)),
+                ("finger_l", Some(8), vec3(-0.15, 0., 0.)),
+                ("thigh_l", Some(0), vec3(-0.15, 0., 0.)),
+                ("shin_l", Some(10), vec3(0., -0.45, 0.02)),
+                ("foot_l", Some(11), vec3(0., -0.45, -0.02)),
+                ("toe_l", Some(12), vec3(0., -0.05, 0.15)),
+            ],
+        )
+    }
+
+    fn bone<'a>(def: &'a RagdollDef, bind_id: &str) -> &'a RagdollBone {
+        def.bones
+            .iter()
+            .find(|bone| bone.bind_id == bind_id)
+            .unwrap()
+    }
+
+    fn bones(bones: &[&str]) -> Vec<String> {
+        bones.iter().map(|bone| bone.to_string()).collect()
+    }
+
+    fn bind_ids_and_parents(def: &RagdollDef) -> Vec<(&str, Option<usize>)> {
+        def.bones
+            .iter()
+            .map(|bone| (bone.bind_id.as_str(), bone.parent))
+            .collect()
+    }
+
+    #[test]
+    fn automatic_bones_skip_extremities() {
+        let def =
+            RagdollDef::from_skeleton(&humanoid(), &[], 0.2, 0.5, 0.3, &HashMap::new()).unwrap();
+        assert_eq!(
+            bind_ids_and_parents(&def),
+            [
+                ("hips", None),
+                ("spine", Some(0)),
+                ("leg_l", Some(0)),
+                ("leg_r", Some(0)),
+                ("head", Some(1)),
+            ]
+        );
+        assert_eq!(def.bones[2].limits, RagdollJointLimits::ball(0.5, 0.3));
+    }
+
+    #[test]
+    fn explicit_bones_by_name_or_bind_id() {
+        let def = RagdollDef::from_skeleton(
+            &humanoid(),
+            &bones(&["head", "HIPS"]),
+            0.2,
+            0.5,
+            0.3,
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            bind_ids_and_parents(&def),
+            [("hips", None), ("head", Some(0))]
+        );
+        let chain = def.bones[1]
+            .chain
+            .iter()
+            .map(|joint| joint.bind_id.as_str());
+        assert_eq!(chain.collect_vec(), ["spine", "head"]);
+    }
+
+    #[test]
+    fn unknown_bones_are_an_error() {
+        let err = RagdollDef::from_skeleton(
+            &humanoid(),
+            &bones(&["hips", "tail"]),
+            0.2,
+            0.5,
+            0.3,
+            &HashMap::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("\"tail\""), "{err}");
+    }
+
+    #[test]
+    fn explicit_bones_need_a_single_hierarchy() {
+        let err = RagdollDef::from_skeleton(
+            &humanoid(),
+            &bones(&["leg_l", "head"]),
+            0.2,
+            0.5,
+            0.3,
+            &HashMap::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("single hierarchy"), "{err}");
+    }
+
+    #[test]
+    fn bone_count_is_limited() {
+        let nodes = (0..70usize)
+            .map(|i| ("bone", i.checked_sub(1), vec3(0., 1., 0.)))
+            .collect_vec();
+        let mut joints = skeleton(Mat4::IDENTITY, &nodes);
+        for (i, joint) in joints.iter_mut().enumerate() {
+            joint.bind_id = format!("bone{i}");
+        }
+        let def = RagdollDef::from_skeleton(&joints, &[], 0.2, 0.5, 0.3, &HashMap::new()).unwrap();
+        assert_eq!(def.bones.len(), MAX_RAGDOLL_BONES);
+
+        let all = joints
+            .iter()
+            .map(|joint| joint.bind_id.clone())
+            .collect_vec();
+        assert!(RagdollDef::from_skeleton(&joints, &all, 0.2, 0.5, 0.3, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn capsules_point_towards_children() {
+        let def = RagdollDef::from_skeleton(
+            &humanoid(),
+            &bones(&["spine", "head", "head_end"]),
+            0.2,
+            0.5,
+            0.3,
+            &HashMap::new(),
+        )
+        .unwrap();
+        let spine = &def.bones[0];
+        assert!(spine.shape_offset.abs_diff_eq(vec3(0., 0.25, 0.), 1e-5));
+        assert!((spine.shape_rotation * Vec3::X).abs_diff_eq(Vec3::Y, 1e-5));
+        assert!((spine.radius - 0.1).abs() < 1e-5);
+        assert!((spine.half_height - 0.15).abs() < 1e-5);
+
+        // Leaves continue in the direction of their parent bone, at half its length
+        let head_end = &def.bones[2];
+        assert!(head_end.shape_offset.abs_diff_eq(vec3(0., 0.075, 0.), 1e-5));
+        assert!((head_end.shape_rotation * Vec3::X).abs_diff_eq(Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn knees_and_elbows_are_hinges() {
+        let def =
+            RagdollDef::from_skeleton(&half_body(), &[], 0.2, 0.5, 0.3, &HashMap::new()).unwrap();
+        let ball = RagdollJointLimits::ball(0.5, 0.3);
+        for bind_id in [
+            "spine", "chest", "neck", "head", "arm_l", "hand_l", "thigh_l", "foot_l",
+        ] {
+            assert_eq!(bone(&def, bind_id).limits, ball, "{bind_id}");
+        }
+
+        // The knee bends the way it's bent in the bind pose
+        let shin = bone(&def, "shin_l");
+        let shin_direction = vec3(0., -0.45, -0.02).normalize();
+        assert!((shin.joint_rotation * Vec3::X).abs_diff_eq(shin_direction, 1e-5));
+        assert!((shin.joint_rotation * Vec3::Z).abs_diff_eq(Vec3::X, 1e-5));
+        assert_eq!(
+            shin.limits.swing_z,
+            (-HINGE_HYPEREXTENSION, HINGE_BEND_LIMIT)
+        );
+        assert_eq!(shin.limits.swing_y, (-HINGE_SIDE_LIMIT, HINGE_SIDE_LIMIT));
+        assert_eq!(shin.limits.twist, HINGE_TWIST_LIMIT);
+
+        // The straight elbow bends both ways, around the axis pointing away from the hips
+        let forearm = bone(&def, "forearm_l");
+        assert!((forearm.joint_rotation * Vec3::X).abs_diff_eq(-Vec3::X, 1e-5));
+        assert!((forearm.joint_rotation * Vec3::Z).abs_diff_eq(Vec3::Y, 1e-5));
+        assert_eq!(
+            forearm.limits.swing_z,
+            (-HINGE_BEND_LIMIT, HINGE_BEND_LIMIT)
+        );
+    }
+
+    #[test]
+    fn bone_limits_can_be_overridden() {
+        let bone_limits = HashMap::from([
+            (
+                "forearm_l".to_string(),
+                RagdollBoneLimits {
+                    hinge: Some(false),
+                    swing_limit: Some(1.0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "SHIN_L".to_string(),
+                RagdollBoneLimits {
+                    swing_limit: Some(2.0),
+                    twist_limit: Some(0.1),
+                    ..Default::default()
+                },
+            ),
+            (
+                "neck".to_string(),
+                RagdollBoneLimits {
+                    hinge: Some(true),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let def =
+            RagdollDef::from_skeleton(&half_body(), &[], 0.2, 0.5, 0.3, &bone_limits).unwrap();
+        let forearm = bone(&def, "forearm_l");
+        assert_eq!(forearm.limits, RagdollJointLimits::ball(1.0, 0.3));
+        assert_eq!(forearm.joint_rotation, forearm.shape_rotation);
+        let shin = bone(&def, "shin_l").limits;
+        assert_eq!(
+            (shin.swing_z, shin.twist),
+            ((-HINGE_HYPEREXTENSION, 2.0), 0.1)
+        );
+        assert_eq!(
+            bone(&def, "neck").limits.swing_y,
+            (-HINGE_SIDE_LIMIT, HINGE_SIDE_LIMIT)
+        );
+
+        let unknown = HashMap::from([("tail".to_string(), RagdollBoneLimits::default())]);
+        let err =
+            RagdollDef::from_skeleton(&half_body(), &[], 0.2, 0.5, 0.3, &unknown).unwrap_err();
+        assert!(err.to_string().contains("\"tail\""), "{err}");
+
+        let skipped = HashMap::from([("finger_l".to_string(), RagdollBoneLimits::default())]);
+        let err =
+            RagdollDef::from_skeleton(&half_body(), &[], 0.2, 0.5, 0.3, &skipped).unwrap_err();
+        assert!(
+            err.to_string().contains("isn't one of the ragdoll's bones"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn a_lone_bone_gets_a_small_sphere() {
+        let joints = skeleton(Mat4::IDENTITY, &[("root", None, Vec3::ZERO)]);
+        let def = RagdollDef::from_skeleton(&joints, &[], 0.2, 0.5, 0.3, &HashMap::new()).unwrap();
+        assert_eq!(def.bones.len(), 1);
+        assert_eq!((def.bones[0].radius, def.bones[0].half_height), (0.05, 0.));
+    }
+
+    #[test]
+    fn poses_follow_the_animated_joints() {
+        let joints = humanoid();
+        let def = RagdollDef::from_skeleton(&joints, &[], 0.2, 0.5, 0.3, &HashMap::new()).unwrap();
+        let transform = Mat4::from_translation(vec3(10., 0., 0.));
+
+        let bind_poses = def.poses(transform, |_, bind_pose| bind_pose);
+        for (bone, pose) in def.bones.iter().zip(bind_poses) {
+            let joint = joints.iter().find(|j| j.bind_id == bone.bind_id).unwrap();
+            assert!(pose.abs_diff_eq(transform * joint.transform, 1e-5));
+        }
+
+        let bend = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        let poses = def.poses(transform, |bind_id, bind_pose| match bind_id {
+            "spine" => bind_pose * Mat4::from_quat(bend),
+            _ => bind_pose,
+        });
+        let (_, head_rotation, head_position) = poses[4].to_scale_rotation_translation();
+        assert!(head_position.abs_diff_eq(vec3(9.5, 1.5, 1.), 1e-5));
+        assert!(head_rotation.abs_diff_eq(bend, 1e-5));
+        // Bones that aren't below the bent joint stay where they were
+        assert!(poses[2].abs_diff_eq(transform * joints[3].transform, 1e-5));
+    }
+}
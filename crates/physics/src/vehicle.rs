@@ -0,0 +1,432 @@
+use ambient_core::{
+    transform::{rotation, translation},
+    FIXED_SERVER_TICK_TIME,
+};
+use ambient_ecs::{components, query, Entity, EntityId, SystemGroup, World};
+use glam::{vec2, Quat, Vec2, Vec3};
+use physxx::{
+    AsPxRigidActor, PxForceMode, PxQueryFlag, PxRaycastCallback, PxRigidActor, PxRigidBody,
+    PxRigidBodyFlag, PxRigidDynamicRef, PxUserData,
+};
+
+use crate::{
+    intersection::mask_filter_data, main_physics_scene, physx::rigid_dynamic, PxShapeUserData,
+};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+components!("physics", {
+    /// How far the wheel has rolled around its axle, in radians.
+    wheel_spin: f32,
+});
+
+/// The top speed of vehicles without a `vehicle_max_speed`.
+pub const DEFAULT_MAX_SPEED: f32 = 50.;
+const SOLVER_ITERATIONS: usize = 8;
+
+/// The definition of a wheel (see the `VehicleWheel` concept).
+#[derive(Debug, Clone)]
+pub struct Wheel {
+    pub offset: Vec3,
+    pub radius: f32,
+    pub suspension_length: f32,
+    pub suspension_stiffness: f32,
+    pub suspension_damping: f32,
+    pub friction: f32,
+    pub sliding_friction: f32,
+    pub peak_slip: f32,
+    pub max_steering_angle: f32,
+    pub drive_torque: f32,
+    pub brake_torque: f32,
+    pub inertia: f32,
+}
+
+impl Wheel {
+    pub fn from_entity(world: &World, id: EntityId) -> Option<Self> {
+        Some(Self {
+            offset: world.get(id, wheel_offset()).ok()?,
+            radius: world.get(id, wheel_radius()).ok()?,
+            suspension_length: world.get(id, wheel_suspension_length()).ok()?,
+            suspension_stiffness: world.get(id, wheel_suspension_stiffness()).ok()?,
+            suspension_damping: world.get(id, wheel_suspension_damping()).ok()?,
+            friction: world.get(id, wheel_friction()).ok()?,
+            sliding_friction: world.get(id, wheel_sliding_friction()).ok()?,
+            peak_slip: world.get(id, wheel_peak_slip()).ok()?,
+            max_steering_angle: world.get(id, wheel_max_steering_angle()).ok()?,
+            drive_torque: world.get(id, wheel_drive_torque()).ok()?,
+            brake_torque: world.get(id, wheel_brake_torque()).ok()?,
+            inertia: world.get(id, wheel_inertia()).ok()?.max(0.01),
+        })
+    }
+
+    /// The extension of the suspension when the ground is `distance` below the wheel's mount, and
+    /// the load that it puts on the tire while the vehicle moves along the ground's normal at
+    /// `normal_velocity`. The suspension only pushes, so the load is never negative.
+    pub fn suspension(&self, distance: f32, normal_velocity: f32) -> (f32, f32) {
+        let extension = (distance - self.radius).clamp(0., self.suspension_length);
+        let compression = self.suspension_length - extension;
+        let load = (self.suspension_stiffness * compression
+            - self.suspension_damping * normal_velocity)
+            .max(0.);
+        (extension, load)
+    }
+
+    /// The tire friction curve: full grip while the tire slides slower than `peak_slip`, which
+    /// falls off to the sliding friction at twice that.
+    pub fn friction_at(&self, slip: f32) -> f32 {
+        let peak_slip = self.peak_slip.max(0.001);
+        let t = ((slip - peak_slip) / peak_slip).clamp(0., 1.);
+        self.friction + (self.sliding_friction - self.friction) * t
+    }
+
+    /// Solves the contact between the tire and the ground for one step, with the vehicle moving
+    /// at `velocity` relative to the ground, as `(longitudinal, lateral)`.
+    ///
+    /// `mass` is the part of the vehicle's mass that rests on the wheel, and the tire and the brake
+    /// can apply at most `max_impulse` and `max_brake_impulse` respectively.
+    pub fn solve(
+        &self,
+        angular_velocity: f32,
+        velocity: Vec2,
+        mass: f32,
+        max_impulse: f32,
+        max_brake_impulse: f32,
+    ) -> WheelSolution {
+        // The mass that resists a change in slip, which is shared between the vehicle and the
+        // spin of the wheel
+        let slip_mass = 1. / (1. / mass + self.radius.powi(2) / self.inertia);
+        let mut angular_velocity = angular_velocity;
+        let mut velocity = velocity;
+        let mut impulse = Vec2::ZERO;
+        let mut brake_impulse = 0.;
+        // The tire and the brake affect each other, so they are solved together by iterating
+        for _ in 0..SOLVER_ITERATIONS {
+            let slip = vec2(angular_velocity * self.radius - velocity.x, velocity.y);
+            let total =
+                (impulse + vec2(slip.x * slip_mass, -slip.y * mass)).clamp_length_max(max_impulse);
+            let delta = total - impulse;
+            impulse = total;
+            angular_velocity -= delta.x * self.radius / self.inertia;
+            velocity += delta / mass;
+
+            let total = (brake_impulse - angular_velocity * self.inertia)
+                .clamp(-max_brake_impulse, max_brake_impulse);
+            angular_velocity += (total - brake_impulse) / self.inertia;
+            brake_impulse = total;
+        }
+        WheelSolution {
+            impulse,
+            angular_velocity,
+            slip: vec2(angular_velocity * self.radius - velocity.x, velocity.y),
+        }
+    }
+}
+
+/// The result of [`Wheel::solve`].
+#[derive(Debug, Clone, Copy)]
+pub struct WheelSolution {
+    /// The impulse that the ground applies to the vehicle, as `(longitudinal, lateral)`.
+    pub impulse: Vec2,
+    pub angular_velocity: f32,
+    /// How fast the tire is still sliding over the ground (see `wheel_slip`).
+    pub slip: Vec2,
+}
+
+struct WheelContact {
+    entity: Option<EntityId>,
+    ground: Option<PxRigidDynamicRef>,
+    point: Vec3,
+    normal: Vec3,
+    distance: f32,
+}
+
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/vehicle/server",
+        vec![
+            query((vehicle_wheels(), rigid_dynamic())).to_system(|q, world, qs, _| {
+                profiling::scope!("update_vehicles");
+                for (id, (wheels, body)) in q.collect_cloned(world, qs) {
+                    update_vehicle(world, id, body, &wheels);
+                }
+            }),
+        ],
+    )
+}
+
+/// Simulates the suspension and tires of a vehicle for one physics step, and applies their forces
+/// to it.
+fn update_vehicle(
+    world: &mut World,
+    id: EntityId,
+    body: PxRigidDynamicRef,
+    wheel_ids: &[EntityId],
+) {
+    if body
+        .get_rigid_body_flags()
+        .contains(PxRigidBodyFlag::KINEMATIC)
+    {
+        return;
+    }
+    let dt = FIXED_SERVER_TICK_TIME.as_secs_f32();
+    let throttle = world
+        .get(id, vehicle_throttle())
+        .unwrap_or(0.)
+        .clamp(-1., 1.);
+    let brake = world.get(id, vehicle_brake()).unwrap_or(0.).clamp(0., 1.);
+    let steering = world
+        .get(id, vehicle_steering())
+        .unwrap_or(0.)
+        .clamp(-1., 1.);
+    let max_speed = world
+        .get(id, vehicle_max_speed())
+        .unwrap_or(DEFAULT_MAX_SPEED)
+        .max(0.001);
+    let mask = world.get(id, collision_mask()).ok();
+
+    let pose = body.get_global_pose();
+    let (position, orientation) = (pose.translation(), pose.rotation());
+    let up = orientation * Vec3::Z;
+
+    let wheels = wheel_ids
+        .iter()
+        .filter_map(|&wheel_id| Some((wheel_id, Wheel::from_entity(world, wheel_id)?)))
+        .collect::<Vec<_>>();
+    let contacts = wheels
+        .iter()
+        .map(|(_, wheel)| {
+            let mount = position + orientation * wheel.offset;
+            raycast_wheel(world, body, mount, -up, wheel, mask)
+        })
+        .collect::<Vec<_>>();
+    // The vehicle's weight is shared between the wheels on the ground
+    let wheel_mass = body.get_mass() / contacts.iter().flatten().count().max(1) as f32;
+
+    for ((wheel_id, wheel), contact) in wheels.into_iter().zip(contacts) {
+        let steering_angle = steering * wheel.max_steering_angle;
+        let wheel_rotation = orientation * Quat::from_rotation_z(steering_angle);
+        let mount = position + orientation * wheel.offset;
+
+        let mut angular_velocity = world.get(wheel_id, wheel_angular_velocity()).unwrap_or(0.);
+        let speed = angular_velocity * wheel.radius * throttle.signum();
+        let drive_torque = throttle * wheel.drive_torque * (1. - speed / max_speed).clamp(0., 1.);
+        angular_velocity += drive_torque / wheel.inertia * dt;
+        let max_brake_impulse = brake * wheel.brake_torque * dt;
+
+        let mut extension = wheel.suspension_length;
+        let mut slip = Vec2::ZERO;
+        if let Some(contact) = &contact {
+            let ground_velocity = contact
+                .ground
+                .map(|ground| ground.get_velocity_at_pos(contact.point))
+                .unwrap_or_default();
+            let velocity = body.get_velocity_at_pos(contact.point) - ground_velocity;
+            let (contact_extension, load) =
+                wheel.suspension(contact.distance, velocity.dot(contact.normal));
+            extension = contact_extension;
+
+            let forward = wheel_rotation * -Vec3::Y;
+            let forward =
+                (forward - contact.normal * forward.dot(contact.normal)).normalize_or_zero();
+            let side = contact.normal.cross(forward);
+            // The tire only loses grip once it's sliding, so the friction depends on the last slip
+            let last_slip = world.get(wheel_id, wheel_slip()).unwrap_or_default();
+            let solution = wheel.solve(
+                angular_velocity,
+                vec2(velocity.dot(forward), velocity.dot(side)),
+                wheel_mass,
+                wheel.friction_at(last_slip.length()) * load * dt,
+                max_brake_impulse,
+            );
+            angular_velocity = solution.angular_velocity;
+            slip = solution.slip;
+
+            let force = contact.normal * load
+                + (forward * solution.impulse.x + side * solution.impulse.y) / dt;
+            body.add_force_at_pos(force, contact.point, Some(PxForceMode::Force), Some(true));
+            if let Some(ground) = contact.ground {
+                if !ground
+                    .get_rigid_body_flags()
+                    .contains(PxRigidBodyFlag::KINEMATIC)
+                {
+                    ground.add_force_at_pos(
+                        -force,
+                        contact.point,
+                        Some(PxForceMode::Force),
+                        Some(true),
+                    );
+                }
+            }
+        } else {
+            angular_velocity = angular_velocity.signum()
+                * (angular_velocity.abs() - max_brake_impulse / wheel.inertia).max(0.);
+        }
+
+        let spin = world.get(wheel_id, wheel_spin()).unwrap_or(0.) + angular_velocity * dt;
+        let spin = spin % std::f32::consts::TAU;
+        let mut data = Entity::new()
+            .with(wheel_spin(), spin)
+            .with(wheel_angular_velocity(), angular_velocity)
+            .with(wheel_steering_angle(), steering_angle)
+            .with(
+                wheel_suspension_compression(),
+                wheel.suspension_length - extension,
+            )
+            .with(wheel_slip(), slip)
+            .with(wheel_in_contact(), contact.is_some())
+            .with(translation(), mount - up * extension)
+            .with(rotation(), wheel_rotation * Quat::from_rotation_x(spin));
+        let mut removed = Vec::new();
+        match contact {
+            Some(contact) => {
+                data.set(wheel_contact_point(), contact.point);
+                data.set(wheel_contact_normal(), contact.normal);
+                match contact.entity {
+                    Some(entity) => data.set(wheel_contact_entity(), entity),
+                    None => removed.push(wheel_contact_entity().desc()),
+                }
+            }
+            None => removed.extend([
+                wheel_contact_point().desc(),
+                wheel_contact_normal().desc(),
+                wheel_contact_entity().desc(),
+            ]),
+        }
+        world.add_components(wheel_id, data).ok();
+        world.remove_components(wheel_id, removed).ok();
+    }
+}
+
+/// Finds the ground under a wheel, ignoring the vehicle itself.
+fn raycast_wheel(
+    world: &World,
+    body: PxRigidDynamicRef,
+    mount: Vec3,
+    down: Vec3,
+    wheel: &Wheel,
+    mask: Option<u32>,
+) -> Option<WheelContact> {
+    let mut filter_data = mask_filter_data(mask)?;
+    filter_data.set_flags(PxQueryFlag::STATIC | PxQueryFlag::DYNAMIC | PxQueryFlag::NO_BLOCK);
+    let mut hits = PxRaycastCallback::new(16);
+    let scene = world.resource(main_physics_scene());
+    let length = wheel.suspension_length + wheel.radius;
+    if !scene.raycast(mount, down, length, &mut hits, None, &filter_data) {
+        return None;
+    }
+    let vehicle = body.as_rigid_actor();
+    let hit = hits
+        .touches()
+        .into_iter()
+        .filter(|hit| hit.actor != Some(vehicle))
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))?;
+    Some(WheelContact {
+        entity: hit
+            .shape
+            .as_ref()
+            .and_then(|shape| shape.get_user_data::<PxShapeUserData>())
+            .map(|ud| ud.entity),
+        ground: hit.actor.and_then(|actor| actor.to_rigid_dynamic()),
+        point: hit.position,
+        normal: hit.normal,
+        distance: hit.distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wheel() -> Wheel {
+        Wheel {
+            offset: Vec3::ZERO,
+            radius: 0.5,
+            suspension_length: 0.3,
+            suspension_stiffness: 1000.,
+            suspension_damping: 100.,
+            friction: 1.,
+            sliding_friction: 0.6,
+            peak_slip: 0.5,
+            max_steering_angle: 0.,
+            drive_torque: 0.,
+            brake_torque: 0.,
+            inertia: 1.,
+        }
+    }
+
+    #[test]
+    fn suspension_compression_is_limited() {
+        let wheel = wheel();
+        // The ground is inside the wheel, so the suspension is fully compressed
+        assert_eq!(wheel.suspension(0.2, 0.), (0., 300.));
+        let (extension, load) = wheel.suspension(0.6, 0.);
+        assert!((extension - 0.1).abs() < 1e-5);
+        assert!((load - 200.).abs() < 1e-3);
+        // Out of reach, the suspension is fully extended
+        assert_eq!(wheel.suspension(2., 0.), (0.3, 0.));
+    }
+
+    #[test]
+    fn suspension_never_pulls() {
+        let wheel = wheel();
+        // Moving into the ground is damped
+        let (_, load) = wheel.suspension(0.7, -1.);
+        assert!((load - 200.).abs() < 1e-3);
+        // Moving away from it quickly would need a negative load
+        assert_eq!(wheel.suspension(0.7, 10.).1, 0.);
+        assert_eq!(wheel.suspension(0.8, 1.).1, 0.);
+    }
+
+    #[test]
+    fn no_load_means_no_grip() {
+        let wheel = wheel();
+        let solution = wheel.solve(4., vec2(1., 0.5), 100., 0., 0.);
+        assert_eq!(solution.impulse, Vec2::ZERO);
+        assert_eq!(solution.angular_velocity, 4.);
+        assert!(solution.slip.abs_diff_eq(vec2(1., 0.5), 1e-5));
+    }
+
+    #[test]
+    fn friction_falls_off_past_the_peak_slip() {
+        let mut wheel = wheel();
+        assert_eq!(wheel.friction_at(0.), 1.);
+        assert_eq!(wheel.friction_at(0.5), 1.);
+        assert!((wheel.friction_at(0.75) - 0.8).abs() < 1e-5);
+        assert_eq!(wheel.friction_at(1.), 0.6);
+        assert_eq!(wheel.friction_at(100.), 0.6);
+
+        wheel.peak_slip = 0.;
+        assert_eq!(wheel.friction_at(0.), 1.);
+        assert_eq!(wheel.friction_at(1.), 0.6);
+    }
+
+    #[test]
+    fn tires_grip_within_the_max_impulse() {
+        let wheel = wheel();
+        let solution = wheel.solve(0., vec2(0.1, 0.1), 100., 1000., 0.);
+        assert!(solution.slip.length() < 1e-3, "{solution:?}");
+        assert!(solution.impulse.length() < 1000.);
+    }
+
+    #[test]
+    fn tire_impulses_saturate() {
+        let wheel = wheel();
+        let solution = wheel.solve(0., vec2(10., 10.), 100., 50., 0.);
+        assert!(
+            (solution.impulse.length() - 50.).abs() < 1e-3,
+            "{solution:?}"
+        );
+        // The ground pushes back against the sliding
+        assert!(solution.impulse.x < 0. && solution.impulse.y < 0.);
+        assert!(solution.slip.length() > 1., "{solution:?}");
+    }
+
+    #[test]
+    fn brakes_stop_the_wheel() {
+        let wheel = wheel();
+        let solution = wheel.solve(10., Vec2::ZERO, 100., 0., 100.);
+        assert!(solution.angular_velocity.abs() < 1e-5);
+        let solution = wheel.solve(10., Vec2::ZERO, 100., 0., 4.);
+        assert!((solution.angular_velocity - 6.).abs() < 1e-5);
+    }
+}
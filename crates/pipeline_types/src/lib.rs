@@ -1,7 +1,9 @@
 pub mod audio;
+pub mod data;
 pub mod materials;
 pub mod models;
 pub use audio::AudioPipeline;
+pub use data::DataPipeline;
 pub use materials::{MaterialsImporter, MaterialsPipeline, PipelinePbrMaterial};
 pub use models::{Collider, ModelImporter, ModelsPipeline};
 use serde::{Deserialize, Serialize};
@@ -53,6 +55,9 @@ pub enum PipelineProcessor {
     /// The audio asset pipeline.
     /// Will import supported audio file formats and produce Ogg Vorbis or WAV files to be used by the runtime.
     Audio(AudioPipeline),
+    /// The data asset pipeline.
+    /// Will copy the source files as-is, so that they can be read at runtime (e.g. JSON or TOML data files).
+    Data(DataPipeline),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{is_default, is_false, is_true, materials::PipelinePbrMaterial, true_value};
@@ -23,6 +25,16 @@ pub struct ModelsPipeline {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_default")]
     pub collider_type: ColliderType,
+    /// If a collider is present, the friction and restitution to use for it.
+    /// The default physics material is used for anything that isn't specified.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider_material: Option<ColliderMaterial>,
+    /// If specified, a ragdoll is generated from the model's skeleton, with a capsule per bone.
+    /// Use the `ragdoll_blend` component to switch between the animated and the simulated pose.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ragdoll: Option<Ragdoll>,
     /// Whether or not this mesh should have its texture sizes capped.
     pub cap_texture_sizes: Option<ModelTextureSize>,
     /// Treats all assets in the pipeline as variations, and outputs a single asset which is a collection of all assets.
@@ -148,6 +160,90 @@ pub enum ColliderType {
     Picking,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ColliderMaterial {
+    /// The friction coefficient of the collider when it is at rest against another collider.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_friction: Option<f32>,
+    /// The friction coefficient of the collider when it is sliding against another collider.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_friction: Option<f32>,
+    /// The bounciness of the collider, from 0 (no bounce) to 1 (a perfectly elastic bounce).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restitution: Option<f32>,
+    /// How the friction of the collider is combined with the friction of the collider it touches.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friction_combine_mode: Option<CombineMode>,
+    /// How the restitution of the collider is combined with the restitution of the collider it touches.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restitution_combine_mode: Option<CombineMode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Ragdoll {
+    /// The names of the bones to simulate. Bones in between them follow their closest simulated parent.
+    /// If empty, all the bones of the model's skeleton are used, except for small ones like fingers and toes.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bones: Vec<String>,
+    /// The radius of each bone's capsule, relative to the length of the bone. Defaults to 0.25.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius: Option<f32>,
+    /// The maximum angle (in degrees) that a bone can swing away from its bind pose. Defaults to 45.
+    /// Hinge-like joints, such as knees and elbows, bend up to 137.5 degrees instead.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swing_limit: Option<f32>,
+    /// The maximum angle (in degrees) that a bone can twist around itself from its bind pose. Defaults to 30.
+    /// Hinge-like joints twist up to 8.6 degrees instead.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twist_limit: Option<f32>,
+    /// Overrides the joint limits of individual bones, by their name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub bone_limits: HashMap<String, RagdollBoneLimits>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RagdollBoneLimits {
+    /// Whether the joint to the parent bone bends around a single axis, like a knee or an elbow.
+    /// By default, the joint in the middle of each limb (e.g. an arm or a leg) does.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hinge: Option<bool>,
+    /// The maximum angle (in degrees) that a hinge-like joint bends, or that the bone swings away otherwise.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swing_limit: Option<f32>,
+    /// The maximum angle (in degrees) that the bone twists around itself.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twist_limit: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CombineMode {
+    #[default]
+    /// Use the average of the two values.
+    Average,
+    /// Use the smaller of the two values.
+    Min,
+    /// Multiply the two values.
+    Multiply,
+    /// Use the larger of the two values.
+    Max,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum MaterialFilter {
@@ -1,63 +1,207 @@
 use std::{collections::HashMap, str::FromStr, sync::Arc};
 
-use ambient_core::{asset_cache, async_ecs::async_run, hierarchy::children, runtime};
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    hierarchy::{add_child, children, despawn_recursive, parent},
+    runtime,
+};
 use ambient_decals::decal;
-use ambient_ecs::{query, query_mut, DeserWorldWithWarnings, EntityId, SystemGroup, World};
+use ambient_ecs::{
+    generated::physics::components::ragdoll_from_url, query, query_mut, DeserWorldWithWarnings,
+    Entity, EntityId, SystemGroup, World,
+};
 use ambient_model::model_from_url;
 use ambient_native_std::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt},
     asset_url::AssetUrl,
     download_asset::{AssetError, BytesFromUrl},
-    unwrap_log_err,
+    log_result, unwrap_log_err,
 };
 use anyhow::Context;
 use async_trait::async_trait;
 
-pub use ambient_ecs::generated::prefab::components::{prefab_from_url, spawned};
+pub use ambient_ecs::generated::prefab::components::{
+    prefab_from_url, prefab_overrides, prefab_root, spawned,
+};
+
+/// Prefabs may contain other prefabs; this bounds the nesting depth so that a prefab which
+/// (indirectly) references itself does not spawn forever.
+const MAX_PREFAB_DEPTH: usize = 16;
 
 pub fn systems() -> SystemGroup {
     SystemGroup::new(
         "prefab",
-        vec![query(prefab_from_url())
-            .spawned()
-            .to_system(|q, world, qs, _| {
-                let mut to_load = HashMap::<String, Vec<EntityId>>::new();
-                for (id, url) in q.collect_cloned(world, qs) {
-                    let url = if url.ends_with("/prefabs/main.json") {
-                        url
-                    } else {
-                        format!("{url}/prefabs/main.json")
-                    };
-                    to_load.entry(url).or_default().push(id);
-                }
-                for (url, ids) in to_load {
-                    let assets = world.resource(asset_cache()).clone();
-                    let url = unwrap_log_err!(AssetUrl::from_str(&url));
-                    let url = PrefabFromUrl(url);
-                    let runtime = world.resource(runtime()).clone();
-                    let async_run = world.resource(async_run()).clone();
-                    runtime.spawn(async move {
-                        let obj = unwrap_log_err!(url.get(&assets).await);
-                        let base_ent_id = obj.resource(children())[0];
-                        // TODO: This only handles prefabs with a single entity
-                        let entity = obj.clone_entity(base_ent_id).unwrap();
-                        async_run.run(move |world| {
-                            for id in ids {
-                                if !world.exists(id) {
-                                    // https://github.com/AmbientRun/Ambient/issues/588
-                                    continue;
-                                }
+        vec![
+            // Changing an instance's overrides re-applies the prefab, so that components that
+            // are no longer overridden are restored from it
+            query(prefab_from_url().changed())
+                .optional_changed(prefab_overrides())
+                .to_system(|q, world, qs, _| {
+                    let mut to_load = HashMap::<String, Vec<(EntityId, String)>>::new();
+                    for (id, url) in q.collect_cloned(world, qs) {
+                        let full_url = if url.ends_with("/prefabs/main.json") {
+                            url.clone()
+                        } else {
+                            format!("{url}/prefabs/main.json")
+                        };
+                        to_load.entry(full_url).or_default().push((id, url));
+                    }
+                    for (url, ids) in to_load {
+                        let assets = world.resource(asset_cache()).clone();
+                        let url = unwrap_log_err!(AssetUrl::from_str(&url));
+                        let url = PrefabFromUrl(url);
+                        let runtime = world.resource(runtime()).clone();
+                        let async_run = world.resource(async_run()).clone();
+                        runtime.spawn(async move {
+                            let obj = unwrap_log_err!(url.get(&assets).await);
+                            async_run.run(move |world| {
+                                for (id, url) in ids {
+                                    // The entity may have been despawned, or pointed at another
+                                    // prefab, while this one was loading
+                                    if world.get_ref(id, prefab_from_url()) != Ok(&url) {
+                                        continue;
+                                    }
 
-                                world.add_components(id, entity.clone()).unwrap();
-                                world.add_component(id, spawned(), ()).unwrap();
-                            }
+                                    log_result!(instantiate_prefab(world, id, &obj));
+                                    world.add_component(id, spawned(), ()).unwrap();
+                                }
+                            });
                         });
-                    });
-                }
-            })],
+                    }
+                }),
+        ],
     )
 }
 
+/// Re-applies the source prefab to every prefab instance in the world, so that edits to the
+/// prefab reach existing instances. Components listed in an instance's `prefab_overrides` keep
+/// their values.
+///
+/// Loaded prefabs are cached, so changed prefabs must be evicted from the asset cache first.
+pub fn reload_prefabs(world: &mut World) {
+    // Nested instances are spawned again when their root is re-applied
+    let instances = query(prefab_from_url())
+        .excl(prefab_root())
+        .collect_cloned(world, None);
+    for (id, url) in instances {
+        // Setting the URL, even to the same value, will reload the prefab
+        world.set(id, prefab_from_url(), url).unwrap();
+    }
+}
+
+/// Applies `prefab` to the existing entity `id`.
+///
+/// The first root entity of the prefab is merged into `id`, except for the components listed in
+/// `prefab_overrides`. The rest of the prefab (the root's descendants, and any additional roots)
+/// is spawned as new entities beneath `id`, and marked with `prefab_root` so that they can be
+/// replaced the next time the prefab is applied. Nested prefabs are spawned as entities with
+/// their own `prefab_from_url`, and will be loaded by the prefab system in turn.
+pub fn instantiate_prefab(world: &mut World, id: EntityId, prefab: &World) -> anyhow::Result<()> {
+    if prefab_depth(world, id) >= MAX_PREFAB_DEPTH {
+        anyhow::bail!(
+            "Prefab instance {id} is nested more than {MAX_PREFAB_DEPTH} levels deep; does the prefab reference itself?"
+        );
+    }
+    despawn_prefab_entities(world, id);
+
+    let roots = prefab.resource_opt(children()).cloned().unwrap_or_default();
+    let Some(&base_ent_id) = roots.first() else {
+        anyhow::bail!("Prefab for {id} has no root entities");
+    };
+
+    // Allocate ids for the rest of the prefab up front, so that references between its
+    // entities can be remapped before they are spawned
+    let mut old_to_new_ids = HashMap::from([(base_ent_id, id)]);
+    let mut to_spawn = Vec::new();
+    for (old_id, entity) in prefab.entities() {
+        if old_id == prefab.resource_entity() || old_id == base_ent_id {
+            continue;
+        }
+        let new_id = EntityId::new();
+        old_to_new_ids.insert(old_id, new_id);
+        to_spawn.push((new_id, entity));
+    }
+
+    let overrides = world
+        .get_ref(id, prefab_overrides())
+        .cloned()
+        .unwrap_or_default();
+    let mut base = prefab.clone_entity(base_ent_id)?;
+    // The instance keeps its own source and overrides, and its children are managed below
+    base.remove_self(prefab_from_url());
+    base.remove_self(prefab_overrides());
+    base.remove_self(children());
+    base.filter(&|desc| !(overrides.contains(&desc.path()) && world.has_component(id, desc)));
+    world.add_components(id, base)?;
+
+    for (new_id, mut entity) in to_spawn {
+        remap_entity_ids(&mut entity, &old_to_new_ids);
+        let parent_id = *entity.get_ref(parent()).unwrap_or(&id);
+        entity.set(parent(), parent_id);
+        entity.set(prefab_root(), id);
+        world.spawn_with_id(new_id, entity);
+        if parent_id == id {
+            add_child(world, id, new_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Despawns all of the entities that were spawned by a previous application of the prefab on `id`.
+fn despawn_prefab_entities(world: &mut World, id: EntityId) {
+    let previous = query(prefab_root())
+        .iter(world, None)
+        .filter(|(_, root)| **root == id)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    if previous.is_empty() {
+        return;
+    }
+    if let Ok(children) = world.get_mut(id, children()) {
+        children.retain(|c| !previous.contains(c));
+    }
+    for entity in previous {
+        despawn_recursive(world, entity);
+    }
+}
+
+fn prefab_depth(world: &World, mut id: EntityId) -> usize {
+    let mut depth = 0;
+    while let Ok(root) = world.get(id, prefab_root()) {
+        depth += 1;
+        if depth >= MAX_PREFAB_DEPTH {
+            break;
+        }
+        id = root;
+    }
+    depth
+}
+
+/// Points every entity reference in `entity` at the newly spawned copy of the referenced entity.
+/// References to entities outside of the prefab are kept, except for `children`, which only
+/// lists the entities spawned from the prefab.
+fn remap_entity_ids(entity: &mut Entity, old_to_new_ids: &HashMap<EntityId, EntityId>) {
+    let remap = |id: &mut EntityId| {
+        if let Some(new_id) = old_to_new_ids.get(id) {
+            *id = *new_id;
+        }
+    };
+    if let Some(children) = entity.get_mut(children()) {
+        children.retain(|c| old_to_new_ids.contains_key(c));
+    }
+    for entry in entity.iter_mut() {
+        if let Some(id) = entry.try_downcast_mut::<EntityId>() {
+            remap(id);
+        } else if let Some(ids) = entry.try_downcast_mut::<Vec<EntityId>>() {
+            ids.iter_mut().for_each(remap);
+        } else if let Some(Some(id)) = entry.try_downcast_mut::<Option<EntityId>>() {
+            remap(id);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PrefabFromUrl(pub AssetUrl);
 
@@ -84,6 +228,20 @@ impl AsyncAssetKey<Result<Arc<World>, AssetError>> for PrefabFromUrl {
                 .context("Failed to resolve model URL")?
                 .into();
         }
+        for (_id, (url,), _) in query_mut((prefab_from_url(),), ()).iter(&mut world, None) {
+            *url = AssetUrl::from_str(url)
+                .context("Invalid nested prefab URL")?
+                .resolve(&obj_url)
+                .context("Failed to resolve nested prefab URL")?
+                .into();
+        }
+        for (_id, (url,), _) in query_mut((ragdoll_from_url(),), ()).iter(&mut world, None) {
+            *url = AssetUrl::from_str(url)
+                .context("Invalid ragdoll URL")?
+                .resolve(&obj_url)
+                .context("Failed to resolve ragdoll URL")?
+                .into();
+        }
         #[cfg(not(target_os = "unknown"))]
         for (_id, (def,), _) in
             query_mut((ambient_physics::collider::collider(),), ()).iter(&mut world, None)
@@ -0,0 +1,101 @@
+use ambient_core::hierarchy::{children, parent};
+use ambient_ecs::{components, query, Entity, EntityId, QueryState, World};
+use ambient_prefab::{
+    instantiate_prefab, prefab_from_url, prefab_overrides, prefab_root, reload_prefabs,
+};
+
+components!("test", {
+    a: f32,
+    b: f32,
+});
+
+fn init() {
+    ambient_ecs::init_components();
+    init_components();
+}
+
+/// A prefab with a root and a single child
+fn prefab(a_value: f32, b_value: f32) -> World {
+    let mut prefab = World::new_unknown("prefab");
+    let root = Entity::new()
+        .with(a(), a_value)
+        .with(b(), b_value)
+        .spawn(&mut prefab);
+    let child = Entity::new()
+        .with(a(), a_value * 2.)
+        .with(parent(), root)
+        .spawn(&mut prefab);
+    prefab.add_component(root, children(), vec![child]).unwrap();
+    prefab.add_resource(children(), vec![root]);
+    prefab
+}
+
+fn spawned_children(world: &World, id: EntityId) -> Vec<EntityId> {
+    query(prefab_root())
+        .iter(world, None)
+        .filter(|(_, root)| **root == id)
+        .map(|(child, _)| child)
+        .collect()
+}
+
+#[test]
+fn edits_reach_instances_with_overrides() {
+    init();
+    let mut world = World::new_unknown("instances");
+    let id = Entity::new()
+        .with(prefab_from_url(), "prefab".to_string())
+        .with(b(), 5.)
+        .with(prefab_overrides(), vec![b().path()])
+        .spawn(&mut world);
+
+    instantiate_prefab(&mut world, id, &prefab(1., 2.)).unwrap();
+    assert_eq!(world.get(id, a()), Ok(1.));
+    assert_eq!(world.get(id, b()), Ok(5.));
+    let first_children = spawned_children(&world, id);
+    assert_eq!(first_children.len(), 1);
+    assert_eq!(world.get(first_children[0], a()), Ok(2.));
+    assert_eq!(world.get_ref(id, children()), Ok(&first_children));
+
+    // The source prefab was edited
+    instantiate_prefab(&mut world, id, &prefab(3., 4.)).unwrap();
+    assert_eq!(world.get(id, a()), Ok(3.));
+    assert_eq!(world.get(id, b()), Ok(5.));
+    let second_children = spawned_children(&world, id);
+    assert_eq!(second_children.len(), 1);
+    assert!(!world.exists(first_children[0]));
+    assert_eq!(world.get(second_children[0], a()), Ok(6.));
+    assert_eq!(world.get_ref(id, children()), Ok(&second_children));
+
+    // Without the override, the prefab's value is restored
+    world.set(id, prefab_overrides(), vec![]).unwrap();
+    instantiate_prefab(&mut world, id, &prefab(3., 4.)).unwrap();
+    assert_eq!(world.get(id, b()), Ok(4.));
+}
+
+#[test]
+fn reload_prefabs_reapplies_root_instances() {
+    init();
+    let mut world = World::new_unknown("reload");
+    let id = Entity::new()
+        .with(prefab_from_url(), "prefab".to_string())
+        .spawn(&mut world);
+    Entity::new()
+        .with(prefab_from_url(), "nested".to_string())
+        .with(prefab_root(), id)
+        .spawn(&mut world);
+
+    let changed = query(prefab_from_url().changed());
+    let mut state = QueryState::new();
+    assert_eq!(changed.iter(&world, Some(&mut state)).count(), 2);
+    assert_eq!(changed.iter(&world, Some(&mut state)).count(), 0);
+
+    reload_prefabs(&mut world);
+    assert_eq!(
+        changed
+            .iter(&world, Some(&mut state))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>(),
+        vec![id]
+    );
+    assert_eq!(world.get_ref(id, prefab_from_url()).unwrap(), "prefab");
+}
@@ -0,0 +1,13 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct InputSettings {
+    /// The player's rebinds of input actions, by package ID and then action name.
+    ///
+    /// Bindings are stored in their string form (e.g. `Key:Space`), so that a binding that
+    /// can't be parsed doesn't prevent the rest of the settings from loading.
+    #[serde(default)]
+    pub bindings: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
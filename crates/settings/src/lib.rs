@@ -13,11 +13,16 @@ pub use render::*;
 mod general;
 pub use general::*;
 
+mod input;
+pub use input::*;
+
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct Settings {
     #[serde(default)]
     pub general: GeneralSettings,
     pub render: RenderSettings,
+    #[serde(default)]
+    pub input: InputSettings,
 }
 
 #[cfg(not(target_os = "unknown"))]
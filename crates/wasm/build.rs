@@ -75,7 +75,7 @@ fn main() {
                 .0;
 
             let mut files = Files::default();
-            let world = resolve.select_world(pkg, None).unwrap();
+            let world = resolve.select_world(pkg, Some("bindings")).unwrap();
             generator.generate(&resolve, world, &mut files);
 
             for (filename, contents) in files.iter() {
@@ -17,6 +17,94 @@ impl IntoBindgen for ambient_input::PlayerRawInput {
                 .into_iter()
                 .map(|b| b.into_bindgen())
                 .collect(),
+            gamepads: self
+                .gamepads
+                .into_iter()
+                .map(|g| g.into_bindgen())
+                .collect(),
+            touches: self.touches.into_iter().map(|t| t.into_bindgen()).collect(),
+        }
+    }
+}
+
+impl IntoBindgen for ambient_input::gamepad::Gamepad {
+    type Item = wit::client_input::Gamepad;
+
+    fn into_bindgen(self) -> Self::Item {
+        Self::Item {
+            id: self.id,
+            name: self.name,
+            buttons: self.buttons.into_iter().map(|b| b.into_bindgen()).collect(),
+            left_stick: self.left_stick.into_bindgen(),
+            right_stick: self.right_stick.into_bindgen(),
+            left_trigger: self.left_trigger,
+            right_trigger: self.right_trigger,
+        }
+    }
+}
+
+impl IntoBindgen for ambient_input::touch::Touch {
+    type Item = wit::client_input::Touch;
+
+    fn into_bindgen(self) -> Self::Item {
+        Self::Item {
+            id: self.id,
+            position: self.position.into_bindgen(),
+            pressure: self.pressure,
+            phase: self.phase.into_bindgen(),
+        }
+    }
+}
+
+impl IntoBindgen for ambient_shared_types::TouchPhase {
+    type Item = wit::client_input::TouchPhase;
+
+    fn into_bindgen(self) -> Self::Item {
+        match self {
+            Self::Started => Self::Item::Started,
+            Self::Moved => Self::Item::Moved,
+            Self::Ended => Self::Item::Ended,
+            Self::Cancelled => Self::Item::Cancelled,
+        }
+    }
+}
+
+impl IntoBindgen for ambient_input::action::Action {
+    type Item = wit::client_input::Action;
+
+    fn into_bindgen(self) -> Self::Item {
+        Self::Item {
+            name: self.name,
+            bindings: self.bindings.iter().map(|b| b.to_string()).collect(),
+            default_bindings: self
+                .default_bindings
+                .iter()
+                .map(|b| b.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl IntoBindgen for ambient_shared_types::GamepadButton {
+    type Item = wit::client_input::GamepadButton;
+
+    fn into_bindgen(self) -> Self::Item {
+        match self {
+            Self::South => Self::Item::South,
+            Self::East => Self::Item::East,
+            Self::North => Self::Item::North,
+            Self::West => Self::Item::West,
+            Self::LeftBumper => Self::Item::LeftBumper,
+            Self::RightBumper => Self::Item::RightBumper,
+            Self::LeftStick => Self::Item::LeftStick,
+            Self::RightStick => Self::Item::RightStick,
+            Self::Select => Self::Item::Select,
+            Self::Start => Self::Item::Start,
+            Self::Mode => Self::Item::Mode,
+            Self::DPadUp => Self::Item::DPadUp,
+            Self::DPadDown => Self::Item::DPadDown,
+            Self::DPadLeft => Self::Item::DPadLeft,
+            Self::DPadRight => Self::Item::DPadRight,
         }
     }
 }
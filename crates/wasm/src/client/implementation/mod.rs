@@ -2,7 +2,14 @@
 //!
 //! If implementing a trait that is only available on the client, it should go in [specific].
 
-use crate::shared::{self, wit};
+use ambient_core::player::local_user_id;
+use ambient_input::player_raw_input;
+
+use crate::shared::{
+    self,
+    conversion::{FromBindgen, IntoBindgen},
+    wit,
+};
 
 use super::Bindings;
 
@@ -67,9 +74,40 @@ impl wit::entity::Host for Bindings {
         shared::implementation::entity::in_area(self.world_mut(), position, radius)
     }
 
+    fn in_box(
+        &mut self,
+        min: wit::types::Vec3,
+        max: wit::types::Vec3,
+    ) -> wasm_bridge::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::in_box(self.world(), min, max)
+    }
+
+    fn nearest(
+        &mut self,
+        position: wit::types::Vec3,
+        count: u32,
+    ) -> wasm_bridge::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::nearest(self.world(), position, count)
+    }
+
+    fn in_frustum(
+        &mut self,
+        projection_view: wit::types::Mat4,
+    ) -> wasm_bridge::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::in_frustum(self.world(), projection_view)
+    }
+
     fn get_all(&mut self, index: u32) -> wasm_bridge::Result<Vec<wit::types::EntityId>> {
         shared::implementation::entity::get_all(self.world_mut(), index)
     }
+
+    fn get_related(
+        &mut self,
+        index: u32,
+        target: wit::types::EntityId,
+    ) -> wasm_bridge::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::get_related(self.world(), index, target)
+    }
 }
 
 impl wit::component::Host for Bindings {
@@ -178,6 +216,20 @@ impl wit::component::Host for Bindings {
         shared::implementation::component::query(&mut self.base.query_states, query, query_event)
     }
 
+    fn query_related(
+        &mut self,
+        query_index: u64,
+        index: u32,
+        target: wit::types::EntityId,
+    ) -> wasm_bridge::Result<()> {
+        shared::implementation::component::query_related(
+            &mut self.base.query_states,
+            query_index,
+            index,
+            target,
+        )
+    }
+
     fn query_eval(
         &mut self,
         query_index: u64,
@@ -188,6 +240,86 @@ impl wit::component::Host for Bindings {
             query_index,
         )
     }
+
+    fn index_query(
+        &mut self,
+        index: u32,
+        query: wit::component::QueryBuild,
+        descending: bool,
+    ) -> wasm_bridge::Result<u64> {
+        shared::implementation::component::index_query(
+            &mut self.base.index_queries,
+            index,
+            query,
+            descending,
+        )
+    }
+
+    fn index_query_bound(
+        &mut self,
+        query_index: u64,
+        upper: bool,
+        inclusive: bool,
+        value: wit::component::Value,
+    ) -> wasm_bridge::Result<()> {
+        shared::implementation::component::index_query_bound(
+            &mut self.base.index_queries,
+            query_index,
+            upper,
+            inclusive,
+            value,
+        )
+    }
+
+    fn index_query_clear_bounds(&mut self, query_index: u64) -> wasm_bridge::Result<()> {
+        shared::implementation::component::index_query_clear_bounds(
+            &mut self.base.index_queries,
+            query_index,
+        )
+    }
+
+    fn index_query_eval(
+        &mut self,
+        query_index: u64,
+        offset: u32,
+        limit: Option<u32>,
+    ) -> wasm_bridge::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+        shared::implementation::component::index_query_eval(
+            unsafe { self.world_ref.world_mut() },
+            &self.base.index_queries,
+            query_index,
+            offset,
+            limit,
+        )
+    }
+
+    fn observe(
+        &mut self,
+        index: u32,
+        event: wit::component::ObserverEvent,
+    ) -> wasm_bridge::Result<u64> {
+        shared::implementation::component::observe(
+            unsafe { self.world_ref.world_mut() },
+            &mut self.base.observers,
+            index,
+            event,
+        )
+    }
+
+    fn observe_eval(
+        &mut self,
+        observer: u64,
+    ) -> wasm_bridge::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+        shared::implementation::component::observe_eval(&self.base.observers, observer)
+    }
+
+    fn unobserve(&mut self, observer: u64) -> wasm_bridge::Result<()> {
+        shared::implementation::component::unobserve(
+            unsafe { self.world_ref.world_mut() },
+            &mut self.base.observers,
+            observer,
+        )
+    }
 }
 
 impl wit::message::Host for Bindings {
@@ -203,6 +335,17 @@ impl wit::player::Host for Bindings {
     ) -> wasm_bridge::Result<Option<wit::types::EntityId>> {
         shared::implementation::player::get_by_user_id(self.world(), user_id)
     }
+
+    fn get_raw_input(
+        &mut self,
+        player_id: wit::types::EntityId,
+    ) -> wasm_bridge::Result<Option<wit::client_input::Input>> {
+        let world = self.world();
+        let local_player_id =
+            ambient_core::player::get_by_user_id(world, world.resource(local_user_id()));
+        Ok((local_player_id == Some(player_id.from_bindgen()))
+            .then(|| world.resource(player_raw_input()).clone().into_bindgen()))
+    }
 }
 
 impl wit::asset::Host for Bindings {
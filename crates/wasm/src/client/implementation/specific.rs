@@ -2,7 +2,7 @@
 //!
 //! If implementing a trait that is also available on the server, it should go in [super].
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use ambient_core::{
     async_ecs::async_run,
@@ -11,9 +11,13 @@ use ambient_core::{
     runtime,
     window::{window_ctl, WindowCtl},
 };
-use ambient_ecs::generated::input::messages::ClipboardGet;
+use ambient_ecs::generated::{input::messages::ClipboardGet, wasm::components::package_ref};
 use ambient_gpu::texture::Texture;
-use ambient_input::{player_prev_raw_input, player_raw_input};
+use ambient_input::{
+    action,
+    gamepad::{GamepadCtl, GamepadDeadzones},
+    gamepad_ctl, gamepad_deadzones, player_prev_raw_input, player_raw_input,
+};
 use ambient_native_std::mesh::MeshBuilder;
 use ambient_network::client::client_state;
 use ambient_procedurals::{
@@ -21,6 +25,7 @@ use ambient_procedurals::{
     procedural_storage,
 };
 use ambient_renderer::pbr_material::{PbrMaterialConfig, PbrMaterialParams};
+use ambient_shared_types::{ActionBinding, ActionBindingParseError};
 
 use anyhow::Context;
 use glam::Vec4;
@@ -148,6 +153,88 @@ impl wit::client_input::Host for Bindings {
             .resource(ambient_core::window::window_ctl())
             .send(ambient_core::window::WindowCtl::GrabCursor(grab_mode))?)
     }
+
+    fn set_gamepad_deadzones(&mut self, stick: f32, trigger: f32) -> anyhow::Result<()> {
+        *self.world_mut().resource_mut(gamepad_deadzones()) = GamepadDeadzones { stick, trigger };
+        Ok(())
+    }
+
+    fn rumble_gamepad(
+        &mut self,
+        gamepad_id: u32,
+        strong: f32,
+        weak: f32,
+        duration_ms: u32,
+    ) -> anyhow::Result<()> {
+        Ok(self
+            .world()
+            .resource(gamepad_ctl())
+            .send(GamepadCtl::Rumble {
+                gamepad_id,
+                strong,
+                weak,
+                duration: Duration::from_millis(duration_ms.into()),
+            })?)
+    }
+
+    fn register_action(
+        &mut self,
+        name: String,
+        default_bindings: Vec<String>,
+    ) -> anyhow::Result<Result<(), String>> {
+        let package = self.world().get(self.id, package_ref())?;
+        let default_bindings = match parse_action_bindings(&default_bindings) {
+            Ok(bindings) => bindings,
+            Err(err) => return Ok(Err(err.0)),
+        };
+        action::register_action(self.world_mut(), package, name, default_bindings);
+        Ok(Ok(()))
+    }
+
+    fn get_action(&mut self, name: String) -> anyhow::Result<(f32, f32)> {
+        let world = self.world();
+        let package = world.get(self.id, package_ref())?;
+        let Some(action) = action::action(world, package, &name) else {
+            return Ok((0.0, 0.0));
+        };
+
+        Ok((
+            action::action_value(&action.bindings, world.resource(player_raw_input())),
+            action::action_value(&action.bindings, world.resource(player_prev_raw_input())),
+        ))
+    }
+
+    fn get_actions(&mut self) -> anyhow::Result<Vec<wit::client_input::Action>> {
+        let world = self.world();
+        let package = world.get(self.id, package_ref())?;
+        Ok(action::actions(world, package).into_bindgen())
+    }
+
+    fn rebind_action(
+        &mut self,
+        name: String,
+        bindings: Vec<String>,
+    ) -> anyhow::Result<Result<(), String>> {
+        let package = self.world().get(self.id, package_ref())?;
+        let bindings = match parse_action_bindings(&bindings) {
+            Ok(bindings) => bindings,
+            Err(err) => return Ok(Err(err.0)),
+        };
+        action::rebind_action(self.world_mut(), package, &name, Some(bindings));
+        Ok(Ok(()))
+    }
+
+    fn reset_action(&mut self, name: String) -> anyhow::Result<()> {
+        let package = self.world().get(self.id, package_ref())?;
+        action::rebind_action(self.world_mut(), package, &name, None);
+        Ok(())
+    }
+}
+
+fn parse_action_bindings(
+    bindings: &[String],
+) -> Result<Vec<ActionBinding>, ActionBindingParseError> {
+    bindings.iter().map(|binding| binding.parse()).collect()
 }
 impl wit::client_camera::Host for Bindings {
     fn clip_position_to_world_ray(
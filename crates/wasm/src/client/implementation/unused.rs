@@ -4,7 +4,11 @@
 use super::Bindings;
 use crate::shared::{implementation::unsupported, wit};
 
-impl wit::server_asset::Host for Bindings {}
+impl wit::server_asset::Host for Bindings {
+    fn read(&mut self, _url: String) -> anyhow::Result<u64> {
+        unsupported()
+    }
+}
 
 impl wit::server_physics::Host for Bindings {
     fn add_force(
@@ -33,6 +37,17 @@ impl wit::server_physics::Host for Bindings {
         unsupported()
     }
 
+    fn add_radial_impulse_with_mask(
+        &mut self,
+        _position: wit::types::Vec3,
+        _impulse: f32,
+        _radius: f32,
+        _falloff_radius: Option<f32>,
+        _mask: u32,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+
     fn add_force_at_position(
         &mut self,
         _entity: wit::types::EntityId,
@@ -105,6 +120,24 @@ impl wit::server_physics::Host for Bindings {
         unsupported()
     }
 
+    fn raycast_first_with_mask(
+        &mut self,
+        _origin: wit::types::Vec3,
+        _direction: wit::types::Vec3,
+        _mask: u32,
+    ) -> anyhow::Result<Option<(wit::types::EntityId, f32)>> {
+        unsupported()
+    }
+
+    fn raycast_with_mask(
+        &mut self,
+        _origin: wit::types::Vec3,
+        _direction: wit::types::Vec3,
+        _mask: u32,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, f32)>> {
+        unsupported()
+    }
+
     fn move_character(
         &mut self,
         _entity: wit::types::EntityId,
@@ -153,9 +186,56 @@ impl wit::server_http::Host for Bindings {
     ) -> anyhow::Result<u64> {
         unsupported()
     }
+    fn send(&mut self, _: wit::server_http::Request) -> anyhow::Result<u64> {
+        unsupported()
+    }
+    fn websocket_connect(&mut self, _: String, _: Vec<(String, String)>) -> anyhow::Result<u64> {
+        unsupported()
+    }
+    fn websocket_send(
+        &mut self,
+        _: u64,
+        _: wit::server_http::WebsocketMessage,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn websocket_close(&mut self, _: u64) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn cancel(&mut self, _: u64) -> anyhow::Result<()> {
+        unsupported()
+    }
 }
 impl wit::server_ambient_package::Host for Bindings {
     fn load(&mut self, _: String) -> anyhow::Result<()> {
         unsupported()
     }
 }
+impl wit::server_storage::Host for Bindings {
+    fn get(
+        &mut self,
+        _: String,
+        _: String,
+    ) -> anyhow::Result<Result<Option<Vec<u8>>, wit::server_storage::StorageError>> {
+        unsupported()
+    }
+    fn list_keys(
+        &mut self,
+        _: String,
+        _: String,
+    ) -> anyhow::Result<Result<Vec<String>, wit::server_storage::StorageError>> {
+        unsupported()
+    }
+    fn commit(
+        &mut self,
+        _: String,
+        _: Vec<(String, Option<Vec<u8>>)>,
+    ) -> anyhow::Result<Result<(), wit::server_storage::StorageError>> {
+        unsupported()
+    }
+    fn get_usage(
+        &mut self,
+    ) -> anyhow::Result<Result<wit::server_storage::Usage, wit::server_storage::StorageError>> {
+        unsupported()
+    }
+}
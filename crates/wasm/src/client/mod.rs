@@ -1,6 +1,7 @@
 use crate::shared;
-use ambient_ecs::{EntityId, SystemGroup, World};
-use ambient_native_std::asset_cache::AssetCache;
+use ambient_core::asset_cache;
+use ambient_ecs::{query, EntityId, SystemGroup, World};
+use ambient_native_std::{asset_cache::AssetCache, download_asset::evict_changed_assets};
 use std::sync::Arc;
 
 mod implementation;
@@ -28,7 +29,24 @@ pub fn initialize(
     Ok(())
 }
 pub fn systems() -> SystemGroup {
-    SystemGroup::new("core/wasm/client", vec![Box::new(shared::systems())])
+    SystemGroup::new(
+        "core/wasm/client",
+        vec![
+            // Assets changed by a watch-mode rebuild on the server need to be downloaded again
+            query(shared::rebuilt_assets().changed()).to_system(|q, world, qs, _| {
+                let rebuilt = q.collect_cloned(world, qs);
+                if rebuilt.is_empty() {
+                    return;
+                }
+
+                let assets = world.resource(asset_cache()).clone();
+                for (_, asset_keys) in rebuilt {
+                    evict_changed_assets(&assets, &asset_keys);
+                }
+            }),
+            Box::new(shared::systems()),
+        ],
+    )
 }
 
 #[derive(Clone)]
@@ -0,0 +1,383 @@
+//! Outbound HTTP requests and WebSocket connections made by server modules.
+//!
+//! These don't touch the world; everything that happens is reported through the `on_event`
+//! callbacks, which are expected to forward the events to the module as messages.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use ambient_ecs::generated::{
+    messages::{HttpResponse, HttpResponseChunk, WebSocketClose, WebSocketMessage, WebSocketOpen},
+    types::HttpMethod,
+};
+use futures::{SinkExt, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+
+pub struct Request {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+    pub timeout: Option<Duration>,
+    pub streaming: bool,
+}
+
+pub enum HttpEvent {
+    Response(HttpResponse),
+    Chunk(HttpResponseChunk),
+}
+
+/// Sends `request`, reporting the response through `on_event`.
+///
+/// An [HttpResponse] is always sent, with `error` set if the request failed. For streaming
+/// requests, its body is empty, and is instead sent through [HttpResponseChunk]s, the last of
+/// which has `end` set.
+pub async fn send(
+    client: &reqwest::Client,
+    response_id: u64,
+    request: Request,
+    mut on_event: impl FnMut(HttpEvent),
+) {
+    let streaming = request.streaming;
+    let response = match build_request(client, request) {
+        Ok(request) => request.send().await.map_err(anyhow::Error::from),
+        Err(err) => Err(err),
+    };
+    let mut response = match response {
+        Ok(response) => response,
+        Err(err) => {
+            on_event(HttpEvent::Response(HttpResponse::new(
+                response_id,
+                0u32,
+                vec![],
+                Some(err.to_string()),
+                HashMap::new(),
+            )));
+            return;
+        }
+    };
+
+    let status = response.status().as_u16() as u32;
+    let headers = collect_headers(response.headers());
+    if !streaming {
+        let response = match response.bytes().await {
+            Ok(body) => HttpResponse::new(response_id, status, body.to_vec(), None, headers),
+            Err(err) => {
+                HttpResponse::new(response_id, status, vec![], Some(err.to_string()), headers)
+            }
+        };
+        on_event(HttpEvent::Response(response));
+        return;
+    }
+
+    on_event(HttpEvent::Response(HttpResponse::new(
+        response_id,
+        status,
+        vec![],
+        None,
+        headers,
+    )));
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(data)) => HttpResponseChunk::new(response_id, data.to_vec(), false, None),
+            Ok(None) => HttpResponseChunk::new(response_id, vec![], true, None),
+            Err(err) => HttpResponseChunk::new(response_id, vec![], true, Some(err.to_string())),
+        };
+        let end = chunk.end;
+        on_event(HttpEvent::Chunk(chunk));
+        if end {
+            break;
+        }
+    }
+}
+
+fn build_request(
+    client: &reqwest::Client,
+    request: Request,
+) -> anyhow::Result<reqwest::RequestBuilder> {
+    let method = match request.method {
+        HttpMethod::Get => reqwest::Method::GET,
+        HttpMethod::Post => reqwest::Method::POST,
+        HttpMethod::Put => reqwest::Method::PUT,
+        HttpMethod::Delete => reqwest::Method::DELETE,
+        HttpMethod::Patch => reqwest::Method::PATCH,
+    };
+
+    let mut builder = client.request(method, &request.url);
+    if !request.headers.is_empty() {
+        let mut header_map = HeaderMap::new();
+        for (key, value) in request.headers {
+            header_map.append(HeaderName::from_str(&key)?, value.parse()?);
+        }
+        builder = builder.headers(header_map);
+    }
+    if let Some(body) = request.body {
+        builder = builder.body(body);
+    }
+    if let Some(timeout) = request.timeout {
+        builder = builder.timeout(timeout);
+    }
+    Ok(builder)
+}
+
+/// Headers that appear more than once are joined with `, `.
+fn collect_headers(headers: &HeaderMap) -> HashMap<String, String> {
+    let mut result: HashMap<String, String> = HashMap::new();
+    for (name, value) in headers {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        result
+            .entry(name.to_string())
+            .and_modify(|existing| {
+                existing.push_str(", ");
+                existing.push_str(&value);
+            })
+            .or_insert_with(|| value.into_owned());
+    }
+    result
+}
+
+pub enum WebSocketEvent {
+    Open(WebSocketOpen),
+    Message(WebSocketMessage),
+    Close(WebSocketClose),
+}
+
+/// Connects to the WebSocket server at `url`, and runs the connection until either side closes it.
+///
+/// Messages received from `outgoing` are sent to the server; dropping the sender closes the
+/// connection. A [WebSocketClose] is always sent last.
+pub async fn run_websocket(
+    connection_id: u64,
+    url: String,
+    headers: Vec<(String, String)>,
+    outgoing: mpsc::UnboundedReceiver<tungstenite::Message>,
+    mut on_event: impl FnMut(WebSocketEvent),
+) {
+    let error = run_websocket_inner(connection_id, url, headers, outgoing, &mut on_event)
+        .await
+        .err()
+        .map(|err| err.to_string());
+    on_event(WebSocketEvent::Close(WebSocketClose::new(
+        connection_id,
+        error,
+    )));
+}
+
+async fn run_websocket_inner(
+    connection_id: u64,
+    url: String,
+    headers: Vec<(String, String)>,
+    mut outgoing: mpsc::UnboundedReceiver<tungstenite::Message>,
+    on_event: &mut impl FnMut(WebSocketEvent),
+) -> anyhow::Result<()> {
+    let mut request = url.into_client_request()?;
+    for (key, value) in headers {
+        request
+            .headers_mut()
+            .append(HeaderName::from_str(&key)?, value.parse()?);
+    }
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+    on_event(WebSocketEvent::Open(WebSocketOpen::new(connection_id)));
+
+    loop {
+        tokio::select! {
+            message = outgoing.recv() => match message {
+                Some(message) => socket.send(message).await?,
+                None => {
+                    socket.close(None).await?;
+                    // Wait for the server to acknowledge the close
+                    while let Some(message) = socket.next().await {
+                        message?;
+                    }
+                    return Ok(());
+                }
+            },
+            message = socket.next() => match message {
+                Some(Ok(tungstenite::Message::Text(text))) => {
+                    on_event(WebSocketEvent::Message(WebSocketMessage::new(
+                        connection_id,
+                        text.into_bytes(),
+                        true,
+                    )));
+                }
+                Some(Ok(tungstenite::Message::Binary(data))) => {
+                    on_event(WebSocketEvent::Message(WebSocketMessage::new(
+                        connection_id,
+                        data,
+                        false,
+                    )));
+                }
+                // Pings and closes are answered by tungstenite itself
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err.into()),
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serves a single HTTP request with `response`, returning the URL to request and the
+    /// received request.
+    async fn serve_once(response: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/test", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let len = stream.read(&mut request).await.unwrap();
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+            String::from_utf8_lossy(&request[..len]).into_owned()
+        });
+        (url, handle)
+    }
+
+    fn request(method: HttpMethod, url: String) -> Request {
+        Request {
+            method,
+            url,
+            headers: vec![],
+            body: None,
+            timeout: None,
+            streaming: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn http_response_with_headers() {
+        let (url, server) = serve_once(
+            "HTTP/1.1 201 Created\r\nContent-Length: 5\r\nX-Test: a\r\nX-Test: b\r\n\r\nhello",
+        )
+        .await;
+
+        let mut events = vec![];
+        let mut request = request(HttpMethod::Put, url);
+        request.headers = vec![("X-Request".to_string(), "1".to_string())];
+        request.body = Some(b"body".to_vec());
+        send(&reqwest::Client::new(), 7, request, |e| events.push(e)).await;
+
+        let received = server.await.unwrap().to_lowercase();
+        assert!(received.starts_with("put /test"));
+        assert!(received.contains("x-request: 1"));
+        assert!(received.ends_with("body"));
+
+        let [HttpEvent::Response(response)] = &events[..] else {
+            panic!("expected a single response");
+        };
+        assert_eq!(response.response_id, 7);
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body, b"hello");
+        assert_eq!(response.headers["x-test"], "a, b");
+        assert_eq!(response.error, None);
+    }
+
+    #[tokio::test]
+    async fn http_streaming_response() {
+        let (url, _server) = serve_once(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n",
+        )
+        .await;
+
+        let mut events = vec![];
+        let mut request = request(HttpMethod::Get, url);
+        request.streaming = true;
+        send(&reqwest::Client::new(), 1, request, |e| events.push(e)).await;
+
+        let Some((HttpEvent::Response(response), chunks)) = events.split_first() else {
+            panic!("expected a response first");
+        };
+        assert_eq!(response.status, 200);
+        assert!(response.body.is_empty());
+
+        let mut body = vec![];
+        for (i, chunk) in chunks.iter().enumerate() {
+            let HttpEvent::Chunk(chunk) = chunk else {
+                panic!("expected only chunks after the response");
+            };
+            assert_eq!(chunk.end, i == chunks.len() - 1);
+            assert_eq!(chunk.error, None);
+            body.extend_from_slice(&chunk.data);
+        }
+        assert_eq!(body, b"abcde");
+    }
+
+    #[tokio::test]
+    async fn http_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        // Accept the connection, but never respond
+        let _server = tokio::spawn(async move { listener.accept().await });
+
+        let mut events = vec![];
+        let mut request = request(HttpMethod::Get, url);
+        request.timeout = Some(Duration::from_millis(100));
+        send(&reqwest::Client::new(), 1, request, |e| events.push(e)).await;
+
+        let [HttpEvent::Response(response)] = &events[..] else {
+            panic!("expected a single response");
+        };
+        assert!(response.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn websocket_echo() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if message.is_text() || message.is_binary() {
+                    socket.send(message).await.unwrap();
+                }
+            }
+        });
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(tungstenite::Message::Text("hi".to_string()))
+            .unwrap();
+        tx.send(tungstenite::Message::Binary(vec![1, 2])).unwrap();
+
+        let mut events = vec![];
+        run_websocket(3, url, vec![], rx, |e| {
+            if let WebSocketEvent::Message(m) = &e {
+                // Close the connection once both messages have been echoed back
+                if !m.is_text {
+                    tx.send(tungstenite::Message::Close(None)).ok();
+                }
+            }
+            events.push(e);
+        })
+        .await;
+
+        assert!(matches!(&events[0], WebSocketEvent::Open(o) if o.connection_id == 3));
+        assert!(matches!(&events[1], WebSocketEvent::Message(m) if m.is_text && m.data == b"hi"));
+        assert!(matches!(&events[2], WebSocketEvent::Message(m) if !m.is_text && m.data == [1, 2]));
+        assert!(matches!(&events[3], WebSocketEvent::Close(c) if c.error.is_none()));
+    }
+
+    #[tokio::test]
+    async fn websocket_connection_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let mut events = vec![];
+        run_websocket(1, url, vec![], rx, |e| events.push(e)).await;
+
+        let [WebSocketEvent::Close(close)] = &events[..] else {
+            panic!("expected only a close");
+        };
+        assert!(close.error.is_some());
+    }
+}
@@ -1,7 +1,13 @@
 //! Used to implement all the *shared* host functions on the server.
 //!
 //! If implementing a trait that is only available on the server, it should go in [specific].
-use crate::shared::{self, wit};
+use ambient_input::player_raw_input;
+
+use crate::shared::{
+    self,
+    conversion::{FromBindgen, IntoBindgen},
+    wit,
+};
 
 use super::Bindings;
 
@@ -62,9 +68,40 @@ impl wit::entity::Host for Bindings {
         shared::implementation::entity::in_area(self.world_mut(), position, radius)
     }
 
+    fn in_box(
+        &mut self,
+        min: wit::types::Vec3,
+        max: wit::types::Vec3,
+    ) -> anyhow::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::in_box(self.world(), min, max)
+    }
+
+    fn nearest(
+        &mut self,
+        position: wit::types::Vec3,
+        count: u32,
+    ) -> anyhow::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::nearest(self.world(), position, count)
+    }
+
+    fn in_frustum(
+        &mut self,
+        projection_view: wit::types::Mat4,
+    ) -> anyhow::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::in_frustum(self.world(), projection_view)
+    }
+
     fn get_all(&mut self, index: u32) -> anyhow::Result<Vec<wit::types::EntityId>> {
         shared::implementation::entity::get_all(self.world_mut(), index)
     }
+
+    fn get_related(
+        &mut self,
+        index: u32,
+        target: wit::types::EntityId,
+    ) -> anyhow::Result<Vec<wit::types::EntityId>> {
+        shared::implementation::entity::get_related(self.world(), index, target)
+    }
 }
 
 #[async_trait::async_trait]
@@ -166,6 +203,20 @@ impl wit::component::Host for Bindings {
         shared::implementation::component::query(&mut self.base.query_states, query, query_event)
     }
 
+    fn query_related(
+        &mut self,
+        query_index: u64,
+        index: u32,
+        target: wit::types::EntityId,
+    ) -> anyhow::Result<()> {
+        shared::implementation::component::query_related(
+            &mut self.base.query_states,
+            query_index,
+            index,
+            target,
+        )
+    }
+
     fn query_eval(
         &mut self,
         query_index: u64,
@@ -176,6 +227,82 @@ impl wit::component::Host for Bindings {
             query_index,
         )
     }
+
+    fn index_query(
+        &mut self,
+        index: u32,
+        query: wit::component::QueryBuild,
+        descending: bool,
+    ) -> anyhow::Result<u64> {
+        shared::implementation::component::index_query(
+            &mut self.base.index_queries,
+            index,
+            query,
+            descending,
+        )
+    }
+
+    fn index_query_bound(
+        &mut self,
+        query_index: u64,
+        upper: bool,
+        inclusive: bool,
+        value: wit::component::Value,
+    ) -> anyhow::Result<()> {
+        shared::implementation::component::index_query_bound(
+            &mut self.base.index_queries,
+            query_index,
+            upper,
+            inclusive,
+            value,
+        )
+    }
+
+    fn index_query_clear_bounds(&mut self, query_index: u64) -> anyhow::Result<()> {
+        shared::implementation::component::index_query_clear_bounds(
+            &mut self.base.index_queries,
+            query_index,
+        )
+    }
+
+    fn index_query_eval(
+        &mut self,
+        query_index: u64,
+        offset: u32,
+        limit: Option<u32>,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+        shared::implementation::component::index_query_eval(
+            unsafe { self.world_ref.world_mut() },
+            &self.base.index_queries,
+            query_index,
+            offset,
+            limit,
+        )
+    }
+
+    fn observe(&mut self, index: u32, event: wit::component::ObserverEvent) -> anyhow::Result<u64> {
+        shared::implementation::component::observe(
+            unsafe { self.world_ref.world_mut() },
+            &mut self.base.observers,
+            index,
+            event,
+        )
+    }
+
+    fn observe_eval(
+        &mut self,
+        observer: u64,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+        shared::implementation::component::observe_eval(&self.base.observers, observer)
+    }
+
+    fn unobserve(&mut self, observer: u64) -> anyhow::Result<()> {
+        shared::implementation::component::unobserve(
+            unsafe { self.world_ref.world_mut() },
+            &mut self.base.observers,
+            observer,
+        )
+    }
 }
 #[async_trait::async_trait]
 impl wit::message::Host for Bindings {
@@ -188,6 +315,17 @@ impl wit::player::Host for Bindings {
     fn get_by_user_id(&mut self, user_id: String) -> anyhow::Result<Option<wit::types::EntityId>> {
         shared::implementation::player::get_by_user_id(self.world(), user_id)
     }
+
+    fn get_raw_input(
+        &mut self,
+        player_id: wit::types::EntityId,
+    ) -> anyhow::Result<Option<wit::client_input::Input>> {
+        Ok(self
+            .world()
+            .get_cloned(player_id.from_bindgen(), player_raw_input())
+            .ok()
+            .map(|input| input.into_bindgen()))
+    }
 }
 
 #[async_trait::async_trait]
@@ -2,7 +2,7 @@
 //!
 //! If implementing a trait that is also available on the client, it should go in [super].
 
-use std::{future::Future, str::FromStr};
+use std::{path::Component, str::FromStr, time::Duration};
 
 use ambient_core::{
     asset_cache,
@@ -11,14 +11,21 @@ use ambient_core::{
     runtime,
 };
 use ambient_ecs::{
-    generated::{messages::HttpResponse, types::HttpMethod},
+    generated::{
+        messages::AssetReadResponse, package::components as package, types::HttpMethod,
+        wasm::components::package_ref,
+    },
     query, EntityId, World,
 };
-use ambient_native_std::asset_url::AbsAssetUrl;
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::BytesFromUrl,
+};
 use ambient_network::server::player_transport;
-use reqwest::header::{HeaderMap, HeaderName};
+use tokio_tungstenite::tungstenite;
 
-use super::super::Bindings;
+use super::super::{http, Bindings};
 
 use crate::shared::{
     self,
@@ -28,9 +35,104 @@ use crate::shared::{
 };
 
 mod physics;
+mod storage;
 
-#[async_trait::async_trait]
-impl shared::wit::server_asset::Host for Bindings {}
+impl shared::wit::server_asset::Host for Bindings {
+    fn read(&mut self, url: String) -> wasm_bridge::Result<u64> {
+        let id = self.id;
+        let hosted = self.hosted;
+        let request_id = self.last_asset_request_id;
+        self.last_asset_request_id += 1;
+        let world = self.world_mut();
+
+        let assets = world.resource(asset_cache()).clone();
+        let runtime = world.resource(runtime());
+        let async_run = world.resource(async_run()).clone();
+
+        // Anything that can't be read is reported to the module like any other failure to read the asset
+        let url = resolve_asset_url(world, &assets, id, hosted, &url);
+
+        runtime.spawn(async move {
+            let result = match url {
+                Ok(url) => BytesFromUrl::new(url, true)
+                    .get(&assets)
+                    .await
+                    .map(|data| data.to_vec())
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err),
+            };
+            let response = match result {
+                Ok(data) => AssetReadResponse::new(request_id, data, None),
+                Err(err) => AssetReadResponse::new(request_id, vec![], Some(err)),
+            };
+
+            async_run.run(move |world| {
+                response.send(world, Some(id)).unwrap();
+            });
+        });
+
+        Ok(request_id)
+    }
+}
+
+/// Resolves `url` against the asset URL of the module's package, and ensures that the result
+/// points into one of the packages loaded on the server (with the same scheme, host and port,
+/// and a path beneath the package's root). This stops modules from reading arbitrary files
+/// or URLs through the server.
+///
+/// Hosted servers only read assets from their own filesystem.
+fn resolve_asset_url(
+    world: &World,
+    assets: &AssetCache,
+    module_id: EntityId,
+    hosted: bool,
+    url: &str,
+) -> Result<AbsAssetUrl, String> {
+    let package_id = world
+        .get(module_id, package_ref())
+        .map_err(|_| "The module does not belong to a package".to_string())?;
+    let url = world
+        .get_ref(package_id, package::asset_url())
+        .ok()
+        .and_then(|url| package_root(url))
+        .ok_or_else(|| "The module's package does not have an asset URL".to_string())?
+        .resolve(url)
+        .map_err(|err| format!("Invalid asset URL {url:?}: {err}"))?;
+    let download_url = url
+        .to_download_url(assets)
+        .map_err(|err| format!("Invalid asset URL {url}: {err}"))?;
+
+    let is_package_asset = query(package::asset_url())
+        .incl(package::is_package())
+        .iter(world, None)
+        .filter_map(|(_, url)| package_root(url))
+        .any(|root| {
+            root.0.scheme() == url.0.scheme()
+                && root.0.host() == url.0.host()
+                && root.0.port_or_known_default() == url.0.port_or_known_default()
+                && url.0.path().starts_with(root.0.path())
+        });
+    // Encoded separators (e.g. `..%2F`) are only decoded when converting to a file path
+    let escapes_root = download_url.to_file_path().map_or(true, |path| {
+        path.is_some_and(|path| path.components().any(|c| c == Component::ParentDir))
+    });
+    if !is_package_asset || escapes_root {
+        return Err(format!("{url} is not an asset of a loaded package"));
+    }
+
+    if hosted && download_url.0.scheme() != "file" {
+        return Err(format!(
+            "Cannot read {url}: reading assets over the network is not supported on hosted servers"
+        ));
+    }
+
+    Ok(url)
+}
+
+/// The root of a package's built assets, as a directory URL.
+fn package_root(asset_url: &str) -> Option<AbsAssetUrl> {
+    Some(AbsAssetUrl::from_str(asset_url).ok()?.as_directory())
+}
 
 #[async_trait::async_trait]
 impl shared::wit::server_message::Host for Bindings {
@@ -100,7 +202,14 @@ fn send_networked(
 
 impl shared::wit::server_http::Host for Bindings {
     fn get(&mut self, url: String, headers: Vec<(String, String)>) -> wasm_bridge::Result<u64> {
-        self.http_request_impl(HttpMethod::Get, url, headers, None)
+        self.http_request_impl(http::Request {
+            method: HttpMethod::Get,
+            url,
+            headers,
+            body: None,
+            timeout: None,
+            streaming: false,
+        })
     }
 
     fn post(
@@ -109,24 +218,114 @@ impl shared::wit::server_http::Host for Bindings {
         headers: Vec<(String, String)>,
         body: Option<Vec<u8>>,
     ) -> wasm_bridge::Result<u64> {
-        self.http_request_impl(HttpMethod::Post, url, headers, body)
+        self.http_request_impl(http::Request {
+            method: HttpMethod::Post,
+            url,
+            headers,
+            body,
+            timeout: None,
+            streaming: false,
+        })
     }
-}
 
-impl Bindings {
-    fn http_request_impl(
+    fn send(&mut self, request: shared::wit::server_http::Request) -> wasm_bridge::Result<u64> {
+        use shared::wit::server_http::Method;
+        self.http_request_impl(http::Request {
+            method: match request.method {
+                Method::Get => HttpMethod::Get,
+                Method::Post => HttpMethod::Post,
+                Method::Put => HttpMethod::Put,
+                Method::Delete => HttpMethod::Delete,
+                Method::Patch => HttpMethod::Patch,
+            },
+            url: request.url,
+            headers: request.headers,
+            body: request.body,
+            timeout: request.timeout_ms.map(Duration::from_millis),
+            streaming: request.streaming,
+        })
+    }
+
+    fn websocket_connect(
         &mut self,
-        method: HttpMethod,
         url: String,
         headers: Vec<(String, String)>,
-        body: Option<Vec<u8>>,
     ) -> wasm_bridge::Result<u64> {
+        if self.hosted {
+            anyhow::bail!("WebSocket connections are not supported on hosted servers");
+        }
+
+        let id = self.id;
+        let connection_id = self.last_websocket_id;
+        self.last_websocket_id += 1;
+
+        let (outgoing_tx, outgoing_rx) = tokio::sync::mpsc::unbounded_channel();
+        // Dropping the sender (when the connection is closed, or the module is unloaded) closes the connection
+        self.websockets.lock().insert(connection_id, outgoing_tx);
+        let websockets = self.websockets.clone();
+
+        let world = self.world_mut();
+        let runtime = world.resource(runtime());
+        let async_run = world.resource(async_run()).clone();
+
+        runtime.spawn(async move {
+            http::run_websocket(connection_id, url, headers, outgoing_rx, move |event| {
+                async_run.run(move |world| {
+                    match event {
+                        http::WebSocketEvent::Open(message) => message.send(world, Some(id)),
+                        http::WebSocketEvent::Message(message) => message.send(world, Some(id)),
+                        http::WebSocketEvent::Close(message) => message.send(world, Some(id)),
+                    }
+                    .unwrap();
+                });
+            })
+            .await;
+            websockets.lock().remove(&connection_id);
+        });
+
+        Ok(connection_id)
+    }
+
+    fn websocket_send(
+        &mut self,
+        connection_id: u64,
+        message: shared::wit::server_http::WebsocketMessage,
+    ) -> wasm_bridge::Result<()> {
+        use shared::wit::server_http::WebsocketMessage;
+        let message = match message {
+            WebsocketMessage::Text(text) => tungstenite::Message::Text(text),
+            WebsocketMessage::Binary(data) => tungstenite::Message::Binary(data),
+        };
+
+        // If the connection has already been closed, the module will have been sent a `WebSocketClose`
+        if let Some(sender) = self.websockets.lock().get(&connection_id) {
+            sender.send(message).ok();
+        }
+        Ok(())
+    }
+
+    fn websocket_close(&mut self, connection_id: u64) -> wasm_bridge::Result<()> {
+        self.websockets.lock().remove(&connection_id);
+        Ok(())
+    }
+
+    fn cancel(&mut self, request_id: u64) -> wasm_bridge::Result<()> {
+        if let Some(request) = self.http_requests.lock().remove(&request_id) {
+            request.abort();
+        }
+        Ok(())
+    }
+}
+
+impl Bindings {
+    fn http_request_impl(&mut self, mut request: http::Request) -> wasm_bridge::Result<u64> {
         if self.hosted {
             anyhow::bail!("HTTP requests are not supported on hosted servers");
         }
 
         let id = self.id;
         let client = self.reqwest_client.clone();
+        let http_requests = self.http_requests.clone();
         let response_id = self.last_http_request_id;
         self.last_http_request_id += 1;
         let world = self.world_mut();
@@ -135,62 +334,30 @@ impl Bindings {
         let runtime = world.resource(runtime());
         let async_run = world.resource(async_run()).clone();
 
-        let resolved_url = AbsAssetUrl::from_str(&url)?
+        request.url = AbsAssetUrl::from_str(&request.url)?
             .to_download_url(assets)?
             .to_string();
 
-        let request = match method {
-            HttpMethod::Get => client.get(&resolved_url),
-            HttpMethod::Post => client.post(&resolved_url),
-        };
-        let request = match body {
-            Some(body) => request.body(body),
-            None => request,
-        };
-        let request = if !headers.is_empty() {
-            let mut header_map = HeaderMap::new();
-            for (key, value) in headers {
-                header_map.insert(HeaderName::from_str(&key)?, value.parse()?);
-            }
-            request.headers(header_map)
-        } else {
-            request
-        };
-
-        runtime.spawn(async move {
-            let wasm_response = run_with_error(response_id, async move {
-                let response = request.send().await?;
-
-                let status = response.status().as_u16() as u32;
-                let body = response.bytes().await?.to_vec();
-                Ok(HttpResponse {
-                    response_id,
-                    body,
-                    status,
-                    error: None,
+        // Hold the lock while spawning, so that a request that finishes immediately
+        // can't try to remove itself before it has been added
+        let mut in_flight = http_requests.lock();
+        let handle = runtime.spawn({
+            let http_requests = http_requests.clone();
+            async move {
+                http::send(&client, response_id, request, move |event| {
+                    async_run.run(move |world| {
+                        match event {
+                            http::HttpEvent::Response(response) => response.send(world, Some(id)),
+                            http::HttpEvent::Chunk(chunk) => chunk.send(world, Some(id)),
+                        }
+                        .unwrap();
+                    });
                 })
-            })
-            .await;
-
-            async_run.run(move |world| {
-                wasm_response.send(world, Some(id)).unwrap();
-            });
-        });
-
-        async fn run_with_error(
-            response_id: u64,
-            fut: impl Future<Output = wasm_bridge::Result<HttpResponse>>,
-        ) -> HttpResponse {
-            match fut.await {
-                Ok(response) => response,
-                Err(err) => HttpResponse {
-                    response_id,
-                    body: vec![],
-                    status: 0,
-                    error: Some(err.to_string()),
-                },
+                .await;
+                http_requests.lock().remove(&response_id);
             }
-        }
+        });
+        in_flight.insert(response_id, handle);
 
         Ok(response_id)
     }
@@ -5,9 +5,9 @@ use crate::shared::{
     wit,
 };
 use ambient_native_std::shapes::Ray;
-use ambient_physics::physx::character_controller;
+use ambient_physics::{collider::collision_mask, physx::character_controller};
 use anyhow::Context;
-use physxx::{PxControllerCollisionFlag, PxControllerFilters};
+use physxx::{PxControllerCollisionFlag, PxControllerFilters, PxFilterData};
 
 impl shared::wit::server_physics::Host for Bindings {
     fn add_force(
@@ -55,6 +55,25 @@ impl shared::wit::server_physics::Host for Bindings {
         Ok(())
     }
 
+    fn add_radial_impulse_with_mask(
+        &mut self,
+        position: wit::types::Vec3,
+        impulse: f32,
+        radius: f32,
+        falloff_radius: Option<f32>,
+        mask: u32,
+    ) -> anyhow::Result<()> {
+        let position = position.from_bindgen();
+        ambient_physics::helpers::PhysicsObjectCollection::from_radius_with_mask(
+            self.world_mut(),
+            position,
+            radius,
+            mask,
+        )
+        .add_radial_impulse(self.world_mut(), position, impulse, falloff_radius);
+        Ok(())
+    }
+
     fn add_force_at_position(
         &mut self,
         entity: wit::types::EntityId,
@@ -197,6 +216,42 @@ impl shared::wit::server_physics::Host for Bindings {
         Ok(result)
     }
 
+    fn raycast_first_with_mask(
+        &mut self,
+        origin: wit::types::Vec3,
+        direction: wit::types::Vec3,
+        mask: u32,
+    ) -> anyhow::Result<Option<(wit::types::EntityId, f32)>> {
+        let direction = get_raycast_direction(direction)?;
+        let result = ambient_physics::intersection::raycast_first_with_mask(
+            self.world(),
+            Ray::new(origin.from_bindgen(), direction),
+            mask,
+        )
+        .map(|t| (t.0.into_bindgen(), t.1.into_bindgen()));
+
+        Ok(result)
+    }
+
+    fn raycast_with_mask(
+        &mut self,
+        origin: wit::types::Vec3,
+        direction: wit::types::Vec3,
+        mask: u32,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, f32)>> {
+        let direction = get_raycast_direction(direction)?;
+        let result = ambient_physics::intersection::raycast_with_mask(
+            self.world(),
+            Ray::new(origin.from_bindgen(), direction),
+            mask,
+        )
+        .into_iter()
+        .map(|t| (t.0.into_bindgen(), t.1.into_bindgen()))
+        .collect();
+
+        Ok(result)
+    }
+
     fn move_character(
         &mut self,
         entity: wit::types::EntityId,
@@ -204,16 +259,20 @@ impl shared::wit::server_physics::Host for Bindings {
         min_dist: f32,
         elapsed_time: f32,
     ) -> anyhow::Result<wit::server_physics::CharacterCollision> {
-        match self
-            .world()
-            .get(entity.from_bindgen(), character_controller())
-        {
+        let entity = entity.from_bindgen();
+        match self.world().get(entity, character_controller()) {
             Ok(controller) => {
+                let filters = match self.world().get(entity, collision_mask()) {
+                    Ok(mask) => {
+                        PxControllerFilters::with_filter_data(PxFilterData::new(mask, 0, 0, 0))
+                    }
+                    Err(_) => PxControllerFilters::new(),
+                };
                 let res = controller.move_controller(
                     displacement.from_bindgen(),
                     min_dist,
                     elapsed_time,
-                    &PxControllerFilters::new(),
+                    &filters,
                     None,
                 );
                 Ok(wit::server_physics::CharacterCollision {
@@ -0,0 +1,78 @@
+use ambient_ecs::generated::{package::components as package, wasm::components::package_ref};
+use anyhow::Context;
+
+use super::super::super::{
+    storage::{StorageError, Write},
+    Bindings,
+};
+use crate::shared::wit::server_storage::{self, Host, Usage};
+
+impl Host for Bindings {
+    fn get(
+        &mut self,
+        namespace: String,
+        key: String,
+    ) -> anyhow::Result<Result<Option<Vec<u8>>, server_storage::StorageError>> {
+        let package_id = self.package_id()?;
+        Ok(self
+            .storage
+            .get(&package_id, &namespace, &key)
+            .map_err(to_wit_error))
+    }
+
+    fn list_keys(
+        &mut self,
+        namespace: String,
+        prefix: String,
+    ) -> anyhow::Result<Result<Vec<String>, server_storage::StorageError>> {
+        let package_id = self.package_id()?;
+        Ok(self
+            .storage
+            .list(&package_id, &namespace, &prefix)
+            .map_err(to_wit_error))
+    }
+
+    fn commit(
+        &mut self,
+        namespace: String,
+        writes: Vec<Write>,
+    ) -> anyhow::Result<Result<(), server_storage::StorageError>> {
+        let package_id = self.package_id()?;
+        Ok(self
+            .storage
+            .commit(&package_id, &namespace, writes)
+            .map_err(to_wit_error))
+    }
+
+    fn get_usage(&mut self) -> anyhow::Result<Result<Usage, server_storage::StorageError>> {
+        let package_id = self.package_id()?;
+        Ok(self
+            .storage
+            .usage(&package_id)
+            .map(|used| Usage {
+                used,
+                quota: self.storage.quota(),
+            })
+            .map_err(to_wit_error))
+    }
+}
+
+impl Bindings {
+    /// Storage is per-package, so all modules in a package share the same database.
+    fn package_id(&self) -> anyhow::Result<String> {
+        let world = self.world();
+        let package_entity = world
+            .get(self.id, package_ref())
+            .context("Module does not belong to a package")?;
+        Ok(world.get_cloned(package_entity, package::id())?)
+    }
+}
+
+fn to_wit_error(err: StorageError) -> server_storage::StorageError {
+    match err {
+        StorageError::InvalidNamespace(ns) => server_storage::StorageError::InvalidNamespace(ns),
+        StorageError::InvalidKey(key) => server_storage::StorageError::InvalidKey(key),
+        StorageError::QuotaExceeded(quota) => server_storage::StorageError::QuotaExceeded(quota),
+        StorageError::Database(err) => server_storage::StorageError::Database(err),
+    }
+}
@@ -37,6 +37,27 @@ impl wit::client_input::Host for Bindings {
     fn set_cursor_lock(&mut self, _: bool) -> anyhow::Result<()> {
         unsupported()
     }
+    fn set_gamepad_deadzones(&mut self, _: f32, _: f32) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn rumble_gamepad(&mut self, _: u32, _: f32, _: f32, _: u32) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn register_action(&mut self, _: String, _: Vec<String>) -> anyhow::Result<Result<(), String>> {
+        unsupported()
+    }
+    fn get_action(&mut self, _: String) -> anyhow::Result<(f32, f32)> {
+        unsupported()
+    }
+    fn get_actions(&mut self) -> anyhow::Result<Vec<wit::client_input::Action>> {
+        unsupported()
+    }
+    fn rebind_action(&mut self, _: String, _: Vec<String>) -> anyhow::Result<Result<(), String>> {
+        unsupported()
+    }
+    fn reset_action(&mut self, _: String) -> anyhow::Result<()> {
+        unsupported()
+    }
 }
 
 impl wit::client_camera::Host for Bindings {
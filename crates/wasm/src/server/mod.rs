@@ -1,19 +1,37 @@
 use crate::shared::{self, message::MessageExt};
-use ambient_ecs::{generated::messages, query, EntityId, FnSystem, SystemGroup, World};
-use ambient_native_std::asset_cache::AssetCache;
+use ambient_core::asset_cache;
+use ambient_ecs::{
+    generated::{messages, network::components::is_synced_resources},
+    query, EntityId, FnSystem, SystemGroup, World,
+};
+use ambient_native_std::{
+    asset_cache::AssetCache,
+    asset_url::AbsAssetUrl,
+    download_asset::{asset_key_download_urls, is_downloaded_from},
+};
 use ambient_network::server::{ForkingEvent, ShutdownEvent};
-use std::{path::PathBuf, sync::Arc};
+use ambient_sys::task::JoinHandle;
+use parking_lot::Mutex;
+use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
+use tokio_tungstenite::tungstenite;
 
+mod http;
 mod implementation;
 mod network;
+mod storage;
+
+pub use storage::DEFAULT_QUOTA as DEFAULT_STORAGE_QUOTA;
 
 pub fn initialize(
     world: &mut World,
     assets: &AssetCache,
     hosted: bool,
     data_path: PathBuf,
+    storage_path: PathBuf,
+    storage_quota: u64,
     messenger: Arc<dyn Fn(&World, EntityId, shared::MessageType, &str) + Send + Sync>,
 ) -> anyhow::Result<()> {
+    let storage = Arc::new(storage::Storage::new(storage_path, storage_quota));
     shared::initialize(
         world,
         assets,
@@ -24,6 +42,11 @@ pub fn initialize(
             id,
             reqwest_client: reqwest::Client::new(),
             last_http_request_id: 0,
+            last_asset_request_id: 0,
+            last_websocket_id: 0,
+            websockets: Default::default(),
+            http_requests: Default::default(),
+            storage: storage.clone(),
             hosted,
         }),
         if hosted {
@@ -55,6 +78,24 @@ pub fn systems() -> SystemGroup {
                         .unwrap();
                 }
             })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module trigger events");
+                let trigger_events = match world.resource_opt(ambient_physics::trigger_events()) {
+                    Some(trigger_events) => trigger_events.clone(),
+                    None => return,
+                };
+                for event in trigger_events {
+                    if event.entered {
+                        messages::TriggerEnter::new(event.trigger, event.other)
+                            .run(world, None)
+                            .unwrap();
+                    } else {
+                        messages::TriggerExit::new(event.trigger, event.other)
+                            .run(world, None)
+                            .unwrap();
+                    }
+                }
+            })),
             Box::new(FnSystem::new(move |world, _| {
                 profiling::scope!("WASM module collider loads");
                 // trigger collider loads
@@ -76,6 +117,55 @@ pub fn systems() -> SystemGroup {
     )
 }
 
+/// Called once the packages being served have been rebuilt (i.e. in watch mode).
+///
+/// `rebuilt_assets` are paths relative to the build root, which is served as the content root.
+///
+/// On success, the modules whose bytecode is among `rebuilt_assets` are reloaded from their
+/// bytecode URLs, which reloads them on the server and on all connected clients, and clients are
+/// told to evict the rebuilt assets through [shared::rebuilt_assets]. The error, if any, is made
+/// available to clients through [shared::rebuild_error], and [messages::WasmRebuild] is sent to
+/// all modules.
+pub fn on_rebuild(world: &mut World, error: Option<String>, rebuilt_assets: Vec<String>) {
+    let synced_resources = query(())
+        .incl(is_synced_resources())
+        .collect_ids(world, None);
+    if let Some(&id) = synced_resources.first() {
+        match &error {
+            Some(error) => world
+                .add_component(id, shared::rebuild_error(), error.clone())
+                .unwrap(),
+            None => {
+                world.remove_component(id, shared::rebuild_error()).ok();
+                world
+                    .add_component(id, shared::rebuilt_assets(), rebuilt_assets.clone())
+                    .unwrap();
+            }
+        }
+    }
+
+    if error.is_none() {
+        let modules = query(shared::bytecode_from_url())
+            .incl(shared::is_module())
+            .collect_cloned(world, None);
+
+        let assets = world.resource(asset_cache()).clone();
+        let rebuilt_urls = asset_key_download_urls(&assets, &rebuilt_assets);
+        for (id, url) in modules {
+            let is_rebuilt = AbsAssetUrl::from_str(&url)
+                .is_ok_and(|url| is_downloaded_from(&assets, &url, &rebuilt_urls));
+            if !is_rebuilt {
+                continue;
+            }
+
+            // Setting the URL, even to the same value, will redownload the bytecode and reload the module
+            world.set(id, shared::bytecode_from_url(), url).unwrap();
+        }
+    }
+
+    messages::WasmRebuild::new(error).run(world, None).unwrap();
+}
+
 pub fn on_forking_systems() -> SystemGroup<ForkingEvent> {
     SystemGroup::new(
         "core/wasm/server/on_forking_systems",
@@ -105,6 +195,13 @@ struct Bindings {
     id: EntityId,
     reqwest_client: reqwest::Client,
     last_http_request_id: u64,
+    last_asset_request_id: u64,
+    last_websocket_id: u64,
+    /// Used to send messages to the open WebSocket connections. Connections remove themselves when closed.
+    websockets: Arc<Mutex<HashMap<u64, tokio::sync::mpsc::UnboundedSender<tungstenite::Message>>>>,
+    /// The HTTP requests in flight, so that they can be cancelled. Requests remove themselves when done.
+    http_requests: Arc<Mutex<HashMap<u64, JoinHandle<()>>>>,
+    storage: Arc<storage::Storage>,
     /// Whether or not this server is running in a hosted environment,
     /// and should thus have some of its functionality disabled
     hosted: bool,
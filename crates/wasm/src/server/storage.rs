@@ -0,0 +1,383 @@
+//! Durable key-value storage for server packages.
+//!
+//! Each package gets its own database file, `<root>/<package_id>.redb`, which is opened lazily on
+//! first use. Entries are grouped into namespaces, each of which is a table in the database, and
+//! the total size of all entries in a package is limited by a quota.
+use std::{collections::HashMap, fmt, path::PathBuf, sync::Arc};
+
+use parking_lot::Mutex;
+use redb::{Database, ReadableTable, TableDefinition, TableError};
+
+/// The default maximum number of bytes a package may store.
+pub const DEFAULT_QUOTA: u64 = 64 * 1024 * 1024;
+
+const MAX_NAMESPACE_LENGTH: usize = 64;
+const MAX_KEY_LENGTH: usize = 1024;
+
+/// Reserved table used to keep track of how many bytes are in use.
+const USAGE_TABLE: TableDefinition<&str, u64> = TableDefinition::new("$usage");
+const USAGE_KEY: &str = "used";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    InvalidNamespace(String),
+    InvalidKey(String),
+    QuotaExceeded(u64),
+    Database(String),
+}
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::InvalidNamespace(ns) => write!(f, "invalid namespace {ns:?}"),
+            StorageError::InvalidKey(key) => write!(f, "invalid key {key:?}"),
+            StorageError::QuotaExceeded(quota) => {
+                write!(f, "storage quota of {quota} bytes exceeded")
+            }
+            StorageError::Database(err) => write!(f, "database error: {err}"),
+        }
+    }
+}
+impl std::error::Error for StorageError {}
+macro_rules! impl_from_redb_error {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for StorageError {
+            fn from(err: $ty) -> Self {
+                StorageError::Database(err.to_string())
+            }
+        })*
+    };
+}
+impl_from_redb_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError
+);
+
+/// A write to apply as part of [Storage::commit]. `None` deletes the key.
+pub type Write = (String, Option<Vec<u8>>);
+
+pub struct Storage {
+    root: PathBuf,
+    quota: u64,
+    databases: Mutex<HashMap<String, Arc<Database>>>,
+}
+impl Storage {
+    pub fn new(root: PathBuf, quota: u64) -> Self {
+        Self {
+            root,
+            quota,
+            databases: Default::default(),
+        }
+    }
+
+    pub fn quota(&self) -> u64 {
+        self.quota
+    }
+
+    pub fn get(
+        &self,
+        package_id: &str,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, StorageError> {
+        validate_namespace(namespace)?;
+
+        let db = self.database(package_id)?;
+        let txn = db.begin_read()?;
+        let table = match txn.open_table(namespace_table(namespace)) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let value = table.get(key)?.map(|v| v.value().to_vec());
+        Ok(value)
+    }
+
+    /// Returns the keys in `namespace` that start with `prefix`, in ascending order.
+    pub fn list(
+        &self,
+        package_id: &str,
+        namespace: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>, StorageError> {
+        validate_namespace(namespace)?;
+
+        let db = self.database(package_id)?;
+        let txn = db.begin_read()?;
+        let table = match txn.open_table(namespace_table(namespace)) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut keys = vec![];
+        for entry in table.range(prefix..)? {
+            let (key, _) = entry?;
+            let key = key.value();
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_string());
+        }
+        Ok(keys)
+    }
+
+    /// Applies all of `writes` to `namespace` atomically. If any of them are invalid, or the
+    /// package would exceed its quota, none of them are applied.
+    pub fn commit(
+        &self,
+        package_id: &str,
+        namespace: &str,
+        writes: Vec<Write>,
+    ) -> Result<(), StorageError> {
+        validate_namespace(namespace)?;
+        for (key, _) in &writes {
+            validate_key(key)?;
+        }
+        if writes.is_empty() {
+            return Ok(());
+        }
+
+        let db = self.database(package_id)?;
+        let txn = db.begin_write()?;
+        {
+            let mut usage = txn.open_table(USAGE_TABLE)?;
+            let mut used = usage.get(USAGE_KEY)?.map(|v| v.value()).unwrap_or_default();
+
+            let mut table = txn.open_table(namespace_table(namespace))?;
+            for (key, value) in &writes {
+                let old = match value {
+                    Some(value) => table.insert(key.as_str(), value.as_slice())?,
+                    None => table.remove(key.as_str())?,
+                };
+                if let Some(old) = old {
+                    used = used.saturating_sub(entry_size(key, old.value()));
+                }
+                if let Some(value) = value {
+                    used += entry_size(key, value);
+                }
+            }
+
+            // Dropping the transaction without committing aborts it
+            if used > self.quota {
+                return Err(StorageError::QuotaExceeded(self.quota));
+            }
+            usage.insert(USAGE_KEY, used)?;
+        }
+        txn.commit()?;
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes used by the package.
+    pub fn usage(&self, package_id: &str) -> Result<u64, StorageError> {
+        let db = self.database(package_id)?;
+        let txn = db.begin_read()?;
+        let used = match txn.open_table(USAGE_TABLE) {
+            Ok(table) => table.get(USAGE_KEY)?.map(|v| v.value()),
+            Err(TableError::TableDoesNotExist(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        Ok(used.unwrap_or_default())
+    }
+
+    fn database(&self, package_id: &str) -> Result<Arc<Database>, StorageError> {
+        let mut databases = self.databases.lock();
+        if let Some(db) = databases.get(package_id) {
+            return Ok(db.clone());
+        }
+
+        // Package IDs are used as file names, so make sure they can't escape the root
+        if package_id.is_empty()
+            || !package_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(StorageError::Database(format!(
+                "invalid package id {package_id:?}"
+            )));
+        }
+
+        std::fs::create_dir_all(&self.root).map_err(|err| {
+            StorageError::Database(format!("failed to create {:?}: {err}", self.root))
+        })?;
+        let db = Arc::new(Database::create(
+            self.root.join(format!("{package_id}.redb")),
+        )?);
+        databases.insert(package_id.to_string(), db.clone());
+        Ok(db)
+    }
+}
+
+fn namespace_table(namespace: &str) -> TableDefinition<'_, &'static str, &'static [u8]> {
+    TableDefinition::new(namespace)
+}
+
+fn entry_size(key: &str, value: &[u8]) -> u64 {
+    (key.len() + value.len()) as u64
+}
+
+fn validate_namespace(namespace: &str) -> Result<(), StorageError> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= MAX_NAMESPACE_LENGTH
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidNamespace(namespace.to_string()))
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), StorageError> {
+    if key.len() <= MAX_KEY_LENGTH {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A storage rooted in a fresh temporary directory, which is removed when dropped.
+    struct TestStorage(Storage);
+    impl TestStorage {
+        fn new(name: &str, quota: u64) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "ambient_storage_{name}_{}",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&root);
+            Self(Storage::new(root, quota))
+        }
+    }
+    impl std::ops::Deref for TestStorage {
+        type Target = Storage;
+        fn deref(&self) -> &Storage {
+            &self.0
+        }
+    }
+    impl Drop for TestStorage {
+        fn drop(&mut self) {
+            self.0.databases.lock().clear();
+            let _ = std::fs::remove_dir_all(&self.0.root);
+        }
+    }
+
+    fn set(key: &str, value: &[u8]) -> Write {
+        (key.to_string(), Some(value.to_vec()))
+    }
+
+    #[test]
+    fn quota_is_enforced() {
+        let storage = TestStorage::new("quota", 16);
+        storage.commit("pkg", "ns", vec![set("a", &[0; 9])]).unwrap();
+        assert_eq!(storage.usage("pkg"), Ok(10));
+
+        assert_eq!(
+            storage.commit("pkg", "ns", vec![set("b", &[0; 9])]),
+            Err(StorageError::QuotaExceeded(16))
+        );
+        assert_eq!(storage.get("pkg", "ns", "b"), Ok(None));
+        assert_eq!(storage.usage("pkg"), Ok(10));
+
+        // Replacing or deleting a value frees up the space it used
+        storage.commit("pkg", "ns", vec![set("a", &[0; 3])]).unwrap();
+        assert_eq!(storage.usage("pkg"), Ok(4));
+        storage
+            .commit("pkg", "other", vec![set("b", &[0; 9])])
+            .unwrap();
+        assert_eq!(storage.usage("pkg"), Ok(14));
+        storage
+            .commit("pkg", "ns", vec![("a".to_string(), None)])
+            .unwrap();
+        assert_eq!(storage.usage("pkg"), Ok(10));
+
+        // Each package has its own quota
+        storage.commit("pkg2", "ns", vec![set("a", &[0; 15])]).unwrap();
+        assert_eq!(storage.usage("pkg2"), Ok(16));
+    }
+
+    #[test]
+    fn transactions_are_atomic() {
+        let storage = TestStorage::new("atomic", 16);
+        storage.commit("pkg", "ns", vec![set("a", b"1")]).unwrap();
+
+        // Going over the quota partway through discards the earlier writes
+        assert_eq!(
+            storage.commit(
+                "pkg",
+                "ns",
+                vec![set("a", b"2"), set("b", b"2"), set("c", &[0; 16])]
+            ),
+            Err(StorageError::QuotaExceeded(16))
+        );
+        assert_eq!(storage.get("pkg", "ns", "a"), Ok(Some(b"1".to_vec())));
+        assert_eq!(storage.get("pkg", "ns", "b"), Ok(None));
+        assert_eq!(storage.usage("pkg"), Ok(2));
+
+        // An invalid key rejects the whole transaction
+        let long_key = "k".repeat(MAX_KEY_LENGTH + 1);
+        assert_eq!(
+            storage.commit("pkg", "ns", vec![set("b", b"2"), set(&long_key, b"2")]),
+            Err(StorageError::InvalidKey(long_key))
+        );
+        assert_eq!(storage.get("pkg", "ns", "b"), Ok(None));
+
+        // A successful transaction applies its writes in order
+        storage
+            .commit(
+                "pkg",
+                "ns",
+                vec![set("a", b"2"), ("a".to_string(), None), set("b", b"3")],
+            )
+            .unwrap();
+        assert_eq!(storage.get("pkg", "ns", "a"), Ok(None));
+        assert_eq!(storage.get("pkg", "ns", "b"), Ok(Some(b"3".to_vec())));
+        assert_eq!(storage.usage("pkg"), Ok(2));
+    }
+
+    #[test]
+    fn list_by_prefix() {
+        let storage = TestStorage::new("list", DEFAULT_QUOTA);
+        assert_eq!(storage.list("pkg", "ns", ""), Ok(vec![]));
+
+        storage
+            .commit(
+                "pkg",
+                "ns",
+                ["b", "a/2", "ab", "a/1", "a"]
+                    .into_iter()
+                    .map(|key| set(key, b""))
+                    .collect(),
+            )
+            .unwrap();
+        storage.commit("pkg", "other", vec![set("a/3", b"")]).unwrap();
+
+        assert_eq!(
+            storage.list("pkg", "ns", "a/"),
+            Ok(vec!["a/1".to_string(), "a/2".to_string()])
+        );
+        assert_eq!(
+            storage.list("pkg", "ns", "a"),
+            Ok(vec![
+                "a".to_string(),
+                "a/1".to_string(),
+                "a/2".to_string(),
+                "ab".to_string()
+            ])
+        );
+        assert_eq!(storage.list("pkg", "ns", "c"), Ok(vec![]));
+        assert_eq!(storage.list("pkg", "ns", "").unwrap().len(), 5);
+        assert_eq!(
+            storage.list("pkg", "", ""),
+            Err(StorageError::InvalidNamespace(String::new()))
+        );
+    }
+}
@@ -1,15 +1,37 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
-use ambient_ecs::{EntityId, PrimitiveComponent, Query, QueryState, World};
+use ambient_core::index::IndexQuery;
+use ambient_ecs::{
+    EntityId, ObservedChange, ObserverId, PrimitiveComponent, Query, QueryState, World,
+};
+use parking_lot::Mutex;
 
-pub type QueryStateMap =
-    slotmap::SlotMap<slotmap::DefaultKey, (Query, QueryState, Vec<PrimitiveComponent>)>;
+/// The queries built by a module: the query, its state, the components it returns, and the
+/// relationships that the entities must have to be returned
+pub type QueryStateMap = slotmap::SlotMap<
+    slotmap::DefaultKey,
+    (
+        Query,
+        QueryState,
+        Vec<PrimitiveComponent>,
+        Vec<(PrimitiveComponent, EntityId)>,
+    ),
+>;
+/// The world observers registered by a module, and the changes they have seen
+/// since the module last evaluated them
+pub type ObserverMap =
+    slotmap::SlotMap<slotmap::DefaultKey, (ObserverId, Arc<Mutex<Vec<ObservedChange>>>)>;
+/// The index queries built by a module, and the components they return
+pub type IndexQueryMap =
+    slotmap::SlotMap<slotmap::DefaultKey, (IndexQuery, Vec<PrimitiveComponent>)>;
 
 #[derive(Clone, Default)]
 pub struct BindingsBase {
     pub spawned_entities: HashSet<EntityId>,
     pub subscribed_messages: HashSet<String>,
     pub query_states: QueryStateMap,
+    pub observers: ObserverMap,
+    pub index_queries: IndexQueryMap,
 }
 
 /// Represents all the bindings for the imported world
@@ -41,6 +63,7 @@ pub trait BindingsBound:
     + super::wit::server_physics::Host
     + super::wit::server_http::Host
     + super::wit::server_ambient_package::Host
+    + super::wit::server_storage::Host
     + Clone
     + Sync
     + Send
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use ambient_ecs::EntityId;
 use ambient_native_std::shapes::Ray;
@@ -367,6 +367,18 @@ where
         self.into_iter().map(|i| i.from_bindgen()).collect()
     }
 }
+impl<K, V> IntoBindgen for HashMap<K, V>
+where
+    K: IntoBindgen,
+    V: IntoBindgen,
+{
+    type Item = Vec<(K::Item, V::Item)>;
+    fn into_bindgen(self) -> Self::Item {
+        self.into_iter()
+            .map(|(k, v)| (k.into_bindgen(), v.into_bindgen()))
+            .collect()
+    }
+}
 impl<T> FromBindgen for &[T]
 where
     T: FromBindgen + Clone,
@@ -1,7 +1,9 @@
+use ambient_core::{index::IndexQuery, relationship};
 use ambient_ecs::{
-    with_component_registry, Component, ComponentEntry, ComponentSet, ComponentValue, Entity,
-    EntityAccessor, EntityId, Enum, PrimitiveComponent, PrimitiveComponentType as PCT, QueryEvent,
-    QueryState, World,
+    primitive_comparator, with_component_registry, Component, ComponentDesc, ComponentEntry,
+    ComponentSet, ComponentValue, Entity, EntityAccessor, EntityId, Enum, Indexed, MessageSerde,
+    ObserverEvent, PrimitiveComponent, PrimitiveComponentType as PCT, QueryEvent, QueryState,
+    Relationship, StructFields, World,
 };
 use ambient_shared_types::primitive_component_definitions;
 use ambient_shared_types::{
@@ -11,11 +13,12 @@ use ambient_shared_types::{
 use anyhow::Context;
 use glam::{IVec2, IVec3, IVec4, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
 use itertools::Itertools;
+use parking_lot::Mutex;
 use paste::paste;
 use slotmap::Key;
-use std::time::Duration;
+use std::{collections::HashMap, ops::Bound, sync::Arc, time::Duration};
 
-use crate::shared::bindings::QueryStateMap;
+use crate::shared::bindings::{IndexQueryMap, ObserverMap, QueryStateMap};
 
 use super::super::{
     conversion::{FromBindgen, IntoBindgen},
@@ -34,10 +37,18 @@ pub fn get_id(index: u32) -> anyhow::Result<Option<String>> {
     }))
 }
 
-pub fn get_component_type<T: ComponentValue>(component_index: u32) -> Option<Component<T>> {
-    let desc = with_component_registry(|r| r.get_by_index(component_index))?;
-
-    Some(Component::new(desc))
+pub fn get_component_type<T: ComponentValue>(component_index: u32) -> anyhow::Result<Component<T>> {
+    let desc = with_component_registry(|r| r.get_by_index(component_index))
+        .with_context(|| format!("No component with index {component_index}"))?;
+    anyhow::ensure!(
+        desc.is::<T>(),
+        "The component {} has type {}, but was given a {}",
+        desc.path(),
+        desc.type_name(),
+        std::any::type_name::<T>()
+    );
+
+    Ok(Component::new(desc))
 }
 
 trait WitValueVisitor<Context> {
@@ -66,25 +77,39 @@ macro_rules! define_component_types {
             value: wit::component::Value,
             mut operation: impl WitValueVisitor<Context>,
         ) -> anyhow::Result<()> {
-            use wit::component::{OptionValue as OV, Value as V, VecValue as VV};
+            use wit::component::{MapValue as MV, OptionValue as OV, Value as V, VecValue as VV};
             match value {
                 $(
                 V::[<Type $value >](value) => {
-                    if let Some(component) = get_component_type::<$type>(index) {
-                        operation.visit(ctx, component, value.from_bindgen())?;
-                    }
+                    let component = get_component_type::<$type>(index)?;
+                    operation.visit(ctx, component, value.from_bindgen())?;
                 }
                 V::TypeVec(VV::[<Type $value >](value)) => {
-                    if let Some(component) = get_component_type::<Vec<$type>>(index) {
-                        operation.visit(ctx, component, value.from_bindgen())?;
-                    }
+                    let component = get_component_type::<Vec<$type>>(index)?;
+                    operation.visit(ctx, component, value.from_bindgen())?;
                 }
                 V::TypeOption(OV::[<Type $value >](value)) => {
-                    if let Some(component) = get_component_type::<Option<$type>>(index) {
-                        operation.visit(ctx, component, value.from_bindgen())?;
-                    }
+                    let component = get_component_type::<Option<$type>>(index)?;
+                    operation.visit(ctx, component, value.from_bindgen())?;
+                }
+                V::TypeMap(MV { keys: VV::TypeString(keys), values: VV::[<Type $value >](values) }) => {
+                    let component = get_component_type::<HashMap<String, $type>>(index)?;
+                    let value = keys.into_iter().zip(values.from_bindgen()).collect();
+                    operation.visit(ctx, component, value)?;
+                }
+                V::TypeMap(MV { keys: VV::TypeU32(keys), values: VV::[<Type $value >](values) }) => {
+                    let component = get_component_type::<HashMap<u32, $type>>(index)?;
+                    let value = keys.into_iter().zip(values.from_bindgen()).collect();
+                    operation.visit(ctx, component, value)?;
                 }
                 ) *
+                V::TypeMap(_) => anyhow::bail!("Unsupported map key type for component {index}"),
+                V::TypeStruct(fields) => {
+                    let component = get_component_type::<Vec<u8>>(index)?;
+                    let layout = struct_layout(component.desc())
+                        .ok_or_else(|| anyhow::anyhow!("Component {index} is not a struct"))?;
+                    operation.visit(ctx, component, struct_fields_to_bytes(&layout, fields)?)?;
+                }
             }
 
             Ok(())
@@ -97,7 +122,16 @@ macro_rules! define_component_types {
             primitive_component: ambient_ecs::PrimitiveComponent,
             mut operation: impl HostValueVisitor<Context>,
         ) -> anyhow::Result<Option<wit::component::Value>> {
-            use wit::component::{OptionValue as OV, Value as V, VecValue as VV};
+            use wit::component::{MapValue as MV, OptionValue as OV, Value as V, VecValue as VV};
+
+            // Struct components are stored as their bytes, but passed to guests as their fields
+            if let Some(layout) = struct_layout(primitive_component.desc) {
+                let component = Component::<Vec<u8>>::new(primitive_component.desc);
+                return operation
+                    .visit(ctx, component)?
+                    .map(|v| Ok(V::TypeStruct(struct_bytes_to_fields(&layout, &v)?)))
+                    .transpose();
+            }
 
             Ok(match primitive_component.ty {
                 $(
@@ -113,14 +147,110 @@ macro_rules! define_component_types {
                     let component = Component::<Option<$type>>::new(primitive_component.desc);
                     operation.visit(ctx, component)?.map(|v| V::TypeOption(OV::[<Type $value>](v.into_bindgen())))
                 },
+                PCT::[<MapString $value>] => {
+                    let component = Component::<HashMap<String, $type>>::new(primitive_component.desc);
+                    operation.visit(ctx, component)?.map(|v| {
+                        let (keys, values): (Vec<_>, Vec<_>) = v.into_bindgen().into_iter().unzip();
+                        V::TypeMap(MV { keys: VV::TypeString(keys), values: VV::[<Type $value>](values) })
+                    })
+                },
+                PCT::[<MapU32 $value>] => {
+                    let component = Component::<HashMap<u32, $type>>::new(primitive_component.desc);
+                    operation.visit(ctx, component)?.map(|v| {
+                        let (keys, values): (Vec<_>, Vec<_>) = v.into_bindgen().into_iter().unzip();
+                        V::TypeMap(MV { keys: VV::TypeU32(keys), values: VV::[<Type $value>](values) })
+                    })
+                },
                 )*
             })
         }
+
+        /// Serializes the fields of a struct component, which must match its layout.
+        fn struct_fields_to_bytes(
+            layout: &[PCT],
+            fields: Vec<wit::component::StructField>,
+        ) -> anyhow::Result<Vec<u8>> {
+            use wit::component::{MapValue as MV, OptionValue as OV, StructField as SF, VecValue as VV};
+
+            anyhow::ensure!(
+                layout.len() == fields.len(),
+                "Expected {} struct fields, got {}",
+                layout.len(),
+                fields.len()
+            );
+
+            let mut output = vec![];
+            for (ty, field) in layout.iter().copied().zip(fields) {
+                match (ty, field) {
+                    $(
+                    (PCT::$value, SF::[<Type $value>](value)) => {
+                        let value: $type = value.from_bindgen();
+                        value.serialize_message_part(&mut output)?;
+                    }
+                    (PCT::[<Vec $value>], SF::TypeVec(VV::[<Type $value>](value))) => {
+                        let value: Vec<$type> = value.from_bindgen();
+                        value.serialize_message_part(&mut output)?;
+                    }
+                    (PCT::[<Option $value>], SF::TypeOption(OV::[<Type $value>](value))) => {
+                        let value: Option<$type> = value.from_bindgen();
+                        value.serialize_message_part(&mut output)?;
+                    }
+                    (PCT::[<MapString $value>], SF::TypeMap(MV { keys: VV::TypeString(keys), values: VV::[<Type $value>](values) })) => {
+                        let value: HashMap<String, $type> = keys.into_iter().zip(values.from_bindgen()).collect();
+                        value.serialize_message_part(&mut output)?;
+                    }
+                    (PCT::[<MapU32 $value>], SF::TypeMap(MV { keys: VV::TypeU32(keys), values: VV::[<Type $value>](values) })) => {
+                        let value: HashMap<u32, $type> = keys.into_iter().zip(values.from_bindgen()).collect();
+                        value.serialize_message_part(&mut output)?;
+                    }
+                    )*
+                    (ty, field) => anyhow::bail!("Struct field {field:?} does not have type {ty:?}"),
+                }
+            }
+            Ok(output)
+        }
+
+        /// Deserializes the fields of a struct component from its bytes.
+        fn struct_bytes_to_fields(
+            layout: &[PCT],
+            mut input: &[u8],
+        ) -> anyhow::Result<Vec<wit::component::StructField>> {
+            use wit::component::{MapValue as MV, OptionValue as OV, StructField as SF, VecValue as VV};
+
+            layout
+                .iter()
+                .map(|ty| {
+                    Ok(match ty {
+                        $(
+                        PCT::$value => SF::[<Type $value>](<$type>::deserialize_message_part(&mut input)?.into_bindgen()),
+                        PCT::[<Vec $value>] => SF::TypeVec(VV::[<Type $value>](Vec::<$type>::deserialize_message_part(&mut input)?.into_bindgen())),
+                        PCT::[<Option $value>] => SF::TypeOption(OV::[<Type $value>](Option::<$type>::deserialize_message_part(&mut input)?.into_bindgen())),
+                        PCT::[<MapString $value>] => {
+                            let value = HashMap::<String, $type>::deserialize_message_part(&mut input)?;
+                            let (keys, values): (Vec<_>, Vec<_>) = value.into_bindgen().into_iter().unzip();
+                            SF::TypeMap(MV { keys: VV::TypeString(keys), values: VV::[<Type $value>](values) })
+                        }
+                        PCT::[<MapU32 $value>] => {
+                            let value = HashMap::<u32, $type>::deserialize_message_part(&mut input)?;
+                            let (keys, values): (Vec<_>, Vec<_>) = value.into_bindgen().into_iter().unzip();
+                            SF::TypeMap(MV { keys: VV::TypeU32(keys), values: VV::[<Type $value>](values) })
+                        }
+                        )*
+                    })
+                })
+                .collect()
+        }
     }};
 }
 
 primitive_component_definitions!(define_component_types);
 
+/// The layout of a struct component, or `None` if the component is not a struct.
+fn struct_layout(desc: ComponentDesc) -> Option<Vec<PCT>> {
+    desc.attribute::<StructFields>()
+        .map(|fields| fields.0.clone())
+}
+
 fn enum_value_to_entry(index: u32, value: &wit::component::Value) -> Option<ComponentEntry> {
     with_component_registry(|cr| {
         if let wit::component::Value::TypeU32(value) = *value {
@@ -374,31 +504,31 @@ pub fn remove_components(
     Ok(world.remove_components(entity_id.from_bindgen(), components)?)
 }
 
+fn get_primitive_components(
+    registry: &ambient_ecs::ComponentRegistry,
+    components: &[u32],
+) -> anyhow::Result<Vec<ambient_ecs::PrimitiveComponent>> {
+    components
+        .iter()
+        .map(|c| {
+            registry
+                .get_primitive_component(*c)
+                .context("no primitive component")
+        })
+        .collect()
+}
+
 pub fn query(
     query_states: &mut QueryStateMap,
     query: wit::component::QueryBuild,
     query_event: wit::component::QueryEvent,
 ) -> anyhow::Result<u64> {
-    fn get_components(
-        registry: &ambient_ecs::ComponentRegistry,
-        components: &[u32],
-    ) -> anyhow::Result<Vec<ambient_ecs::PrimitiveComponent>> {
-        components
-            .iter()
-            .map(|c| {
-                registry
-                    .get_primitive_component(*c)
-                    .context("no primitive component")
-            })
-            .collect()
-    }
-
     let (components, include, exclude, changed) = with_component_registry(|cr| {
         anyhow::Ok((
-            get_components(cr, &query.components)?,
-            get_components(cr, &query.includes)?,
-            get_components(cr, &query.excludes)?,
-            get_components(cr, &query.changed)?,
+            get_primitive_components(cr, &query.components)?,
+            get_primitive_components(cr, &query.includes)?,
+            get_primitive_components(cr, &query.excludes)?,
+            get_primitive_components(cr, &query.changed)?,
         ))
     })?;
 
@@ -422,11 +552,36 @@ pub fn query(
     }
 
     Ok(query_states
-        .insert((query, QueryState::new(), components))
+        .insert((query, QueryState::new(), components, vec![]))
         .data()
         .as_ffi())
 }
 
+pub fn query_related(
+    query_states: &mut QueryStateMap,
+    query_index: u64,
+    index: u32,
+    target: wit::types::EntityId,
+) -> anyhow::Result<()> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(query_index));
+    let (query, _, _, related) = query_states
+        .get_mut(key)
+        .context("no query state for key")?;
+
+    let component = with_component_registry(|cr| cr.get_primitive_component(index))
+        .with_context(|| format!("The component {index} does not exist"))?;
+    anyhow::ensure!(
+        component.desc.has_attribute::<Relationship>()
+            && matches!(component.ty, PCT::EntityId | PCT::VecEntityId),
+        "The component {} is not a relationship",
+        component.desc.path()
+    );
+
+    *query = query.clone().incl_ref(component.desc);
+    related.push((component, target.from_bindgen()));
+    Ok(())
+}
+
 pub fn query_eval(
     world: &World,
     query_states: &mut QueryStateMap,
@@ -434,12 +589,17 @@ pub fn query_eval(
 ) -> anyhow::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
     let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(query_index));
 
-    let (query, query_state, primitive_components) =
+    let (query, query_state, primitive_components, related) =
         query_states.get(key).context("no query state for key")?;
 
     let mut query_state = query_state.clone();
     let result = query
         .iter(world, Some(&mut query_state))
+        .filter(|ea| {
+            related
+                .iter()
+                .all(|(pc, target)| relationship::refers_to(world, pc.desc, ea.id(), *target))
+        })
         .map(|ea| {
             (
                 ea.id().into_bindgen(),
@@ -454,3 +614,214 @@ pub fn query_eval(
 
     Ok(result)
 }
+
+pub fn index_query(
+    index_queries: &mut IndexQueryMap,
+    index: u32,
+    query: wit::component::QueryBuild,
+    descending: bool,
+) -> anyhow::Result<u64> {
+    let (component, components, include, exclude) = with_component_registry(|cr| {
+        anyhow::Ok((
+            cr.get_primitive_component(index)
+                .with_context(|| format!("The component {index} does not exist"))?,
+            get_primitive_components(cr, &query.components)?,
+            get_primitive_components(cr, &query.includes)?,
+            get_primitive_components(cr, &query.excludes)?,
+        ))
+    })?;
+    anyhow::ensure!(
+        component.desc.has_attribute::<Indexed>() && primitive_comparator(component.ty).is_some(),
+        "The component {} is not indexed",
+        component.desc.path()
+    );
+
+    let mut index_query = IndexQuery::new(component.desc);
+    index_query.descending = descending;
+    for component in components.iter().chain(&include) {
+        index_query.filter = index_query.filter.incl_ref(component.desc);
+    }
+    for component in exclude {
+        index_query.filter = index_query.filter.excl_ref(component.desc);
+    }
+
+    Ok(index_queries
+        .insert((index_query, components))
+        .data()
+        .as_ffi())
+}
+
+pub fn index_query_bound(
+    index_queries: &mut IndexQueryMap,
+    query_index: u64,
+    upper: bool,
+    inclusive: bool,
+    value: wit::component::Value,
+) -> anyhow::Result<()> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(query_index));
+    let (index_query, _) = index_queries
+        .get_mut(key)
+        .context("no index query for key")?;
+
+    let value = wit_value_to_entry(index_query.component.index() as _, value)?;
+    let bound = if inclusive {
+        Bound::Included(value)
+    } else {
+        Bound::Excluded(value)
+    };
+    if upper {
+        index_query.end = bound;
+    } else {
+        index_query.start = bound;
+    }
+    Ok(())
+}
+
+pub fn index_query_clear_bounds(
+    index_queries: &mut IndexQueryMap,
+    query_index: u64,
+) -> anyhow::Result<()> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(query_index));
+    let (index_query, _) = index_queries
+        .get_mut(key)
+        .context("no index query for key")?;
+
+    index_query.start = Bound::Unbounded;
+    index_query.end = Bound::Unbounded;
+    Ok(())
+}
+
+pub fn index_query_eval(
+    world: &mut World,
+    index_queries: &IndexQueryMap,
+    query_index: u64,
+    offset: u32,
+    limit: Option<u32>,
+) -> anyhow::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(query_index));
+    let (index_query, primitive_components) =
+        index_queries.get(key).context("no index query for key")?;
+
+    let ids = index_query
+        .evaluate(world, offset as usize, limit.map(|limit| limit as usize))
+        .with_context(|| {
+            format!(
+                "The component {} is not indexed",
+                index_query.component.path()
+            )
+        })?;
+    ids.into_iter()
+        .map(|id| {
+            let ea = EntityAccessor::World { id };
+            let values = primitive_components
+                .iter()
+                .map(|pc| get_component_entity_accessor(world, &ea, pc.clone()))
+                .flatten_ok()
+                .collect::<anyhow::Result<_>>()?;
+            Ok((id.into_bindgen(), values))
+        })
+        .collect()
+}
+
+pub fn observe(
+    world: &mut World,
+    observers: &mut ObserverMap,
+    index: u32,
+    event: wit::component::ObserverEvent,
+) -> anyhow::Result<u64> {
+    let desc = with_component_registry(|cr| cr.get_by_index(index))
+        .with_context(|| format!("The component {index} does not exist"))?;
+    let event = match event {
+        wit::component::ObserverEvent::Added => ObserverEvent::Added,
+        wit::component::ObserverEvent::Removed => ObserverEvent::Removed,
+        wit::component::ObserverEvent::Changed => ObserverEvent::Changed,
+    };
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let observer = world.observe(desc, event, {
+        let changes = changes.clone();
+        move |_, change| changes.lock().push(change.clone())
+    });
+
+    Ok(observers.insert((observer, changes)).data().as_ffi())
+}
+
+pub fn unobserve(
+    world: &mut World,
+    observers: &mut ObserverMap,
+    observer: u64,
+) -> anyhow::Result<()> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(observer));
+    let (observer, _) = observers.remove(key).context("no observer for key")?;
+    world.unobserve(observer);
+    Ok(())
+}
+
+pub fn observe_eval(
+    observers: &ObserverMap,
+    observer: u64,
+) -> anyhow::Result<Vec<(wit::types::EntityId, Vec<wit::component::Value>)>> {
+    let key = slotmap::DefaultKey::from(slotmap::KeyData::from_ffi(observer));
+    let (_, changes) = observers.get(key).context("no observer for key")?;
+
+    let changes = std::mem::take(&mut *changes.lock());
+    changes
+        .into_iter()
+        .map(|change| {
+            let values = change
+                .old
+                .into_iter()
+                .chain(change.new)
+                .map(|entry| entry_to_wit_value(&entry))
+                .collect::<anyhow::Result<Option<Vec<_>>>>()?
+                .with_context(|| {
+                    format!(
+                        "The component {} is not a primitive component",
+                        change.component.path()
+                    )
+                })?;
+            Ok((change.id.into_bindgen(), values))
+        })
+        .collect()
+}
+
+/// Converts `value` into an entry of the component `index`, checking that it has the
+/// component's type.
+fn wit_value_to_entry(index: u32, value: wit::component::Value) -> anyhow::Result<ComponentEntry> {
+    struct EntryProducer;
+    impl<'a> WitValueVisitor<&'a mut Option<ComponentEntry>> for EntryProducer {
+        fn visit<T: ComponentValue>(
+            &mut self,
+            ctx: &'a mut Option<ComponentEntry>,
+            component: Component<T>,
+            value: T,
+        ) -> anyhow::Result<()> {
+            *ctx = Some(ComponentEntry::new(component, value));
+            Ok(())
+        }
+    }
+
+    let mut entry = None;
+    visit_wit_value(&mut entry, index, value, EntryProducer)?;
+    entry.with_context(|| format!("The value does not have the type of the component {index}"))
+}
+
+fn entry_to_wit_value(entry: &ComponentEntry) -> anyhow::Result<Option<wit::component::Value>> {
+    struct EntryGet;
+    impl<'a> HostValueVisitor<&'a ComponentEntry> for EntryGet {
+        fn visit<T: ComponentValue>(
+            &mut self,
+            ctx: &'a ComponentEntry,
+            _component: Component<T>,
+        ) -> anyhow::Result<Option<T>> {
+            Ok(ctx.try_downcast_ref::<T>().cloned())
+        }
+    }
+
+    let Some(primitive_component) =
+        with_component_registry(|cr| cr.get_primitive_component(entry.index()))
+    else {
+        return Ok(None);
+    };
+    visit_host_value(entry, primitive_component, EntryGet)
+}
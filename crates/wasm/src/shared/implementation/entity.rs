@@ -1,10 +1,15 @@
 use std::collections::HashSet;
 
-use ambient_core::transform::{local_to_world, translation};
-use ambient_ecs::{query as ecs_query, with_component_registry, EntityId, World};
+use ambient_core::{
+    relationship,
+    spatial::{flush_spatial_index, SpatialIndex},
+    transform::{local_to_world, translation},
+};
+use ambient_ecs::{with_component_registry, EntityId, World};
 
 use ambient_network::ServerWorldExt;
 
+use ambient_native_std::shapes::{Frustum, AABB};
 use anyhow::Context;
 use glam::Mat4;
 
@@ -89,14 +94,58 @@ pub fn in_area(
     centre: wit::types::Vec3,
     radius: f32,
 ) -> anyhow::Result<Vec<wit::types::EntityId>> {
+    // The index is only updated once per frame, so bring it up to date with anything that
+    // was moved since then before using it to find the candidates.
+    flush_spatial_index(world);
     let centre = centre.from_bindgen();
-    Ok(ecs_query((translation(),))
-        .iter(world, None)
-        .filter_map(|(id, (pos,))| ((*pos - centre).length() < radius).then_some(id))
+    Ok(spatial_index(world)?
+        .query_sphere(centre, radius)
+        .into_iter()
+        .filter(|id| {
+            world
+                .get(*id, translation())
+                .map_or(false, |pos| (pos - centre).length() < radius)
+        })
         .map(|id| id.into_bindgen())
         .collect())
 }
 
+pub fn in_box(
+    world: &World,
+    min: wit::types::Vec3,
+    max: wit::types::Vec3,
+) -> anyhow::Result<Vec<wit::types::EntityId>> {
+    let aabb = AABB {
+        min: min.from_bindgen(),
+        max: max.from_bindgen(),
+    };
+    Ok(spatial_index(world)?.query_aabb(&aabb).into_bindgen())
+}
+
+pub fn nearest(
+    world: &World,
+    position: wit::types::Vec3,
+    count: u32,
+) -> anyhow::Result<Vec<wit::types::EntityId>> {
+    Ok(spatial_index(world)?
+        .nearest(position.from_bindgen(), count as usize)
+        .into_bindgen())
+}
+
+pub fn in_frustum(
+    world: &World,
+    projection_view: wit::types::Mat4,
+) -> anyhow::Result<Vec<wit::types::EntityId>> {
+    let frustum = Frustum::from_projection_view(projection_view.from_bindgen());
+    Ok(spatial_index(world)?.query_frustum(&frustum).into_bindgen())
+}
+
+fn spatial_index(world: &World) -> anyhow::Result<&SpatialIndex> {
+    world
+        .resource_opt(ambient_core::spatial::spatial_index())
+        .context("This world does not have a spatial index")
+}
+
 pub fn get_all(world: &mut World, index: u32) -> anyhow::Result<Vec<wit::types::EntityId>> {
     let desc = match with_component_registry(|r| r.get_by_index(index)) {
         Some(c) => c,
@@ -110,3 +159,14 @@ pub fn get_all(world: &mut World, index: u32) -> anyhow::Result<Vec<wit::types::
             .collect(),
     )
 }
+
+pub fn get_related(
+    world: &World,
+    index: u32,
+    target: wit::types::EntityId,
+) -> anyhow::Result<Vec<wit::types::EntityId>> {
+    let desc = with_component_registry(|r| r.get_by_index(index))
+        .with_context(|| format!("The component {index} does not exist"))?;
+
+    Ok(relationship::get_related(world, desc, target.from_bindgen()).into_bindgen())
+}
@@ -125,7 +125,15 @@ pub fn process_network_message(
     name: String,
     data: Vec<u8>,
 ) -> anyhow::Result<()> {
-    use crate::shared::message;
+    use crate::shared::{message, server_message_hook};
+
+    if user_id.is_none() {
+        if let Some(hook) = world.resource_opt(server_message_hook()).cloned() {
+            if !hook(world, package_id, &name, &data) {
+                return Ok(());
+            }
+        }
+    }
 
     message::send(
         world,
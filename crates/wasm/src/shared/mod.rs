@@ -12,7 +12,10 @@ pub mod wit;
 
 pub use ambient_ecs::generated::wasm::components::*;
 use ambient_sys::task::PlatformBoxFuture;
-pub use internal::{messenger, module_bytecode, module_errors, module_state, module_state_maker};
+pub use internal::{
+    messenger, module_bytecode, module_errors, module_state, module_state_maker, rebuild_error,
+    rebuilt_assets, server_message_hook,
+};
 pub use module::*;
 use tracing::{Instrument, Span};
 
@@ -40,7 +43,9 @@ mod internal {
         components, Debuggable, Description, EntityId, Networked, Resource, Store, World,
     };
 
-    use super::{MessageType, ModuleBytecode, ModuleErrors, ModuleState, ModuleStateMaker};
+    use super::{
+        MessageType, ModuleBytecode, ModuleErrors, ModuleState, ModuleStateMaker, ServerMessageHook,
+    };
 
     components!("wasm::shared", {
         module_state: ModuleState,
@@ -48,11 +53,17 @@ mod internal {
         module_bytecode: ModuleBytecode,
         @[Networked, Store, Debuggable]
         module_errors: ModuleErrors,
+        @[Networked, Debuggable, Description["The error from the most recent watch-mode rebuild, if it failed. Attached to the synchronized resources entity."]]
+        rebuild_error: String,
+        @[Networked, Debuggable, Description["The build outputs that changed in the most recent watch-mode rebuild, relative to the build root (which is served as the content root). Attached to the synchronized resources entity."]]
+        rebuilt_assets: Vec<String>,
 
         @[Resource, Description["Used to signal messages from the WASM host/runtime."]]
         messenger: Arc<dyn Fn(&World, EntityId, MessageType, &str) + Send + Sync>,
         @[Resource]
         module_state_maker: ModuleStateMaker,
+        @[Resource, Description["Called with each message from the server before it is delivered to the modules. The message is dropped if this returns false."]]
+        server_message_hook: ServerMessageHook,
     });
 }
 
@@ -60,17 +71,21 @@ mod internal {
 mod native_bindings {
     use std::sync::Arc;
 
-    use ambient_ecs::{components, Resource};
+    use ambient_ecs::{components, Description, Resource};
     use wasi_cap_std_sync::Dir;
 
     components!("wasm::shared", {
         @[Resource]
         preopened_dir: Arc<Dir>,
+        @[Resource, Description["If set, the random number generators of modules are seeded from this instead of the system, so that their behaviour can be reproduced."]]
+        module_rng_seed: u64,
     });
 }
 
 use self::message::WorldEventSource;
 
+#[cfg(not(target_os = "unknown"))]
+pub use self::native_bindings::module_rng_seed;
 #[cfg(not(target_os = "unknown"))]
 use self::native_bindings::preopened_dir;
 
@@ -238,6 +253,9 @@ pub type ModuleStateMaker = Arc<
     dyn Fn(ModuleStateArgs<'_>) -> PlatformBoxFuture<anyhow::Result<ModuleState>> + Send + Sync,
 >;
 
+/// Called with the target package, name and data of a message from the server.
+pub type ServerMessageHook = Arc<dyn Fn(&mut World, EntityId, &str, &[u8]) -> bool + Send + Sync>;
+
 /// Initialize the core of the WASM runtime
 pub fn initialize<'a, Bindings: bindings::BindingsBound + 'static>(
     world: &mut World,
@@ -282,17 +300,42 @@ pub(crate) fn reload_all(world: &mut World) {
 }
 
 fn reload(world: &mut World, module_id: EntityId, new_bytecode: Option<ModuleBytecode>) {
+    let saved_state = new_bytecode
+        .as_ref()
+        .and_then(|_| save_state(world, module_id));
+
     unload(world, module_id, "reloading");
 
     if let Some(new_bytecode) = new_bytecode {
         if !new_bytecode.0.is_empty() {
-            load(world, module_id, &new_bytecode.0);
+            load(world, module_id, &new_bytecode.0, saved_state);
+        }
+    }
+}
+
+/// Asks a loaded module for the state it would like to keep across a reload.
+fn save_state(world: &mut World, module_id: EntityId) -> Option<Vec<u8>> {
+    let mut state = world.get_cloned(module_id, module_state()).ok()?;
+    match run_and_catch_panics(|| state.save_state(world)) {
+        Ok(saved_state) => saved_state,
+        Err(err) => {
+            let messenger = world.resource(messenger()).clone();
+            messenger(
+                world,
+                module_id,
+                MessageType::Warn,
+                &format!("Failed to save state before reloading: {err}"),
+            );
+            None
         }
     }
 }
 
 /// Loads a wasm module from the given bytecode and attaches it to the given entity.
-fn load(world: &mut World, id: EntityId, component_bytecode: &[u8]) {
+///
+/// If `saved_state` is provided, it is handed to the module after it has been initialized;
+/// if the module cannot restore it, it starts from scratch instead.
+fn load(world: &mut World, id: EntityId, component_bytecode: &[u8], saved_state: Option<Vec<u8>>) {
     let messenger = world.resource(messenger()).clone();
     let module_state_maker = world.resource(module_state_maker()).clone();
 
@@ -310,6 +353,14 @@ fn load(world: &mut World, id: EntityId, component_bytecode: &[u8]) {
     let preopened_dir = world
         .resource_opt(preopened_dir())
         .map(|d| d.try_clone().unwrap());
+    // Each module gets its own seed, which stays the same across sessions
+    #[cfg(not(target_os = "unknown"))]
+    let rng_seed = world.resource_opt(module_rng_seed()).map(|seed| {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (seed, &name).hash(&mut hasher);
+        hasher.finish()
+    });
 
     // Spawn the module on another thread to ensure that it does not block the main thread during compilation.
     // TODO: offload to thread
@@ -330,6 +381,8 @@ fn load(world: &mut World, id: EntityId, component_bytecode: &[u8]) {
             id,
             #[cfg(not(target_os = "unknown"))]
             preopened_dir,
+            #[cfg(not(target_os = "unknown"))]
+            rng_seed,
         })
         .await;
         tracing::info!("Finished loading module {name}");
@@ -346,6 +399,10 @@ fn load(world: &mut World, id: EntityId, component_bytecode: &[u8]) {
                             sms.listen_to_message(id.to_string());
                         }
 
+                        if let Some(saved_state) = saved_state {
+                            restore_state(world, id, &mut sms, &saved_state);
+                        }
+
                         world.add_component(id, module_state(), sms).unwrap();
 
                         tracing::info!("Running startup event for module {name}");
@@ -366,6 +423,24 @@ fn load(world: &mut World, id: EntityId, component_bytecode: &[u8]) {
     rt.spawn(task);
 }
 
+fn restore_state(world: &mut World, id: EntityId, state: &mut ModuleState, saved_state: &[u8]) {
+    let messenger = world.resource(messenger()).clone();
+    match run_and_catch_panics(|| state.restore_state(world, saved_state)) {
+        Ok(()) => messenger(
+            world,
+            id,
+            MessageType::Info,
+            "Restored state after reloading",
+        ),
+        Err(err) => messenger(
+            world,
+            id,
+            MessageType::Warn,
+            &format!("Could not restore state after reloading; starting from scratch: {err}"),
+        ),
+    }
+}
+
 fn update_errors(world: &mut World, errors: &[(EntityId, String)]) {
     let messenger = world.resource(messenger()).clone();
     for (id, err) in errors {
@@ -441,6 +516,14 @@ pub(crate) fn unload(world: &mut World, module_id: EntityId, reason: &str) {
         .map(|sms| sms.drain_spawned_entities())
         .unwrap_or_default();
 
+    let observers = world
+        .get_mut(module_id, module_state())
+        .map(|sms| sms.drain_observers())
+        .unwrap_or_default();
+    for observer in observers {
+        world.unobserve(observer);
+    }
+
     if let Ok(module_errors) = world.get_mut(module_id, module_errors()) {
         module_errors.0.clear();
     }
@@ -5,7 +5,7 @@ use super::engine::EngineKey;
 use super::ModuleStateMaker;
 use super::{bindings::BindingsBound, conversion::IntoBindgen};
 use super::{ModuleStateMaker, WorldEventSource};
-use ambient_ecs::{EntityId, World};
+use ambient_ecs::{EntityId, ObserverId, World};
 use ambient_native_std::asset_cache::{AssetCache, SyncAssetKeyExt};
 use ambient_sys::task::PlatformBoxFuture;
 use data_encoding::BASE64;
@@ -117,7 +117,13 @@ pub trait ModuleStateBehavior: Send + Sync {
         message_name: &str,
         message_data: &[u8],
     ) -> anyhow::Result<()>;
+    /// Asks the module for the state it wants to keep across a reload, if any.
+    fn save_state(&mut self, world: &mut World) -> anyhow::Result<Option<Vec<u8>>>;
+    /// Hands the state saved by a previous instance of the module to this one.
+    /// Returns an error if the module could not restore it.
+    fn restore_state(&mut self, world: &mut World, state: &[u8]) -> anyhow::Result<()>;
     fn drain_spawned_entities(&mut self) -> HashSet<EntityId>;
+    fn drain_observers(&mut self) -> Vec<ObserverId>;
     fn listen_to_message(&mut self, event_name: String);
     fn supports_message(&self, event_name: &str) -> bool;
 }
@@ -132,6 +138,9 @@ pub struct ModuleStateArgs<'a> {
     #[cfg(not(target_os = "unknown"))]
     /// Makes the `data` directory available during development
     pub preopened_dir: Option<wasi_cap_std_sync::Dir>,
+    #[cfg(not(target_os = "unknown"))]
+    /// Seeds the module's random number generators, instead of using the system's
+    pub rng_seed: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -183,10 +192,22 @@ impl ModuleStateBehavior for ModuleState {
             .run(world, message_source, message_name, message_data)
     }
 
+    fn save_state(&mut self, world: &mut World) -> anyhow::Result<Option<Vec<u8>>> {
+        self.inner.write().save_state(world)
+    }
+
+    fn restore_state(&mut self, world: &mut World, state: &[u8]) -> anyhow::Result<()> {
+        self.inner.write().restore_state(world, state)
+    }
+
     fn drain_spawned_entities(&mut self) -> HashSet<EntityId> {
         self.inner.write().drain_spawned_entities()
     }
 
+    fn drain_observers(&mut self) -> Vec<ObserverId> {
+        self.inner.write().drain_observers()
+    }
+
     fn listen_to_message(&mut self, message_name: String) {
         self.inner.write().listen_to_message(message_name)
     }
@@ -207,7 +228,9 @@ struct InstanceState<Bindings: BindingsBound> {
     /// Stores the context and loaded instances
     store: Store<BindingContext<Bindings>>,
 
-    guest_bindings: shared::wit::Bindings,
+    guest_bindings: shared::wit::CoreBindings,
+    /// Only present if the guest exports `guest-hot-reload`
+    hot_reload_bindings: Option<shared::wit::optional::HotReload>,
     _guest_instance: Instance,
 
     stdout_consumer: WasiOutputStreamConsumer,
@@ -245,6 +268,14 @@ impl<Bindings: BindingsBound> InstanceState<Bindings> {
             wasi.preopened_dir(dir, DirPerms::all(), FilePerms::all(), "/");
         }
 
+        #[cfg(not(target_os = "unknown"))]
+        if let Some(seed) = args.rng_seed {
+            use rand::{rngs::StdRng, SeedableRng};
+            wasi.secure_random(StdRng::seed_from_u64(seed))
+                .insecure_random(StdRng::seed_from_u64(seed.wrapping_add(1)))
+                .insecure_random_seed(seed.into());
+        }
+
         let wasi = wasi.build(&mut table)?;
         let mut store = Store::new(
             engine.inner(),
@@ -271,7 +302,7 @@ impl<Bindings: BindingsBound> InstanceState<Bindings> {
         let component = {
             preview2::command::add_to_linker(&mut linker)?;
 
-            shared::wit::Bindings::add_to_linker(&mut linker, |x| &mut x.bindings)?;
+            shared::wit::CoreBindings::add_to_linker(&mut linker, |x| &mut x.bindings)?;
 
             // Browsers won't compile larger wasm modules synchronously to avoid locking up the browser
             component::Component::new_async(engine.inner(), args.component_bytecode).await?
@@ -281,14 +312,14 @@ impl<Bindings: BindingsBound> InstanceState<Bindings> {
         let component = tokio::task::block_in_place(|| -> anyhow::Result<_> {
             preview2::command::sync::add_to_linker(&mut linker)?;
 
-            shared::wit::Bindings::add_to_linker(&mut linker, |x| &mut x.bindings)?;
+            shared::wit::CoreBindings::add_to_linker(&mut linker, |x| &mut x.bindings)?;
 
             component::Component::new(engine.inner(), args.component_bytecode)
         })?;
 
         let (guest_bindings, guest_instance) = async {
             let (guest_bindings, guest_instance) =
-                shared::wit::Bindings::instantiate(&mut store, &component, &linker)?;
+                shared::wit::CoreBindings::instantiate(&mut store, &component, &linker)?;
 
             // Initialise the runtime.
             guest_bindings
@@ -298,9 +329,14 @@ impl<Bindings: BindingsBound> InstanceState<Bindings> {
         }
         .await?;
 
+        // Modules built against an older API won't have these exports
+        let hot_reload_bindings =
+            shared::wit::optional::HotReload::new(&mut store, &guest_instance).ok();
+
         Ok(Self {
             store,
             guest_bindings,
+            hot_reload_bindings,
             _guest_instance: guest_instance,
 
             stdout_consumer,
@@ -341,6 +377,26 @@ mod miri_is_going_to_scream {
     unsafe impl<Bindings> Sync for InstanceState<Bindings> where Bindings: BindingsBound + Send + Sync {}
 }
 
+impl<Bindings: BindingsBound> InstanceState<Bindings> {
+    /// Calls into the guest with access to the `world`, and forwards any output it produced.
+    fn with_world<R>(
+        &mut self,
+        world: &mut World,
+        f: impl FnOnce(&mut Self) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        self.store.data_mut().bindings.set_world(world);
+
+        let result = f(self);
+
+        self.store.data_mut().bindings.clear_world();
+
+        self.stdout_consumer.process_incoming(world);
+        self.stderr_consumer.process_incoming(world);
+
+        result
+    }
+}
+
 impl<Bindings: BindingsBound> ModuleStateBehavior for InstanceState<Bindings> {
     fn run(
         &mut self,
@@ -349,37 +405,66 @@ impl<Bindings: BindingsBound> ModuleStateBehavior for InstanceState<Bindings> {
         message_name: &str,
         message_data: &[u8],
     ) -> anyhow::Result<()> {
-        self.store.data_mut().bindings.set_world(world);
+        let source = match message_source {
+            WorldEventSource::Runtime => shared::wit::guest::Source::Runtime,
+            WorldEventSource::Server => shared::wit::guest::Source::Server,
+            WorldEventSource::Client(user_id) => {
+                shared::wit::guest::Source::Client(user_id.clone())
+            }
+            WorldEventSource::Local(module) => {
+                shared::wit::guest::Source::Local(module.into_bindgen())
+            }
+        };
 
-        let guest = &self.guest_bindings.ambient_bindings_guest();
-        let result = guest.call_exec(
-            &mut self.store,
-            &match message_source {
-                WorldEventSource::Runtime => shared::wit::guest::Source::Runtime,
-                WorldEventSource::Server => shared::wit::guest::Source::Server,
-                WorldEventSource::Client(user_id) => {
-                    shared::wit::guest::Source::Client(user_id.clone())
-                }
-                WorldEventSource::Local(module) => {
-                    shared::wit::guest::Source::Local(module.into_bindgen())
-                }
-            },
-            message_name,
-            message_data,
-        );
+        self.with_world(world, |this| {
+            this.guest_bindings.ambient_bindings_guest().call_exec(
+                &mut this.store,
+                &source,
+                message_name,
+                message_data,
+            )
+        })
+    }
 
-        self.store.data_mut().bindings.clear_world();
+    fn save_state(&mut self, world: &mut World) -> anyhow::Result<Option<Vec<u8>>> {
+        if self.hot_reload_bindings.is_none() {
+            return Ok(None);
+        }
 
-        self.stdout_consumer.process_incoming(world);
-        self.stderr_consumer.process_incoming(world);
+        self.with_world(world, |this| {
+            let hot_reload = this.hot_reload_bindings.as_ref().unwrap();
+            hot_reload
+                .ambient_bindings_guest_hot_reload()
+                .call_save_state(&mut this.store)?
+                .map_err(anyhow::Error::msg)
+        })
+    }
 
-        result
+    fn restore_state(&mut self, world: &mut World, state: &[u8]) -> anyhow::Result<()> {
+        if self.hot_reload_bindings.is_none() {
+            anyhow::bail!("The module does not support restoring state");
+        }
+
+        self.with_world(world, |this| {
+            let hot_reload = this.hot_reload_bindings.as_ref().unwrap();
+            hot_reload
+                .ambient_bindings_guest_hot_reload()
+                .call_restore_state(&mut this.store, state)?
+                .map_err(anyhow::Error::msg)
+        })
     }
 
     fn drain_spawned_entities(&mut self) -> HashSet<EntityId> {
         std::mem::take(&mut self.store.data_mut().bindings.base_mut().spawned_entities)
     }
 
+    fn drain_observers(&mut self) -> Vec<ObserverId> {
+        std::mem::take(&mut self.store.data_mut().bindings.base_mut().observers)
+            .into_iter()
+            .map(|(_, (observer, _))| observer)
+            .collect()
+    }
+
     fn listen_to_message(&mut self, event_name: String) {
         self.store
             .data_mut()
@@ -1,7 +1,20 @@
 wasm_bridge::component::bindgen!({
     path: "wit",
+    world: "core-bindings",
     async: false,
 });
 
 pub use ambient::bindings::*;
 pub use exports::ambient::bindings::guest;
+
+/// Exports that guests are not required to have. These are looked up after the guest has been
+/// instantiated with [CoreBindings].
+pub mod optional {
+    wasm_bridge::component::bindgen!({
+        path: "wit",
+        world: "hot-reload",
+        async: false,
+    });
+
+    pub use exports::ambient::bindings::guest_hot_reload;
+}
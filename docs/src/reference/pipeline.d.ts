@@ -58,6 +58,49 @@ export type Pipeline = {
       "TriggerArea" | 
       /// This object should only be present in the picking scene.
       "Picking",
+    /// If a collider is present, the friction and restitution to use for it.
+    /// The default physics material is used for anything that isn't specified.
+    collider_material?: {
+      /// The friction coefficient of the collider when it is at rest against another collider.
+      static_friction?: f32,
+      /// The friction coefficient of the collider when it is sliding against another collider.
+      dynamic_friction?: f32,
+      /// The bounciness of the collider, from 0 (no bounce) to 1 (a perfectly elastic bounce).
+      restitution?: f32,
+      /// How the friction of the collider is combined with the friction of the collider it touches.
+      friction_combine_mode?: 
+        /// Use the average of the two values.
+        "Average" | 
+        /// Use the smaller of the two values.
+        "Min" | 
+        /// Multiply the two values.
+        "Multiply" | 
+        /// Use the larger of the two values.
+        "Max",
+      /// How the restitution of the collider is combined with the restitution of the collider it touches.
+      restitution_combine_mode?: 
+        /// Use the average of the two values.
+        "Average" | 
+        /// Use the smaller of the two values.
+        "Min" | 
+        /// Multiply the two values.
+        "Multiply" | 
+        /// Use the larger of the two values.
+        "Max",
+    },
+    /// If specified, a ragdoll is generated from the model's skeleton, with a capsule per bone.
+    /// Use the `ragdoll_blend` component to switch between the animated and the simulated pose.
+    ragdoll?: {
+      /// The names of the bones to simulate. Bones in between them follow their closest simulated parent.
+      /// If empty, all the bones of the model's skeleton are used, except for small ones like fingers and toes.
+      bones?: string[],
+      /// The radius of each bone's capsule, relative to the length of the bone. Defaults to 0.25.
+      radius?: f32,
+      /// The maximum angle (in degrees) that a bone can swing away from its bind pose. Defaults to 45.
+      swing_limit?: f32,
+      /// The maximum angle (in degrees) that a bone can twist around itself from its bind pose. Defaults to 30.
+      twist_limit?: f32,
+    },
     /// Whether or not this mesh should have its texture sizes capped.
     cap_texture_sizes?: 
       /// Cap this model's textures to 128x128.
@@ -240,6 +283,10 @@ export type Pipeline = {
     type: "Audio",
     /// Whether or not the audio should be converted to Ogg Vorbis.
     convert?: boolean,
+  } | {
+    /// The data asset pipeline.
+    /// Will copy the source files as-is, so that they can be read at runtime (e.g. JSON or TOML data files).
+    type: "Data",
   },
   /// Filter the sources used to feed this pipeline.
   /// This is a list of glob patterns for accepted files.
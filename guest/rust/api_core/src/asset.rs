@@ -25,3 +25,67 @@ impl From<wit::asset::UrlError> for UrlError {
 pub fn url_for_package_asset(package_id: EntityId, path: &str) -> Result<String, UrlError> {
     Ok(wit::asset::url(package_id.into_bindgen(), path)?)
 }
+
+#[cfg(feature = "server")]
+#[derive(Error, Debug)]
+/// Errors that can occur when reading an asset.
+pub enum ReadError {
+    #[error("Failed to read asset: {0}")]
+    /// The asset could not be read.
+    Read(String),
+    #[error("Asset is not valid UTF-8: {0}")]
+    /// The asset was expected to be text, but was not valid UTF-8.
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Failed to parse asset as JSON: {0}")]
+    /// The asset could not be parsed as JSON.
+    Json(#[from] serde_json::Error),
+    #[error("Failed to parse asset as TOML: {0}")]
+    /// The asset could not be parsed as TOML.
+    Toml(#[from] toml::de::Error),
+}
+
+#[cfg(feature = "server")]
+/// **\[Server-only\]** Reads the contents of the asset at `url`.
+///
+/// This goes through the server's asset cache, so it can be used to read data files
+/// shipped with a package, e.g. `asset::read(packages::this::assets::url("items.bin"))`.
+///
+/// Relative URLs are resolved against the root of this package's build. Only assets that
+/// belong to a package loaded on the server can be read, and hosted servers cannot read
+/// assets over the network.
+pub async fn read(url: impl AsRef<str>) -> Result<Vec<u8>, ReadError> {
+    use crate::{core::messages::AssetReadResponse, global};
+
+    let request_id = wit::server_asset::read(url.as_ref());
+    let response = global::wait_for_runtime_message(move |message: &AssetReadResponse| {
+        message.request_id == request_id
+    })
+    .await;
+
+    match response.error {
+        Some(error) => Err(ReadError::Read(error)),
+        None => Ok(response.data),
+    }
+}
+
+#[cfg(feature = "server")]
+/// **\[Server-only\]** Reads the asset at `url` as UTF-8 text.
+pub async fn read_string(url: impl AsRef<str>) -> Result<String, ReadError> {
+    Ok(String::from_utf8(read(url).await?)?)
+}
+
+#[cfg(feature = "server")]
+/// **\[Server-only\]** Reads the asset at `url` and deserializes it from JSON.
+pub async fn read_json<T: serde::de::DeserializeOwned>(
+    url: impl AsRef<str>,
+) -> Result<T, ReadError> {
+    Ok(serde_json::from_slice(&read(url).await?)?)
+}
+
+#[cfg(feature = "server")]
+/// **\[Server-only\]** Reads the asset at `url` and deserializes it from TOML.
+pub async fn read_toml<T: serde::de::DeserializeOwned>(
+    url: impl AsRef<str>,
+) -> Result<T, ReadError> {
+    Ok(toml::from_str(&read_string(url).await?)?)
+}
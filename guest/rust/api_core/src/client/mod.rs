@@ -1,9 +1,6 @@
 /// **\[Client-only\]** Audio functionality, including loading sounds and playback.
 pub mod audio;
 
-/// **\[Client-only\]** Input retrieval and manipulation.
-pub mod input;
-
 /// **\[Client-only\]** Manipulating the operating system's clipboard.
 pub mod clipboard;
 
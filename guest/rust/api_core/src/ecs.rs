@@ -1,12 +1,17 @@
 use std::marker::PhantomData;
 
 pub use crate::internal::component::{
+    __internal_get_component, __internal_struct_from_result, __internal_struct_from_value,
+    __internal_struct_into_result, __internal_struct_into_value,
+    index::{index_query, IndexQuery, IndexQueryBuilder},
+    observer::{on_added, on_changed, on_removed},
     query::{
         change_query, despawn_query, query, spawn_query, ChangeQuery, EventQuery, GeneralQuery,
         GeneralQueryBuilder, QueryEvent, UntrackedChangeQuery,
     },
-    Component, ComponentOptionValue, ComponentValue, ComponentVecValue, ComponentsTuple, Entity,
-    EnumComponent, SupportedValue, UntypedComponent, __internal_get_component,
+    Component, ComponentMapValue, ComponentOptionValue, ComponentValue, ComponentVecValue,
+    ComponentsTuple, Entity, EnumComponent, RelationshipComponent, SupportedMapKey,
+    SupportedStructField, SupportedValue, UntypedComponent,
 };
 
 use ambient_shared_types::ComponentIndex;
@@ -1,8 +1,8 @@
 use crate::{
     core::hierarchy::components::{children, parent},
-    global::{EntityId, Vec3},
+    global::{EntityId, Mat4, Vec3},
     internal::{
-        component::{Component, Entity, SupportedValue, UntypedComponent},
+        component::{Component, Entity, RelationshipComponent, SupportedValue, UntypedComponent},
         conversion::{FromBindgen, IntoBindgen},
         generated::ambient_core::hierarchy::components::unmanaged_children,
         wit,
@@ -82,11 +82,38 @@ pub fn get_all<T>(component: Component<T>) -> Vec<EntityId> {
     wit::entity::get_all(component.index()).from_bindgen()
 }
 
-/// Gets all of the entities within `radius` of `position`.
+/// Gets all of the entities that refer to `target` through the relationship `component`,
+/// in no particular order.
+///
+/// For example, `get_related(owned_by(), player)` returns everything owned by `player`.
+pub fn get_related(component: impl RelationshipComponent, target: EntityId) -> Vec<EntityId> {
+    wit::entity::get_related(component.index(), target.into_bindgen()).from_bindgen()
+}
+
+/// Gets all of the entities whose `translation` is within `radius` of `position`.
+///
+/// This uses the engine's spatial index like the other spatial queries, but brings it up to
+/// date first, so entities that were spawned or moved this frame are included.
 pub fn in_area(position: Vec3, radius: f32) -> Vec<EntityId> {
     wit::entity::in_area(position.into_bindgen(), radius).from_bindgen()
 }
 
+/// Gets all of the entities whose bounds intersect the axis-aligned box from `min` to `max`.
+pub fn in_box(min: Vec3, max: Vec3) -> Vec<EntityId> {
+    wit::entity::in_box(min.into_bindgen(), max.into_bindgen()).from_bindgen()
+}
+
+/// Gets the `count` entities closest to `position`, sorted from nearest to furthest.
+pub fn nearest(position: Vec3, count: u32) -> Vec<EntityId> {
+    wit::entity::nearest(position.into_bindgen(), count).from_bindgen()
+}
+
+/// Gets all of the entities that are at least partially inside the frustum of
+/// `projection_view` (for example, a camera's `projection_view`).
+pub fn in_frustum(projection_view: Mat4) -> Vec<EntityId> {
+    wit::entity::in_frustum(projection_view.into_bindgen()).from_bindgen()
+}
+
 /// Retrieves the component `component` for `entity` if it exists, or `None` if it doesn't.
 pub fn get_component<T: SupportedValue>(entity: EntityId, component: Component<T>) -> Option<T> {
     T::from_result(wit::component::get_component(
@@ -0,0 +1,129 @@
+//! Keeping state across hot reloads of this module.
+//!
+//! When a module's code changes (e.g. while running with `ambient run --watch`), the running
+//! instance is unloaded and a new one is started by calling `main` again. Anything that lives
+//! only inside the module, such as [State]s, caches or timers, is lost in the process.
+//!
+//! State registered with [preserve] is saved before the old instance is unloaded, and restored
+//! into the new instance after its `main` has run, but before
+//! [ModuleLoad](crate::core::messages::ModuleLoad) is sent. Restoration is all-or-nothing:
+//! if any preserved value cannot be restored (for example, because its `version` changed or it
+//! no longer deserializes), none of them are, and the module starts from scratch as usual.
+use std::{cell::RefCell, collections::HashMap, io::Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    global::State,
+    message::{MessageSerde, MessageSerdeError},
+};
+
+type SaveFn = Box<dyn Fn(&mut Vec<u8>) -> Result<(), MessageSerdeError>>;
+type Restorer = Box<dyn FnOnce()>;
+type LoadFn = Box<dyn Fn(&mut dyn Read) -> Result<Restorer, MessageSerdeError>>;
+
+struct Preserved {
+    version: u32,
+    save: SaveFn,
+    load: LoadFn,
+}
+
+thread_local! {
+    static PRESERVED: RefCell<HashMap<String, Preserved>> = RefCell::new(HashMap::new());
+}
+
+/// Preserves the value of `state` across hot reloads of this module, identifying it by `key`.
+///
+/// This should be called from `main` with a freshly-created `state`. If the module is being
+/// reloaded, the value saved by the previous instance under the same `key` will be written
+/// into `state` once `main` has finished.
+///
+/// `version` should be changed whenever the layout of `T` changes in a way that would make
+/// previously-saved values invalid; mismatched versions cause the module to start from scratch.
+///
+/// Calling this again with the same `key` replaces the previous registration.
+pub fn preserve<T: MessageSerde + 'static>(key: impl Into<String>, version: u32, state: State<T>) {
+    let save = {
+        let state = state.clone();
+        Box::new(move |output: &mut Vec<u8>| state.read().serialize_message_part(output))
+    };
+    let load = Box::new(move |input: &mut dyn Read| {
+        let value = T::deserialize_message_part(input)?;
+        let state = state.clone();
+        Ok(Box::new(move || *state.write() = value) as Restorer)
+    });
+
+    PRESERVED.with(|preserved| {
+        preserved.borrow_mut().insert(
+            key.into(),
+            Preserved {
+                version,
+                save,
+                load,
+            },
+        )
+    });
+}
+
+/// Stops preserving the state registered under `key`.
+pub fn forget(key: &str) {
+    PRESERVED.with(|preserved| preserved.borrow_mut().remove(key));
+}
+
+/// Serializes all preserved state, if there is any.
+pub(crate) fn save() -> Result<Option<Vec<u8>>, String> {
+    PRESERVED.with(|preserved| {
+        let preserved = preserved.borrow();
+        if preserved.is_empty() {
+            return Ok(None);
+        }
+
+        let mut entries = HashMap::new();
+        for (key, entry) in preserved.iter() {
+            let mut data = vec![];
+            data.write_u32::<BigEndian>(entry.version)
+                .map_err(MessageSerdeError::from)
+                .and_then(|_| (entry.save)(&mut data))
+                .map_err(|err| format!("Failed to save preserved state `{key}`: {err}"))?;
+            entries.insert(key.clone(), data);
+        }
+
+        let mut output = vec![];
+        entries
+            .serialize_message_part(&mut output)
+            .map_err(|err| format!("Failed to save preserved state: {err}"))?;
+        Ok(Some(output))
+    })
+}
+
+/// Restores the state saved by a previous instance of this module. Returns an error,
+/// leaving all state untouched, if any of it could not be restored.
+pub(crate) fn restore(saved: &[u8]) -> Result<(), String> {
+    let entries = HashMap::<String, Vec<u8>>::deserialize_message_part(&mut &saved[..])
+        .map_err(|err| format!("Failed to read preserved state: {err}"))?;
+
+    let restorers = PRESERVED.with(|preserved| {
+        let preserved = preserved.borrow();
+        entries
+            .iter()
+            .filter_map(|(key, data)| Some((key, data, preserved.get(key)?)))
+            .map(|(key, data, entry)| {
+                let mut input = data.as_slice();
+                let version = input.read_u32::<BigEndian>().ok();
+                if version != Some(entry.version) {
+                    return Err(format!(
+                        "Preserved state `{key}` has version {version:?}, expected {}",
+                        entry.version
+                    ));
+                }
+                (entry.load)(&mut input)
+                    .map_err(|err| format!("Failed to restore preserved state `{key}`: {err}"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    for restore in restorers {
+        restore();
+    }
+    Ok(())
+}
@@ -1,31 +1,41 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "client")]
+use std::time::Duration;
 
+#[cfg(feature = "client")]
 use crate::{
     core::ui::{components::focus, messages::FocusChanged},
     entity,
-    global::{CursorIcon, Vec2},
-    internal::{
-        conversion::{FromBindgen, IntoBindgen},
-        wit,
-    },
+    global::CursorIcon,
+    internal::conversion::IntoBindgen,
     prelude::ModuleMessage,
 };
+use crate::{
+    global::Vec2,
+    internal::{conversion::FromBindgen, wit},
+};
 
-pub use ambient_shared_types::MouseButton;
+pub use ambient_shared_types::{
+    ActionBinding, ActionBindingParseError, GamepadAxis, GamepadButton, InputSource, MouseButton,
+    TouchPhase,
+};
 
-/// Gets the local player's most recent raw input state.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Gets the local player's most recent raw input state.
 ///
 /// To determine if the player just supplied an input, compare it to [get_previous] or use [get_delta].
 pub fn get() -> Input {
     wit::client_input::get().from_bindgen()
 }
 
-/// Gets the local player's raw input state prior to the most recent update.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Gets the local player's raw input state prior to the most recent update.
 pub fn get_previous() -> Input {
     wit::client_input::get_previous().from_bindgen()
 }
 
-/// Gets the changes to the local player's raw input state in the last update,
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Gets the changes to the local player's raw input state in the last update,
 /// as well as the current raw input state.
 ///
 /// This is a wrapper for [get_previous], [get] and [Input::delta].
@@ -34,32 +44,126 @@ pub fn get_delta() -> (InputDelta, Input) {
     (c.delta(&p), c)
 }
 
-/// Sets the cursor icon.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the cursor icon.
 pub fn set_cursor(icon: CursorIcon) {
     wit::client_input::set_cursor(icon.into_bindgen());
 }
 
-/// Sets the cursor's visibility.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the cursor's visibility.
 pub fn set_cursor_visible(visible: bool) {
     wit::client_input::set_cursor_visible(visible);
 }
 
-/// Sets the cursor's lock state. If set, the cursor will not be able to move outside of the window.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the cursor's lock state. If set, the cursor will not be able to move outside of the window.
 ///
 /// You may want to combine this with [set_cursor_visible].
 pub fn set_cursor_lock(locked: bool) {
     wit::client_input::set_cursor_lock(locked);
 }
 
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the deadzones applied to gamepad sticks and triggers, as a fraction of their range.
+///
+/// Values inside a deadzone are reported as zero, and values outside it are rescaled to cover
+/// the full range. The defaults are `0.15` for sticks and `0.05` for triggers.
+pub fn set_gamepad_deadzones(stick: f32, trigger: f32) {
+    wit::client_input::set_gamepad_deadzones(stick, trigger);
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Rumbles the gamepad with the given `gamepad_id` for `duration`.
+///
+/// `strong` drives the low-frequency motor and `weak` drives the high-frequency motor; both range
+/// from `0.0` to `1.0`. Starting a new rumble replaces any rumble that is still playing. Gamepads
+/// without force feedback support ignore this.
+pub fn rumble_gamepad(gamepad_id: u32, strong: f32, weak: f32, duration: Duration) {
+    wit::client_input::rumble_gamepad(
+        gamepad_id,
+        strong,
+        weak,
+        duration.as_millis().try_into().unwrap_or(u32::MAX),
+    );
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Registers the input action `name` for this package with `default_bindings`.
+///
+/// This replaces the default bindings of any action with the same name, including actions
+/// declared in the `[actions]` section of the package's manifest. The player's rebinds of the
+/// action are kept.
+///
+/// Returns an error with the first binding that the runtime could not parse, in which case
+/// the action is left unchanged.
+pub fn register_action(
+    name: &str,
+    default_bindings: &[ActionBinding],
+) -> Result<(), ActionBindingParseError> {
+    wit::client_input::register_action(name, &bindings_to_strings(default_bindings))
+        .map_err(ActionBindingParseError)
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Gets the state of this package's input action `name`.
+///
+/// Actions that have not been declared or registered are never pressed.
+pub fn get_action(name: &str) -> ActionState {
+    let (value, previous_value) = wit::client_input::get_action(name);
+    ActionState {
+        value,
+        previous_value,
+    }
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Gets all of this package's input actions, sorted by name.
+///
+/// Use this to show the player which bindings are in use, e.g. in a rebinding menu.
+pub fn get_actions() -> Vec<Action> {
+    wit::client_input::get_actions()
+        .into_iter()
+        .map(|action| action.from_bindgen())
+        .collect()
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the player's bindings for this package's input action `name`, replacing its default
+/// bindings. The bindings are saved in the player's settings, so they persist across sessions.
+///
+/// Returns an error with the first binding that the runtime could not parse, in which case
+/// the action is left unchanged.
+pub fn rebind_action(
+    name: &str,
+    bindings: &[ActionBinding],
+) -> Result<(), ActionBindingParseError> {
+    wit::client_input::rebind_action(name, &bindings_to_strings(bindings))
+        .map_err(ActionBindingParseError)
+}
+
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Restores the default bindings of this package's input action `name`.
+pub fn reset_action(name: &str) {
+    wit::client_input::reset_action(name);
+}
+
+#[cfg(feature = "client")]
+fn bindings_to_strings(bindings: &[ActionBinding]) -> Vec<String> {
+    bindings.iter().map(|binding| binding.to_string()).collect()
+}
+
 /// Focus id of the "game"; i.e. 3D world rather than any UI element
 pub const GAME_FOCUS_ID: &str = "Game";
 
-/// Returns true if the "game" is focused; i.e. no UI element but the playable 3D game world
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Returns true if the "game" is focused; i.e. no UI element but the playable 3D game world
 pub fn is_game_focused() -> bool {
     entity::get_component(entity::resources(), focus()).unwrap_or_default() == GAME_FOCUS_ID
 }
 
-/// Sets the global focus, and notifies all listeners.
+#[cfg(feature = "client")]
+/// **\[Client-only\]** Sets the global focus, and notifies all listeners.
 pub fn set_focus(focus_id: impl Into<String>) {
     let focus_id = focus_id.into();
     entity::set_component(entity::resources(), focus(), focus_id.clone());
@@ -457,6 +561,179 @@ impl FromBindgen for wit::client_input::MouseButton {
     }
 }
 
+impl FromBindgen for wit::client_input::GamepadButton {
+    type Item = GamepadButton;
+
+    fn from_bindgen(self) -> Self::Item {
+        match self {
+            Self::South => Self::Item::South,
+            Self::East => Self::Item::East,
+            Self::North => Self::Item::North,
+            Self::West => Self::Item::West,
+            Self::LeftBumper => Self::Item::LeftBumper,
+            Self::RightBumper => Self::Item::RightBumper,
+            Self::LeftStick => Self::Item::LeftStick,
+            Self::RightStick => Self::Item::RightStick,
+            Self::Select => Self::Item::Select,
+            Self::Start => Self::Item::Start,
+            Self::Mode => Self::Item::Mode,
+            Self::DPadUp => Self::Item::DPadUp,
+            Self::DPadDown => Self::Item::DPadDown,
+            Self::DPadLeft => Self::Item::DPadLeft,
+            Self::DPadRight => Self::Item::DPadRight,
+        }
+    }
+}
+
+/// The state of a connected gamepad. See [Input::gamepads].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gamepad {
+    /// The identifier of this gamepad. It stays the same until the gamepad is disconnected.
+    pub id: u32,
+    /// The name the operating system reports for this gamepad.
+    pub name: String,
+    /// All of the buttons being pressed this frame.
+    pub buttons: HashSet<GamepadButton>,
+    /// The position of the left stick, from `-1.0` to `1.0` on each axis, with +Y being up.
+    ///
+    /// The stick deadzone has already been applied; see [set_gamepad_deadzones].
+    pub left_stick: Vec2,
+    /// The position of the right stick, from `-1.0` to `1.0` on each axis, with +Y being up.
+    ///
+    /// The stick deadzone has already been applied; see [set_gamepad_deadzones].
+    pub right_stick: Vec2,
+    /// How far the left trigger is pressed, from `0.0` to `1.0`.
+    ///
+    /// The trigger deadzone has already been applied; see [set_gamepad_deadzones].
+    pub left_trigger: f32,
+    /// How far the right trigger is pressed, from `0.0` to `1.0`.
+    ///
+    /// The trigger deadzone has already been applied; see [set_gamepad_deadzones].
+    pub right_trigger: f32,
+}
+
+impl FromBindgen for wit::client_input::Gamepad {
+    type Item = Gamepad;
+    fn from_bindgen(self) -> Self::Item {
+        Self::Item {
+            id: self.id,
+            name: self.name,
+            buttons: self.buttons.into_iter().map(|b| b.from_bindgen()).collect(),
+            left_stick: self.left_stick.from_bindgen(),
+            right_stick: self.right_stick.from_bindgen(),
+            left_trigger: self.left_trigger,
+            right_trigger: self.right_trigger,
+        }
+    }
+}
+
+impl FromBindgen for wit::client_input::TouchPhase {
+    type Item = TouchPhase;
+
+    fn from_bindgen(self) -> Self::Item {
+        match self {
+            Self::Started => Self::Item::Started,
+            Self::Moved => Self::Item::Moved,
+            Self::Ended => Self::Item::Ended,
+            Self::Cancelled => Self::Item::Cancelled,
+        }
+    }
+}
+
+/// A finger touching the screen. See [Input::touches].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Touch {
+    /// The identifier of this touch. It stays the same until the touch ends, after which it
+    /// may be reused.
+    pub id: u64,
+    /// The current position of the touch, in the same space as [Input::mouse_position].
+    pub position: Vec2,
+    /// How hard the screen is being pressed, from `0.0` to `1.0`. `1.0` if the device does not
+    /// report pressure.
+    pub pressure: f32,
+    /// [TouchPhase::Started] on the frame the touch started, [TouchPhase::Ended] or
+    /// [TouchPhase::Cancelled] on the frame it finished, and [TouchPhase::Moved] in between.
+    pub phase: TouchPhase,
+}
+
+impl FromBindgen for wit::client_input::Touch {
+    type Item = Touch;
+    fn from_bindgen(self) -> Self::Item {
+        Self::Item {
+            id: self.id,
+            position: self.position.from_bindgen(),
+            pressure: self.pressure,
+            phase: self.phase.from_bindgen(),
+        }
+    }
+}
+
+/// The state of an input action this frame and last frame. Get this with [get_action].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActionState {
+    /// The value of the action this frame, from `-1.0` to `1.0`.
+    ///
+    /// This is the sum of the values of the action's bindings: `1.0` (or `-1.0` for negative
+    /// bindings) for each held key or button, and the value of each bound axis.
+    pub value: f32,
+    /// The value of the action last frame.
+    pub previous_value: f32,
+}
+impl ActionState {
+    /// The value an action has to reach, in either direction, to count as pressed.
+    pub const PRESS_THRESHOLD: f32 = 0.5;
+
+    /// Returns true if the action is pressed this frame.
+    pub fn is_pressed(&self) -> bool {
+        self.value.abs() >= Self::PRESS_THRESHOLD
+    }
+
+    /// Returns true if the action was pressed last frame.
+    pub fn was_pressed(&self) -> bool {
+        self.previous_value.abs() >= Self::PRESS_THRESHOLD
+    }
+
+    /// Returns true if the action is pressed this frame, but was not pressed last frame.
+    pub fn just_pressed(&self) -> bool {
+        self.is_pressed() && !self.was_pressed()
+    }
+
+    /// Returns true if the action was pressed last frame, but is not pressed this frame.
+    pub fn just_released(&self) -> bool {
+        !self.is_pressed() && self.was_pressed()
+    }
+}
+
+/// An input action of this package. Get these with [get_actions].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    /// The name of the action.
+    pub name: String,
+    /// The bindings in use: the player's rebinds if there are any, otherwise the defaults.
+    pub bindings: Vec<ActionBinding>,
+    /// The default bindings of the action.
+    pub default_bindings: Vec<ActionBinding>,
+}
+
+impl FromBindgen for wit::client_input::Action {
+    type Item = Action;
+    fn from_bindgen(self) -> Self::Item {
+        // The host only hands out bindings it has parsed, so these are always valid
+        let parse = |bindings: Vec<String>| {
+            bindings
+                .iter()
+                .filter_map(|binding| binding.parse().ok())
+                .collect()
+        };
+
+        Self::Item {
+            name: self.name,
+            bindings: parse(self.bindings),
+            default_bindings: parse(self.default_bindings),
+        }
+    }
+}
+
 /// The state of a player's raw input. Get these with [get] or [get_previous].
 #[derive(Clone, Debug, PartialEq)]
 pub struct Input {
@@ -472,6 +749,17 @@ pub struct Input {
     pub mouse_wheel: f32,
     /// All of the mouse buttons being pressed this frame.
     pub mouse_buttons: HashSet<MouseButton>,
+    /// All of the connected gamepads, in the order they were connected.
+    ///
+    /// To react to gamepads being connected or disconnected, listen to the
+    /// `GamepadConnected` and `GamepadDisconnected` messages.
+    pub gamepads: Vec<Gamepad>,
+    /// All of the fingers touching the screen, in the order they started touching it.
+    /// Touches that finished this frame are included one last time.
+    ///
+    /// For taps, long presses, pans, pinches and rotations, listen to the `GestureTap`,
+    /// `GestureLongPress`, `GesturePan`, `GesturePinch` and `GestureRotate` messages.
+    pub touches: Vec<Touch>,
 }
 
 impl FromBindgen for wit::client_input::Input {
@@ -487,10 +775,28 @@ impl FromBindgen for wit::client_input::Input {
                 .into_iter()
                 .map(|b| b.from_bindgen())
                 .collect(),
+            gamepads: self
+                .gamepads
+                .into_iter()
+                .map(|g| g.from_bindgen())
+                .collect(),
+            touches: self.touches.into_iter().map(|t| t.from_bindgen()).collect(),
         }
     }
 }
 
+impl Input {
+    /// Returns the gamepad with the given `gamepad_id`, if it is connected.
+    pub fn gamepad(&self, gamepad_id: u32) -> Option<&Gamepad> {
+        self.gamepads.iter().find(|g| g.id == gamepad_id)
+    }
+
+    /// Returns the touch with the given `touch_id`, if it is touching the screen.
+    pub fn touch(&self, touch_id: u64) -> Option<&Touch> {
+        self.touches.iter().find(|t| t.id == touch_id)
+    }
+}
+
 /// The changes between the player's input state this update ([get]) and their input state
 /// last update ([get_previous]). Get this with [get_delta] or [Input::delta].
 #[derive(Clone, Debug, PartialEq)]
@@ -509,6 +815,15 @@ pub struct InputDelta {
     pub mouse_buttons: HashSet<MouseButton>,
     /// All of the mouse buttons that were released this frame.
     pub mouse_buttons_released: HashSet<MouseButton>,
+    /// All of the gamepad buttons that were pressed this frame, but not last frame, by gamepad id.
+    ///
+    /// Gamepads with no newly-pressed buttons are not included.
+    pub gamepad_buttons: HashMap<u32, HashSet<GamepadButton>>,
+    /// All of the gamepad buttons that were released this frame, by gamepad id.
+    ///
+    /// Gamepads with no released buttons are not included. Disconnecting a gamepad does not
+    /// release its buttons.
+    pub gamepad_buttons_released: HashMap<u32, HashSet<GamepadButton>>,
 }
 
 impl Input {
@@ -516,6 +831,19 @@ impl Input {
     pub fn delta(&self, previous: &Input) -> InputDelta {
         let (p, c) = (previous, self);
 
+        let gamepad_buttons = |a: &Input, b: &Input| {
+            a.gamepads
+                .iter()
+                .filter_map(|ga| {
+                    let pressed = match b.gamepad(ga.id) {
+                        Some(gb) => &ga.buttons - &gb.buttons,
+                        None => ga.buttons.clone(),
+                    };
+                    (!pressed.is_empty()).then_some((ga.id, pressed))
+                })
+                .collect::<HashMap<_, _>>()
+        };
+
         InputDelta {
             keys: &c.keys - &p.keys,
             keys_released: &p.keys - &c.keys,
@@ -523,10 +851,16 @@ impl Input {
             mouse_wheel: c.mouse_wheel - p.mouse_wheel,
             mouse_buttons: &c.mouse_buttons - &p.mouse_buttons,
             mouse_buttons_released: &p.mouse_buttons - &c.mouse_buttons,
+            gamepad_buttons: gamepad_buttons(c, p),
+            gamepad_buttons_released: gamepad_buttons(p, c)
+                .into_iter()
+                .filter(|(id, _)| c.gamepad(*id).is_some())
+                .collect(),
         }
     }
 }
 
+#[cfg(feature = "client")]
 impl IntoBindgen for CursorIcon {
     type Item = wit::client_input::CursorIcon;
     fn into_bindgen(self) -> Self::Item {
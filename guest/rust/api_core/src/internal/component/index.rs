@@ -0,0 +1,194 @@
+use std::{
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    core::messages::Frame,
+    global::{CallbackReturn, EntityId, OkEmpty},
+    internal::{
+        component::{Component, ComponentsTuple, UntypedComponent},
+        conversion::FromBindgen,
+        wit,
+    },
+    message::Listener,
+    message::RuntimeMessage,
+};
+
+use ambient_shared_types::ComponentIndex;
+
+use super::traits::SupportedValue;
+
+/// Creates a new [IndexQueryBuilder] that will find the entities that have `component`, sorted by
+/// its value, and return their `components`. It can be [built](IndexQueryBuilder::build) to create
+/// an [IndexQuery].
+///
+/// `component` must have the `Indexed` attribute in its package manifest. Unlike a
+/// [query](super::query::query), the entities are found and sorted by the host, so only the
+/// requested page of results is copied to the module.
+pub fn index_query<
+    T: SupportedValue + Clone + 'static,
+    Components: ComponentsTuple + Copy + Clone + 'static,
+>(
+    component: Component<T>,
+    components: Components,
+) -> IndexQueryBuilder<T, Components> {
+    IndexQuery::create(component, components)
+}
+
+/// An ECS query that returns entities in order of the value of an indexed component.
+///
+/// The query is cheap to copy and evaluate, so it's recommended that you build it once
+/// and reuse it.
+#[derive(Debug)]
+pub struct IndexQuery<T, Components>(u64, PhantomData<(Component<T>, Components)>);
+impl<T, Components: Copy> Clone for IndexQuery<T, Components> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, Components: Copy> Copy for IndexQuery<T, Components> {}
+impl<T: SupportedValue + Clone + 'static, Components: ComponentsTuple + Copy + Clone + 'static>
+    IndexQuery<T, Components>
+{
+    /// Creates a new [IndexQueryBuilder] that will find the entities that have `component`,
+    /// sorted by its value, and return their `components`.
+    pub fn create(
+        component: Component<T>,
+        components: Components,
+    ) -> IndexQueryBuilder<T, Components> {
+        IndexQueryBuilder {
+            component,
+            components: components.as_indices(),
+            include: vec![],
+            exclude: vec![],
+            descending: false,
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            _data: PhantomData,
+        }
+    }
+
+    /// Evaluate the query and return all the results in order.
+    pub fn evaluate(&self) -> Vec<(EntityId, Components::Data)> {
+        self.eval(0, None)
+    }
+
+    /// Evaluate the query and return at most `limit` results in order, after skipping the
+    /// first `offset`.
+    ///
+    /// For example, `evaluate_page(0, 10)` returns the top 10 of a leaderboard.
+    pub fn evaluate_page(&self, offset: u32, limit: u32) -> Vec<(EntityId, Components::Data)> {
+        self.eval(offset, Some(limit))
+    }
+
+    /// Changes the range of values that the entities must have to be returned.
+    pub fn set_range(&self, range: impl RangeBounds<T>) {
+        set_bounds(self.0, range.start_bound(), range.end_bound());
+    }
+
+    /// Consume this query and call `callback` (`fn`) each frame with the result of the query.
+    pub fn each_frame<R: CallbackReturn>(
+        self,
+        mut callback: impl FnMut(Vec<(EntityId, Components::Data)>) -> R + 'static,
+    ) -> Listener {
+        Frame::subscribe(move |_| {
+            let results = self.evaluate();
+            if !results.is_empty() {
+                callback(results).into_result()?;
+            }
+            OkEmpty
+        })
+    }
+
+    fn eval(&self, offset: u32, limit: Option<u32>) -> Vec<(EntityId, Components::Data)> {
+        wit::component::index_query_eval(self.0, offset, limit)
+            .into_iter()
+            .map(|(id, components)| {
+                (
+                    id.from_bindgen(),
+                    Components::from_component_types(components)
+                        .expect("invalid type conversion on index query"),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Build an [IndexQuery] for the ECS.
+pub struct IndexQueryBuilder<T, Components> {
+    component: Component<T>,
+    components: Vec<ComponentIndex>,
+    include: Vec<ComponentIndex>,
+    exclude: Vec<ComponentIndex>,
+    descending: bool,
+    start: Bound<T>,
+    end: Bound<T>,
+    _data: PhantomData<Components>,
+}
+impl<T: SupportedValue + Clone + 'static, Components: ComponentsTuple + Copy + Clone + 'static>
+    IndexQueryBuilder<T, Components>
+{
+    /// The entities must include the components in `requires`.
+    pub fn requires(mut self, requires: impl ComponentsTuple) -> Self {
+        self.include.extend_from_slice(&requires.as_indices());
+        self
+    }
+
+    /// The entities must not include the components in `exclude`.
+    pub fn excludes(mut self, excludes: impl ComponentsTuple) -> Self {
+        self.exclude.extend_from_slice(&excludes.as_indices());
+        self
+    }
+
+    /// The entities' value of the indexed component must be within `range`,
+    /// e.g. `..20` or `10.0..=20.0`.
+    pub fn range(mut self, range: impl RangeBounds<T>) -> Self {
+        self.start = range.start_bound().cloned();
+        self.end = range.end_bound().cloned();
+        self
+    }
+
+    /// Return the entities from the highest value to the lowest, instead of from the lowest
+    /// to the highest.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// Builds an [IndexQuery].
+    pub fn build(self) -> IndexQuery<T, Components> {
+        let query = wit::component::index_query(
+            self.component.index(),
+            &wit::component::QueryBuild {
+                components: self.components,
+                includes: self.include,
+                excludes: self.exclude,
+                changed: vec![],
+            },
+            self.descending,
+        );
+        set_bounds(query, self.start.as_ref(), self.end.as_ref());
+        IndexQuery(query, PhantomData)
+    }
+
+    /// Consume this builder and call `callback` (`fn`) each frame with the result of the query.
+    pub fn each_frame<R: CallbackReturn>(
+        self,
+        callback: impl FnMut(Vec<(EntityId, Components::Data)>) -> R + 'static,
+    ) -> Listener {
+        self.build().each_frame(callback)
+    }
+}
+
+fn set_bounds<T: SupportedValue + Clone>(query: u64, start: Bound<&T>, end: Bound<&T>) {
+    wit::component::index_query_clear_bounds(query);
+    for (upper, bound) in [(false, start), (true, end)] {
+        let (value, inclusive) = match bound {
+            Bound::Included(value) => (value, true),
+            Bound::Excluded(value) => (value, false),
+            Bound::Unbounded => continue,
+        };
+        wit::component::index_query_bound(query, upper, inclusive, &value.clone().into_result());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::internal::wit;
+use crate::{global::EntityId, internal::wit};
 use std::marker::PhantomData;
 
+pub(crate) mod index;
+pub(crate) mod observer;
 pub(crate) mod query;
 pub(crate) mod traits;
 
@@ -8,8 +10,12 @@ mod entity;
 pub use entity::*;
 
 pub use traits::{
-    get_component as __internal_get_component, ComponentOptionValue, ComponentValue,
-    ComponentVecValue, SupportedValue, SupportedValueRef,
+    get_component as __internal_get_component, struct_from_result as __internal_struct_from_result,
+    struct_from_value as __internal_struct_from_value,
+    struct_into_result as __internal_struct_into_result,
+    struct_into_value as __internal_struct_into_value, ComponentMapValue, ComponentOptionValue,
+    ComponentValue, ComponentVecValue, SupportedMapKey, SupportedStructField, SupportedValue,
+    SupportedValueRef,
 };
 
 pub(crate) use ambient_shared_types::ComponentIndex;
@@ -52,6 +58,14 @@ impl<T> PartialEq for Component<T> {
     }
 }
 
+/// A [Component] that refers to other entities: an [EntityId] for a many-to-one relationship,
+/// or a `Vec<EntityId>` for a many-to-many relationship.
+///
+/// The component must have the `Relationship` attribute in its package manifest to be used as one.
+pub trait RelationshipComponent: UntypedComponent + Copy {}
+impl RelationshipComponent for Component<EntityId> {}
+impl RelationshipComponent for Component<Vec<EntityId>> {}
+
 /// A tuple of [Component]s.
 pub trait ComponentsTuple {
     /// The types of the data stored in this tuple
@@ -0,0 +1,101 @@
+use crate::{
+    core::messages::Frame,
+    global::{CallbackReturn, EntityId, OkEmpty, ResultEmpty},
+    internal::{component::Component, conversion::FromBindgen, wit},
+    message::Listener,
+    message::RuntimeMessage,
+};
+
+use super::{traits::SupportedValue, UntypedComponent};
+
+/// Calls `callback` (`fn`) with the entity and the new value each time `component`
+/// is added to an entity, including when the entity is spawned.
+///
+/// Unlike a [spawn_query](super::query::spawn_query), this sees every addition, even if
+/// the component is removed again before the next frame.
+///
+/// This should be called once (e.g. in `main`) and not every frame. Stopping the returned
+/// [Listener] removes the observer.
+pub fn on_added<T: SupportedValue + 'static, R: CallbackReturn>(
+    component: Component<T>,
+    mut callback: impl FnMut(EntityId, T) -> R + 'static,
+) -> Listener {
+    observe(
+        component,
+        wit::component::ObserverEvent::Added,
+        move |id, [new]| callback(id, value(component, new)?).into_result(),
+    )
+}
+
+/// Calls `callback` (`fn`) with the entity and the old value each time `component`
+/// is removed from an entity, including when the entity is despawned.
+///
+/// This should be called once (e.g. in `main`) and not every frame. Stopping the returned
+/// [Listener] removes the observer.
+pub fn on_removed<T: SupportedValue + 'static, R: CallbackReturn>(
+    component: Component<T>,
+    mut callback: impl FnMut(EntityId, T) -> R + 'static,
+) -> Listener {
+    observe(
+        component,
+        wit::component::ObserverEvent::Removed,
+        move |id, [old]| callback(id, value(component, old)?).into_result(),
+    )
+}
+
+/// Calls `callback` (`fn`) with the entity, the old value and the new value each time
+/// `component` is overwritten on an entity.
+///
+/// Unlike a [change_query](super::query::change_query), this sees every write, including
+/// several writes in the same frame and writes of the same value.
+///
+/// This should be called once (e.g. in `main`) and not every frame. Stopping the returned
+/// [Listener] removes the observer.
+pub fn on_changed<T: SupportedValue + 'static, R: CallbackReturn>(
+    component: Component<T>,
+    mut callback: impl FnMut(EntityId, T, T) -> R + 'static,
+) -> Listener {
+    observe(
+        component,
+        wit::component::ObserverEvent::Changed,
+        move |id, [old, new]| {
+            callback(id, value(component, old)?, value(component, new)?).into_result()
+        },
+    )
+}
+
+/// Registers the observer, and passes the values of each change to `callback` every frame until
+/// the returned [Listener] is stopped.
+fn observe<T, const N: usize>(
+    component: Component<T>,
+    event: wit::component::ObserverEvent,
+    mut callback: impl FnMut(EntityId, [wit::component::Value; N]) -> ResultEmpty + 'static,
+) -> Listener {
+    let observer = wit::component::observe(component.index(), event);
+    let listener = Frame::subscribe(move |_| {
+        for (id, values) in wit::component::observe_eval(observer) {
+            let values = values.try_into().map_err(|values: Vec<_>| {
+                anyhow::anyhow!(
+                    "expected {N} values from the observer, got {}",
+                    values.len()
+                )
+            })?;
+            callback(id.from_bindgen(), values)?;
+        }
+        OkEmpty
+    });
+    listener.on_stop(move || wit::component::unobserve(observer));
+    listener
+}
+
+fn value<T: SupportedValue>(
+    component: Component<T>,
+    value: wit::component::Value,
+) -> anyhow::Result<T> {
+    T::from_result(value).ok_or_else(|| {
+        anyhow::anyhow!(
+            "invalid type conversion on observer for component {}",
+            component.index()
+        )
+    })
+}
@@ -3,7 +3,11 @@ use std::marker::PhantomData;
 use crate::{
     core::messages::Frame,
     global::{CallbackReturn, EntityId, OkEmpty},
-    internal::{component::ComponentsTuple, conversion::FromBindgen, wit},
+    internal::{
+        component::{ComponentsTuple, RelationshipComponent},
+        conversion::{FromBindgen, IntoBindgen},
+        wit,
+    },
     message::Listener,
     message::RuntimeMessage,
 };
@@ -101,6 +105,12 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> GeneralQueryBuilder<C
         self
     }
 
+    /// The entities must refer to `target` through the relationship `component`.
+    pub fn related(mut self, component: impl RelationshipComponent, target: EntityId) -> Self {
+        self.0.related(component, target);
+        self
+    }
+
     /// Builds a [GeneralQuery].
     pub fn build(self) -> GeneralQuery<Components> {
         GeneralQuery(QueryImpl::new(
@@ -164,6 +174,12 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> ChangeQuery<Component
         self
     }
 
+    /// The entities must refer to `target` through the relationship `component`.
+    pub fn related(mut self, component: impl RelationshipComponent, target: EntityId) -> Self {
+        self.0 .0.related(component, target);
+        self
+    }
+
     /// The query will return results when these components change values.
     ///
     /// Note that this does *not* implicitly [requires](Self::requires) the components; this allows you to track
@@ -215,6 +231,12 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> EventQuery<Components
         self
     }
 
+    /// The entities must refer to `target` through the relationship `component`.
+    pub fn related(mut self, component: impl RelationshipComponent, target: EntityId) -> Self {
+        self.0.related(component, target);
+        self
+    }
+
     /// Each time the entity associated with `components` experiences the event,
     /// the `callback` (`fn`) is called with the result of the query.
     pub fn bind<R: CallbackReturn>(
@@ -276,6 +298,7 @@ struct QueryBuilderImpl<Components: ComponentsTuple + Copy + Clone + 'static> {
     components: Vec<ComponentIndex>,
     include: Vec<ComponentIndex>,
     exclude: Vec<ComponentIndex>,
+    related: Vec<(ComponentIndex, EntityId)>,
     _data: PhantomData<Components>,
 }
 impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Components> {
@@ -284,6 +307,7 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Comp
             components,
             include: vec![],
             exclude: vec![],
+            related: vec![],
             _data: PhantomData,
         }
     }
@@ -293,8 +317,11 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Comp
     pub fn excludes(&mut self, exclude: impl ComponentsTuple) {
         self.exclude.extend_from_slice(&exclude.as_indices());
     }
+    pub fn related(&mut self, component: impl RelationshipComponent, target: EntityId) {
+        self.related.push((component.index(), target));
+    }
     fn build_impl(self, changed: Vec<ComponentIndex>, event: wit::component::QueryEvent) -> u64 {
-        wit::component::query(
+        let query = wit::component::query(
             &wit::component::QueryBuild {
                 components: self.components,
                 includes: self.include,
@@ -302,6 +329,10 @@ impl<Components: ComponentsTuple + Copy + Clone + 'static> QueryBuilderImpl<Comp
                 changed,
             },
             event,
-        )
+        );
+        for (component, target) in self.related {
+            wit::component::query_related(query, component, target.into_bindgen());
+        }
+        query
     }
 }
@@ -13,7 +13,7 @@ use crate::{
 use serde::{Deserialize, Serialize};
 
 use ambient_shared_types::primitive_component_definitions;
-use std::time::Duration;
+use std::{collections::HashMap, hash::Hash, time::Duration};
 
 #[doc(hidden)]
 pub fn get_component<T>(id: &str) -> Component<T> {
@@ -42,6 +42,59 @@ where
     fn into_value(self) -> ComponentValue;
 }
 
+/// Implemented by all types that can be used as the keys of map values in components.
+pub trait SupportedMapKey: Eq + Hash
+where
+    Self: Sized,
+{
+}
+impl SupportedMapKey for String {}
+impl SupportedMapKey for u32 {}
+
+/// Implemented by all types that can be used as the fields of struct components.
+///
+/// Structs are passed to and from the host as a flat list of their fields, where the fields of
+/// nested structs are listed in place of the struct.
+pub trait SupportedStructField
+where
+    Self: Sized,
+{
+    #[doc(hidden)]
+    fn push_struct_fields(self, fields: &mut Vec<ComponentValue>);
+
+    #[doc(hidden)]
+    fn take_struct_fields(fields: &mut dyn Iterator<Item = ComponentValue>) -> Option<Self>;
+}
+
+#[doc(hidden)]
+pub fn struct_from_value<T: SupportedStructField>(value: ComponentValue) -> Option<T> {
+    match value {
+        ComponentValue::Struct(fields) => {
+            let mut fields = fields.into_iter();
+            let value = T::take_struct_fields(&mut fields)?;
+            fields.next().is_none().then_some(value)
+        }
+        _ => None,
+    }
+}
+
+#[doc(hidden)]
+pub fn struct_into_value<T: SupportedStructField>(value: T) -> ComponentValue {
+    let mut fields = vec![];
+    value.push_struct_fields(&mut fields);
+    ComponentValue::Struct(fields)
+}
+
+#[doc(hidden)]
+pub fn struct_from_result<T: SupportedStructField>(result: wit::component::Value) -> Option<T> {
+    struct_from_value(result.from_bindgen())
+}
+
+#[doc(hidden)]
+pub fn struct_into_result<T: SupportedStructField>(value: T) -> wit::component::Value {
+    struct_into_value(value).into_bindgen()
+}
+
 /// Implemented by all types that can be used as referenceable values in components.
 pub trait SupportedValueRef
 where
@@ -62,6 +115,9 @@ macro_rules! define_component_types {
             )*
             Vec(ComponentVecValue),
             Option(ComponentOptionValue),
+            Map(ComponentMapValue),
+            /// The fields of a struct, in order. The fields of nested structs are listed in place of the struct.
+            Struct(Vec<ComponentValue>),
         }
 
         /// A vector value that can be stored in a component.
@@ -82,6 +138,14 @@ macro_rules! define_component_types {
             )*
         }
 
+        /// A map value that can be stored in a component. The keys and values are stored in the same order.
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+        #[allow(missing_docs)]
+        pub struct ComponentMapValue {
+            pub keys: ComponentVecValue,
+            pub values: ComponentVecValue,
+        }
+
         impl IntoBindgen for ComponentValue {
             type Item = wit::component::Value;
             fn into_bindgen(self) -> Self::Item {
@@ -91,6 +155,32 @@ macro_rules! define_component_types {
                     )*
                     Self::Vec(v) => wit::component::Value::TypeVec(v.into_bindgen()),
                     Self::Option(v) => wit::component::Value::TypeOption(v.into_bindgen()),
+                    Self::Map(v) => wit::component::Value::TypeMap(v.into_bindgen()),
+                    Self::Struct(v) => {
+                        let mut fields = vec![];
+                        for value in v {
+                            value.push_wit_struct_fields(&mut fields);
+                        }
+                        wit::component::Value::TypeStruct(fields)
+                    }
+                }
+            }
+        }
+        impl ComponentValue {
+            fn push_wit_struct_fields(self, fields: &mut Vec<wit::component::StructField>) {
+                use wit::component::StructField as SF;
+                match self {
+                    $(
+                        Self::[<$value>](v) => fields.push(SF::[< Type $value >](v.into_bindgen())),
+                    )*
+                    Self::Vec(v) => fields.push(SF::TypeVec(v.into_bindgen())),
+                    Self::Option(v) => fields.push(SF::TypeOption(v.into_bindgen())),
+                    Self::Map(v) => fields.push(SF::TypeMap(v.into_bindgen())),
+                    Self::Struct(v) => {
+                        for value in v {
+                            value.push_wit_struct_fields(fields);
+                        }
+                    }
                 }
             }
         }
@@ -103,6 +193,41 @@ macro_rules! define_component_types {
                     )*
                     wit::component::Value::TypeVec(v) => ComponentValue::Vec(v.from_bindgen()),
                     wit::component::Value::TypeOption(v) => ComponentValue::Option(v.from_bindgen()),
+                    wit::component::Value::TypeMap(v) => ComponentValue::Map(v.from_bindgen()),
+                    wit::component::Value::TypeStruct(v) => ComponentValue::Struct(v.into_iter().map(|v| v.from_bindgen()).collect()),
+                }
+            }
+        }
+        impl FromBindgen for wit::component::StructField {
+            type Item = ComponentValue;
+            fn from_bindgen(self) -> Self::Item {
+                use wit::component::StructField as SF;
+                match self {
+                    $(
+                        SF::[< Type $value >](v) => ComponentValue::[<$value>](v.from_bindgen()),
+                    )*
+                    SF::TypeVec(v) => ComponentValue::Vec(v.from_bindgen()),
+                    SF::TypeOption(v) => ComponentValue::Option(v.from_bindgen()),
+                    SF::TypeMap(v) => ComponentValue::Map(v.from_bindgen()),
+                }
+            }
+        }
+
+        impl IntoBindgen for ComponentMapValue {
+            type Item = wit::component::MapValue;
+            fn into_bindgen(self) -> Self::Item {
+                wit::component::MapValue {
+                    keys: self.keys.into_bindgen(),
+                    values: self.values.into_bindgen(),
+                }
+            }
+        }
+        impl FromBindgen for wit::component::MapValue {
+            type Item = ComponentMapValue;
+            fn from_bindgen(self) -> Self::Item {
+                ComponentMapValue {
+                    keys: self.keys.from_bindgen(),
+                    values: self.values.from_bindgen(),
                 }
             }
         }
@@ -243,6 +368,75 @@ macro_rules! define_component_types {
                 }
             }
         }
+        impl SupportedStructField for $type {
+            fn push_struct_fields(self, fields: &mut Vec<ComponentValue>) {
+                fields.push(self.into_value());
+            }
+
+            fn take_struct_fields(fields: &mut dyn Iterator<Item = ComponentValue>) -> Option<Self> {
+                Self::from_value(fields.next()?)
+            }
+        }
+        impl SupportedStructField for Vec<$type> {
+            fn push_struct_fields(self, fields: &mut Vec<ComponentValue>) {
+                fields.push(self.into_value());
+            }
+
+            fn take_struct_fields(fields: &mut dyn Iterator<Item = ComponentValue>) -> Option<Self> {
+                Self::from_value(fields.next()?)
+            }
+        }
+        impl SupportedStructField for Option<$type> {
+            fn push_struct_fields(self, fields: &mut Vec<ComponentValue>) {
+                fields.push(self.into_value());
+            }
+
+            fn take_struct_fields(fields: &mut dyn Iterator<Item = ComponentValue>) -> Option<Self> {
+                Self::from_value(fields.next()?)
+            }
+        }
+        impl<K: SupportedMapKey> SupportedStructField for HashMap<K, $type>
+        where
+            Vec<K>: SupportedValue,
+        {
+            fn push_struct_fields(self, fields: &mut Vec<ComponentValue>) {
+                fields.push(self.into_value());
+            }
+
+            fn take_struct_fields(fields: &mut dyn Iterator<Item = ComponentValue>) -> Option<Self> {
+                Self::from_value(fields.next()?)
+            }
+        }
+        impl<K: SupportedMapKey> SupportedValue for HashMap<K, $type>
+        where
+            Vec<K>: SupportedValue,
+        {
+            fn from_result(result: wit::component::Value) -> Option<Self> {
+                Self::from_value(result.from_bindgen())
+            }
+
+            fn into_result(self) -> wit::component::Value {
+                self.into_value().into_bindgen()
+            }
+
+            fn from_value(value: ComponentValue) -> Option<Self> {
+                match value {
+                    ComponentValue::Map(ComponentMapValue { keys, values: ComponentVecValue::[<$value>](values) }) => {
+                        let keys = Vec::<K>::from_value(ComponentValue::Vec(keys))?;
+                        Some(keys.into_iter().zip(values).collect())
+                    }
+                    _ => None,
+                }
+            }
+
+            fn into_value(self) -> ComponentValue {
+                let (keys, values): (Vec<K>, Vec<$type>) = self.into_iter().unzip();
+                let ComponentValue::Vec(keys) = keys.into_value() else {
+                    unreachable!("map keys must be converted to a vec value");
+                };
+                ComponentValue::Map(ComponentMapValue { keys, values: ComponentVecValue::[<$value>](values) })
+            }
+        }
         ) *
     } }
 }
@@ -34,6 +34,7 @@ pub(crate) struct Executor {
     current_callbacks: RefCell<Callbacks>,
     incoming_callbacks: RefCell<Callbacks>,
     callbacks_to_remove: RefCell<Vec<(String, u128)>>,
+    unregister_hooks: RefCell<HashMap<u128, Box<dyn FnOnce()>>>,
 }
 // WebAssembly, at time of writing, is single-threaded. This is a convenient little lie
 // to make it easy to use this in a global context.
@@ -49,6 +50,7 @@ impl Executor {
             current_callbacks: RefCell::new(Default::default()),
             incoming_callbacks: RefCell::new(Default::default()),
             callbacks_to_remove: RefCell::new(Default::default()),
+            unregister_hooks: RefCell::new(Default::default()),
         }
     }
 
@@ -127,7 +129,16 @@ impl Executor {
         uid
     }
 
+    /// Calls `hook` when the callback `uid` is unregistered.
+    pub fn on_unregister(&self, uid: u128, hook: Box<dyn FnOnce()>) {
+        self.unregister_hooks.borrow_mut().insert(uid, hook);
+    }
+
     pub fn unregister_callback(&self, event_name: &str, uid: u128) {
+        let hook = self.unregister_hooks.borrow_mut().remove(&uid);
+        if let Some(hook) = hook {
+            hook();
+        }
         if let Some(entry) = self.incoming_callbacks.borrow_mut().on.get_mut(event_name) {
             if entry.remove(&uid).is_some() {
                 return;
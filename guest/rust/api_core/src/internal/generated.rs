@@ -2167,6 +2167,67 @@ mod raw {
                 pub fn gpu_ui_size() -> Component<Vec4> {
                     *GPU_UI_SIZE
                 }
+                static GRID_COLUMNS: Lazy<Component<Vec<Vec2>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_columns"));
+                #[doc = "**Grid columns**: The column tracks of a `Grid` layout, from left to right.\n\nEach track is `[pixels, fraction]`: `[100, 0]` is 100 pixels wide, `[0, 1]` takes one fraction of the remaining space, `[80, 1]` does the same but is at least 80 pixels wide, and `[0, 0]` fits its content.\n\nIf not attached, the grid has a single column that takes all of the space.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_columns() -> Component<Vec<Vec2>> {
+                    *GRID_COLUMNS
+                }
+                static GRID_ROWS: Lazy<Component<Vec<Vec2>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_rows"));
+                #[doc = "**Grid rows**: The row tracks of a `Grid` layout, from top to bottom. See `grid_columns` for the format of each track.\n\nRows that are needed to fit the children but are not specified fit their content.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_rows() -> Component<Vec<Vec2>> {
+                    *GRID_ROWS
+                }
+                static GRID_GAP: Lazy<Component<Vec2>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_gap"));
+                #[doc = "**Grid gap**: Space between the columns (x) and the rows (y) of a `Grid` layout.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_gap() -> Component<Vec2> {
+                    *GRID_GAP
+                }
+                static GRID_COLUMN: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_column"));
+                #[doc = "**Grid column**: The column this element is placed in, when it is a child of a `Grid` layout.\n\nChildren without a `grid_column` or `grid_row` are placed in the first free cells, row by row.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_column() -> Component<u32> {
+                    *GRID_COLUMN
+                }
+                static GRID_ROW: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_row"));
+                #[doc = "**Grid row**: The row this element is placed in, when it is a child of a `Grid` layout.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_row() -> Component<u32> {
+                    *GRID_ROW
+                }
+                static GRID_COLUMN_SPAN: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::layout::grid_column_span")
+                });
+                #[doc = "**Grid column span**: The number of columns this element spans, when it is a child of a `Grid` layout. Defaults to 1.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_column_span() -> Component<u32> {
+                    *GRID_COLUMN_SPAN
+                }
+                static GRID_ROW_SPAN: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_row_span"));
+                #[doc = "**Grid row span**: The number of rows this element spans, when it is a child of a `Grid` layout. Defaults to 1.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_row_span() -> Component<u32> {
+                    *GRID_ROW_SPAN
+                }
+                static FLEX_GROW: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_grow"));
+                #[doc = "**Flex grow**: How much of the remaining space this element takes, relative to its siblings, when it is a child of a `Flow` layout.\n\nElements with a `flex_grow`, `flex_shrink` or `flex_basis` are sized by the `Flow` along its orientation, and the `Flow` no longer wraps them onto multiple lines. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn flex_grow() -> Component<f32> {
+                    *FLEX_GROW
+                }
+                static FLEX_SHRINK: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_shrink"));
+                #[doc = "**Flex shrink**: How much this element shrinks, relative to its siblings and its `flex_basis`, when the children of a `Flow` layout do not fit. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn flex_shrink() -> Component<f32> {
+                    *FLEX_SHRINK
+                }
+                static FLEX_BASIS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_basis"));
+                #[doc = "**Flex basis**: The size of this element along the orientation of its parent `Flow` layout, before any remaining space is distributed with `flex_grow` and `flex_shrink`. Defaults to 0.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn flex_basis() -> Component<f32> {
+                    *FLEX_BASIS
+                }
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -2495,6 +2556,8 @@ mod raw {
                     Bookcase,
                     #[doc = "Width to children."]
                     WidthToChildren,
+                    #[doc = "Grid layout with column and row tracks."]
+                    Grid,
                 }
                 impl crate::ecs::EnumComponent for Layout {
                     fn to_u32(&self) -> u32 {
@@ -2503,6 +2566,7 @@ mod raw {
                             Self::Dock => Layout::Dock as u32,
                             Self::Bookcase => Layout::Bookcase as u32,
                             Self::WidthToChildren => Layout::WidthToChildren as u32,
+                            Self::Grid => Layout::Grid as u32,
                         }
                     }
                     fn from_u32(value: u32) -> Option<Self> {
@@ -2518,6 +2582,9 @@ mod raw {
                         if value == Layout::WidthToChildren as u32 {
                             return Some(Self::WidthToChildren);
                         }
+                        if value == Layout::Grid as u32 {
+                            return Some(Self::Grid);
+                        }
                         None
                     }
                 }
@@ -2707,6 +2774,12 @@ mod raw {
                 pub fn server_modules() -> Component<Vec<EntityId>> {
                     *SERVER_MODULES
                 }
+                static INPUT_ACTIONS: Lazy<Component<std::collections::HashMap<String, String>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::package::input_actions"));
+                #[doc = "**Input Actions**: The input actions declared in the package's manifest, mapped to their comma-separated default bindings (e.g. `Key:Space, Gamepad:South`). If not attached, the package does not declare any actions.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn input_actions() -> Component<std::collections::HashMap<String, String>> {
+                    *INPUT_ACTIONS
+                }
             }
             #[doc = r" Auto-generated concept definitions. Concepts are collections of components that describe some form of gameplay concept."]
             #[doc = r""]
@@ -3144,6 +3217,12 @@ mod raw {
                 pub fn angular_velocity() -> Component<Vec3> {
                     *ANGULAR_VELOCITY
                 }
+                static CCD: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ccd"));
+                #[doc = "**CCD**: Whether swept continuous collision detection (CCD) is enabled for this dynamic entity, which stops fast-moving objects from passing through thin colliders.\n\nIf not attached, it is enabled for all dynamic entities that are not kinematic. It is not supported for kinematic entities; use `speculative_ccd` for those instead.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn ccd() -> Component<bool> {
+                    *CCD
+                }
                 static CUBE_COLLIDER: Lazy<Component<Vec3>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::cube_collider"));
                 #[doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -3184,6 +3263,19 @@ mod raw {
                 pub fn collider_loads() -> Component<Vec<EntityId>> {
                     *COLLIDER_LOADS
                 }
+                static COLLISION_LAYER: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collision_layer")
+                });
+                #[doc = "**Collision layer**: The collision layers this entity's collider is on, as a bitmask (one bit per layer).\n\nLayers can be named in the package manifest under `[collision_layers]`. If not attached, the collider is on layer 0 only.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"]
+                pub fn collision_layer() -> Component<u32> {
+                    *COLLISION_LAYER
+                }
+                static COLLISION_MASK: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::collision_mask"));
+                #[doc = "**Collision mask**: The collision layers this entity's collider collides with, as a bitmask (one bit per layer).\n\nTwo colliders only collide if each one's `collision_layer` overlaps the other's `collision_mask`. If not attached, the collider collides with all layers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 4294967295"]
+                pub fn collision_mask() -> Component<u32> {
+                    *COLLISION_MASK
+                }
                 static CONTACT_OFFSET: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::contact_offset"));
                 #[doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -3202,6 +3294,30 @@ mod raw {
                 pub fn dynamic() -> Component<bool> {
                     *DYNAMIC
                 }
+                static DYNAMIC_FRICTION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::dynamic_friction")
+                });
+                #[doc = "**Dynamic friction**: The friction coefficient of this entity's collider when it is sliding against another collider.\n\nCombined with the other collider's friction using `friction_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn dynamic_friction() -> Component<f32> {
+                    *DYNAMIC_FRICTION
+                }
+                static ENTITIES_IN_TRIGGER: Lazy<Component<Vec<EntityId>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::entities_in_trigger")
+                });
+                #[doc = "**Entities in trigger**: The entities that are currently inside this trigger area collider (a collider whose type is `TriggerArea`).\n\nThis is updated by the physics simulation; use the `TriggerEnter` and `TriggerExit` messages to react to changes.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn entities_in_trigger() -> Component<Vec<EntityId>> {
+                    *ENTITIES_IN_TRIGGER
+                }
+                static FRICTION_COMBINE_MODE: Lazy<
+                    Component<crate::ambient_core::physics::types::CombineMode>,
+                > = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::friction_combine_mode")
+                });
+                #[doc = "**Friction combine mode**: How the friction of this entity's collider is combined with the friction of the collider it touches.\n\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn friction_combine_mode(
+                ) -> Component<crate::ambient_core::physics::types::CombineMode> {
+                    *FRICTION_COMBINE_MODE
+                }
                 static KINEMATIC: Lazy<Component<()>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::kinematic"));
                 #[doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -3241,12 +3357,60 @@ mod raw {
                 pub fn plane_collider() -> Component<()> {
                     *PLANE_COLLIDER
                 }
+                static RAGDOLL_BLEND: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ragdoll_blend"));
+                #[doc = "**Ragdoll blend**: How much this entity's ragdoll (see `ragdoll_from_url`) overrides its animation, from 0 (fully animated) to 1 (fully simulated).\n\nThe ragdoll is simulated while this is above 0, starting from the model's bind pose, and is removed when it goes back to 0.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn ragdoll_blend() -> Component<f32> {
+                    *RAGDOLL_BLEND
+                }
+                static RAGDOLL_BONE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ragdoll_bone"));
+                #[doc = "**Ragdoll bone**: The bind ID of the model bone that this ragdoll bone entity (see `ragdoll_bones`) simulates.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn ragdoll_bone() -> Component<String> {
+                    *RAGDOLL_BONE
+                }
+                static RAGDOLL_BONES: Lazy<Component<Vec<EntityId>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ragdoll_bones"));
+                #[doc = "**Ragdoll bones**: The entities simulating the bones of this entity's ragdoll while it is active (see `ragdoll_blend`), with parents before their children.\n\nEach of them has a `ragdoll_bone`, and its `translation` and `rotation` are the world transform of that bone in the physics scene.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn ragdoll_bones() -> Component<Vec<EntityId>> {
+                    *RAGDOLL_BONES
+                }
+                static RAGDOLL_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::ragdoll_from_url")
+                });
+                #[doc = "**Ragdoll from URL**: Load a ragdoll for this entity's model from this URL. The ragdoll is generated by the model pipeline when `ragdoll` is set.\n\nUse `ragdoll_blend` to switch between the animated and the simulated pose.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn ragdoll_from_url() -> Component<String> {
+                    *RAGDOLL_FROM_URL
+                }
                 static REST_OFFSET: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::rest_offset"));
                 #[doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn rest_offset() -> Component<f32> {
                     *REST_OFFSET
                 }
+                static RESTITUTION: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::restitution"));
+                #[doc = "**Restitution**: The bounciness of this entity's collider, from 0 (no bounce) to 1 (a perfectly elastic bounce).\n\nCombined with the other collider's restitution using `restitution_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"]
+                pub fn restitution() -> Component<f32> {
+                    *RESTITUTION
+                }
+                static RESTITUTION_COMBINE_MODE: Lazy<
+                    Component<crate::ambient_core::physics::types::CombineMode>,
+                > = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::restitution_combine_mode")
+                });
+                #[doc = "**Restitution combine mode**: How the restitution of this entity's collider is combined with the restitution of the collider it touches.\n\nIf the two colliders use different modes, the one that comes last in `CombineMode` is used.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn restitution_combine_mode(
+                ) -> Component<crate::ambient_core::physics::types::CombineMode> {
+                    *RESTITUTION_COMBINE_MODE
+                }
+                static SPECULATIVE_CCD: Lazy<Component<bool>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::speculative_ccd")
+                });
+                #[doc = "**Speculative CCD**: If true, speculative contacts are generated for this dynamic entity, based on its velocity.\n\nThis is cheaper than swept CCD (see `ccd`) and also works for kinematic entities, but can miss collisions with objects that rotate quickly.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"]
+                pub fn speculative_ccd() -> Component<bool> {
+                    *SPECULATIVE_CCD
+                }
                 static SPHERE_COLLIDER: Lazy<Component<f32>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::sphere_collider")
                 });
@@ -3254,6 +3418,13 @@ mod raw {
                 pub fn sphere_collider() -> Component<f32> {
                     *SPHERE_COLLIDER
                 }
+                static STATIC_FRICTION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::static_friction")
+                });
+                #[doc = "**Static friction**: The friction coefficient of this entity's collider when it is at rest against another collider.\n\nCombined with the other collider's friction using `friction_combine_mode`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn static_friction() -> Component<f32> {
+                    *STATIC_FRICTION
+                }
                 static UNIT_MASS: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::unit_mass"));
                 #[doc = "**Unit mass**: The mass of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -3272,6 +3443,39 @@ mod raw {
                 pub fn unit_yaw() -> Component<f32> {
                     *UNIT_YAW
                 }
+                static VEHICLE_BRAKE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::vehicle_brake"));
+                #[doc = "**Vehicle brake**: How hard this vehicle is braking, from 0 to 1. Each wheel (see `vehicle_wheels`) is braked with this fraction of its `wheel_brake_torque`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_brake() -> Component<f32> {
+                    *VEHICLE_BRAKE
+                }
+                static VEHICLE_MAX_SPEED: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_max_speed")
+                });
+                #[doc = "**Vehicle max speed**: The top speed (in meters/second) of this vehicle's wheels. The drive torque of each wheel fades out as it spins up to this speed, which also stops wheels that are in the air or skidding from spinning up forever.\n\nIf not attached, it is 50 m/s.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 50.0"]
+                pub fn vehicle_max_speed() -> Component<f32> {
+                    *VEHICLE_MAX_SPEED
+                }
+                static VEHICLE_STEERING: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_steering")
+                });
+                #[doc = "**Vehicle steering**: How much this vehicle is steering, from -1 (right) to 1 (left). Each wheel (see `vehicle_wheels`) is turned by this fraction of its `wheel_max_steering_angle`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_steering() -> Component<f32> {
+                    *VEHICLE_STEERING
+                }
+                static VEHICLE_THROTTLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_throttle")
+                });
+                #[doc = "**Vehicle throttle**: How much this vehicle is accelerating, from -1 (full reverse) to 1 (full forward). Each wheel (see `vehicle_wheels`) is driven with this fraction of its `wheel_drive_torque`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_throttle() -> Component<f32> {
+                    *VEHICLE_THROTTLE
+                }
+                static VEHICLE_WHEELS: Lazy<Component<Vec<EntityId>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::vehicle_wheels"));
+                #[doc = "**Vehicle wheels**: The wheels of this vehicle, which are entities with the `VehicleWheel` concept. The vehicle itself must be a dynamic physics object (with a collider, `dynamic` and `physics_controlled`), which drives along its local -Y axis with Z up.\n\nEach wheel is simulated with a raycast from its `wheel_offset` down the suspension every physics step, and its `translation` and `rotation` are set to its world transform, so the wheel entities should not have a `parent`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_wheels() -> Component<Vec<EntityId>> {
+                    *VEHICLE_WHEELS
+                }
                 static VISUALIZE_COLLIDER: Lazy<Component<()>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::visualize_collider")
                 });
@@ -3279,6 +3483,225 @@ mod raw {
                 pub fn visualize_collider() -> Component<()> {
                     *VISUALIZE_COLLIDER
                 }
+                static WHEEL_ANGULAR_VELOCITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_angular_velocity")
+                });
+                #[doc = "**Wheel angular velocity**: How fast this wheel is spinning around its axle, in radians/second. Positive values roll the vehicle forward.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_angular_velocity() -> Component<f32> {
+                    *WHEEL_ANGULAR_VELOCITY
+                }
+                static WHEEL_BRAKE_TORQUE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_brake_torque")
+                });
+                #[doc = "**Wheel brake torque**: The torque (in N·m) that stops this wheel from spinning when its vehicle is braking fully (see `vehicle_brake`).\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_brake_torque() -> Component<f32> {
+                    *WHEEL_BRAKE_TORQUE
+                }
+                static WHEEL_CONTACT_ENTITY: Lazy<Component<EntityId>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_contact_entity")
+                });
+                #[doc = "**Wheel contact entity**: The entity that this wheel is touching, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_contact_entity() -> Component<EntityId> {
+                    *WHEEL_CONTACT_ENTITY
+                }
+                static WHEEL_CONTACT_NORMAL: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_contact_normal")
+                });
+                #[doc = "**Wheel contact normal**: The normal of the surface that this wheel is touching, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_contact_normal() -> Component<Vec3> {
+                    *WHEEL_CONTACT_NORMAL
+                }
+                static WHEEL_CONTACT_POINT: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_contact_point")
+                });
+                #[doc = "**Wheel contact point**: The point (in world space) where this wheel is touching the ground, while `wheel_in_contact` is true.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_contact_point() -> Component<Vec3> {
+                    *WHEEL_CONTACT_POINT
+                }
+                static WHEEL_DRIVE_TORQUE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_drive_torque")
+                });
+                #[doc = "**Wheel drive torque**: The torque (in N·m) that drives this wheel when its vehicle is at full throttle (see `vehicle_throttle`). Wheels with a drive torque of 0 are not driven.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_drive_torque() -> Component<f32> {
+                    *WHEEL_DRIVE_TORQUE
+                }
+                static WHEEL_FRICTION: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::wheel_friction"));
+                #[doc = "**Wheel friction**: The peak friction coefficient of this wheel's tire, which it has while its `wheel_slip` is below `wheel_peak_slip`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_friction() -> Component<f32> {
+                    *WHEEL_FRICTION
+                }
+                static WHEEL_IN_CONTACT: Lazy<Component<bool>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_in_contact")
+                });
+                #[doc = "**Wheel in contact**: Whether this wheel is touching the ground.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_in_contact() -> Component<bool> {
+                    *WHEEL_IN_CONTACT
+                }
+                static WHEEL_INERTIA: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::wheel_inertia"));
+                #[doc = "**Wheel inertia**: The moment of inertia (in kg·m²) of this wheel around its axle. Lighter wheels spin up and lock up more easily.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_inertia() -> Component<f32> {
+                    *WHEEL_INERTIA
+                }
+                static WHEEL_MAX_STEERING_ANGLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_max_steering_angle")
+                });
+                #[doc = "**Wheel max steering angle**: The angle (in radians) that this wheel is turned by when its vehicle is steering fully (see `vehicle_steering`). Use negative values for wheels that steer the opposite way, and 0 for wheels that don't steer.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_max_steering_angle() -> Component<f32> {
+                    *WHEEL_MAX_STEERING_ANGLE
+                }
+                static WHEEL_OFFSET: Lazy<Component<Vec3>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::wheel_offset"));
+                #[doc = "**Wheel offset**: The top of this wheel's suspension, relative to its vehicle. At full extension, the center of the wheel is `wheel_suspension_length` below it.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_offset() -> Component<Vec3> {
+                    *WHEEL_OFFSET
+                }
+                static WHEEL_PEAK_SLIP: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_peak_slip")
+                });
+                #[doc = "**Wheel peak slip**: How fast (in meters/second) this wheel's tire can slide over the ground before it loses grip. Above this, its friction falls from `wheel_friction` to `wheel_sliding_friction`, which it reaches at twice this speed.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_peak_slip() -> Component<f32> {
+                    *WHEEL_PEAK_SLIP
+                }
+                static WHEEL_RADIUS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::wheel_radius"));
+                #[doc = "**Wheel radius**: The radius of this wheel.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_radius() -> Component<f32> {
+                    *WHEEL_RADIUS
+                }
+                static WHEEL_SLIDING_FRICTION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_sliding_friction")
+                });
+                #[doc = "**Wheel sliding friction**: The friction coefficient of this wheel's tire while it is sliding over the ground (see `wheel_peak_slip`).\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_sliding_friction() -> Component<f32> {
+                    *WHEEL_SLIDING_FRICTION
+                }
+                static WHEEL_SLIP: Lazy<Component<Vec2>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::wheel_slip"));
+                #[doc = "**Wheel slip**: How fast (in meters/second) this wheel's tire is sliding over the ground, as `(longitudinal, lateral)`. The longitudinal slip is positive when the wheel is spinning faster than the ground, and negative when it's skidding.\n\nThis is useful for tire smoke and skid marks.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_slip() -> Component<Vec2> {
+                    *WHEEL_SLIP
+                }
+                static WHEEL_STEERING_ANGLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_steering_angle")
+                });
+                #[doc = "**Wheel steering angle**: The angle (in radians) that this wheel is currently turned by.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_steering_angle() -> Component<f32> {
+                    *WHEEL_STEERING_ANGLE
+                }
+                static WHEEL_SUSPENSION_COMPRESSION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_suspension_compression")
+                });
+                #[doc = "**Wheel suspension compression**: How far (in meters) this wheel's suspension is compressed, from 0 at full extension to `wheel_suspension_length`.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn wheel_suspension_compression() -> Component<f32> {
+                    *WHEEL_SUSPENSION_COMPRESSION
+                }
+                static WHEEL_SUSPENSION_DAMPING: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_suspension_damping")
+                });
+                #[doc = "**Wheel suspension damping**: The damping (in N·s/m) of this wheel's suspension, which stops its vehicle from bouncing.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_suspension_damping() -> Component<f32> {
+                    *WHEEL_SUSPENSION_DAMPING
+                }
+                static WHEEL_SUSPENSION_LENGTH: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_suspension_length")
+                });
+                #[doc = "**Wheel suspension length**: How far (in meters) this wheel's suspension can travel.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_suspension_length() -> Component<f32> {
+                    *WHEEL_SUSPENSION_LENGTH
+                }
+                static WHEEL_SUSPENSION_STIFFNESS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::wheel_suspension_stiffness")
+                });
+                #[doc = "**Wheel suspension stiffness**: The stiffness (in N/m) of this wheel's suspension.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wheel_suspension_stiffness() -> Component<f32> {
+                    *WHEEL_SUSPENSION_STIFFNESS
+                }
+            }
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use crate::{global::serde, message::*};
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**CombineMode**: How the material properties of two touching colliders are combined."]
+                pub enum CombineMode {
+                    #[default]
+                    #[doc = "Use the average of the two values."]
+                    Average,
+                    #[doc = "Use the smaller of the two values."]
+                    Min,
+                    #[doc = "Multiply the two values."]
+                    Multiply,
+                    #[doc = "Use the larger of the two values."]
+                    Max,
+                }
+                impl crate::ecs::EnumComponent for CombineMode {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Average => CombineMode::Average as u32,
+                            Self::Min => CombineMode::Min as u32,
+                            Self::Multiply => CombineMode::Multiply as u32,
+                            Self::Max => CombineMode::Max as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == CombineMode::Average as u32 {
+                            return Some(Self::Average);
+                        }
+                        if value == CombineMode::Min as u32 {
+                            return Some(Self::Min);
+                        }
+                        if value == CombineMode::Multiply as u32 {
+                            return Some(Self::Multiply);
+                        }
+                        if value == CombineMode::Max as u32 {
+                            return Some(Self::Max);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for CombineMode {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for CombineMode {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
             #[doc = r" Auto-generated concept definitions. Concepts are collections of components that describe some form of gameplay concept."]
             #[doc = r""]
@@ -3356,6 +3779,139 @@ mod raw {
                         }
                     }
                 }
+                #[doc = "**Vehicle Wheel**: A wheel of a vehicle (see `vehicle_wheels`), which is simulated with a raycast suspension and a tire friction model. The suggested values suit a car of about 1000 kg with four wheels; set the `mass` of the vehicle to match.\n\nWhile the vehicle is simulated, the wheel's contact with the ground is described by `wheel_in_contact`, `wheel_contact_point`, `wheel_contact_normal`, `wheel_contact_entity`, `wheel_suspension_compression` and `wheel_slip`.\n\n**Required**:\n- `wheel_brake_torque`: The torque (in N·m) that stops this wheel from spinning when its vehicle is braking fully (see `vehicle_brake`).\n- `wheel_drive_torque`: The torque (in N·m) that drives this wheel when its vehicle is at full throttle (see `vehicle_throttle`). Wheels with a drive torque of 0 are not driven.\n- `wheel_friction`: The peak friction coefficient of this wheel's tire, which it has while its `wheel_slip` is below `wheel_peak_slip`.\n- `wheel_inertia`: The moment of inertia (in kg·m²) of this wheel around its axle. Lighter wheels spin up and lock up more easily.\n- `wheel_max_steering_angle`: The angle (in radians) that this wheel is turned by when its vehicle is steering fully (see `vehicle_steering`). Use negative values for wheels that steer the opposite way, and 0 for wheels that don't steer.\n- `wheel_offset`: The top of this wheel's suspension, relative to its vehicle. At full extension, the center of the wheel is `wheel_suspension_length` below it.\n- `wheel_peak_slip`: How fast (in meters/second) this wheel's tire can slide over the ground before it loses grip. Above this, its friction falls from `wheel_friction` to `wheel_sliding_friction`, which it reaches at twice this speed.\n- `wheel_radius`: The radius of this wheel.\n- `wheel_sliding_friction`: The friction coefficient of this wheel's tire while it is sliding over the ground (see `wheel_peak_slip`).\n- `wheel_suspension_damping`: The damping (in N·s/m) of this wheel's suspension, which stops its vehicle from bouncing.\n- `wheel_suspension_length`: How far (in meters) this wheel's suspension can travel.\n- `wheel_suspension_stiffness`: The stiffness (in N/m) of this wheel's suspension."]
+                #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+                #[serde(crate = "self::serde")]
+                pub struct VehicleWheel {
+                    #[doc = "**Component**: `ambient_core::physics::wheel_brake_torque`\n\n**Suggested value**: `1500f32`\n\n**Component description**: The torque (in N·m) that stops this wheel from spinning when its vehicle is braking fully (see `vehicle_brake`).\n\n"]
+                    pub wheel_brake_torque: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_drive_torque`\n\n**Suggested value**: `0f32`\n\n**Component description**: The torque (in N·m) that drives this wheel when its vehicle is at full throttle (see `vehicle_throttle`). Wheels with a drive torque of 0 are not driven.\n\n"]
+                    pub wheel_drive_torque: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_friction`\n\n**Suggested value**: `1.2f32`\n\n**Component description**: The peak friction coefficient of this wheel's tire, which it has while its `wheel_slip` is below `wheel_peak_slip`.\n\n"]
+                    pub wheel_friction: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_inertia`\n\n**Suggested value**: `1f32`\n\n**Component description**: The moment of inertia (in kg·m²) of this wheel around its axle. Lighter wheels spin up and lock up more easily.\n\n"]
+                    pub wheel_inertia: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_max_steering_angle`\n\n**Suggested value**: `0f32`\n\n**Component description**: The angle (in radians) that this wheel is turned by when its vehicle is steering fully (see `vehicle_steering`). Use negative values for wheels that steer the opposite way, and 0 for wheels that don't steer.\n\n"]
+                    pub wheel_max_steering_angle: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_offset`\n\n**Suggested value**: `Vec3::new(0f32, 0f32, 0f32, )`\n\n**Component description**: The top of this wheel's suspension, relative to its vehicle. At full extension, the center of the wheel is `wheel_suspension_length` below it.\n\n"]
+                    pub wheel_offset: Vec3,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_peak_slip`\n\n**Suggested value**: `0.5f32`\n\n**Component description**: How fast (in meters/second) this wheel's tire can slide over the ground before it loses grip. Above this, its friction falls from `wheel_friction` to `wheel_sliding_friction`, which it reaches at twice this speed.\n\n"]
+                    pub wheel_peak_slip: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_radius`\n\n**Suggested value**: `0.4f32`\n\n**Component description**: The radius of this wheel.\n\n"]
+                    pub wheel_radius: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_sliding_friction`\n\n**Suggested value**: `0.8f32`\n\n**Component description**: The friction coefficient of this wheel's tire while it is sliding over the ground (see `wheel_peak_slip`).\n\n"]
+                    pub wheel_sliding_friction: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_suspension_damping`\n\n**Suggested value**: `4000f32`\n\n**Component description**: The damping (in N·s/m) of this wheel's suspension, which stops its vehicle from bouncing.\n\n"]
+                    pub wheel_suspension_damping: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_suspension_length`\n\n**Suggested value**: `0.3f32`\n\n**Component description**: How far (in meters) this wheel's suspension can travel.\n\n"]
+                    pub wheel_suspension_length: f32,
+                    #[doc = "**Component**: `ambient_core::physics::wheel_suspension_stiffness`\n\n**Suggested value**: `50000f32`\n\n**Component description**: The stiffness (in N/m) of this wheel's suspension.\n\n"]
+                    pub wheel_suspension_stiffness: f32,
+                }
+                impl Concept for VehicleWheel {
+                    fn make(self) -> Entity {
+                        let mut entity = Entity :: new () . with (crate :: ambient_core :: physics :: components :: wheel_brake_torque () , self . wheel_brake_torque) . with (crate :: ambient_core :: physics :: components :: wheel_drive_torque () , self . wheel_drive_torque) . with (crate :: ambient_core :: physics :: components :: wheel_friction () , self . wheel_friction) . with (crate :: ambient_core :: physics :: components :: wheel_inertia () , self . wheel_inertia) . with (crate :: ambient_core :: physics :: components :: wheel_max_steering_angle () , self . wheel_max_steering_angle) . with (crate :: ambient_core :: physics :: components :: wheel_offset () , self . wheel_offset) . with (crate :: ambient_core :: physics :: components :: wheel_peak_slip () , self . wheel_peak_slip) . with (crate :: ambient_core :: physics :: components :: wheel_radius () , self . wheel_radius) . with (crate :: ambient_core :: physics :: components :: wheel_sliding_friction () , self . wheel_sliding_friction) . with (crate :: ambient_core :: physics :: components :: wheel_suspension_damping () , self . wheel_suspension_damping) . with (crate :: ambient_core :: physics :: components :: wheel_suspension_length () , self . wheel_suspension_length) . with (crate :: ambient_core :: physics :: components :: wheel_suspension_stiffness () , self . wheel_suspension_stiffness) ;
+                        entity
+                    }
+                    fn get_spawned(id: EntityId) -> Option<Self> {
+                        Some (Self { wheel_brake_torque : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_brake_torque ()) ? , wheel_drive_torque : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_drive_torque ()) ? , wheel_friction : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_friction ()) ? , wheel_inertia : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_inertia ()) ? , wheel_max_steering_angle : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_max_steering_angle ()) ? , wheel_offset : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_offset ()) ? , wheel_peak_slip : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_peak_slip ()) ? , wheel_radius : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_radius ()) ? , wheel_sliding_friction : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_sliding_friction ()) ? , wheel_suspension_damping : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_suspension_damping ()) ? , wheel_suspension_length : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_suspension_length ()) ? , wheel_suspension_stiffness : entity :: get_component (id , crate :: ambient_core :: physics :: components :: wheel_suspension_stiffness ()) ? , })
+                    }
+                    fn get_unspawned(entity: &Entity) -> Option<Self> {
+                        Some (Self { wheel_brake_torque : entity . get (crate :: ambient_core :: physics :: components :: wheel_brake_torque ()) ? , wheel_drive_torque : entity . get (crate :: ambient_core :: physics :: components :: wheel_drive_torque ()) ? , wheel_friction : entity . get (crate :: ambient_core :: physics :: components :: wheel_friction ()) ? , wheel_inertia : entity . get (crate :: ambient_core :: physics :: components :: wheel_inertia ()) ? , wheel_max_steering_angle : entity . get (crate :: ambient_core :: physics :: components :: wheel_max_steering_angle ()) ? , wheel_offset : entity . get (crate :: ambient_core :: physics :: components :: wheel_offset ()) ? , wheel_peak_slip : entity . get (crate :: ambient_core :: physics :: components :: wheel_peak_slip ()) ? , wheel_radius : entity . get (crate :: ambient_core :: physics :: components :: wheel_radius ()) ? , wheel_sliding_friction : entity . get (crate :: ambient_core :: physics :: components :: wheel_sliding_friction ()) ? , wheel_suspension_damping : entity . get (crate :: ambient_core :: physics :: components :: wheel_suspension_damping ()) ? , wheel_suspension_length : entity . get (crate :: ambient_core :: physics :: components :: wheel_suspension_length ()) ? , wheel_suspension_stiffness : entity . get (crate :: ambient_core :: physics :: components :: wheel_suspension_stiffness ()) ? , })
+                    }
+                    fn contained_by_spawned(id: EntityId) -> bool {
+                        entity :: has_components (id , & [& crate :: ambient_core :: physics :: components :: wheel_brake_torque () , & crate :: ambient_core :: physics :: components :: wheel_drive_torque () , & crate :: ambient_core :: physics :: components :: wheel_friction () , & crate :: ambient_core :: physics :: components :: wheel_inertia () , & crate :: ambient_core :: physics :: components :: wheel_max_steering_angle () , & crate :: ambient_core :: physics :: components :: wheel_offset () , & crate :: ambient_core :: physics :: components :: wheel_peak_slip () , & crate :: ambient_core :: physics :: components :: wheel_radius () , & crate :: ambient_core :: physics :: components :: wheel_sliding_friction () , & crate :: ambient_core :: physics :: components :: wheel_suspension_damping () , & crate :: ambient_core :: physics :: components :: wheel_suspension_length () , & crate :: ambient_core :: physics :: components :: wheel_suspension_stiffness ()])
+                    }
+                    fn contained_by_unspawned(entity: &Entity) -> bool {
+                        entity.has_components(&[
+                            &crate::ambient_core::physics::components::wheel_brake_torque(),
+                            &crate::ambient_core::physics::components::wheel_drive_torque(),
+                            &crate::ambient_core::physics::components::wheel_friction(),
+                            &crate::ambient_core::physics::components::wheel_inertia(),
+                            &crate::ambient_core::physics::components::wheel_max_steering_angle(),
+                            &crate::ambient_core::physics::components::wheel_offset(),
+                            &crate::ambient_core::physics::components::wheel_peak_slip(),
+                            &crate::ambient_core::physics::components::wheel_radius(),
+                            &crate::ambient_core::physics::components::wheel_sliding_friction(),
+                            &crate::ambient_core::physics::components::wheel_suspension_damping(),
+                            &crate::ambient_core::physics::components::wheel_suspension_length(),
+                            &crate::ambient_core::physics::components::wheel_suspension_stiffness(),
+                        ])
+                    }
+                }
+                impl ConceptSuggested for VehicleWheel {
+                    #[doc = "```\nwheel_brake_torque: 1500f32,\nwheel_drive_torque: 0f32,\nwheel_friction: 1.2f32,\nwheel_inertia: 1f32,\nwheel_max_steering_angle: 0f32,\nwheel_offset: Vec3::new(0f32, 0f32, 0f32, ),\nwheel_peak_slip: 0.5f32,\nwheel_radius: 0.4f32,\nwheel_sliding_friction: 0.8f32,\nwheel_suspension_damping: 4000f32,\nwheel_suspension_length: 0.3f32,\nwheel_suspension_stiffness: 50000f32,\n```"]
+                    fn suggested() -> Self {
+                        Self {
+                            wheel_brake_torque: 1500f32,
+                            wheel_drive_torque: 0f32,
+                            wheel_friction: 1.2f32,
+                            wheel_inertia: 1f32,
+                            wheel_max_steering_angle: 0f32,
+                            wheel_offset: Vec3::new(0f32, 0f32, 0f32),
+                            wheel_peak_slip: 0.5f32,
+                            wheel_radius: 0.4f32,
+                            wheel_sliding_friction: 0.8f32,
+                            wheel_suspension_damping: 4000f32,
+                            wheel_suspension_length: 0.3f32,
+                            wheel_suspension_stiffness: 50000f32,
+                        }
+                    }
+                }
+                impl ConceptComponents for VehicleWheel {
+                    type Required = (
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<Vec3>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                        Component<f32>,
+                    );
+                    type Optional = ();
+                    fn required() -> Self::Required {
+                        (
+                            crate::ambient_core::physics::components::wheel_brake_torque(),
+                            crate::ambient_core::physics::components::wheel_drive_torque(),
+                            crate::ambient_core::physics::components::wheel_friction(),
+                            crate::ambient_core::physics::components::wheel_inertia(),
+                            crate::ambient_core::physics::components::wheel_max_steering_angle(),
+                            crate::ambient_core::physics::components::wheel_offset(),
+                            crate::ambient_core::physics::components::wheel_peak_slip(),
+                            crate::ambient_core::physics::components::wheel_radius(),
+                            crate::ambient_core::physics::components::wheel_sliding_friction(),
+                            crate::ambient_core::physics::components::wheel_suspension_damping(),
+                            crate::ambient_core::physics::components::wheel_suspension_length(),
+                            crate::ambient_core::physics::components::wheel_suspension_stiffness(),
+                        )
+                    }
+                    fn optional() -> Self::Optional {
+                        ()
+                    }
+                    fn from_required_data(
+                        required: <Self::Required as ComponentsTuple>::Data,
+                    ) -> Self {
+                        Self {
+                            wheel_brake_torque: required.0,
+                            wheel_drive_torque: required.1,
+                            wheel_friction: required.2,
+                            wheel_inertia: required.3,
+                            wheel_max_steering_angle: required.4,
+                            wheel_offset: required.5,
+                            wheel_peak_slip: required.6,
+                            wheel_radius: required.7,
+                            wheel_sliding_friction: required.8,
+                            wheel_suspension_damping: required.9,
+                            wheel_suspension_length: required.10,
+                            wheel_suspension_stiffness: required.11,
+                        }
+                    }
+                }
             }
         }
         pub mod player {
@@ -3406,6 +3962,19 @@ mod raw {
                 pub fn spawned() -> Component<()> {
                     *SPAWNED
                 }
+                static PREFAB_OVERRIDES: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::prefab::prefab_overrides")
+                });
+                #[doc = "**Prefab overrides**: The paths of components (e.g. `ambient_core::transform::translation`) that this prefab instance overrides.\n\nWhen the prefab is applied, these components keep the instance's values instead of being replaced by the prefab's.\n\nAll other components continue to follow the source prefab whenever it is re-applied (i.e. when `prefab_from_url` changes).\n\n*Attributes*: Debuggable, Store"]
+                pub fn prefab_overrides() -> Component<Vec<String>> {
+                    *PREFAB_OVERRIDES
+                }
+                static PREFAB_ROOT: Lazy<Component<EntityId>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::prefab::prefab_root"));
+                #[doc = "**Prefab root**: The prefab instance that spawned this entity as part of its hierarchy.\n\nThese entities are despawned and respawned when the prefab instance is re-applied.\n\n*Attributes*: Debuggable"]
+                pub fn prefab_root() -> Component<EntityId> {
+                    *PREFAB_ROOT
+                }
             }
         }
         pub mod primitives {
@@ -5045,6 +5614,72 @@ mod raw {
             }
             impl RuntimeMessage for ColliderLoads {}
             #[derive(Clone, Debug)]
+            #[doc = "**TriggerEnter**: Sent when an entity enters a trigger area collider (a collider whose type is `TriggerArea`)."]
+            pub struct TriggerEnter {
+                pub trigger: EntityId,
+                pub other: EntityId,
+            }
+            impl TriggerEnter {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(trigger: impl Into<EntityId>, other: impl Into<EntityId>) -> Self {
+                    Self {
+                        trigger: trigger.into(),
+                        other: other.into(),
+                    }
+                }
+            }
+            impl Message for TriggerEnter {
+                fn id() -> &'static str {
+                    "ambient_core::TriggerEnter"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.other.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        trigger: EntityId::deserialize_message_part(&mut input)?,
+                        other: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for TriggerEnter {}
+            #[derive(Clone, Debug)]
+            #[doc = "**TriggerExit**: Sent when an entity leaves a trigger area collider (a collider whose type is `TriggerArea`), or is despawned while inside it."]
+            pub struct TriggerExit {
+                pub trigger: EntityId,
+                pub other: EntityId,
+            }
+            impl TriggerExit {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(trigger: impl Into<EntityId>, other: impl Into<EntityId>) -> Self {
+                    Self {
+                        trigger: trigger.into(),
+                        other: other.into(),
+                    }
+                }
+            }
+            impl Message for TriggerExit {
+                fn id() -> &'static str {
+                    "ambient_core::TriggerExit"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.other.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        trigger: EntityId::deserialize_message_part(&mut input)?,
+                        other: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for TriggerExit {}
+            #[derive(Clone, Debug)]
             #[doc = "**ModuleLoad**: Sent to a module when it loads."]
             pub struct ModuleLoad;
             impl ModuleLoad {
@@ -5375,101 +6010,723 @@ mod raw {
             }
             impl RuntimeMessage for WindowCursorLockChange {}
             #[derive(Clone, Debug)]
-            #[doc = "**HttpResponse**: Sent when an HTTP response is received."]
-            pub struct HttpResponse {
-                pub response_id: u64,
-                pub status: u32,
-                pub body: Vec<u8>,
-                pub error: Option<String>,
+            #[doc = "**GamepadConnected**: Sent when a gamepad is connected. Gamepads that are already connected when the client starts are reported as well."]
+            pub struct GamepadConnected {
+                pub gamepad_id: u32,
+                pub name: String,
             }
-            impl HttpResponse {
+            impl GamepadConnected {
                 #[allow(clippy::too_many_arguments)]
-                pub fn new(
-                    response_id: impl Into<u64>,
-                    status: impl Into<u32>,
-                    body: impl Into<Vec<u8>>,
-                    error: impl Into<Option<String>>,
-                ) -> Self {
+                pub fn new(gamepad_id: impl Into<u32>, name: impl Into<String>) -> Self {
                     Self {
-                        response_id: response_id.into(),
-                        status: status.into(),
-                        body: body.into(),
-                        error: error.into(),
+                        gamepad_id: gamepad_id.into(),
+                        name: name.into(),
                     }
                 }
             }
-            impl Message for HttpResponse {
+            impl Message for GamepadConnected {
                 fn id() -> &'static str {
-                    "ambient_core::HttpResponse"
+                    "ambient_core::GamepadConnected"
                 }
                 fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
                     let mut output = vec![];
-                    self.response_id.serialize_message_part(&mut output)?;
-                    self.status.serialize_message_part(&mut output)?;
-                    self.body.serialize_message_part(&mut output)?;
-                    self.error.serialize_message_part(&mut output)?;
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.name.serialize_message_part(&mut output)?;
                     Ok(output)
                 }
                 fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
                     Ok(Self {
-                        response_id: u64::deserialize_message_part(&mut input)?,
-                        status: u32::deserialize_message_part(&mut input)?,
-                        body: Vec::<u8>::deserialize_message_part(&mut input)?,
-                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        name: String::deserialize_message_part(&mut input)?,
                     })
                 }
             }
-            impl RuntimeMessage for HttpResponse {}
+            impl RuntimeMessage for GamepadConnected {}
             #[derive(Clone, Debug)]
-            #[doc = "**WasmRebuild**: Sent when a request for WASM rebuilding is completed."]
-            pub struct WasmRebuild {
-                pub error: Option<String>,
+            #[doc = "**GamepadDisconnected**: Sent when a gamepad is disconnected."]
+            pub struct GamepadDisconnected {
+                pub gamepad_id: u32,
             }
-            impl WasmRebuild {
+            impl GamepadDisconnected {
                 #[allow(clippy::too_many_arguments)]
-                pub fn new(error: impl Into<Option<String>>) -> Self {
+                pub fn new(gamepad_id: impl Into<u32>) -> Self {
                     Self {
-                        error: error.into(),
+                        gamepad_id: gamepad_id.into(),
                     }
                 }
             }
-            impl Message for WasmRebuild {
+            impl Message for GamepadDisconnected {
                 fn id() -> &'static str {
-                    "ambient_core::WasmRebuild"
+                    "ambient_core::GamepadDisconnected"
                 }
                 fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
                     let mut output = vec![];
-                    self.error.serialize_message_part(&mut output)?;
+                    self.gamepad_id.serialize_message_part(&mut output)?;
                     Ok(output)
                 }
                 fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
                     Ok(Self {
-                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
                     })
                 }
             }
-            impl RuntimeMessage for WasmRebuild {}
-        }
-        #[doc = r" Auto-generated type definitions."]
-        pub mod types {
-            use crate::{global::serde, message::*};
-            #[derive(
-                Copy, Clone, Debug, PartialEq, Eq, serde :: Serialize, serde :: Deserialize, Default,
-            )]
-            #[serde(crate = "self::serde")]
-            #[doc = "**HttpMethod**: The HTTP method."]
-            pub enum HttpMethod {
-                #[default]
-                #[doc = "GET"]
-                Get,
+            impl RuntimeMessage for GamepadDisconnected {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadButtonInput**: Sent when a button on a gamepad is pressed or released."]
+            pub struct GamepadButtonInput {
+                pub gamepad_id: u32,
+                pub button: u32,
+                pub pressed: bool,
+            }
+            impl GamepadButtonInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    button: impl Into<u32>,
+                    pressed: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        button: button.into(),
+                        pressed: pressed.into(),
+                    }
+                }
+            }
+            impl Message for GamepadButtonInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadButtonInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.button.serialize_message_part(&mut output)?;
+                    self.pressed.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        button: u32::deserialize_message_part(&mut input)?,
+                        pressed: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadButtonInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadStickInput**: Sent when an analog stick on a gamepad moves. `stick` is 0 for the left stick and 1 for the right stick. `value` is the raw position of the stick, before the deadzone is applied, with +Y pointing up."]
+            pub struct GamepadStickInput {
+                pub gamepad_id: u32,
+                pub stick: u32,
+                pub value: Vec2,
+            }
+            impl GamepadStickInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    stick: impl Into<u32>,
+                    value: impl Into<Vec2>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        stick: stick.into(),
+                        value: value.into(),
+                    }
+                }
+            }
+            impl Message for GamepadStickInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadStickInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.stick.serialize_message_part(&mut output)?;
+                    self.value.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        stick: u32::deserialize_message_part(&mut input)?,
+                        value: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadStickInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GamepadTriggerInput**: Sent when an analog trigger on a gamepad moves. `trigger` is 0 for the left trigger and 1 for the right trigger. `value` is the raw position of the trigger between 0 and 1, before the deadzone is applied."]
+            pub struct GamepadTriggerInput {
+                pub gamepad_id: u32,
+                pub trigger: u32,
+                pub value: f32,
+            }
+            impl GamepadTriggerInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    gamepad_id: impl Into<u32>,
+                    trigger: impl Into<u32>,
+                    value: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        gamepad_id: gamepad_id.into(),
+                        trigger: trigger.into(),
+                        value: value.into(),
+                    }
+                }
+            }
+            impl Message for GamepadTriggerInput {
+                fn id() -> &'static str {
+                    "ambient_core::GamepadTriggerInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.gamepad_id.serialize_message_part(&mut output)?;
+                    self.trigger.serialize_message_part(&mut output)?;
+                    self.value.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        gamepad_id: u32::deserialize_message_part(&mut input)?,
+                        trigger: u32::deserialize_message_part(&mut input)?,
+                        value: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GamepadTriggerInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WindowTouchInput**: Sent when the window receives a touch input. `phase` is 0 when the touch starts, 1 when it moves, 2 when it ends and 3 when it is cancelled. `position` is in logical pixels, and `pressure` is from 0 to 1, or 1 if the device doesn't report pressure."]
+            pub struct WindowTouchInput {
+                pub touch_id: u64,
+                pub phase: u32,
+                pub position: Vec2,
+                pub pressure: f32,
+            }
+            impl WindowTouchInput {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    touch_id: impl Into<u64>,
+                    phase: impl Into<u32>,
+                    position: impl Into<Vec2>,
+                    pressure: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        touch_id: touch_id.into(),
+                        phase: phase.into(),
+                        position: position.into(),
+                        pressure: pressure.into(),
+                    }
+                }
+            }
+            impl Message for WindowTouchInput {
+                fn id() -> &'static str {
+                    "ambient_core::WindowTouchInput"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.touch_id.serialize_message_part(&mut output)?;
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.position.serialize_message_part(&mut output)?;
+                    self.pressure.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        touch_id: u64::deserialize_message_part(&mut input)?,
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                        pressure: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WindowTouchInput {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureTap**: Sent when one finger briefly touches the screen without moving."]
+            pub struct GestureTap {
+                pub position: Vec2,
+            }
+            impl GestureTap {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(position: impl Into<Vec2>) -> Self {
+                    Self {
+                        position: position.into(),
+                    }
+                }
+            }
+            impl Message for GestureTap {
+                fn id() -> &'static str {
+                    "ambient_core::GestureTap"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.position.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureTap {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureLongPress**: Sent when one finger has touched the screen without moving for long enough to be a long press. The touch does not produce a tap when it ends."]
+            pub struct GestureLongPress {
+                pub position: Vec2,
+            }
+            impl GestureLongPress {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(position: impl Into<Vec2>) -> Self {
+                    Self {
+                        position: position.into(),
+                    }
+                }
+            }
+            impl Message for GestureLongPress {
+                fn id() -> &'static str {
+                    "ambient_core::GestureLongPress"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.position.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureLongPress {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GesturePan**: Sent while one finger is dragged across the screen. `phase` is 0 when the pan starts, 1 while it moves, 2 when it ends and 3 when it is cancelled. `delta` is the movement since the previous `GesturePan`."]
+            pub struct GesturePan {
+                pub phase: u32,
+                pub position: Vec2,
+                pub delta: Vec2,
+            }
+            impl GesturePan {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    position: impl Into<Vec2>,
+                    delta: impl Into<Vec2>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        position: position.into(),
+                        delta: delta.into(),
+                    }
+                }
+            }
+            impl Message for GesturePan {
+                fn id() -> &'static str {
+                    "ambient_core::GesturePan"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.position.serialize_message_part(&mut output)?;
+                    self.delta.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        position: Vec2::deserialize_message_part(&mut input)?,
+                        delta: Vec2::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GesturePan {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GesturePinch**: Sent while two fingers move toward or away from each other. `phase` is as for `GesturePan`. `center` is the midpoint between the fingers, and `scale` is the ratio between the distance between them and the distance at the previous `GesturePinch`."]
+            pub struct GesturePinch {
+                pub phase: u32,
+                pub center: Vec2,
+                pub scale: f32,
+            }
+            impl GesturePinch {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    center: impl Into<Vec2>,
+                    scale: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        center: center.into(),
+                        scale: scale.into(),
+                    }
+                }
+            }
+            impl Message for GesturePinch {
+                fn id() -> &'static str {
+                    "ambient_core::GesturePinch"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.center.serialize_message_part(&mut output)?;
+                    self.scale.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        center: Vec2::deserialize_message_part(&mut input)?,
+                        scale: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GesturePinch {}
+            #[derive(Clone, Debug)]
+            #[doc = "**GestureRotate**: Sent while two fingers rotate around each other. `phase` is as for `GesturePan`. `center` is the midpoint between the fingers, and `angle` is how far the line between them has turned since the previous `GestureRotate`, in radians, clockwise on screen."]
+            pub struct GestureRotate {
+                pub phase: u32,
+                pub center: Vec2,
+                pub angle: f32,
+            }
+            impl GestureRotate {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    phase: impl Into<u32>,
+                    center: impl Into<Vec2>,
+                    angle: impl Into<f32>,
+                ) -> Self {
+                    Self {
+                        phase: phase.into(),
+                        center: center.into(),
+                        angle: angle.into(),
+                    }
+                }
+            }
+            impl Message for GestureRotate {
+                fn id() -> &'static str {
+                    "ambient_core::GestureRotate"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.phase.serialize_message_part(&mut output)?;
+                    self.center.serialize_message_part(&mut output)?;
+                    self.angle.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        phase: u32::deserialize_message_part(&mut input)?,
+                        center: Vec2::deserialize_message_part(&mut input)?,
+                        angle: f32::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for GestureRotate {}
+            #[derive(Clone, Debug)]
+            #[doc = "**HttpResponse**: Sent when an HTTP response is received. For streaming requests, this is sent as soon as the headers are received with an empty `body`, which is then delivered through `HttpResponseChunk` messages."]
+            pub struct HttpResponse {
+                pub response_id: u64,
+                pub status: u32,
+                pub body: Vec<u8>,
+                pub error: Option<String>,
+                pub headers: std::collections::HashMap<String, String>,
+            }
+            impl HttpResponse {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    response_id: impl Into<u64>,
+                    status: impl Into<u32>,
+                    body: impl Into<Vec<u8>>,
+                    error: impl Into<Option<String>>,
+                    headers: impl Into<std::collections::HashMap<String, String>>,
+                ) -> Self {
+                    Self {
+                        response_id: response_id.into(),
+                        status: status.into(),
+                        body: body.into(),
+                        error: error.into(),
+                        headers: headers.into(),
+                    }
+                }
+            }
+            impl Message for HttpResponse {
+                fn id() -> &'static str {
+                    "ambient_core::HttpResponse"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.response_id.serialize_message_part(&mut output)?;
+                    self.status.serialize_message_part(&mut output)?;
+                    self.body.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    self.headers.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        response_id: u64::deserialize_message_part(&mut input)?,
+                        status: u32::deserialize_message_part(&mut input)?,
+                        body: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                        headers:
+                            std::collections::HashMap::<String, String>::deserialize_message_part(
+                                &mut input,
+                            )?,
+                    })
+                }
+            }
+            impl RuntimeMessage for HttpResponse {}
+            #[derive(Clone, Debug)]
+            #[doc = "**HttpResponseChunk**: Sent for each chunk of the body of a streaming HTTP response. The last chunk has `end` set, along with `error` if the body could not be read in full."]
+            pub struct HttpResponseChunk {
+                pub response_id: u64,
+                pub data: Vec<u8>,
+                pub end: bool,
+                pub error: Option<String>,
+            }
+            impl HttpResponseChunk {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    response_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    end: impl Into<bool>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        response_id: response_id.into(),
+                        data: data.into(),
+                        end: end.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for HttpResponseChunk {
+                fn id() -> &'static str {
+                    "ambient_core::HttpResponseChunk"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.response_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.end.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        response_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        end: bool::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for HttpResponseChunk {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketOpen**: Sent when an outbound WebSocket connection has been established."]
+            pub struct WebSocketOpen {
+                pub connection_id: u64,
+            }
+            impl WebSocketOpen {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(connection_id: impl Into<u64>) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketOpen {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketOpen"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketOpen {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketMessage**: Sent when a message is received on an outbound WebSocket connection. `is_text` is set if it was a text message, in which case `data` is valid UTF-8."]
+            pub struct WebSocketMessage {
+                pub connection_id: u64,
+                pub data: Vec<u8>,
+                pub is_text: bool,
+            }
+            impl WebSocketMessage {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    connection_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    is_text: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                        data: data.into(),
+                        is_text: is_text.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketMessage {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketMessage"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.is_text.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        is_text: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketMessage {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WebSocketClose**: Sent when an outbound WebSocket connection is closed, whether by the remote end, the module or an error. `error` is set if the connection failed, including if it could not be established."]
+            pub struct WebSocketClose {
+                pub connection_id: u64,
+                pub error: Option<String>,
+            }
+            impl WebSocketClose {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    connection_id: impl Into<u64>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        connection_id: connection_id.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for WebSocketClose {
+                fn id() -> &'static str {
+                    "ambient_core::WebSocketClose"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.connection_id.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        connection_id: u64::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WebSocketClose {}
+            #[derive(Clone, Debug)]
+            #[doc = "**WasmRebuild**: Sent when a request for WASM rebuilding is completed."]
+            pub struct WasmRebuild {
+                pub error: Option<String>,
+            }
+            impl WasmRebuild {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(error: impl Into<Option<String>>) -> Self {
+                    Self {
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for WasmRebuild {
+                fn id() -> &'static str {
+                    "ambient_core::WasmRebuild"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WasmRebuild {}
+            #[derive(Clone, Debug)]
+            #[doc = "**AssetReadResponse**: Sent when a request to read an asset on the server has completed."]
+            pub struct AssetReadResponse {
+                pub request_id: u64,
+                pub data: Vec<u8>,
+                pub error: Option<String>,
+            }
+            impl AssetReadResponse {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    request_id: impl Into<u64>,
+                    data: impl Into<Vec<u8>>,
+                    error: impl Into<Option<String>>,
+                ) -> Self {
+                    Self {
+                        request_id: request_id.into(),
+                        data: data.into(),
+                        error: error.into(),
+                    }
+                }
+            }
+            impl Message for AssetReadResponse {
+                fn id() -> &'static str {
+                    "ambient_core::AssetReadResponse"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.request_id.serialize_message_part(&mut output)?;
+                    self.data.serialize_message_part(&mut output)?;
+                    self.error.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        request_id: u64::deserialize_message_part(&mut input)?,
+                        data: Vec::<u8>::deserialize_message_part(&mut input)?,
+                        error: Option::<String>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for AssetReadResponse {}
+        }
+        #[doc = r" Auto-generated type definitions."]
+        pub mod types {
+            use crate::{global::serde, message::*};
+            #[derive(
+                Copy, Clone, Debug, PartialEq, Eq, serde :: Serialize, serde :: Deserialize, Default,
+            )]
+            #[serde(crate = "self::serde")]
+            #[doc = "**HttpMethod**: The HTTP method."]
+            pub enum HttpMethod {
+                #[default]
+                #[doc = "GET"]
+                Get,
                 #[doc = "POST"]
                 Post,
+                #[doc = "PUT"]
+                Put,
+                #[doc = "DELETE"]
+                Delete,
+                #[doc = "PATCH"]
+                Patch,
             }
             impl crate::ecs::EnumComponent for HttpMethod {
                 fn to_u32(&self) -> u32 {
                     match self {
                         Self::Get => HttpMethod::Get as u32,
                         Self::Post => HttpMethod::Post as u32,
+                        Self::Put => HttpMethod::Put as u32,
+                        Self::Delete => HttpMethod::Delete as u32,
+                        Self::Patch => HttpMethod::Patch as u32,
                     }
                 }
                 fn from_u32(value: u32) -> Option<Self> {
@@ -5479,6 +6736,15 @@ mod raw {
                     if value == HttpMethod::Post as u32 {
                         return Some(Self::Post);
                     }
+                    if value == HttpMethod::Put as u32 {
+                        return Some(Self::Put);
+                    }
+                    if value == HttpMethod::Delete as u32 {
+                        return Some(Self::Delete);
+                    }
+                    if value == HttpMethod::Patch as u32 {
+                        return Some(Self::Patch);
+                    }
                     None
                 }
             }
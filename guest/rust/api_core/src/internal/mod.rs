@@ -8,7 +8,7 @@ pub(crate) mod wit;
 
 use crate::internal::executor::EXECUTOR;
 
-use wit::{__link_section, exports, guest};
+use wit::{__link_section, exports, guest, guest_hot_reload};
 wit::export_bindings!(Guest);
 
 extern "Rust" {
@@ -26,3 +26,13 @@ impl guest::Guest for Guest {
         EXECUTOR.execute(source, message_name, message_data);
     }
 }
+
+impl guest_hot_reload::GuestHotReload for Guest {
+    fn save_state() -> Result<Option<Vec<u8>>, String> {
+        crate::hot_reload::save()
+    }
+
+    fn restore_state(state: Vec<u8>) -> Result<(), String> {
+        crate::hot_reload::restore(&state)
+    }
+}
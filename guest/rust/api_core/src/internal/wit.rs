@@ -2,5 +2,8 @@
 
 include!("bindings.rs");
 
-pub(crate) use self::{ambient::bindings::*, exports::ambient::bindings::guest};
+pub(crate) use self::{
+    ambient::bindings::*,
+    exports::ambient::bindings::{guest, guest_hot_reload},
+};
 pub(crate) use export_bindings;
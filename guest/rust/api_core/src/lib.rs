@@ -25,6 +25,9 @@ pub mod ecs;
 pub mod entity;
 /// Global functions and types for your convenience.
 pub mod global;
+pub mod hot_reload;
+/// Input retrieval and manipulation. Most of it is client-only; the server can read the raw input of players.
+pub mod input;
 /// Messaging to other packages and to the other side of the network boundary.
 pub mod message;
 /// Player-specific functionality.
@@ -248,6 +248,11 @@ impl Listener {
     pub fn stop(&self) {
         EXECUTOR.unregister_callback(&self.0, self.1);
     }
+
+    /// Calls `hook` when this stops listening.
+    pub(crate) fn on_stop(&self, hook: impl FnOnce() + 'static) {
+        EXECUTOR.on_unregister(self.1, Box::new(hook));
+    }
 }
 
 /// Message context.
@@ -1,6 +1,10 @@
 use crate::{
     global::EntityId,
-    internal::{conversion::FromBindgen, wit},
+    input::Input,
+    internal::{
+        conversion::{FromBindgen, IntoBindgen},
+        wit,
+    },
 };
 
 /// Get a player's entity ID from their user ID.
@@ -8,6 +12,14 @@ pub fn get_by_user_id(user_id: &str) -> Option<EntityId> {
     wit::player::get_by_user_id(user_id).from_bindgen()
 }
 
+/// Get the most recent raw input of the player with `player_id`.
+///
+/// On the server, this is the input last received from the player's client. On the client, only
+/// the local player's input is available.
+pub fn get_raw_input(player_id: EntityId) -> Option<Input> {
+    wit::player::get_raw_input(player_id.into_bindgen()).from_bindgen()
+}
+
 /// Get the local player's entity ID.
 #[cfg(feature = "client")]
 pub fn get_local() -> EntityId {
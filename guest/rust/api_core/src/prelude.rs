@@ -1,11 +1,13 @@
 pub use crate::{
     asset, camera,
     ecs::{
-        change_query, despawn_query, query, spawn_query, Component, ComponentsTuple, Concept,
-        ConceptComponents, ConceptQuery, ConceptSuggested, Entity, QueryEvent,
+        change_query, despawn_query, index_query, query, spawn_query, Component, ComponentsTuple,
+        Concept, ConceptComponents, ConceptQuery, ConceptSuggested, Entity, QueryEvent,
     },
     entity,
     global::*,
+    hot_reload,
+    input::{self, ActionBinding, GamepadButton, KeyCode, MouseButton, TouchPhase},
     main, message,
     message::{Message, ModuleMessage, RuntimeMessage},
     player,
@@ -14,10 +16,7 @@ pub use anyhow::{anyhow, Context as AnyhowContext};
 pub use rand::prelude::*;
 
 #[cfg(feature = "client")]
-pub use crate::client::{
-    audio,
-    input::{self, KeyCode, MouseButton},
-};
+pub use crate::client::audio;
 
 #[cfg(feature = "server")]
-pub use crate::server::{http, physics};
+pub use crate::server::{http, physics, storage};
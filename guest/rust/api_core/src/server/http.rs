@@ -1,61 +1,400 @@
-use std::{collections::HashMap, fmt};
-
-use thiserror::Error;
-
-use crate::{core::messages::HttpResponse, global, internal::wit};
-
-#[derive(Error, Debug, Clone)]
-/// Errors that can occur when making an HTTP request.
-pub struct HttpError(pub String);
-impl fmt::Display for HttpError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "HTTP error: {}", self.0)
-    }
-}
-
-/// Sends an HTTP GET request to the given URL, and returns the response body.
-///
-/// Any errors in sending or receiving will be returned as an [HttpError].
-///
-/// **NOTE**: This may be replaced with `wasi-http` support in the future,
-/// which will allow the use of native Rust libraries like `reqwest`.
-pub async fn get(
-    url: impl AsRef<str>,
-    headers: Option<HashMap<String, String>>,
-) -> Result<Vec<u8>, HttpError> {
-    let url = url.as_ref();
-    let headers = headers.unwrap_or_default().into_iter().collect::<Vec<_>>();
-    let response_id = wit::server_http::get(url, &headers);
-
-    wait_for_response(response_id).await
-}
-
-/// Sends an HTTP POST request to the given URL, and returns the response body.
-///
-/// Any errors in sending or receiving will be returned as an [HttpError].
-///
-/// **NOTE**: This may be replaced with `wasi-http` support in the future,
-/// which will allow the use of native Rust libraries like `reqwest`.
-pub async fn post(
-    url: impl AsRef<str>,
-    headers: Option<HashMap<String, String>>,
-    body: Option<&[u8]>,
-) -> Result<Vec<u8>, HttpError> {
-    let url = url.as_ref();
-    let headers = headers.unwrap_or_default().into_iter().collect::<Vec<_>>();
-    let response_id = wit::server_http::post(url, &headers, body);
-
-    wait_for_response(response_id).await
-}
-
-async fn wait_for_response(response_id: u64) -> Result<Vec<u8>, HttpError> {
-    let response = global::wait_for_runtime_message(move |message: &HttpResponse| {
-        message.response_id == response_id
-    })
-    .await;
-
-    match response.error {
-        Some(error) => Err(HttpError(error)),
-        None => Ok(response.body),
-    }
-}
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt,
+    rc::Rc,
+    task::Poll,
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+use crate::{
+    core::{
+        messages::{
+            HttpResponse, HttpResponseChunk, WebSocketClose,
+            WebSocketMessage as WebSocketMessageEvent, WebSocketOpen,
+        },
+        types::HttpMethod,
+    },
+    global,
+    internal::wit,
+    message::{Listener, RuntimeMessage},
+};
+
+#[derive(Error, Debug, Clone)]
+/// Errors that can occur when making an HTTP request.
+pub struct HttpError(pub String);
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP error: {}", self.0)
+    }
+}
+
+/// Sends an HTTP GET request to the given URL, and returns the response body.
+///
+/// Any errors in sending or receiving will be returned as an [HttpError].
+///
+/// **NOTE**: This may be replaced with `wasi-http` support in the future,
+/// which will allow the use of native Rust libraries like `reqwest`.
+pub async fn get(
+    url: impl AsRef<str>,
+    headers: Option<HashMap<String, String>>,
+) -> Result<Vec<u8>, HttpError> {
+    let url = url.as_ref();
+    let headers = headers.unwrap_or_default().into_iter().collect::<Vec<_>>();
+    let response_id = wit::server_http::get(url, &headers);
+
+    Ok(wait_for_response(response_id).await?.body)
+}
+
+/// Sends an HTTP POST request to the given URL, and returns the response body.
+///
+/// Any errors in sending or receiving will be returned as an [HttpError].
+///
+/// **NOTE**: This may be replaced with `wasi-http` support in the future,
+/// which will allow the use of native Rust libraries like `reqwest`.
+pub async fn post(
+    url: impl AsRef<str>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<&[u8]>,
+) -> Result<Vec<u8>, HttpError> {
+    let url = url.as_ref();
+    let headers = headers.unwrap_or_default().into_iter().collect::<Vec<_>>();
+    let response_id = wit::server_http::post(url, &headers, body);
+
+    Ok(wait_for_response(response_id).await?.body)
+}
+
+/// An HTTP request, for when more control is needed than [get] and [post] offer.
+///
+/// ```ignore
+/// let response = http::Request::new(HttpMethod::Put, "https://example.com/scores/1")
+///     .header("Authorization", "Bearer secret")
+///     .json(&score)?
+///     .timeout(Duration::from_secs(5))
+///     .send()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: HttpMethod,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+}
+impl Request {
+    /// Creates a request with the given `method` to `url`.
+    pub fn new(method: HttpMethod, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: vec![],
+            body: None,
+            timeout: None,
+        }
+    }
+
+    /// Adds a header to the request.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the body of the request.
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Sets the body of the request to `value` serialized as JSON, and sets the `Content-Type` header.
+    pub fn json<T: Serialize>(self, value: &T) -> Result<Self, HttpError> {
+        let body = serde_json::to_vec(value).map_err(|err| HttpError(err.to_string()))?;
+        Ok(self.header("Content-Type", "application/json").body(body))
+    }
+
+    /// Sets how long to wait for the request to complete, including receiving the whole body,
+    /// before failing. By default, there is no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends the request, and waits for the whole response to be received.
+    pub async fn send(self) -> Result<Response, HttpError> {
+        let response_id = self.send_impl(false);
+        let response = wait_for_response(response_id).await?;
+        Ok(Response {
+            status: response.status as u16,
+            headers: response.headers,
+            body: response.body,
+        })
+    }
+
+    /// Sends the request, and waits for the response headers to be received. The body can then
+    /// be read in chunks as it arrives, which is useful for large downloads.
+    pub async fn send_streaming(self) -> Result<StreamingResponse, HttpError> {
+        let response_id = self.send_impl(true);
+        // Start listening for chunks before any can arrive
+        let (chunks, listener) = subscribe_queue(move |chunk: &HttpResponseChunk| {
+            (chunk.response_id == response_id).then(|| chunk.clone())
+        });
+        let response = wait_for_response(response_id).await?;
+        Ok(StreamingResponse {
+            response_id,
+            status: response.status as u16,
+            headers: response.headers,
+            chunks,
+            listener,
+            finished: false,
+        })
+    }
+
+    fn send_impl(self, streaming: bool) -> u64 {
+        use wit::server_http::Method;
+        wit::server_http::send(&wit::server_http::Request {
+            method: match self.method {
+                HttpMethod::Get => Method::Get,
+                HttpMethod::Post => Method::Post,
+                HttpMethod::Put => Method::Put,
+                HttpMethod::Delete => Method::Delete,
+                HttpMethod::Patch => Method::Patch,
+            },
+            url: self.url,
+            headers: self.headers,
+            body: self.body,
+            timeout_ms: self.timeout.map(|t| t.as_millis() as u64),
+            streaming,
+        })
+    }
+}
+
+/// A complete HTTP response.
+#[derive(Debug, Clone)]
+pub struct Response {
+    /// The status code.
+    pub status: u16,
+    /// The headers, with lowercase names. Headers that appeared more than once are joined with `, `.
+    pub headers: HashMap<String, String>,
+    /// The body.
+    pub body: Vec<u8>,
+}
+impl Response {
+    /// Returns true if the status code is in the 200-299 range.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Parses the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, HttpError> {
+        serde_json::from_slice(&self.body).map_err(|err| HttpError(err.to_string()))
+    }
+}
+
+/// An HTTP response whose body is received in chunks. Created by [Request::send_streaming].
+///
+/// Dropping it before the whole body has been received cancels the transfer.
+pub struct StreamingResponse {
+    response_id: u64,
+    /// The status code.
+    pub status: u16,
+    /// The headers, with lowercase names. Headers that appeared more than once are joined with `, `.
+    pub headers: HashMap<String, String>,
+    chunks: Rc<RefCell<VecDeque<HttpResponseChunk>>>,
+    listener: Listener,
+    finished: bool,
+}
+impl StreamingResponse {
+    /// Waits for the next chunk of the body. Returns `None` once the whole body has been received.
+    pub async fn chunk(&mut self) -> Option<Result<Vec<u8>, HttpError>> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            let chunk = pop_queue(&self.chunks).await;
+            if chunk.end {
+                self.finished = true;
+                self.listener.stop();
+                if let Some(error) = chunk.error {
+                    return Some(Err(HttpError(error)));
+                }
+            }
+            // The final chunk is usually empty
+            if !chunk.data.is_empty() {
+                return Some(Ok(chunk.data));
+            }
+        }
+    }
+}
+impl Drop for StreamingResponse {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.listener.stop();
+            wit::server_http::cancel(self.response_id);
+        }
+    }
+}
+
+/// A message sent or received over a [WebSocket].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebSocketMessage {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+enum WebSocketEvent {
+    Open,
+    Message(WebSocketMessage),
+    Close(Option<String>),
+}
+
+/// An outbound WebSocket connection. Dropping it closes the connection.
+pub struct WebSocket {
+    connection_id: u64,
+    events: Rc<RefCell<VecDeque<WebSocketEvent>>>,
+    listeners: [Listener; 3],
+    closed: bool,
+}
+impl WebSocket {
+    /// Connects to the WebSocket server at `url` (`ws://` or `wss://`), sending the given
+    /// `headers` with the handshake.
+    pub async fn connect(
+        url: impl AsRef<str>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<Self, HttpError> {
+        let headers = headers.unwrap_or_default().into_iter().collect::<Vec<_>>();
+        let connection_id = wit::server_http::websocket_connect(url.as_ref(), &headers);
+
+        let (events, open_listener) = subscribe_queue(move |message: &WebSocketOpen| {
+            (message.connection_id == connection_id).then_some(WebSocketEvent::Open)
+        });
+        let message_listener = subscribe_to(&events, move |message: &WebSocketMessageEvent| {
+            (message.connection_id == connection_id).then(|| {
+                WebSocketEvent::Message(if message.is_text {
+                    WebSocketMessage::Text(String::from_utf8_lossy(&message.data).into_owned())
+                } else {
+                    WebSocketMessage::Binary(message.data.clone())
+                })
+            })
+        });
+        let close_listener = subscribe_to(&events, move |message: &WebSocketClose| {
+            (message.connection_id == connection_id)
+                .then(|| WebSocketEvent::Close(message.error.clone()))
+        });
+
+        let mut socket = Self {
+            connection_id,
+            events,
+            listeners: [open_listener, message_listener, close_listener],
+            closed: false,
+        };
+        match pop_queue(&socket.events).await {
+            WebSocketEvent::Open => Ok(socket),
+            WebSocketEvent::Close(error) => {
+                socket.closed = true;
+                Err(HttpError(
+                    error.unwrap_or_else(|| "connection closed".to_string()),
+                ))
+            }
+            WebSocketEvent::Message(_) => unreachable!("messages can't arrive before opening"),
+        }
+    }
+
+    /// Sends a text message.
+    pub fn send_text(&self, text: impl Into<String>) {
+        self.send(WebSocketMessage::Text(text.into()));
+    }
+
+    /// Sends a binary message.
+    pub fn send_binary(&self, data: impl Into<Vec<u8>>) {
+        self.send(WebSocketMessage::Binary(data.into()));
+    }
+
+    /// Sends a message. Messages sent after the connection has closed are ignored.
+    pub fn send(&self, message: WebSocketMessage) {
+        let message = match message {
+            WebSocketMessage::Text(text) => wit::server_http::WebsocketMessage::Text(text),
+            WebSocketMessage::Binary(data) => wit::server_http::WebsocketMessage::Binary(data),
+        };
+        wit::server_http::websocket_send(self.connection_id, &message);
+    }
+
+    /// Waits for the next message from the server.
+    ///
+    /// Returns `None` once the connection has been closed, or an error if it closed due to one.
+    pub async fn next(&mut self) -> Option<Result<WebSocketMessage, HttpError>> {
+        loop {
+            if self.closed {
+                return None;
+            }
+
+            match pop_queue(&self.events).await {
+                WebSocketEvent::Open => {}
+                WebSocketEvent::Message(message) => return Some(Ok(message)),
+                WebSocketEvent::Close(error) => {
+                    self.closed = true;
+                    if let Some(error) = error {
+                        return Some(Err(HttpError(error)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Closes the connection.
+    pub fn close(self) {}
+}
+impl Drop for WebSocket {
+    fn drop(&mut self) {
+        for listener in &self.listeners {
+            listener.stop();
+        }
+        wit::server_http::websocket_close(self.connection_id);
+    }
+}
+
+async fn wait_for_response(response_id: u64) -> Result<HttpResponse, HttpError> {
+    let response = global::wait_for_runtime_message(move |message: &HttpResponse| {
+        message.response_id == response_id
+    })
+    .await;
+
+    match response.error {
+        Some(error) => Err(HttpError(error)),
+        None => Ok(response),
+    }
+}
+
+/// Queues up the relevant messages of type `T`, as mapped by `filter`, until they are popped.
+fn subscribe_queue<T: RuntimeMessage + 'static, U: 'static>(
+    filter: impl Fn(&T) -> Option<U> + 'static,
+) -> (Rc<RefCell<VecDeque<U>>>, Listener) {
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    let listener = subscribe_to(&queue, filter);
+    (queue, listener)
+}
+
+fn subscribe_to<T: RuntimeMessage + 'static, U: 'static>(
+    queue: &Rc<RefCell<VecDeque<U>>>,
+    filter: impl Fn(&T) -> Option<U> + 'static,
+) -> Listener {
+    let queue = queue.clone();
+    T::subscribe(move |message| {
+        if let Some(item) = filter(&message) {
+            queue.borrow_mut().push_back(item);
+        }
+    })
+}
+
+async fn pop_queue<U>(queue: &Rc<RefCell<VecDeque<U>>>) -> U {
+    std::future::poll_fn(|_cx| match queue.borrow_mut().pop_front() {
+        Some(item) => Poll::Ready(item),
+        None => Poll::Pending,
+    })
+    .await
+}
@@ -1,5 +1,8 @@
 /// **\[Server-only\]** Physics-related functionality, including applying forces, changing physical properties, and more.
 pub mod physics;
 
-/// **\[Server-only\]** HTTP-related functionality, including sending requests and receiving responses.
+/// **\[Server-only\]** HTTP-related functionality, including sending requests, receiving responses and connecting to WebSocket servers.
 pub mod http;
+
+/// **\[Server-only\]** Persistent key-value storage that survives server restarts. Each package has its own storage, divided into namespaces and limited by a quota.
+pub mod storage;
@@ -60,6 +60,24 @@ pub fn add_radial_impulse(
     )
 }
 
+/// Applies an `impulse` (a [f32]) outwards to all entitities within `radius` of the `position`, with
+/// an optional `falloff_radius`, only affecting entities on one of the collision layers in `mask`.
+pub fn add_radial_impulse_with_mask(
+    position: Vec3,
+    impulse: f32,
+    radius: f32,
+    falloff_radius: FalloffRadius,
+    mask: u32,
+) {
+    wit::server_physics::add_radial_impulse_with_mask(
+        position.into_bindgen(),
+        impulse,
+        radius,
+        falloff_radius.into(),
+        mask,
+    )
+}
+
 /// Applies a `force` (a [Vec3]) at a given `position` (a [Vec3]) to the `entity` (an [EntityId]) specified.
 ///
 /// `force` is a vector in world space, which means it has both direction and magnitude. To push objects upwards
@@ -162,6 +180,16 @@ pub fn raycast(origin: Vec3, direction: Vec3) -> Vec<RaycastHit> {
         .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
         .collect()
 }
+/// Casts a ray from `origin` in `direction`, and returns the [RaycastHit]s along the way,
+/// only hitting colliders on one of the collision layers in `mask`.
+///
+/// `direction` must be normalized.
+pub fn raycast_with_mask(origin: Vec3, direction: Vec3, mask: u32) -> Vec<RaycastHit> {
+    wit::server_physics::raycast_with_mask(origin.into_bindgen(), direction.into_bindgen(), mask)
+        .into_iter()
+        .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
+        .collect()
+}
 /// Casts a ray from `origin` in `direction`, and returns the first [RaycastHit] if it hits.
 ///
 /// `direction` must be normalized.
@@ -169,6 +197,18 @@ pub fn raycast_first(origin: Vec3, direction: Vec3) -> Option<RaycastHit> {
     wit::server_physics::raycast_first(origin.into_bindgen(), direction.into_bindgen())
         .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
 }
+/// Casts a ray from `origin` in `direction`, and returns the first [RaycastHit] if it hits,
+/// only hitting colliders on one of the collision layers in `mask`.
+///
+/// `direction` must be normalized.
+pub fn raycast_first_with_mask(origin: Vec3, direction: Vec3, mask: u32) -> Option<RaycastHit> {
+    wit::server_physics::raycast_first_with_mask(
+        origin.into_bindgen(),
+        direction.into_bindgen(),
+        mask,
+    )
+    .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
+}
 fn raycast_result_to_hit(
     origin: Vec3,
     direction: Vec3,
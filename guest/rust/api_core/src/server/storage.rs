@@ -0,0 +1,137 @@
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+use crate::internal::wit;
+
+#[derive(Error, Debug)]
+/// Errors that can occur when using persistent storage.
+pub enum StorageError {
+    #[error("Invalid namespace: {0:?}")]
+    /// The namespace is empty, too long (more than 64 bytes), or contains characters other than
+    /// ASCII letters, digits, `_`, `-`, `.` and `/`.
+    InvalidNamespace(String),
+    #[error("Invalid key: {0:?}")]
+    /// The key is too long (more than 1024 bytes).
+    InvalidKey(String),
+    #[error("Storage quota of {0} bytes exceeded")]
+    /// Applying the writes would take this package over its storage quota.
+    QuotaExceeded(u64),
+    #[error("Database error: {0}")]
+    /// The underlying database failed.
+    Database(String),
+    #[error("Failed to (de)serialize value as JSON: {0}")]
+    /// The value could not be converted to or from JSON.
+    Json(#[from] serde_json::Error),
+}
+impl From<wit::server_storage::StorageError> for StorageError {
+    fn from(value: wit::server_storage::StorageError) -> Self {
+        use wit::server_storage::StorageError as WitError;
+        match value {
+            WitError::InvalidNamespace(namespace) => StorageError::InvalidNamespace(namespace),
+            WitError::InvalidKey(key) => StorageError::InvalidKey(key),
+            WitError::QuotaExceeded(quota) => StorageError::QuotaExceeded(quota),
+            WitError::Database(err) => StorageError::Database(err),
+        }
+    }
+}
+
+/// How much storage this package is using.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// The number of bytes used by all keys and values.
+    pub used: u64,
+    /// The maximum number of bytes that can be used.
+    pub quota: u64,
+}
+
+/// Returns the value stored under `key` in `namespace`, if any.
+pub fn get(namespace: &str, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+    Ok(wit::server_storage::get(namespace, key)?)
+}
+
+/// Returns the value stored under `key` in `namespace`, deserialized from JSON, if any.
+pub fn get_json<T: DeserializeOwned>(
+    namespace: &str,
+    key: &str,
+) -> Result<Option<T>, StorageError> {
+    get(namespace, key)?
+        .map(|value| Ok(serde_json::from_slice(&value)?))
+        .transpose()
+}
+
+/// Stores `value` under `key` in `namespace`, replacing any existing value.
+pub fn set(namespace: &str, key: &str, value: impl Into<Vec<u8>>) -> Result<(), StorageError> {
+    Transaction::new(namespace).set(key, value).commit()
+}
+
+/// Stores `value` under `key` in `namespace` as JSON, replacing any existing value.
+pub fn set_json<T: Serialize>(namespace: &str, key: &str, value: &T) -> Result<(), StorageError> {
+    Transaction::new(namespace).set_json(key, value)?.commit()
+}
+
+/// Removes `key` from `namespace`. Does nothing if it is not present.
+pub fn delete(namespace: &str, key: &str) -> Result<(), StorageError> {
+    Transaction::new(namespace).delete(key).commit()
+}
+
+/// Returns the keys in `namespace` that start with `prefix`, in ascending order.
+///
+/// Use an empty `prefix` to list all keys in the namespace.
+pub fn list(namespace: &str, prefix: &str) -> Result<Vec<String>, StorageError> {
+    Ok(wit::server_storage::list_keys(namespace, prefix)?)
+}
+
+/// Returns how much storage this package is using, and how much it can use.
+pub fn usage() -> Result<Usage, StorageError> {
+    let wit::server_storage::Usage { used, quota } = wit::server_storage::get_usage()?;
+    Ok(Usage { used, quota })
+}
+
+/// A set of writes to a namespace that are applied atomically: either all of them are
+/// persisted, or none of them are.
+///
+/// Modules run one at a time, so values read with [get] before committing a transaction
+/// cannot be changed by another module in the meantime.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    namespace: String,
+    writes: Vec<(String, Option<Vec<u8>>)>,
+}
+impl Transaction {
+    /// Creates an empty transaction for `namespace`.
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            writes: vec![],
+        }
+    }
+
+    /// Stores `value` under `key` when the transaction is committed.
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<Vec<u8>>) -> Self {
+        self.writes.push((key.into(), Some(value.into())));
+        self
+    }
+
+    /// Stores `value` as JSON under `key` when the transaction is committed.
+    pub fn set_json<T: Serialize>(
+        self,
+        key: impl Into<String>,
+        value: &T,
+    ) -> Result<Self, StorageError> {
+        Ok(self.set(key, serde_json::to_vec(value)?))
+    }
+
+    /// Removes `key` when the transaction is committed.
+    pub fn delete(mut self, key: impl Into<String>) -> Self {
+        self.writes.push((key.into(), None));
+        self
+    }
+
+    /// Applies all of the writes, in order.
+    ///
+    /// If any key is invalid, or the writes would take this package over its quota,
+    /// nothing is written.
+    pub fn commit(self) -> Result<(), StorageError> {
+        Ok(wit::server_storage::commit(&self.namespace, &self.writes)?)
+    }
+}
@@ -0,0 +1,88 @@
+use ambient_api::{
+    core::layout::components::{
+        fit_horizontal, fit_vertical, flex_basis, flex_grow, grid_column, grid_column_span,
+        grid_columns, grid_gap, grid_row, grid_rows, height, space_between_items, width,
+    },
+    prelude::*,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(_hooks: &mut Hooks) -> Element {
+    let cell = |text: &str| {
+        FlowRow::el([Text::el(text)])
+            .with_background(vec4(1., 1., 1., 0.05))
+            .with(fit_horizontal(), Fit::Parent)
+            .with(fit_vertical(), Fit::Parent)
+            .with_padding_even(5.)
+    };
+    let slot = |i: usize| {
+        FlowRow::el([Text::el(format!("Item {i}"))])
+            .with_background(vec4(0.2, 0.2, 0.3, 1.))
+            .with(fit_horizontal(), Fit::None)
+            .with(fit_vertical(), Fit::None)
+            .with(width(), 64.)
+            .with(height(), 64.)
+            .with_padding_even(5.)
+    };
+
+    FlowColumn::el([
+        Grid::el([
+            cell("Header spanning all columns").with(grid_column_span(), 3),
+            cell("Fixed"),
+            cell("Fraction"),
+            FlowRow::el([Text::el("Auto, sized to its content")])
+                .with_background(vec4(1., 1., 1., 0.05))
+                .with_padding_even(5.),
+            cell("Placed in the last row")
+                .with(grid_column(), 1)
+                .with(grid_row(), 2),
+        ])
+        .with_background(vec4(0.1, 0.1, 0.1, 1.))
+        .with(
+            grid_columns(),
+            GridTrack::to_components([
+                GridTrack::Pixels(100.),
+                GridTrack::Fraction(1.),
+                GridTrack::Auto,
+            ]),
+        )
+        .with(
+            grid_rows(),
+            GridTrack::to_components([GridTrack::Pixels(30.); 3]),
+        )
+        .with(grid_gap(), vec2(5., 5.))
+        .with(fit_vertical(), Fit::Children)
+        .with(width(), 500.)
+        .with_padding_even(10.),
+        Grid::el((0..10).map(slot))
+            .with_background(vec4(0.1, 0.1, 0.1, 1.))
+            .with(
+                grid_columns(),
+                GridTrack::to_components([GridTrack::Auto; 4]),
+            )
+            .with(grid_gap(), vec2(5., 5.))
+            .with(fit_horizontal(), Fit::Children)
+            .with(fit_vertical(), Fit::Children)
+            .with_padding_even(10.),
+        FlowRow::el([
+            cell("Grow 1").with(flex_grow(), 1.),
+            cell("Grow 2").with(flex_grow(), 2.),
+            cell("Basis 100").with(flex_basis(), 100.),
+        ])
+        .with_background(vec4(0.1, 0.1, 0.1, 1.))
+        .with(fit_horizontal(), Fit::None)
+        .with(fit_vertical(), Fit::Children)
+        .with(width(), 500.)
+        .with(space_between_items(), 5.)
+        .with_padding_even(10.),
+    ])
+    .with(space_between_items(), 5.)
+    .with_padding_even(STREET)
+}
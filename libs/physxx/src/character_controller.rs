@@ -4,7 +4,7 @@ use glam::{DVec3, Vec3};
 use physx_sys::PxControllerCollisionFlag::*;
 
 use crate::{
-    to_glam_vec3, to_glam_vec3_f64, to_physx_vec3, to_physx_vec3_f64, PxMaterial,
+    to_glam_vec3, to_glam_vec3_f64, to_physx_vec3, to_physx_vec3_f64, PxFilterData, PxMaterial,
     PxRigidDynamicRef, PxSceneRef,
 };
 
@@ -152,10 +152,26 @@ unsafe impl Send for PxControllerDescRef {}
 //     }
 // }
 
-pub struct PxControllerFilters(physx_sys::PxControllerFilters);
+pub struct PxControllerFilters(
+    physx_sys::PxControllerFilters,
+    Option<Box<physx_sys::PxFilterData>>,
+);
 impl PxControllerFilters {
     pub fn new() -> Self {
-        Self(unsafe { physx_sys::PxControllerFilters_new(null_mut(), null_mut(), null_mut()) })
+        Self(
+            unsafe { physx_sys::PxControllerFilters_new(null_mut(), null_mut(), null_mut()) },
+            None,
+        )
+    }
+    /// The controller only collides with shapes whose query filter data has a bit in common
+    /// with `data`.
+    pub fn with_filter_data(data: PxFilterData) -> Self {
+        // PhysX keeps a pointer to the filter data, so it's boxed to keep it in place
+        let data = Box::new(data.to_physx());
+        Self(
+            unsafe { physx_sys::PxControllerFilters_new(&*data, null_mut(), null_mut()) },
+            Some(data),
+        )
     }
 }
 
@@ -13,9 +13,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     sweep::PxSweepHit, to_glam_vec3, to_physx_vec3, AsArticulationBase, AsPxActor, PxActorRef,
-    PxAggregateRef, PxCollectionRef, PxConstraintRef, PxDefaultCpuDispatcherRef, PxGeometry,
-    PxHitFlags, PxPhysicsRef, PxPvdSceneClientRef, PxRaycastHit, PxRigidActorRef, PxShape,
-    PxTransform,
+    PxAggregateRef, PxCollectionRef, PxConstraintRef, PxDefaultCpuDispatcherRef, PxFilterData,
+    PxGeometry, PxHitFlags, PxPhysicsRef, PxPvdSceneClientRef, PxRaycastHit, PxRigidActorRef,
+    PxShape, PxTransform,
 };
 
 pub fn extract_contact_points(
@@ -290,6 +290,14 @@ impl PxSceneRef {
         }
     }
 
+    /// Makes the scene filter all pairs of the actor's shapes again, e.g. after their
+    /// simulation filter data has changed.
+    pub fn reset_filtering(&self, actor: &dyn AsPxActor) {
+        unsafe {
+            physx_sys::PxScene_resetFiltering_mut(self.0, actor.as_actor().0);
+        }
+    }
+
     pub fn add_aggregate(&self, aggregate: &PxAggregateRef) {
         unsafe {
             physx_sys::PxScene_addAggregate_mut(self.0, aggregate.0);
@@ -497,6 +505,11 @@ impl PxQueryFilterData {
     pub fn set_flags(&mut self, flags: PxQueryFlag) {
         self.0.flags.mBits = flags.bits as u16;
     }
+    /// Only shapes whose query filter data has a bit in common with `data` are hit, unless `data`
+    /// is all zeroes.
+    pub fn set_data(&mut self, data: PxFilterData) {
+        self.0.data = data.to_physx();
+    }
 }
 impl Default for PxQueryFilterData {
     fn default() -> Self {
@@ -21,6 +21,37 @@ impl Default for PxShapeFlag {
     }
 }
 
+/// User data for collision filtering. PhysX doesn't interpret it, except for scene queries with
+/// non-zero filter data: those only hit shapes whose query filter data has a bit in common with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PxFilterData {
+    pub word0: u32,
+    pub word1: u32,
+    pub word2: u32,
+    pub word3: u32,
+}
+impl PxFilterData {
+    pub fn new(word0: u32, word1: u32, word2: u32, word3: u32) -> Self {
+        Self {
+            word0,
+            word1,
+            word2,
+            word3,
+        }
+    }
+    pub(crate) fn from_physx(data: physx_sys::PxFilterData) -> Self {
+        Self::new(data.word0, data.word1, data.word2, data.word3)
+    }
+    pub(crate) fn to_physx(self) -> physx_sys::PxFilterData {
+        physx_sys::PxFilterData {
+            word0: self.word0,
+            word1: self.word1,
+            word2: self.word2,
+            word3: self.word3,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PxShape(pub *mut physx_sys::PxShape, pub usize);
 impl PxShape {
@@ -66,6 +97,15 @@ impl PxShape {
     pub fn set_geometry(&self, geometry: &dyn PxGeometry) {
         unsafe { physx_sys::PxShape_setGeometry_mut(self.0, geometry.as_geometry_ptr()) }
     }
+    pub fn set_materials(&self, materials: &[&PxMaterial]) {
+        let mats = materials
+            .iter()
+            .map(|x| x.0)
+            .collect::<Vec<*mut physx_sys::PxMaterial>>();
+        unsafe {
+            physx_sys::PxShape_setMaterials_mut(self.0, mats.as_ptr(), materials.len() as u16)
+        }
+    }
     pub fn get_geometry_type(&self) -> PxGeometryType {
         PxGeometryType::from_i32(unsafe { physx_sys::PxShape_getGeometryType(self.0) }).unwrap()
     }
@@ -107,6 +147,18 @@ impl PxShape {
     pub fn set_contact_offset(&self, offset: f32) {
         unsafe { physx_sys::PxShape_setContactOffset_mut(self.0, offset) }
     }
+    pub fn get_simulation_filter_data(&self) -> PxFilterData {
+        PxFilterData::from_physx(unsafe { physx_sys::PxShape_getSimulationFilterData(self.0) })
+    }
+    pub fn set_simulation_filter_data(&self, data: &PxFilterData) {
+        unsafe { physx_sys::PxShape_setSimulationFilterData_mut(self.0, &data.to_physx()) }
+    }
+    pub fn get_query_filter_data(&self) -> PxFilterData {
+        PxFilterData::from_physx(unsafe { physx_sys::PxShape_getQueryFilterData(self.0) })
+    }
+    pub fn set_query_filter_data(&self, data: &PxFilterData) {
+        unsafe { physx_sys::PxShape_setQueryFilterData_mut(self.0, &data.to_physx()) }
+    }
     pub fn get_rest_offset(&self) -> f32 {
         unsafe { physx_sys::PxShape_getRestOffset(self.0) }
     }
@@ -21,6 +21,10 @@ pub struct BuildMetadata {
     pub settings: BuildSettings,
     #[serde(default)]
     pub asset: Vec<BuildAsset>,
+    /// The files in the build directory other than this metadata, relative to it. Used to copy
+    /// a deployment in full.
+    #[serde(default)]
+    pub files: Vec<String>,
 }
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct BuildSettings {
@@ -18,6 +18,7 @@ pub struct Component {
 pub enum ContainerType {
     Vec,
     Option,
+    Map,
 }
 
 #[derive(Deserialize, Clone, PartialEq, Eq, Serialize)]
@@ -29,15 +30,28 @@ pub enum ComponentType {
         #[serde(alias = "container_type")]
         type_: ContainerType,
         element_type: ItemPathBuf,
+        /// The type of the keys of a `Map`. Defaults to `String`.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        key_type: Option<ItemPathBuf>,
     },
 }
 impl std::fmt::Debug for ComponentType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Item(arg0) => write!(f, "{}", arg0),
+            Self::Contained {
+                type_: ContainerType::Map,
+                element_type,
+                key_type,
+            } => match key_type {
+                Some(key_type) => write!(f, "Map<{}, {}>", key_type, element_type),
+                None => write!(f, "Map<String, {}>", element_type),
+            },
             Self::Contained {
                 type_,
                 element_type,
+                ..
             } => write!(f, "{:?}<{}>", type_, element_type),
         }
     }
@@ -10,5 +10,9 @@ mod message;
 pub use message::*;
 mod enum_;
 pub use enum_::*;
+mod struct_;
+pub use struct_::*;
 mod build_metadata;
 pub use build_metadata::*;
+mod lockfile;
+pub use lockfile::*;
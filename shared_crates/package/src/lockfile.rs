@@ -0,0 +1,66 @@
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::PackageId;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LockfileError {
+    #[error("Failed to parse lockfile")]
+    ParseError(#[from] toml::de::Error),
+    #[error("Failed to serialize lockfile")]
+    SerializeError(#[from] toml::ser::Error),
+}
+
+/// Records the exact version and deployment that each remote dependency in a package's
+/// dependency graph was resolved to, so that subsequent builds are reproducible.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Lockfile {
+    #[serde(default)]
+    #[serde(rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub id: PackageId,
+    pub version: Version,
+    pub deployment: String,
+}
+
+impl Lockfile {
+    pub const FILENAME: &'static str = "ambient.lock";
+
+    pub fn parse(contents: &str) -> Result<Self, LockfileError> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, LockfileError> {
+        Ok(format!(
+            "# This file is automatically generated by Ambient.\n# It is not intended for manual editing.\n{}",
+            toml::to_string_pretty(self)?
+        ))
+    }
+
+    pub fn get(&self, id: &PackageId) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| &p.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_roundtrip_lockfile() {
+        let lockfile = Lockfile {
+            packages: vec![LockedPackage {
+                id: PackageId::new("cezekiuth6khuiykw66bmepsggaoztyv").unwrap(),
+                version: Version::parse("0.1.2").unwrap(),
+                deployment: "5KT8nxvV4vfRUd3KWeLMSD".to_string(),
+            }],
+        };
+
+        let serialized = lockfile.to_toml_string().unwrap();
+        assert_eq!(Lockfile::parse(&serialized), Ok(lockfile));
+    }
+}
@@ -7,8 +7,11 @@ use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use thiserror::Error;
 
+use ambient_shared_types::ActionBinding;
+
 use crate::{
     Component, Concept, Enum, ItemPathBuf, Message, PascalCaseIdentifier, SnakeCaseIdentifier,
+    Struct,
 };
 
 #[derive(Error, Debug, PartialEq)]
@@ -37,6 +40,17 @@ pub struct Manifest {
     #[serde(alias = "enum")]
     pub enums: IndexMap<PascalCaseIdentifier, Enum>,
     #[serde(default)]
+    #[serde(alias = "struct")]
+    pub structs: IndexMap<PascalCaseIdentifier, Struct>,
+    /// Input actions and their default bindings.
+    #[serde(default)]
+    #[serde(alias = "action")]
+    pub actions: IndexMap<SnakeCaseIdentifier, Vec<ActionBinding>>,
+    /// Named collision layers, and the bit they occupy in collision masks.
+    #[serde(default)]
+    #[serde(alias = "collision_layer")]
+    pub collision_layers: IndexMap<SnakeCaseIdentifier, CollisionLayerIndex>,
+    #[serde(default)]
     pub includes: HashMap<SnakeCaseIdentifier, PathBuf>,
     #[serde(default)]
     pub dependencies: IndexMap<SnakeCaseIdentifier, Dependency>,
@@ -58,6 +72,43 @@ impl Manifest {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Ord, Eq, Hash, Default, Serialize)]
+#[serde(transparent)]
+/// The index of a collision layer. Guaranteed to be less than [`CollisionLayerIndex::COUNT`].
+pub struct CollisionLayerIndex(pub(crate) u8);
+impl<'de> Deserialize<'de> for CollisionLayerIndex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        CollisionLayerIndex::new(u8::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+impl CollisionLayerIndex {
+    /// The number of available collision layers.
+    pub const COUNT: u8 = 32;
+
+    /// Attempts to create a new collision layer index.
+    pub fn new(index: u8) -> Result<Self, String> {
+        if index >= Self::COUNT {
+            return Err(format!(
+                "Collision layer index {index} is out of range; it must be less than {}",
+                Self::COUNT
+            ));
+        }
+        Ok(Self(index))
+    }
+
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+
+    /// The mask with only this layer's bit set.
+    pub fn mask(&self) -> u32 {
+        1 << self.0
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Ord, Eq, Hash, Default, Serialize)]
 #[serde(transparent)]
 /// A checksummed package ID. Guaranteed to be a valid `SnakeCaseIdentifier` as well.
@@ -266,6 +317,15 @@ impl Dependency {
     pub fn id_version(&self) -> Option<(&PackageId, &str)> {
         self.id.as_ref().zip(self.version.as_deref())
     }
+
+    /// Parses `version` as a semver requirement. A full version (e.g. `0.1.0`) pins that exact
+    /// version (`=0.1.0`); anything else, such as `0.1` or `^0.1.0`, is a range.
+    pub fn version_req(&self) -> Option<Result<VersionReq, semver::Error>> {
+        self.version.as_deref().map(|version| match Version::parse(version) {
+            Ok(_) => VersionReq::parse(&format!("={version}")),
+            Err(_) => VersionReq::parse(version),
+        })
+    }
 }
 
 #[derive(Deserialize, Clone, Debug, Default, PartialEq, Serialize)]
@@ -293,12 +353,13 @@ pub enum Region {
 mod tests {
     use std::path::PathBuf;
 
+    use ambient_shared_types::{ActionBinding, GamepadAxis, GamepadButton, VirtualKeyCode};
     use indexmap::IndexMap;
 
     use crate::{
-        Build, BuildRust, Component, ComponentType, Components, Concept, ConceptValue,
-        ContainerType, Dependency, Enum, Identifier, ItemPathBuf, Manifest, ManifestParseError,
-        Package, PackageId, PascalCaseIdentifier, SnakeCaseIdentifier,
+        Build, BuildRust, CollisionLayerIndex, Component, ComponentType, Components, Concept,
+        ConceptValue, ContainerType, Dependency, Enum, Identifier, ItemPathBuf, Manifest,
+        ManifestParseError, Package, PackageId, PascalCaseIdentifier, SnakeCaseIdentifier, Struct,
     };
     use semver::Version;
 
@@ -342,6 +403,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exact_versions_are_pinned() {
+        let requirement = |version: &str| {
+            Dependency {
+                path: None,
+                id: None,
+                version: Some(version.to_string()),
+                deployment: None,
+                enabled: None,
+            }
+            .version_req()
+            .unwrap()
+            .unwrap()
+            .to_string()
+        };
+
+        assert_eq!(requirement("0.1.0"), "=0.1.0");
+        assert_eq!(requirement("1.2.3-beta.1"), "=1.2.3-beta.1");
+        assert_eq!(requirement("0.1"), "^0.1");
+        assert_eq!(requirement("^0.1.0"), "^0.1.0");
+        assert_eq!(requirement(">=0.1, <0.3"), ">=0.1, <0.3");
+    }
+
     #[test]
     fn will_fail_on_legacy_project_toml() {
         const TOML: &str = r#"
@@ -658,6 +742,155 @@ mod tests {
         )
     }
 
+    #[test]
+    fn can_parse_structs() {
+        const TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Inventory"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [structs.InventorySlot]
+        description = "A single slot in an inventory"
+        [structs.InventorySlot.fields]
+        item = "String"
+        count = "U32"
+        tags = { type = "Vec", element_type = "String" }
+        "#;
+
+        assert_eq!(
+            Manifest::parse(TOML),
+            Ok(Manifest {
+                package: Package {
+                    id: Some(PackageId("lktsfudbjw2qikhyumt573ozxhadkiwm".to_string())),
+                    name: "Inventory".to_string(),
+                    version: Version::parse("0.0.1").unwrap(),
+                    ..Default::default()
+                },
+                structs: IndexMap::from_iter([(
+                    pci("InventorySlot"),
+                    Struct {
+                        description: Some("A single slot in an inventory".to_string()),
+                        fields: IndexMap::from_iter([
+                            (sci("item"), ComponentType::Item(ipb("String"))),
+                            (sci("count"), ComponentType::Item(ipb("U32"))),
+                            (
+                                sci("tags"),
+                                ComponentType::Contained {
+                                    type_: ContainerType::Vec,
+                                    element_type: ipb("String"),
+                                    key_type: None,
+                                }
+                            ),
+                        ])
+                    }
+                )]),
+                ..Default::default()
+            })
+        )
+    }
+
+    #[test]
+    fn can_parse_actions() {
+        const TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Racer"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [actions]
+        boost = ["Key:Space", "Gamepad:South"]
+        steer = ["Key:D", "-Key:A", "GamepadAxis:LeftStickX"]
+        "#;
+
+        assert_eq!(
+            Manifest::parse(TOML),
+            Ok(Manifest {
+                package: Package {
+                    id: Some(PackageId("lktsfudbjw2qikhyumt573ozxhadkiwm".to_string())),
+                    name: "Racer".to_string(),
+                    version: Version::parse("0.0.1").unwrap(),
+                    ..Default::default()
+                },
+                actions: IndexMap::from_iter([
+                    (
+                        sci("boost"),
+                        vec![VirtualKeyCode::Space.into(), GamepadButton::South.into()]
+                    ),
+                    (
+                        sci("steer"),
+                        vec![
+                            VirtualKeyCode::D.into(),
+                            ActionBinding::from(VirtualKeyCode::A).negated(),
+                            GamepadAxis::LeftStickX.into(),
+                        ]
+                    ),
+                ]),
+                ..Default::default()
+            })
+        );
+
+        const INVALID_TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Racer"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [actions]
+        boost = ["Spacebar"]
+        "#;
+
+        assert!(Manifest::parse(INVALID_TOML).is_err());
+    }
+
+    #[test]
+    fn can_parse_collision_layers() {
+        const TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Racer"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [collision_layers]
+        player = 1
+        pickup = 31
+        "#;
+
+        assert_eq!(
+            Manifest::parse(TOML),
+            Ok(Manifest {
+                package: Package {
+                    id: Some(PackageId("lktsfudbjw2qikhyumt573ozxhadkiwm".to_string())),
+                    name: "Racer".to_string(),
+                    version: Version::parse("0.0.1").unwrap(),
+                    ..Default::default()
+                },
+                collision_layers: IndexMap::from_iter([
+                    (sci("player"), CollisionLayerIndex(1)),
+                    (sci("pickup"), CollisionLayerIndex(31)),
+                ]),
+                ..Default::default()
+            })
+        );
+
+        const INVALID_TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Racer"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [collision_layers]
+        player = 32
+        "#;
+
+        assert!(Manifest::parse(INVALID_TOML).is_err());
+    }
+
     #[test]
     fn can_parse_container_types() {
         const TOML: &str = r#"
@@ -671,6 +904,8 @@ mod tests {
         test = { type = "I32", name = "Test", description = "Test" }
         vec_test = { type = { container_type = "Vec", element_type = "I32" }, name = "Test", description = "Test" }
         option_test = { type = { container_type = "Option", element_type = "I32" }, name = "Test", description = "Test" }
+        map_test = { type = { container_type = "Map", element_type = "I32" }, name = "Test", description = "Test" }
+        u32_map_test = { type = { container_type = "Map", key_type = "U32", element_type = "I32" }, name = "Test", description = "Test" }
 
         "#;
 
@@ -706,7 +941,8 @@ mod tests {
                             description: Some("Test".to_string()),
                             type_: ComponentType::Contained {
                                 type_: ContainerType::Vec,
-                                element_type: i("I32").into()
+                                element_type: i("I32").into(),
+                                key_type: None,
                             },
                             attributes: vec![],
                             default: None,
@@ -719,7 +955,36 @@ mod tests {
                             description: Some("Test".to_string()),
                             type_: ComponentType::Contained {
                                 type_: ContainerType::Option,
-                                element_type: i("I32").into()
+                                element_type: i("I32").into(),
+                                key_type: None,
+                            },
+                            attributes: vec![],
+                            default: None,
+                        }
+                    ),
+                    (
+                        ipb("map_test"),
+                        Component {
+                            name: Some("Test".to_string()),
+                            description: Some("Test".to_string()),
+                            type_: ComponentType::Contained {
+                                type_: ContainerType::Map,
+                                element_type: i("I32").into(),
+                                key_type: None,
+                            },
+                            attributes: vec![],
+                            default: None,
+                        }
+                    ),
+                    (
+                        ipb("u32_map_test"),
+                        Component {
+                            name: Some("Test".to_string()),
+                            description: Some("Test".to_string()),
+                            type_: ComponentType::Contained {
+                                type_: ContainerType::Map,
+                                element_type: i("I32").into(),
+                                key_type: Some(i("U32").into()),
                             },
                             attributes: vec![],
                             default: None,
@@ -0,0 +1,11 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{ComponentType, SnakeCaseIdentifier};
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct Struct {
+    #[serde(default)]
+    pub description: Option<String>,
+    pub fields: IndexMap<SnakeCaseIdentifier, ComponentType>,
+}
@@ -164,7 +164,7 @@ pub fn write(output_path: &Path, json_path: &Path, autoreload: bool) -> anyhow::
             false,
         )?;
 
-        // Write Vec and Option types
+        // Write Vec, Option and Map types
         for container_items in [&manifest.vec_items, &manifest.option_items] {
             ctx.write_items(
                 &output_path,
@@ -174,6 +174,14 @@ pub fn write(output_path: &Path, json_path: &Path, autoreload: bool) -> anyhow::
                     .map(|t| (&manifest.get(t).data.id, t)),
             )?;
         }
+        ctx.write_items(
+            &output_path,
+            "type",
+            manifest
+                .map_items
+                .iter()
+                .map(|t| (&manifest.get(t).data.id, t)),
+        )?;
     }
 
     // Packages
@@ -0,0 +1,30 @@
+use ambient_package_semantic::Package;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+pub fn generate(package: &Package) -> anyhow::Result<TokenStream> {
+    let layers = &package.manifest.collision_layers;
+    if layers.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let constants = layers.iter().map(|(name, index)| {
+        let ident = syn::Ident::new(&name.as_str().to_uppercase(), Span::call_site());
+        let bit = index.index();
+        let doc = format!("The mask for the `{name}` collision layer (bit {bit}).");
+        let mask = index.mask();
+        quote! {
+            #[doc = #doc]
+            pub const #ident: u32 = #mask;
+        }
+    });
+
+    Ok(quote! {
+        /// Masks for the collision layers declared in this package's manifest.
+        ///
+        /// Combine them with `|` to build `collision_layer` and `collision_mask` values.
+        pub mod collision_layers {
+            #(#constants)*
+        }
+    })
+}
@@ -4,8 +4,12 @@ use quote::quote;
 
 use crate::{make_path, Context};
 
-pub fn generate(context: Context, items: &ItemMap, scope: &Scope) -> anyhow::Result<TokenStream> {
-    let enums = scope
+pub fn generate(
+    context: Context,
+    items: &ItemMap,
+    scope: &Scope,
+) -> anyhow::Result<Vec<TokenStream>> {
+    scope
         .types
         .values()
         .filter_map(|id| context.extract_item_if_relevant(items, *id))
@@ -86,6 +90,17 @@ pub fn generate(context: Context, items: &ItemMap, scope: &Scope) -> anyhow::Res
                             self.to_u32().into_value()
                         }
                     }
+                    impl #guest_api_path::ecs::SupportedStructField for #enum_name {
+                        fn push_struct_fields(self, fields: &mut Vec<#guest_api_path::ecs::ComponentValue>) {
+                            use #ecs_prefix EnumComponent;
+                            #guest_api_path::ecs::SupportedStructField::push_struct_fields(self.to_u32(), fields)
+                        }
+
+                        fn take_struct_fields(fields: &mut dyn Iterator<Item = #guest_api_path::ecs::ComponentValue>) -> Option<Self> {
+                            use #ecs_prefix EnumComponent;
+                            <u32 as #guest_api_path::ecs::SupportedStructField>::take_struct_fields(fields).and_then(Self::from_u32)
+                        }
+                    }
                     // Code generation for Vec/Option is disabled as you cannot implement foreign traits on foreign types
                     // Need to think about how to best solve this
                     // impl #guest_api_path::ecs::SupportedValue for Vec<#enum_name> {
@@ -141,22 +156,5 @@ pub fn generate(context: Context, items: &ItemMap, scope: &Scope) -> anyhow::Res
                 #message_serde_impl
             })
         })
-        .collect::<anyhow::Result<Vec<_>>>()?;
-
-    if enums.is_empty() {
-        return Ok(quote! {});
-    }
-
-    let includes = context
-        .guest_api_path()
-        .map(|s| quote! { use #s::{global::serde, message::*}; })
-        .unwrap_or(quote! { use serde; use ambient_package_rt::message_serde::*; });
-
-    Ok(quote! {
-        /// Auto-generated type definitions.
-        pub mod types {
-            #includes
-            #(#enums)*
-        }
-    })
+        .collect()
 }
@@ -7,11 +7,13 @@ use quote::{quote, ToTokens};
 use std::collections::HashMap;
 
 mod assets;
+mod collision_layers;
 mod components;
 mod concepts;
 mod context;
 mod enums;
 mod messages;
+mod structs;
 
 pub use context::Context;
 
@@ -42,6 +44,10 @@ pub async fn generate_code(
                 map.insert(items.get_option_id(*type_id), quote! {Option::<#ty_tokens>});
             }
         }
+        for ((key, value), map_id) in items.map_items() {
+            let (key, value) = (map[key].clone(), map[value].clone());
+            map.insert(*map_id, quote! {std::collections::HashMap::<#key, #value>});
+        }
         TypePrinter(map)
     };
 
@@ -123,6 +129,7 @@ fn generate_package(
     };
     let generated_output = generate_scope(context, items, type_printer, generate_from_scope)?;
     let assets = assets::generate(context, items, generate_from_scope)?;
+    let collision_layers = collision_layers::generate(package)?;
     let components_init = components::generate_init(context, items, generate_from_scope)?;
 
     let id = make_path(package.data.id.as_str());
@@ -131,6 +138,7 @@ fn generate_package(
             #entity
             #generated_output
             #assets
+            #collision_layers
             #components_init
         }
     })
@@ -168,7 +176,7 @@ fn generate_scope(
     let components = components::generate(context, items, type_printer, scope)?;
     let concepts = concepts::generate(context, items, type_printer, scope)?;
     let messages = messages::generate(context, items, type_printer, scope)?;
-    let types = enums::generate(context, items, scope)?;
+    let types = generate_types(context, items, type_printer, scope)?;
 
     Ok(quote! {
         #(#scopes)*
@@ -180,6 +188,42 @@ fn generate_scope(
     })
 }
 
+fn generate_types(
+    context: context::Context,
+    items: &ItemMap,
+    type_printer: &TypePrinter,
+    scope: &Scope,
+) -> anyhow::Result<TokenStream> {
+    let enums = enums::generate(context, items, scope)?;
+    let structs = structs::generate(context, items, type_printer, scope)?;
+    if enums.is_empty() && structs.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let includes = match context {
+        Context::Host => quote! {
+            use serde;
+            use ambient_package_rt::message_serde::*;
+            use glam::{Vec2, Vec3, Vec4, UVec2, UVec3, UVec4, Mat4, Quat};
+            use crate::EntityId;
+        },
+        Context::GuestApi | Context::GuestUser => {
+            let api_path = context.guest_api_path().unwrap();
+            quote! { use #api_path::{prelude::*, global::serde, message::*}; }
+        }
+    };
+
+    Ok(quote! {
+        /// Auto-generated type definitions.
+        #[allow(unused_imports)]
+        pub mod types {
+            #includes
+            #(#enums)*
+            #(#structs)*
+        }
+    })
+}
+
 fn make_path(id: &str) -> syn::Path {
     syn::parse_str(id).unwrap()
 }
@@ -193,9 +237,21 @@ impl TypePrinter {
         prefix: Option<&str>,
         id: ItemId<Type>,
     ) -> anyhow::Result<TokenStream> {
-        match self.0.get(&id) {
-            Some(ts) => Ok(ts.clone()),
-            None => context.get_path(items, prefix, id),
+        if let Some(ts) = self.0.get(&id) {
+            return Ok(ts.clone());
+        }
+
+        // Containers of user-defined types are not pre-registered, so print them recursively.
+        match items.get(id).inner {
+            TypeInner::Vec(inner) => {
+                let inner = self.get(context, items, prefix, inner)?;
+                Ok(quote! { Vec::<#inner> })
+            }
+            TypeInner::Option(inner) => {
+                let inner = self.get(context, items, prefix, inner)?;
+                Ok(quote! { Option::<#inner> })
+            }
+            _ => context.get_path(items, prefix, id),
         }
     }
 }
@@ -0,0 +1,134 @@
+use ambient_package_semantic::{Item, ItemMap, Scope};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{make_path, Context, TypePrinter};
+
+pub fn generate(
+    context: Context,
+    items: &ItemMap,
+    type_printer: &TypePrinter,
+    scope: &Scope,
+) -> anyhow::Result<Vec<TokenStream>> {
+    scope
+        .types
+        .values()
+        .filter_map(|id| context.extract_item_if_relevant(items, *id))
+        .filter(|ty| ty.inner.as_struct().is_some())
+        .map(|ty| {
+            let (data, struct_) = (ty.data(), ty.inner.as_struct().unwrap());
+            let id = data.id.as_str();
+            let doc_comment = if let Some(desc) = &struct_.description {
+                format!("**{}**: {}", id, desc)
+            } else {
+                format!("**{}**", id)
+            };
+
+            let struct_name = make_path(id);
+            let field_names = struct_
+                .fields
+                .keys()
+                .map(|id| make_path(id.as_str()))
+                .collect::<Vec<_>>();
+            let field_types = struct_
+                .fields
+                .values()
+                .map(|ty| type_printer.get(context, items, None, ty.as_resolved().unwrap()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let main = quote! {
+                #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, Default)]
+                #[serde(crate = "self::serde")]
+                #[doc = #doc_comment]
+                pub struct #struct_name {
+                    #(pub #field_names: #field_types,)*
+                }
+            };
+
+            // Structs that can be flattened are passed to the host as their fields. Any other struct
+            // is passed as its serialized bytes.
+            let supported_value = match context.guest_api_path() {
+                Some(guest_api_path) if struct_.flattened_fields(items).is_some() => quote! {
+                    impl #guest_api_path::ecs::SupportedStructField for #struct_name {
+                        fn push_struct_fields(self, fields: &mut Vec<#guest_api_path::ecs::ComponentValue>) {
+                            #(#guest_api_path::ecs::SupportedStructField::push_struct_fields(self.#field_names, fields);)*
+                        }
+
+                        fn take_struct_fields(fields: &mut dyn Iterator<Item = #guest_api_path::ecs::ComponentValue>) -> Option<Self> {
+                            Some(Self {
+                                #(#field_names: <#field_types as #guest_api_path::ecs::SupportedStructField>::take_struct_fields(fields)?,)*
+                            })
+                        }
+                    }
+                    impl #guest_api_path::ecs::SupportedValue for #struct_name {
+                        fn from_result(result: #guest_api_path::ecs::WitComponentValue) -> Option<Self> {
+                            #guest_api_path::ecs::__internal_struct_from_result(result)
+                        }
+
+                        fn into_result(self) -> #guest_api_path::ecs::WitComponentValue {
+                            #guest_api_path::ecs::__internal_struct_into_result(self)
+                        }
+
+                        fn from_value(value: #guest_api_path::ecs::ComponentValue) -> Option<Self> {
+                            #guest_api_path::ecs::__internal_struct_from_value(value)
+                        }
+
+                        fn into_value(self) -> #guest_api_path::ecs::ComponentValue {
+                            #guest_api_path::ecs::__internal_struct_into_value(self)
+                        }
+                    }
+                },
+                Some(guest_api_path) => quote! {
+                    impl #guest_api_path::ecs::SupportedValue for #struct_name {
+                        fn from_result(result: #guest_api_path::ecs::WitComponentValue) -> Option<Self> {
+                            Vec::<u8>::from_result(result).and_then(|v| Self::deserialize_message_part(&mut v.as_slice()).ok())
+                        }
+
+                        fn into_result(self) -> #guest_api_path::ecs::WitComponentValue {
+                            let mut output = vec![];
+                            self.serialize_message_part(&mut output).unwrap();
+                            output.into_result()
+                        }
+
+                        fn from_value(value: #guest_api_path::ecs::ComponentValue) -> Option<Self> {
+                            Vec::<u8>::from_value(value).and_then(|v| Self::deserialize_message_part(&mut v.as_slice()).ok())
+                        }
+
+                        fn into_value(self) -> #guest_api_path::ecs::ComponentValue {
+                            let mut output = vec![];
+                            self.serialize_message_part(&mut output).unwrap();
+                            output.into_value()
+                        }
+                    }
+                },
+                None => quote! {},
+            };
+
+            let message_serde_impl = quote! {
+                impl MessageSerde for #struct_name {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        #(self.#field_names.serialize_message_part(output)?;)*
+                        Ok(())
+                    }
+
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            #(#field_names: <#field_types>::deserialize_message_part(input)?,)*
+                        })
+                    }
+                }
+            };
+
+            Ok(quote! {
+                #main
+                #supported_value
+                #message_serde_impl
+            })
+        })
+        .collect()
+}
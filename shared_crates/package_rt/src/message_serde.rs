@@ -1,4 +1,4 @@
-use std::{io::Read, time::Duration};
+use std::{collections::HashMap, hash::Hash, io::Read, time::Duration};
 
 use ambient_shared_types::{procedural_storage_handle_definitions, ComponentIndex};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -339,6 +339,27 @@ impl<T: MessageSerde> MessageSerde for Option<T> {
         })
     }
 }
+impl<K: MessageSerde + Eq + Hash, V: MessageSerde> MessageSerde for HashMap<K, V> {
+    fn serialize_message_part(&self, output: &mut Vec<u8>) -> Result<(), MessageSerdeError> {
+        output.write_u32::<BigEndian>(self.len().try_into()?)?;
+        for (key, value) in self {
+            key.serialize_message_part(output)?;
+            value.serialize_message_part(output)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize_message_part(input: &mut dyn Read) -> Result<Self, MessageSerdeError> {
+        let length = usize::try_from(input.read_u32::<BigEndian>()?).unwrap();
+        let mut data = HashMap::with_capacity(length);
+        for _ in 0..length {
+            let key = K::deserialize_message_part(input)?;
+            let value = V::deserialize_message_part(input)?;
+            data.insert(key, value);
+        }
+        Ok(data)
+    }
+}
 impl MessageSerde for Duration {
     fn serialize_message_part(&self, output: &mut Vec<u8>) -> Result<(), MessageSerdeError> {
         output.write_u64::<BigEndian>(self.as_nanos().try_into().unwrap())?;
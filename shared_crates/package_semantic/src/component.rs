@@ -2,8 +2,8 @@ use ambient_package::ItemPathBuf;
 use anyhow::Context as AnyhowContext;
 
 use crate::{
-    Attribute, Item, ItemData, ItemId, ItemType, ItemVariant, ResolvableItemId, ResolvableValue,
-    Resolve, Semantic, Type,
+    Attribute, Item, ItemData, ItemId, ItemType, ItemVariant, PrimitiveType, ResolvableItemId,
+    ResolvableValue, Resolve, Semantic, Type, TypeInner,
 };
 
 #[derive(Clone, PartialEq, Debug)]
@@ -80,6 +80,56 @@ impl Resolve for Component {
             });
         }
 
+        // Relationships refer to other entities, so they must hold entity IDs
+        let relationship = semantic.standard_definitions.attributes.relationship;
+        if attributes
+            .iter()
+            .any(|a| a.as_resolved() == Some(relationship))
+        {
+            let refers_to_entities = match &semantic.items.get(type_id).inner {
+                TypeInner::Primitive(PrimitiveType::EntityId) => true,
+                TypeInner::Vec(element_id) => {
+                    semantic.items.get(*element_id).inner.as_primitive()
+                        == Some(PrimitiveType::EntityId)
+                }
+                _ => false,
+            };
+            anyhow::ensure!(
+                refers_to_entities,
+                "The component `{}` has the `Relationship` attribute, so its type must be `EntityId` or `Vec<EntityId>`",
+                self.data.id
+            );
+        }
+
+        // Indexes are sorted by the component's value, so it must have a total order
+        let indexed = semantic.standard_definitions.attributes.indexed;
+        if attributes.iter().any(|a| a.as_resolved() == Some(indexed)) {
+            let orderable = matches!(
+                semantic.items.get(type_id).inner.as_primitive(),
+                Some(
+                    PrimitiveType::Bool
+                        | PrimitiveType::EntityId
+                        | PrimitiveType::F32
+                        | PrimitiveType::F64
+                        | PrimitiveType::String
+                        | PrimitiveType::U8
+                        | PrimitiveType::U16
+                        | PrimitiveType::U32
+                        | PrimitiveType::U64
+                        | PrimitiveType::I8
+                        | PrimitiveType::I16
+                        | PrimitiveType::I32
+                        | PrimitiveType::I64
+                        | PrimitiveType::Duration
+                )
+            );
+            anyhow::ensure!(
+                orderable,
+                "The component `{}` has the `Indexed` attribute, so its type must be a number, `Bool`, `String`, `EntityId` or `Duration`",
+                self.data.id
+            );
+        }
+
         // If this is an enum, emit the `Enum` attribute
         if semantic.items.get(type_id).inner.as_enum().is_some() {
             attributes.push(ResolvableItemId::Resolved(
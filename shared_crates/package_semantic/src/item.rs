@@ -28,6 +28,7 @@ pub struct ItemMap {
     items: HashMap<Ulid, ItemVariant>,
     vec_items: HashMap<ItemId<Type>, ItemId<Type>>,
     option_items: HashMap<ItemId<Type>, ItemId<Type>>,
+    map_items: HashMap<(ItemId<Type>, ItemId<Type>), ItemId<Type>>,
 }
 impl ItemMap {
     pub fn add<T: Item>(&mut self, item: T) -> ItemId<T> {
@@ -64,6 +65,23 @@ impl ItemMap {
         }
     }
 
+    /// Adds a map type from `key` to `value`. Unlike other types, maps do not get `Vec` and `Option` variants.
+    pub fn add_map(&mut self, key: ItemId<Type>, value: ItemId<Type>) -> ItemId<Type> {
+        let key_data = self.get(key).data().clone();
+        let value_data = self.get(value).data().clone();
+        let map_id = self.add_raw(Type::new(
+            ItemData {
+                id: PascalCaseIdentifier::new(&format!("Map{}{}", key_data.id, value_data.id))
+                    .unwrap()
+                    .into(),
+                ..value_data
+            },
+            TypeInner::Map { key, value },
+        ));
+        self.map_items.insert((key, value), map_id);
+        map_id
+    }
+
     // We use ULID creation as this code is only run on the server, where randomness is available.
     // If this runs on the client, then yes, it should panic!
     #[allow(clippy::disallowed_methods)]
@@ -99,6 +117,11 @@ impl ItemMap {
         self.option_items.get(&id).copied().unwrap()
     }
 
+    /// Returns the map type from `key` to `value`, if maps between these types are supported.
+    pub fn get_map_id(&self, key: ItemId<Type>, value: ItemId<Type>) -> Option<ItemId<Type>> {
+        self.map_items.get(&(key, value)).copied()
+    }
+
     pub fn get_scope_id(
         &self,
         start_scope_id: ItemId<Scope>,
@@ -251,6 +274,10 @@ impl ItemMap {
     pub fn option_items(&self) -> &HashMap<ItemId<Type>, ItemId<Type>> {
         &self.option_items
     }
+
+    pub fn map_items(&self) -> &HashMap<(ItemId<Type>, ItemId<Type>), ItemId<Type>> {
+        &self.map_items
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -9,17 +9,19 @@ use anyhow::Context as AnyhowContext;
 use async_recursion::async_recursion;
 
 use ambient_package::{
-    BuildMetadata, ComponentType, Identifier, ItemPath, ItemPathBuf, Manifest, PackageId,
+    BuildMetadata, ComponentType, Identifier, ItemPath, ItemPathBuf, Lockfile, Manifest, PackageId,
     PascalCaseIdentifier, SnakeCaseIdentifier,
 };
-use ambient_shared_types::{primitive_component_definitions, urls::API_URL};
+use ambient_shared_types::primitive_component_definitions;
 
 mod scope;
-use package::{GetError, ParentJoinError, RetrievableDeployment};
+use package::{GetError, ParentJoinError};
 pub use scope::Scope;
 
 mod package;
-pub use package::{Dependency, LocalOrRemote, Package, PackageLocator, RetrievableFile};
+pub use package::{
+    Dependency, LocalOrRemote, Package, PackageLocator, RetrievableDeployment, RetrievableFile,
+};
 
 mod item;
 pub use item::{
@@ -40,18 +42,23 @@ pub use primitive_type::PrimitiveType;
 
 mod type_;
 use thiserror::Error;
-pub use type_::{Enum, Type, TypeInner};
+pub use type_::{Enum, Struct, Type, TypeInner};
 
 mod message;
 pub use message::Message;
 
 mod value;
-use url::Url;
 pub use value::{ResolvableValue, ScalarValue, Value};
 
 mod printer;
 pub use printer::Printer;
 
+mod version;
+pub use version::{resolve_versions, select_version, PackageVersion};
+
+mod registry;
+pub use registry::{Registries, Registry, RegistryError, RegistryParseError};
+
 mod util;
 
 pub type Schema = HashMap<&'static str, &'static str>;
@@ -90,11 +97,31 @@ pub enum PackageAddError {
         include_source: RetrievableFile,
         source: ambient_package::ManifestParseError,
     },
-    #[error("The package `{package_id}` does not have a version `{version}`")]
-    InvalidPackageVersion {
+    #[error("The version requirement `{requirement}` for the package `{package_id}` is invalid")]
+    InvalidVersionRequirement {
+        package_id: PackageId,
+        requirement: String,
+    },
+    #[error("The package `{package_id}` is not published to any of the registries: {registries}")]
+    FailedToGetPackageVersions {
+        package_id: PackageId,
+        registries: String,
+    },
+    #[error(
+        "Failed to get the versions of the package `{package_id}` from the registry {registry}"
+    )]
+    RegistryError {
+        package_id: PackageId,
+        registry: String,
+        source: RegistryError,
+    },
+    #[error("The package `{package_id}` does not have a version matching all of the requirements: {requirements}")]
+    NoMatchingVersion {
         package_id: PackageId,
-        version: String,
+        requirements: String,
     },
+    #[error("Failed to find a consistent set of versions for the dependency graph")]
+    VersionResolutionFailed,
     #[error("{0}")]
     PackageConflictError(Box<PackageConflictError>),
     #[error("{0}")]
@@ -109,6 +136,8 @@ pub enum PackageAddError {
     #[error("{0}")]
     BuildMetadataError(ambient_package::BuildMetadataError),
     #[error("{0}")]
+    LockfileError(ambient_package::LockfileError),
+    #[error("{0}")]
     IdentifierCaseError(ambient_package::IdentifierCaseOwnedError),
 }
 
@@ -132,6 +161,11 @@ impl From<ambient_package::BuildMetadataError> for Box<PackageAddError> {
         Box::new(PackageAddError::BuildMetadataError(val))
     }
 }
+impl From<ambient_package::LockfileError> for Box<PackageAddError> {
+    fn from(val: ambient_package::LockfileError) -> Self {
+        Box::new(PackageAddError::LockfileError(val))
+    }
+}
 impl From<ambient_package::IdentifierCaseOwnedError> for Box<PackageAddError> {
     fn from(val: ambient_package::IdentifierCaseOwnedError) -> Self {
         Box::new(PackageAddError::IdentifierCaseError(val))
@@ -166,7 +200,7 @@ impl fmt::Display for PackageConflictError {
 
         write!(
             f,
-            "Package conflict found:\n  - {existing_package}{}\n\n  - {new_package}{}\n\nThe system does not currently support multiple versions of the same package in the dependency tree.",
+            "Package conflict found:\n  - {existing_package}{}\n\n  - {new_package}{}\n\nThe system does not currently support multiple versions of the same package in the dependency tree. Use compatible version requirements (e.g. `version = \"0.1\"`) so that a single version can be selected.",
             imported_by(existing_package_dependent.as_ref()),
             imported_by(new_package_dependent.as_ref())
         )
@@ -182,6 +216,11 @@ pub struct Semantic {
     pub id_to_locator: HashMap<Identifier, PackageLocator>,
     pub ambient_package_id: ItemId<Package>,
     pub standard_definitions: StandardDefinitions,
+    /// The versions selected for the remote dependencies of the root user package.
+    /// Populated when the first user package is added.
+    pub lockfile: Option<Lockfile>,
+    /// The registries used to look up the versions of remote dependencies.
+    pub registries: Registries,
     ignore_local_dependencies: bool,
 }
 impl Semantic {
@@ -202,6 +241,10 @@ impl Semantic {
             id_to_locator: HashMap::new(),
             ambient_package_id: ItemId::empty_you_should_really_initialize_this(),
             standard_definitions,
+            lockfile: None,
+            registries: Registries::from_env().with_context(|| {
+                format!("Failed to parse registries from {}", Registries::ENV_VAR)
+            })?,
             ignore_local_dependencies,
         };
 
@@ -269,6 +312,24 @@ impl Semantic {
             .into());
         }
 
+        // When the root user package is added, select the versions for the entire dependency
+        // graph up front, so that all packages depending on the same package agree on its version.
+        if dependent_package_id.is_none()
+            && self.lockfile.is_none()
+            && !matches!(retrievable_manifest, RetrievableFile::Ambient(_))
+        {
+            let existing_lockfile = retrievable_manifest
+                .parent_join(Path::new(Lockfile::FILENAME))?
+                .get()
+                .await
+                .ok()
+                .map(|s| Lockfile::parse(&s))
+                .transpose()?;
+
+            self.resolve_versions(&retrievable_manifest, existing_lockfile.as_ref())
+                .await?;
+        }
+
         let build_metadata = retrievable_manifest
             .parent_join(Path::new(BuildMetadata::FILENAME))?
             .get()
@@ -316,6 +377,8 @@ impl Semantic {
             let Some(source) = package_dependency_to_retrievable_file(
                 &retrievable_manifest,
                 self.ignore_local_dependencies,
+                &self.registries,
+                self.lockfile.as_ref(),
                 &dependency,
             )
             .await?
@@ -366,6 +429,38 @@ impl Semantic {
         Ok(id)
     }
 
+    /// Selects versions for all remote dependencies reachable from `root_manifest`,
+    /// preferring those in `existing_lockfile` where they are still compatible.
+    ///
+    /// If the root package has a vendor directory, it is used as the highest-precedence registry.
+    /// The result is stored in [Self::lockfile] and used for subsequently-added packages.
+    pub async fn resolve_versions(
+        &mut self,
+        root_manifest: &RetrievableFile,
+        existing_lockfile: Option<&Lockfile>,
+    ) -> Result<&Lockfile, Box<PackageAddError>> {
+        // Packages vendored with `ambient package vendor` take precedence over all other
+        // registries, so that the package can be built offline.
+        #[cfg(not(target_os = "unknown"))]
+        if let Some(vendor_path) = root_manifest
+            .as_local_path()
+            .and_then(|p| Some(p.parent()?.join(Registries::VENDOR_DIRECTORY)))
+            .filter(|p| p.is_dir())
+        {
+            self.registries.prepend(Registry::Local(vendor_path));
+        }
+
+        let lockfile = resolve_versions(
+            root_manifest,
+            self.ignore_local_dependencies,
+            &self.registries,
+            existing_lockfile,
+        )
+        .await?;
+
+        Ok(self.lockfile.insert(lockfile))
+    }
+
     pub fn resolve_all(&mut self) -> anyhow::Result<()> {
         let package_ids = self.packages.values().copied().collect::<Vec<_>>();
         for package_id in package_ids {
@@ -425,10 +520,27 @@ impl Semantic {
             ComponentType::Contained {
                 type_,
                 element_type,
-            } => get_type_id(&self.items, scope_id, element_type.as_path()).map(|id| match type_ {
-                ambient_package::ContainerType::Vec => self.items.get_vec_id(id),
-                ambient_package::ContainerType::Option => self.items.get_option_id(id),
-            }),
+                key_type,
+            } => {
+                let id = get_type_id(&self.items, scope_id, element_type.as_path())?;
+                match type_ {
+                    ambient_package::ContainerType::Vec => Some(self.items.get_vec_id(id)),
+                    ambient_package::ContainerType::Option => Some(self.items.get_option_id(id)),
+                    ambient_package::ContainerType::Map => {
+                        let key_id = match key_type {
+                            Some(key_type) => {
+                                get_type_id(&self.items, scope_id, key_type.as_path())?
+                            }
+                            None => *self
+                                .items
+                                .get(self.root_scope_id)
+                                .types
+                                .get(&PascalCaseIdentifier::new("String").unwrap())?,
+                        };
+                        self.items.get_map_id(key_id, id)
+                    }
+                }
+            }
         })
     }
 
@@ -628,6 +740,17 @@ impl Semantic {
                 .insert(segment.clone(), enum_id);
         }
 
+        for (segment, struct_ty) in manifest.structs.iter() {
+            let struct_id = items.add(Type::from_package_struct(
+                make_item_data(scope_id, &Identifier::from(segment.clone())),
+                struct_ty,
+            ));
+            items
+                .get_mut(scope_id)
+                .types
+                .insert(segment.clone(), struct_id);
+        }
+
         Ok(scope_id)
     }
 }
@@ -644,9 +767,14 @@ pub struct StandardAttributes {
     pub resource: ItemId<Attribute>,
     pub maybe_resource: ItemId<Attribute>,
     pub store: ItemId<Attribute>,
+    pub relationship: ItemId<Attribute>,
+    pub indexed: ItemId<Attribute>,
     pub enum_: ItemId<Attribute>,
 }
 
+/// The types that can be used as the keys of a `Map`.
+pub const MAP_KEY_TYPES: &[&str] = &["String", "U32"];
+
 pub fn create_root_scope(
     items: &mut ItemMap,
 ) -> anyhow::Result<(ItemId<Scope>, StandardDefinitions)> {
@@ -681,6 +809,20 @@ pub fn create_root_scope(
         items.get_mut(root_scope).types.insert(id, item_id);
     }
 
+    // Maps are only supported from string and integer keys to primitive values.
+    let primitive_type_ids = items
+        .get(root_scope)
+        .types
+        .values()
+        .copied()
+        .collect::<Vec<_>>();
+    for key in MAP_KEY_TYPES {
+        let key_id = items.get(root_scope).types[&PascalCaseIdentifier::new(key).unwrap()];
+        for value_id in &primitive_type_ids {
+            items.add_map(key_id, *value_id);
+        }
+    }
+
     fn make_attribute(
         items: &mut ItemMap,
         root_scope: ItemId<Scope>,
@@ -706,6 +848,8 @@ pub fn create_root_scope(
         resource: make_attribute(items, root_scope, "Resource")?,
         maybe_resource: make_attribute(items, root_scope, "MaybeResource")?,
         store: make_attribute(items, root_scope, "Store")?,
+        relationship: make_attribute(items, root_scope, "Relationship")?,
+        indexed: make_attribute(items, root_scope, "Indexed")?,
         enum_: make_attribute(items, root_scope, "Enum")?,
     };
 
@@ -716,42 +860,55 @@ pub fn create_root_scope(
 pub async fn package_dependency_to_retrievable_file(
     retrievable_manifest: &RetrievableFile,
     ignore_local_dependencies: bool,
+    registries: &Registries,
+    lockfile: Option<&Lockfile>,
     dependency: &ambient_package::Dependency,
-) -> Result<Option<RetrievableFile>, PackageAddError> {
+) -> Result<Option<RetrievableFile>, Box<PackageAddError>> {
     let path = dependency
         .path
         .as_ref()
         .filter(|_| !ignore_local_dependencies);
 
+    let deployment_manifest =
+        |id: &str| RetrievableFile::Deployment(registries.deployment_manifest(id));
+
+    let version_requirement = dependency
+        .id
+        .as_ref()
+        .map(|id| version::version_requirement(id, dependency).map(|r| r.map(|r| (id, r))))
+        .transpose()?
+        .flatten();
+
     // path takes precedence over other remote access
-    let retrievable_file = match (path, dependency.id_version(), &dependency.deployment) {
+    let retrievable_file = match (path, version_requirement, &dependency.deployment) {
         (None, None, None) => None,
         (Some(path), _, _) => Some(retrievable_manifest.parent_join(&path.join("ambient.toml"))?),
-        (_, Some((id, version)), _) => {
-            let package_version_url = Url::parse(&format!(
-                "{API_URL}/packages/versions/{id}/{version}"
-            ))
-            .map_err(|_| PackageAddError::InvalidPackageVersion {
-                package_id: id.clone(),
-                version: version.to_owned(),
-            })?;
-
-            let deployment = util::retrieve_url(&package_version_url)
-                .await
-                .map_err(|_| PackageAddError::InvalidPackageVersion {
-                    package_id: id.clone(),
-                    version: version.to_owned(),
-                })?;
+        (_, Some((id, requirement)), _) => {
+            // Use the resolved version if there is one; otherwise, select the newest
+            // compatible version for this dependency alone.
+            let locked = lockfile
+                .and_then(|l| l.get(id))
+                .filter(|p| requirement.matches(&p.version));
+
+            let deployment = match locked {
+                Some(locked) => locked.deployment.clone(),
+                None => {
+                    let available = registries.versions(id).await?;
+                    select_version(&available, std::slice::from_ref(&requirement), None)
+                        .ok_or_else(|| {
+                            Box::new(PackageAddError::NoMatchingVersion {
+                                package_id: id.clone(),
+                                requirements: requirement.to_string(),
+                            })
+                        })?
+                        .deployment
+                        .clone()
+                }
+            };
 
-            Some(RetrievableFile::Deployment(RetrievableDeployment {
-                id: deployment,
-                path: PathBuf::from("ambient.toml"),
-            }))
+            Some(deployment_manifest(&deployment))
         }
-        (_, _, Some(deployment)) => Some(RetrievableFile::Deployment(RetrievableDeployment {
-            id: deployment.clone(),
-            path: PathBuf::from("ambient.toml"),
-        })),
+        (_, _, Some(deployment)) => Some(deployment_manifest(deployment)),
     };
     Ok(retrievable_file)
 }
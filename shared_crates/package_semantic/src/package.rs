@@ -12,7 +12,8 @@ use url::Url;
 use crate::{
     schema,
     util::{retrieve_file, retrieve_url, RetrieveError},
-    Item, ItemData, ItemId, ItemType, ItemVariant, Resolve, Scope, Semantic,
+    Item, ItemData, ItemId, ItemType, ItemVariant, Registry, RegistryError, Resolve, Scope,
+    Semantic,
 };
 use semver::Version;
 
@@ -77,28 +78,34 @@ pub enum ParentJoinError {
 pub struct RetrievableDeployment {
     pub id: String,
     pub path: PathBuf,
+    /// The registry the deployment is retrieved from.
+    pub registry: Registry,
 }
 impl RetrievableDeployment {
-    pub fn url(&self) -> Url {
-        let mut url = ambient_shared_types::urls::deployment_url(&self.id);
-        url.push('/');
-
-        let path = self.path.to_string_lossy();
-        Url::parse(&url)
-            .unwrap_or_else(|e| panic!("invalid deployment url {url}: {e}"))
-            .join(path.as_ref())
-            .unwrap_or_else(|e| panic!("invalid deployment url after join {url} + {path}: {e}"))
+    /// The URL of the file at [Self::path] within the deployment on its registry.
+    pub fn url(&self) -> Result<Url, RegistryError> {
+        self.registry.deployment_url(&self.id, &self.path)
     }
 
     /// Retrieves the manifest from the cache if it exists, otherwise retrieves
     /// it from the deployment and caches it
     pub async fn retrieve_manifest(&self) -> Result<String, RetrieveError> {
+        if let Registry::Local(root) = &self.registry {
+            return retrieve_file(&Registry::deployment_path(root, &self.id).join(&self.path));
+        }
+
         let cache_path = ambient_dirs::deployment_cache_path(&self.id).join(&self.path);
         if cache_path.exists() {
             return retrieve_file(&cache_path);
         }
 
-        let manifest = retrieve_url(&self.url()).await?;
+        let url = self
+            .url()
+            .map_err(|_| RetrieveError::InvalidDeploymentUrl {
+                deployment: self.id.clone(),
+                path: self.path.clone(),
+            })?;
+        let manifest = retrieve_url(&url).await?;
 
         std::fs::create_dir_all(cache_path.parent().unwrap()).ok();
         std::fs::write(&cache_path, &manifest).ok();
@@ -164,6 +171,7 @@ impl RetrievableFile {
                 RetrievableFile::Deployment(RetrievableDeployment {
                     id: old_deployment.id.clone(),
                     path: parent_join(&old_deployment.path, suffix)?,
+                    registry: old_deployment.registry.clone(),
                 })
             }
         })
@@ -197,7 +205,12 @@ impl RetrievableFile {
                 }
 
                 #[cfg(not(target_os = "unknown"))]
-                Some(LocalOrRemote::Remote(deployment.url()))
+                match &deployment.registry {
+                    Registry::Local(root) => Some(LocalOrRemote::Local(
+                        Registry::deployment_path(root, &deployment.id).join(&deployment.path),
+                    )),
+                    Registry::Remote(_) => Some(LocalOrRemote::Remote(deployment.url().ok()?)),
+                }
             }
         }
     }
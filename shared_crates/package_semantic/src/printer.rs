@@ -272,6 +272,26 @@ impl Printer {
                 Ok(())
             })?;
         }
+        if let TypeInner::Struct(st) = &type_.inner {
+            self.with_indent(|p| {
+                p.print_indent();
+                println!(
+                    "description: {:?}",
+                    st.description.as_deref().unwrap_or_default()
+                );
+
+                p.print_indent();
+                println!("fields:");
+                p.with_indent(|p| {
+                    for (name, ty) in &st.fields {
+                        p.print_indent();
+                        println!("{name}: {}", write_resolvable_id(items, ty)?);
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        }
         Ok(())
     }
 
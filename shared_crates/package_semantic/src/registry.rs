@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+
+use ambient_package::PackageId;
+use ambient_shared_types::urls::{deployment_url, API_URL};
+use thiserror::Error;
+use url::Url;
+
+use crate::{package::RetrievableDeployment, PackageAddError, PackageVersion};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RegistryParseError {
+    #[error("Invalid registry URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("The registry URL {0} is not a valid file path")]
+    InvalidFileUrl(Url),
+}
+
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error("Invalid registry URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("The registry path {0:?} can't be turned into a URL")]
+    InvalidPath(PathBuf),
+    #[error("Failed to request {url}")]
+    RequestFailed { url: Url, source: reqwest::Error },
+    #[error("Failed to read {path:?}")]
+    ReadFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse the versions listed at {location}")]
+    InvalidVersions {
+        location: String,
+        source: serde_json::Error,
+    },
+}
+
+/// A source of published package versions and their deployments.
+///
+/// A remote registry lists the versions of a package at `{url}/packages/versions/{package_id}`,
+/// in the same format as the `packages/{package_id}.json` files of a local registry. Its
+/// deployments are served from `{url}/deployments/{deployment_id}/`, except for those of the
+/// Ambient package API, which are served from the Ambient assets server.
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
+pub enum Registry {
+    /// A package API server, e.g. `https://api.ambient.run`.
+    Remote(Url),
+    /// A directory on disk, laid out as produced by `ambient package vendor`:
+    /// - `packages/{package_id}.json`: the versions of the package and their deployments
+    /// - `deployments/{deployment_id}/`: the contents of each deployment
+    Local(PathBuf),
+}
+impl Default for Registry {
+    fn default() -> Self {
+        Self::Remote(Url::parse(API_URL).unwrap())
+    }
+}
+impl Registry {
+    /// Parses a registry from a URL or a filesystem path. `file://` URLs and paths are
+    /// treated as local registries.
+    pub fn parse(registry: &str) -> Result<Self, RegistryParseError> {
+        if Path::new(registry).is_absolute() {
+            return Ok(Self::Local(PathBuf::from(registry)));
+        }
+
+        match Url::parse(registry) {
+            Ok(url) if url.scheme() == "file" => {
+                Ok(Self::Local(url.to_file_path().map_err(|_| {
+                    RegistryParseError::InvalidFileUrl(url.clone())
+                })?))
+            }
+            Ok(url) => Ok(Self::Remote(url)),
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                Ok(Self::Local(PathBuf::from(registry)))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// The path to the version listing for `package_id` within the local registry at `root`.
+    pub fn package_versions_path(root: &Path, package_id: &PackageId) -> PathBuf {
+        root.join("packages")
+            .join(format!("{}.json", package_id.as_str()))
+    }
+
+    /// The path to the contents of `deployment` within the local registry at `root`.
+    pub fn deployment_path(root: &Path, deployment: &str) -> PathBuf {
+        root.join("deployments").join(deployment)
+    }
+
+    /// The URL of the file at `path` within `deployment` on this registry.
+    pub fn deployment_url(&self, deployment: &str, path: &Path) -> Result<Url, RegistryError> {
+        let base = match self {
+            Registry::Remote(url) if url.as_str().trim_end_matches('/') == API_URL => {
+                Url::parse(&format!("{}/", deployment_url(deployment)))?
+            }
+            Registry::Remote(url) => Url::parse(&format!(
+                "{}/deployments/{deployment}/",
+                url.as_str().trim_end_matches('/')
+            ))?,
+            Registry::Local(root) => {
+                let path = Self::deployment_path(root, deployment);
+                #[cfg(target_os = "unknown")]
+                return Err(RegistryError::InvalidPath(path));
+
+                #[cfg(not(target_os = "unknown"))]
+                {
+                    let path = std::env::current_dir()
+                        .map(|dir| dir.join(&path))
+                        .unwrap_or(path);
+                    Url::from_directory_path(&path).map_err(|_| RegistryError::InvalidPath(path))?
+                }
+            }
+        };
+
+        Ok(base.join(&ambient_std::path::path_to_unix_string_lossy(path))?)
+    }
+
+    /// Gets the versions of `package_id` published to this registry, or `None` if it has none.
+    async fn versions(
+        &self,
+        package_id: &PackageId,
+    ) -> Result<Option<Vec<PackageVersion>>, RegistryError> {
+        let (location, versions) = match self {
+            Registry::Remote(url) => {
+                let url = Url::parse(&format!(
+                    "{}/packages/versions/{}",
+                    url.as_str().trim_end_matches('/'),
+                    package_id.as_str()
+                ))?;
+                let request_failed = |source| RegistryError::RequestFailed {
+                    url: url.clone(),
+                    source,
+                };
+                let response = reqwest::get(url.clone()).await.map_err(request_failed)?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                let versions = response
+                    .error_for_status()
+                    .map_err(request_failed)?
+                    .text()
+                    .await
+                    .map_err(request_failed)?;
+                (url.to_string(), versions)
+            }
+            Registry::Local(root) => {
+                let path = Self::package_versions_path(root, package_id);
+
+                // Local registries can't be read on the web
+                #[cfg(target_os = "unknown")]
+                {
+                    let _path = path;
+                    return Ok(None);
+                }
+
+                #[cfg(not(target_os = "unknown"))]
+                match std::fs::read_to_string(&path) {
+                    Ok(versions) => (path.to_string_lossy().to_string(), versions),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(source) => return Err(RegistryError::ReadFailed { path, source }),
+                }
+            }
+        };
+
+        serde_json::from_str(&versions)
+            .map(Some)
+            .map_err(|source| RegistryError::InvalidVersions { location, source })
+    }
+}
+impl std::fmt::Display for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Registry::Remote(url) => write!(f, "{url}"),
+            Registry::Local(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The registries to query for package versions, in order of precedence.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Registries(pub Vec<Registry>);
+impl Default for Registries {
+    fn default() -> Self {
+        Self(vec![Registry::default()])
+    }
+}
+impl Registries {
+    /// The environment variable that overrides the default registries. It is a
+    /// comma-separated list of URLs and/or paths.
+    pub const ENV_VAR: &'static str = "AMBIENT_REGISTRIES";
+    /// The directory within a package that `ambient package vendor` writes to. If present,
+    /// it is used as a registry ahead of all others.
+    pub const VENDOR_DIRECTORY: &'static str = "vendor";
+
+    /// Uses the registries specified by [Self::ENV_VAR], falling back to the default
+    /// registry if it is not set.
+    pub fn from_env() -> Result<Self, RegistryParseError> {
+        match std::env::var(Self::ENV_VAR) {
+            Ok(registries) => Ok(Self(
+                registries
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .map(Registry::parse)
+                    .collect::<Result<_, _>>()?,
+            )),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Adds `registry` with the highest precedence, if it is not already present.
+    pub fn prepend(&mut self, registry: Registry) {
+        if !self.0.contains(&registry) {
+            self.0.insert(0, registry);
+        }
+    }
+
+    /// Gets the versions of `package_id` from the first registry that lists the package.
+    /// Registries are queried in order, so a package in a local registry ahead of the remote
+    /// ones is resolved without network access.
+    pub(crate) async fn versions(
+        &self,
+        package_id: &PackageId,
+    ) -> Result<Vec<PackageVersion>, Box<PackageAddError>> {
+        for registry in &self.0 {
+            match registry.versions(package_id).await {
+                Ok(Some(versions)) => return Ok(versions),
+                Ok(None) => continue,
+                Err(source) => {
+                    return Err(Box::new(PackageAddError::RegistryError {
+                        package_id: package_id.clone(),
+                        registry: registry.to_string(),
+                        source,
+                    }))
+                }
+            }
+        }
+
+        Err(Box::new(PackageAddError::FailedToGetPackageVersions {
+            package_id: package_id.clone(),
+            registries: self
+                .0
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }))
+    }
+
+    /// Gets the manifest of `deployment`. It is read from the first local registry that has
+    /// the deployment, and otherwise from the first remote registry.
+    pub fn deployment_manifest(&self, deployment: &str) -> RetrievableDeployment {
+        let local = self.0.iter().find(|r| match r {
+            Registry::Local(root) => Registry::deployment_path(root, deployment).is_dir(),
+            Registry::Remote(_) => false,
+        });
+        let remote = || {
+            self.0
+                .iter()
+                .find(|r| matches!(r, Registry::Remote(_)))
+                .or(self.0.first())
+        };
+
+        RetrievableDeployment {
+            id: deployment.to_owned(),
+            path: PathBuf::from("ambient.toml"),
+            registry: local.or_else(remote).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_registries() {
+        assert_eq!(
+            Registry::parse("https://api.ambient.run"),
+            Ok(Registry::Remote(
+                Url::parse("https://api.ambient.run").unwrap()
+            ))
+        );
+        assert_eq!(
+            Registry::parse("vendor"),
+            Ok(Registry::Local(PathBuf::from("vendor")))
+        );
+
+        #[cfg(unix)]
+        {
+            assert_eq!(
+                Registry::parse("/opt/ambient/registry"),
+                Ok(Registry::Local(PathBuf::from("/opt/ambient/registry")))
+            );
+            assert_eq!(
+                Registry::parse("file:///opt/ambient/registry"),
+                Ok(Registry::Local(PathBuf::from("/opt/ambient/registry")))
+            );
+            assert_eq!(
+                Registry::parse("file://mirror/registry"),
+                Err(RegistryParseError::InvalidFileUrl(
+                    Url::parse("file://mirror/registry").unwrap()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn deployment_urls_come_from_the_registry() {
+        let url = |registry: &str, path: &str| {
+            Registry::parse(registry)
+                .unwrap()
+                .deployment_url("abc", Path::new(path))
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            url(API_URL, "ambient.toml"),
+            format!("{}/ambient.toml", deployment_url("abc"))
+        );
+        assert_eq!(
+            url("https://mirror.example.com/ambient/", "assets/a.png"),
+            "https://mirror.example.com/ambient/deployments/abc/assets/a.png"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            url("/opt/ambient/registry", "ambient.toml"),
+            "file:///opt/ambient/registry/deployments/abc/ambient.toml"
+        );
+    }
+
+    #[tokio::test]
+    async fn versions_come_from_the_first_registry_listing_the_package() {
+        let root = std::env::temp_dir().join(format!(
+            "ambient_package_semantic_registries_{}",
+            std::process::id()
+        ));
+        let package_id = PackageId::new("ytdci6e6yiglca2d6awwpi2lt5h6sqvg").unwrap();
+        let write = |registry: &str, contents: &str| {
+            let path = Registry::package_versions_path(&root.join(registry), &package_id);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+        write("first", "[]");
+        write(
+            "second",
+            r#"[{ "version": "1.0.0", "deployment": "second" }]"#,
+        );
+        write("invalid", "not json");
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        let registries = |names: &[&str]| {
+            Registries(
+                names
+                    .iter()
+                    .map(|name| Registry::Local(root.join(name)))
+                    .collect(),
+            )
+        };
+
+        // Registries that don't list the package are skipped
+        let versions = registries(&["empty", "second", "first"])
+            .versions(&package_id)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].deployment, "second");
+        assert!(registries(&["first", "second"])
+            .versions(&package_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Errors are reported instead of skipping the registry
+        assert!(matches!(
+            *registries(&["invalid", "second"])
+                .versions(&package_id)
+                .await
+                .unwrap_err(),
+            PackageAddError::RegistryError {
+                source: RegistryError::InvalidVersions { .. },
+                ..
+            }
+        ));
+        assert!(matches!(
+            *registries(&["empty"])
+                .versions(&package_id)
+                .await
+                .unwrap_err(),
+            PackageAddError::FailedToGetPackageVersions { .. }
+        ));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
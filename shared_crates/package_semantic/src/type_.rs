@@ -1,7 +1,13 @@
-use ambient_package::{ComponentType, PascalCaseIdentifier};
+use std::collections::HashSet;
+
+use ambient_package::{ComponentType, PascalCaseIdentifier, SnakeCaseIdentifier};
+use anyhow::Context as AnyhowContext;
 use indexmap::IndexMap;
 
-use crate::{Item, ItemData, ItemId, ItemType, ItemVariant, PrimitiveType, Resolve, Semantic};
+use crate::{
+    Item, ItemData, ItemId, ItemMap, ItemType, ItemVariant, PrimitiveType, ResolvableItemId,
+    Resolve, Semantic,
+};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Type {
@@ -13,7 +19,12 @@ pub enum TypeInner {
     Primitive(PrimitiveType),
     Vec(ItemId<Type>),
     Option(ItemId<Type>),
+    Map {
+        key: ItemId<Type>,
+        value: ItemId<Type>,
+    },
     Enum(Enum),
+    Struct(Struct),
 }
 impl TypeInner {
     pub fn as_primitive(&self) -> Option<PrimitiveType> {
@@ -30,6 +41,13 @@ impl TypeInner {
         }
     }
 
+    pub fn as_struct(&self) -> Option<&Struct> {
+        match self {
+            Self::Struct(v) => Some(v),
+            _ => None,
+        }
+    }
+
     pub fn as_vec(&self) -> Option<ItemId<Type>> {
         match self {
             Self::Vec(v) => Some(*v),
@@ -43,6 +61,14 @@ impl TypeInner {
             _ => None,
         }
     }
+
+    /// Returns the key and value types of a map.
+    pub fn as_map(&self) -> Option<(ItemId<Type>, ItemId<Type>)> {
+        match self {
+            Self::Map { key, value } => Some((*key, *value)),
+            _ => None,
+        }
+    }
 }
 impl Type {
     pub fn new(data: ItemData, inner: TypeInner) -> Self {
@@ -58,6 +84,20 @@ impl Type {
             }),
         )
     }
+
+    pub(crate) fn from_package_struct(data: ItemData, value: &ambient_package::Struct) -> Self {
+        Self::new(
+            data,
+            TypeInner::Struct(Struct {
+                description: value.description.clone(),
+                fields: value
+                    .fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), ResolvableItemId::Unresolved(v.clone())))
+                    .collect(),
+            }),
+        )
+    }
 }
 impl Item for Type {
     const TYPE: ItemType = ItemType::Type;
@@ -86,13 +126,85 @@ impl Item for Type {
     }
 }
 impl Resolve for Type {
-    fn resolve(self, _semantic: &mut Semantic, _self_id: ItemId<Self>) -> anyhow::Result<Self> {
+    fn resolve(mut self, semantic: &mut Semantic, self_id: ItemId<Self>) -> anyhow::Result<Self> {
+        let TypeInner::Struct(struct_) = &mut self.inner else {
+            return Ok(self);
+        };
+        let parent_id = self.data.parent_id.unwrap();
+
+        let mut fields = IndexMap::new();
+        for (name, type_) in &struct_.fields {
+            let id = match type_ {
+                ResolvableItemId::Unresolved(path) => semantic
+                    .get_contextual_type_id(parent_id, path)
+                    .with_context(|| {
+                        format!(
+                            "Failed to resolve type `{path:?}` for field `{name}` of struct `{}`",
+                            self.data.id
+                        )
+                    })?,
+                ResolvableItemId::Resolved(id) => *id,
+            };
+            fields.insert(name.clone(), ResolvableItemId::Resolved(id));
+        }
+        struct_.fields = fields;
+
+        // Structs are stored inline, so a struct can't contain itself, directly or through
+        // other structs
+        if let Some(path) = find_struct_cycle(semantic, self_id, &self, &mut HashSet::new()) {
+            anyhow::bail!(
+                "Struct `{}` cannot contain itself (through `{}`)",
+                self.data.id,
+                path.join("` -> `")
+            );
+        }
+
         Ok(self)
     }
 
     fn already_resolved(&self) -> bool {
-        true
+        match &self.inner {
+            TypeInner::Struct(s) => s.fields.values().all(|f| f.as_resolved().is_some()),
+            _ => true,
+        }
+    }
+}
+
+/// Returns the fields through which the struct `owner` contains the struct `root`, if it does.
+///
+/// The fields of structs that have not been resolved yet are looked up from their scope.
+fn find_struct_cycle(
+    semantic: &Semantic,
+    root: ItemId<Type>,
+    owner: &Type,
+    visited: &mut HashSet<ItemId<Type>>,
+) -> Option<Vec<String>> {
+    let struct_ = owner.inner.as_struct()?;
+    let parent_id = owner.data.parent_id?;
+    for (name, type_) in &struct_.fields {
+        let id = match type_ {
+            ResolvableItemId::Unresolved(path) => {
+                // Unresolvable fields are reported when their own struct is resolved
+                let Some(id) = semantic.get_contextual_type_id(parent_id, path) else {
+                    continue;
+                };
+                id
+            }
+            ResolvableItemId::Resolved(id) => *id,
+        };
+        let field = format!("{}.{name}", owner.data.id);
+        if id == root {
+            return Some(vec![field]);
+        }
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(mut path) = find_struct_cycle(semantic, root, semantic.items.get(id), visited) {
+            path.insert(0, field);
+            return Some(path);
+        }
     }
+    None
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -100,3 +212,205 @@ pub struct Enum {
     pub description: Option<String>,
     pub members: IndexMap<PascalCaseIdentifier, String>,
 }
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Struct {
+    pub description: Option<String>,
+    pub fields: IndexMap<SnakeCaseIdentifier, ResolvableItemId<Type>>,
+}
+impl Struct {
+    /// Returns the types of the fields of this struct in order, with the fields of nested
+    /// structs in place of the struct. This is how struct components are passed to and from
+    /// guests.
+    ///
+    /// Returns `None` if a field cannot be passed this way, such as a `Vec` of structs.
+    pub fn flattened_fields(&self, items: &ItemMap) -> Option<Vec<ItemId<Type>>> {
+        let is_primitive = |id: ItemId<Type>| items.get(id).inner.as_primitive().is_some();
+
+        let mut fields = vec![];
+        for id in self.fields.values() {
+            let id = id.as_resolved()?;
+            match &items.get(id).inner {
+                TypeInner::Struct(struct_) => fields.extend(struct_.flattened_fields(items)?),
+                TypeInner::Primitive(_) | TypeInner::Enum(_) => fields.push(id),
+                TypeInner::Vec(inner) | TypeInner::Option(inner) if is_primitive(*inner) => {
+                    fields.push(id)
+                }
+                TypeInner::Map { key, value }
+                    if matches!(
+                        items.get(*key).inner.as_primitive(),
+                        Some(PrimitiveType::String | PrimitiveType::U32)
+                    ) && is_primitive(*value) =>
+                {
+                    fields.push(id)
+                }
+                _ => return None,
+            }
+        }
+        Some(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_package::{ItemPathBuf, PascalCaseIdentifier};
+
+    use crate::{create_root_scope, ItemSource};
+
+    use super::*;
+
+    fn add_type(items: &mut ItemMap, id: &str, inner: TypeInner) -> ItemId<Type> {
+        items.add(Type::new(
+            ItemData {
+                parent_id: None,
+                id: PascalCaseIdentifier::new(id).unwrap().into(),
+                source: ItemSource::User,
+            },
+            inner,
+        ))
+    }
+
+    fn add_struct(items: &mut ItemMap, id: &str, fields: &[(&str, ItemId<Type>)]) -> ItemId<Type> {
+        let fields = fields
+            .iter()
+            .map(|(name, id)| {
+                (
+                    SnakeCaseIdentifier::new(name).unwrap(),
+                    ResolvableItemId::Resolved(*id),
+                )
+            })
+            .collect();
+        add_type(
+            items,
+            id,
+            TypeInner::Struct(Struct {
+                description: None,
+                fields,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_flattened_fields() {
+        let mut items = ItemMap::default();
+        let (root_scope, _) = create_root_scope(&mut items).unwrap();
+        let primitive = |items: &ItemMap, id: &str| {
+            *items
+                .get(root_scope)
+                .types
+                .get(&PascalCaseIdentifier::new(id).unwrap())
+                .unwrap()
+        };
+        let (f32_type, u32_type, string_type) = (
+            primitive(&items, "F32"),
+            primitive(&items, "U32"),
+            primitive(&items, "String"),
+        );
+        let vec_u32_type = items.get_vec_id(u32_type);
+        let map_string_f32_type = items.get_map_id(string_type, f32_type).unwrap();
+        let enum_type = add_type(
+            &mut items,
+            "Mode",
+            TypeInner::Enum(Enum {
+                description: None,
+                members: Default::default(),
+            }),
+        );
+
+        let inner = add_struct(&mut items, "Inner", &[("a", f32_type), ("mode", enum_type)]);
+        let outer = add_struct(
+            &mut items,
+            "Outer",
+            &[
+                ("counts", vec_u32_type),
+                ("inner", inner),
+                ("weights", map_string_f32_type),
+            ],
+        );
+        assert_eq!(
+            items
+                .get(outer)
+                .inner
+                .as_struct()
+                .unwrap()
+                .flattened_fields(&items),
+            Some(vec![vec_u32_type, f32_type, enum_type, map_string_f32_type])
+        );
+
+        // A list of structs can't be flattened
+        let vec_inner = add_type(&mut items, "VecInner", TypeInner::Vec(inner));
+        let with_vec = add_struct(&mut items, "WithVec", &[("inners", vec_inner)]);
+        assert_eq!(
+            items
+                .get(with_vec)
+                .inner
+                .as_struct()
+                .unwrap()
+                .flattened_fields(&items),
+            None
+        );
+    }
+
+    /// Adds a struct to the root scope with fields referring to types by name.
+    fn add_named_struct(
+        semantic: &mut Semantic,
+        id: &str,
+        fields: &[(&str, &str)],
+    ) -> ItemId<Type> {
+        let scope = semantic.root_scope_id;
+        let name = PascalCaseIdentifier::new(id).unwrap();
+        let fields = fields
+            .iter()
+            .map(|(name, type_)| {
+                (
+                    SnakeCaseIdentifier::new(name).unwrap(),
+                    ResolvableItemId::Unresolved(ComponentType::Item(
+                        ItemPathBuf::new(*type_).unwrap(),
+                    )),
+                )
+            })
+            .collect();
+        let id = semantic.items.add(Type::new(
+            ItemData {
+                parent_id: Some(scope),
+                id: name.clone().into(),
+                source: ItemSource::User,
+            },
+            TypeInner::Struct(Struct {
+                description: None,
+                fields,
+            }),
+        ));
+        semantic.items.get_mut(scope).types.insert(name, id);
+        id
+    }
+
+    #[tokio::test]
+    async fn test_recursive_structs_are_rejected() {
+        let mut semantic = Semantic::new(false).await.unwrap();
+        let direct = add_named_struct(&mut semantic, "Direct", &[("inner", "Direct")]);
+        let a = add_named_struct(&mut semantic, "A", &[("x", "F32"), ("b", "B")]);
+        let b = add_named_struct(&mut semantic, "B", &[("a", "A")]);
+        let c = add_named_struct(&mut semantic, "C", &[("b", "B")]);
+        let fine = add_named_struct(&mut semantic, "Fine", &[("x", "F32")]);
+        let nested = add_named_struct(&mut semantic, "Nested", &[("f", "Fine"), ("g", "Fine")]);
+
+        let error = |semantic: &mut Semantic, id| semantic.resolve(id).unwrap_err().to_string();
+        assert_eq!(
+            error(&mut semantic, direct),
+            "Struct `Direct` cannot contain itself (through `Direct.inner`)"
+        );
+        assert_eq!(
+            error(&mut semantic, a),
+            "Struct `A` cannot contain itself (through `A.b` -> `B.a`)"
+        );
+        assert_eq!(
+            error(&mut semantic, b),
+            "Struct `B` cannot contain itself (through `B.a` -> `A.b`)"
+        );
+        // `C` only contains the cycle; it is reported for `A` and `B`
+        assert!(semantic.resolve(c).is_ok());
+        assert!(semantic.resolve(nested).is_ok());
+        assert!(semantic.resolve(fine).is_ok());
+    }
+}
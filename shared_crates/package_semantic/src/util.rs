@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 use url::Url;
@@ -15,6 +15,8 @@ pub enum RetrieveError {
     InvalidFilePathForUrl { url: Url },
     #[error("failed to get text from URL {url:?}")]
     FailedToGetTextFromUrl { url: Url },
+    #[error("invalid URL for {path:?} in deployment {deployment}")]
+    InvalidDeploymentUrl { deployment: String, path: PathBuf },
 }
 
 pub fn retrieve_file(path: &Path) -> Result<String, RetrieveError> {
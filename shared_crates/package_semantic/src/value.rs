@@ -7,13 +7,14 @@ use std::{
 use super::{PrimitiveType, Type};
 use crate::{ItemId, ItemMap, TypeInner};
 
-use ambient_package::PascalCaseIdentifier;
+use ambient_package::{PascalCaseIdentifier, SnakeCaseIdentifier};
 use ambient_shared_types::{
     primitive_component_definitions, ProceduralMaterialHandle, ProceduralMeshHandle,
     ProceduralSamplerHandle, ProceduralTextureHandle,
 };
 use anyhow::Context as AnyhowContext;
 use glam::{IVec2, IVec3, IVec4, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+use indexmap::IndexMap;
 
 pub type EntityId = u128;
 
@@ -201,7 +202,9 @@ pub enum Value {
     Scalar(ScalarValue),
     Vec(Vec<ScalarValue>),
     Option(Option<ScalarValue>),
+    Map(Vec<(ScalarValue, ScalarValue)>),
     Enum(ItemId<Type>, PascalCaseIdentifier),
+    Struct(ItemId<Type>, IndexMap<SnakeCaseIdentifier, Value>),
 }
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -224,7 +227,27 @@ impl fmt::Display for Value {
                     write!(f, "None")
                 }
             }
+            Self::Map(v) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in v.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{k}: {v}")?;
+                }
+                write!(f, "}}")
+            }
             Self::Enum(ty, v) => write!(f, "{ty}::{v}"),
+            Self::Struct(ty, fields) => {
+                write!(f, "{ty} {{ ")?;
+                for (i, (name, v)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {v}")?;
+                }
+                write!(f, " }}")
+            }
         }
     }
 }
@@ -272,6 +295,41 @@ impl Value {
                     Self::Option(Some(ScalarValue::from_toml(&arr[0], inner_ty)?))
                 }
             }
+            TypeInner::Map { key, value: v } => {
+                let key_ty = items.get(*key).inner.as_primitive().with_context(|| {
+                    format!(
+                        "Expected primitive key type, got {:?}",
+                        items.get(*key).inner
+                    )
+                })?;
+                let value_ty = items.get(*v).inner.as_primitive().with_context(|| {
+                    format!("Expected primitive type, got {:?}", items.get(*v).inner)
+                })?;
+
+                let table = value
+                    .as_table()
+                    .with_context(|| format!("Expected table, got {:?}", value))?;
+
+                Self::Map(
+                    table
+                        .iter()
+                        .map(|(k, v)| {
+                            // TOML keys are always strings, so integer keys need to be parsed
+                            let k =
+                                match key_ty {
+                                    PrimitiveType::String => toml::Value::String(k.clone()),
+                                    _ => toml::Value::Integer(k.parse().with_context(|| {
+                                        format!("Expected integer key, got {k:?}")
+                                    })?),
+                                };
+                            Ok((
+                                ScalarValue::from_toml(&k, key_ty)?,
+                                ScalarValue::from_toml(v, value_ty)?,
+                            ))
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                )
+            }
             TypeInner::Enum(e) => {
                 let variant = value.as_str().with_context(|| {
                     format!("Expected string for enum variant, got {:?}", value)
@@ -290,13 +348,41 @@ impl Value {
 
                 Self::Enum(ty_id, variant.0.clone())
             }
+            TypeInner::Struct(st) => {
+                let table = value
+                    .as_table()
+                    .with_context(|| format!("Expected table for struct, got {:?}", value))?;
+                if let Some(unknown) = table
+                    .keys()
+                    .find(|k| !st.fields.keys().any(|f| f.as_str() == k.as_str()))
+                {
+                    anyhow::bail!("Unknown field `{unknown}` for struct `{}`", ty.data.id);
+                }
+
+                let mut fields = IndexMap::new();
+                for (name, field_ty) in &st.fields {
+                    let field_ty = field_ty.as_resolved().with_context(|| {
+                        format!("Field `{name}` of struct `{}` is unresolved", ty.data.id)
+                    })?;
+                    let field_value = table.get(name.as_str()).with_context(|| {
+                        format!("Missing field `{name}` for struct `{}`", ty.data.id)
+                    })?;
+                    fields.insert(
+                        name.clone(),
+                        Self::from_toml(field_value, items, field_ty)
+                            .with_context(|| format!("Invalid value for field `{name}`"))?,
+                    );
+                }
+
+                Self::Struct(ty_id, fields)
+            }
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{create_root_scope, Enum, ItemData, ItemSource};
+    use crate::{create_root_scope, Enum, ItemData, ItemSource, ResolvableItemId, Struct};
 
     use super::*;
 
@@ -536,5 +622,67 @@ mod tests {
             Value::from_toml(&toml::Value::String("B".to_string()), &items, enum_type).unwrap(),
             Value::Enum(enum_type, PascalCaseIdentifier::new("B").unwrap()),
         );
+
+        let struct_type = items.add(Type::new(
+            ItemData {
+                parent_id: None,
+                id: PascalCaseIdentifier::new("MyStruct").unwrap().into(),
+                source: ItemSource::User,
+            },
+            TypeInner::Struct(Struct {
+                description: None,
+                fields: IndexMap::from_iter([
+                    (
+                        SnakeCaseIdentifier::new("count").unwrap(),
+                        ResolvableItemId::Resolved(u32_type),
+                    ),
+                    (
+                        SnakeCaseIdentifier::new("state").unwrap(),
+                        ResolvableItemId::Resolved(enum_type),
+                    ),
+                ]),
+            }),
+        ));
+
+        let struct_toml: toml::Value = toml::from_str("count = 3\nstate = \"C\"").unwrap();
+        assert_eq!(
+            Value::from_toml(&struct_toml, &items, struct_type).unwrap(),
+            Value::Struct(
+                struct_type,
+                IndexMap::from_iter([
+                    (
+                        SnakeCaseIdentifier::new("count").unwrap(),
+                        Value::Scalar(ScalarValue::U32(3))
+                    ),
+                    (
+                        SnakeCaseIdentifier::new("state").unwrap(),
+                        Value::Enum(enum_type, PascalCaseIdentifier::new("C").unwrap())
+                    ),
+                ])
+            )
+        );
+
+        let missing_field: toml::Value = toml::from_str("count = 3").unwrap();
+        assert!(Value::from_toml(&missing_field, &items, struct_type).is_err());
+
+        let map_string_u32_type = items.get_map_id(string_type, u32_type).unwrap();
+        let map_toml: toml::Value = toml::from_str("a = 1\nb = 2").unwrap();
+        assert_eq!(
+            Value::from_toml(&map_toml, &items, map_string_u32_type).unwrap(),
+            Value::Map(vec![
+                (ScalarValue::String("a".to_string()), ScalarValue::U32(1)),
+                (ScalarValue::String("b".to_string()), ScalarValue::U32(2)),
+            ])
+        );
+
+        let map_u32_bool_type = items.get_map_id(u32_type, bool_type).unwrap();
+        let map_toml: toml::Value = toml::from_str("1 = true").unwrap();
+        assert_eq!(
+            Value::from_toml(&map_toml, &items, map_u32_bool_type).unwrap(),
+            Value::Map(vec![(ScalarValue::U32(1), ScalarValue::Bool(true))])
+        );
+
+        let map_toml: toml::Value = toml::from_str("one = true").unwrap();
+        assert!(Value::from_toml(&map_toml, &items, map_u32_bool_type).is_err());
     }
 }
@@ -0,0 +1,636 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ambient_package::{LockedPackage, Lockfile, Manifest, PackageId};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::{PackageAddError, Registries, RetrievableFile};
+
+/// The number of candidate versions that will be tried before giving up on finding a consistent
+/// set of versions.
+const MAX_RESOLUTION_STEPS: usize = 10_000;
+
+/// A published version of a package, as returned by a registry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PackageVersion {
+    pub version: Version,
+    pub deployment: String,
+}
+
+/// Selects the version to use from `available` that satisfies all of `requirements`.
+///
+/// If `locked` is specified and still satisfies the requirements, it is used; otherwise,
+/// the highest matching version is selected.
+pub fn select_version<'a>(
+    available: &'a [PackageVersion],
+    requirements: &[VersionReq],
+    locked: Option<&Version>,
+) -> Option<&'a PackageVersion> {
+    let candidates = available
+        .iter()
+        .filter(|v| requirements.iter().all(|r| r.matches(&v.version)));
+
+    match locked {
+        Some(locked) => {
+            let candidates = candidates.collect::<Vec<_>>();
+            candidates
+                .iter()
+                .find(|v| &v.version == locked)
+                .or_else(|| candidates.iter().max_by(|a, b| a.version.cmp(&b.version)))
+                .copied()
+        }
+        None => candidates.max_by(|a, b| a.version.cmp(&b.version)),
+    }
+}
+
+pub(crate) fn version_requirement(
+    package_id: &PackageId,
+    dependency: &ambient_package::Dependency,
+) -> Result<Option<VersionReq>, Box<PackageAddError>> {
+    dependency.version_req().transpose().map_err(|_| {
+        Box::new(PackageAddError::InvalidVersionRequirement {
+            package_id: package_id.clone(),
+            requirement: dependency.version.clone().unwrap_or_default(),
+        })
+    })
+}
+
+/// A requirement placed on a remote package by a manifest.
+type Requirement = (PackageId, VersionReq);
+
+/// Walks the dependency graph starting at `root`, and selects a single version for each
+/// remote package such that every requirement on that package is satisfied.
+///
+/// Versions are tried from the newest down, and an earlier selection is revisited if the
+/// requirements of a later one can't be met. Versions in `lockfile` are tried first, without
+/// querying the registries for that package unless they have to be replaced.
+pub async fn resolve_versions(
+    root: &RetrievableFile,
+    ignore_local_dependencies: bool,
+    registries: &Registries,
+    lockfile: Option<&Lockfile>,
+) -> Result<Lockfile, Box<PackageAddError>> {
+    let manifest_requirements =
+        |file| manifest_requirements(file, ignore_local_dependencies, registries);
+
+    // The resolver works from what has been fetched so far; each time it needs something
+    // else, that is fetched and the resolution starts over.
+    let mut resolver = Resolver {
+        root: manifest_requirements(root.clone()).await?,
+        lockfile,
+        available: HashMap::new(),
+        dependencies: HashMap::new(),
+    };
+    loop {
+        match resolver.resolve() {
+            Ok(selected) => {
+                return Ok(Lockfile {
+                    packages: selected
+                        .into_iter()
+                        .map(|(id, v)| LockedPackage {
+                            id,
+                            version: v.version,
+                            deployment: v.deployment,
+                        })
+                        .collect(),
+                })
+            }
+            Err(Unresolved::MissingVersions(id)) => {
+                let versions = registries.versions(&id).await?;
+                resolver.available.insert(id, versions);
+            }
+            Err(Unresolved::MissingDependencies(deployment)) => {
+                let file = RetrievableFile::Deployment(registries.deployment_manifest(&deployment));
+                let requirements = manifest_requirements(file).await?;
+                resolver.dependencies.insert(deployment, requirements);
+            }
+            Err(Unresolved::NoMatchingVersion(package_id, requirements)) => {
+                return Err(Box::new(PackageAddError::NoMatchingVersion {
+                    package_id,
+                    requirements: requirements
+                        .iter()
+                        .map(|r| r.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                }))
+            }
+            Err(Unresolved::TooManySteps) => {
+                return Err(Box::new(PackageAddError::VersionResolutionFailed))
+            }
+        }
+    }
+}
+
+/// Collects the requirements on remote packages made by the manifest `root`, and by the
+/// local and deployment dependencies it includes.
+async fn manifest_requirements(
+    root: RetrievableFile,
+    ignore_local_dependencies: bool,
+    registries: &Registries,
+) -> Result<Vec<Requirement>, Box<PackageAddError>> {
+    let mut requirements = vec![];
+    let mut visited = HashSet::new();
+    let mut queue = vec![root];
+    while let Some(file) = queue.pop() {
+        if !visited.insert(file.clone()) {
+            continue;
+        }
+
+        let manifest = Manifest::parse(&file.get().await?).map_err(|source| {
+            Box::new(PackageAddError::ManifestParseError {
+                manifest_path: file.clone(),
+                source,
+            })
+        })?;
+
+        for dependency in manifest.dependencies.values() {
+            if let Some(path) = dependency
+                .path
+                .as_ref()
+                .filter(|_| !ignore_local_dependencies)
+            {
+                queue.push(file.parent_join(&path.join("ambient.toml"))?);
+            } else if let Some(id) = dependency
+                .id
+                .as_ref()
+                .filter(|_| dependency.version.is_some())
+            {
+                requirements.extend(version_requirement(id, dependency)?.map(|r| (id.clone(), r)));
+            } else if let Some(deployment) = &dependency.deployment {
+                queue.push(RetrievableFile::Deployment(
+                    registries.deployment_manifest(deployment),
+                ));
+            }
+        }
+    }
+    Ok(requirements)
+}
+
+/// Why [Resolver::resolve] could not select the versions.
+#[derive(Debug, PartialEq)]
+enum Unresolved {
+    /// The published versions of the package are needed.
+    MissingVersions(PackageId),
+    /// The requirements of the deployment are needed.
+    MissingDependencies(String),
+    /// There is no version of the package that satisfies these requirements.
+    NoMatchingVersion(PackageId, Vec<VersionReq>),
+    TooManySteps,
+}
+
+/// A backtracking search for a version of each required package.
+struct Resolver<'a> {
+    /// The requirements of the root package
+    root: Vec<Requirement>,
+    lockfile: Option<&'a Lockfile>,
+    /// The published versions of each package fetched so far
+    available: HashMap<PackageId, Vec<PackageVersion>>,
+    /// The requirements of each deployment fetched so far
+    dependencies: HashMap<String, Vec<Requirement>>,
+}
+impl Resolver<'_> {
+    fn resolve(&self) -> Result<BTreeMap<PackageId, PackageVersion>, Unresolved> {
+        let mut search = Search {
+            selected: BTreeMap::new(),
+            requirements: self.root.clone(),
+            steps: 0,
+            conflict: None,
+        };
+        if self.search(&mut search)? {
+            Ok(search.selected)
+        } else {
+            Err(search.conflict.unwrap_or(Unresolved::TooManySteps))
+        }
+    }
+
+    /// Selects a version for the next package that doesn't have one, and recurses. Returns
+    /// false if there is no version of it that works with the current selections.
+    fn search(&self, search: &mut Search) -> Result<bool, Unresolved> {
+        let Some(id) = search
+            .requirements
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| !search.selected.contains_key(*id))
+            .min()
+            .cloned()
+        else {
+            return Ok(true);
+        };
+        let requirements = search
+            .requirements
+            .iter()
+            .filter(|(other, _)| *other == id)
+            .map(|(_, r)| r.clone())
+            .collect::<Vec<_>>();
+
+        let (candidates, complete) = self.candidates(&id, &requirements)?;
+        if candidates.is_empty() && complete {
+            search
+                .conflict
+                .get_or_insert(Unresolved::NoMatchingVersion(id, requirements));
+            return Ok(false);
+        }
+
+        for candidate in candidates {
+            search.steps += 1;
+            if search.steps > MAX_RESOLUTION_STEPS {
+                return Err(Unresolved::TooManySteps);
+            }
+            let dependencies = self
+                .dependencies
+                .get(&candidate.deployment)
+                .ok_or_else(|| Unresolved::MissingDependencies(candidate.deployment.clone()))?;
+
+            search.selected.insert(id.clone(), candidate);
+            let conflict = dependencies.iter().find(|(dependency, requirement)| {
+                search
+                    .selected
+                    .get(dependency)
+                    .is_some_and(|v| !requirement.matches(&v.version))
+            });
+            if let Some((dependency, requirement)) = conflict {
+                search.conflict.get_or_insert_with(|| {
+                    let mut requirements = search
+                        .requirements
+                        .iter()
+                        .filter(|(other, _)| other == dependency)
+                        .map(|(_, r)| r.clone())
+                        .collect::<Vec<_>>();
+                    requirements.push(requirement.clone());
+                    Unresolved::NoMatchingVersion(dependency.clone(), requirements)
+                });
+            } else {
+                let len = search.requirements.len();
+                search.requirements.extend(dependencies.iter().cloned());
+                if self.search(search)? {
+                    return Ok(true);
+                }
+                search.requirements.truncate(len);
+            }
+            search.selected.remove(&id);
+        }
+
+        if complete {
+            Ok(false)
+        } else {
+            // Only the locked version was known, and it didn't work out
+            Err(Unresolved::MissingVersions(id))
+        }
+    }
+
+    /// Returns the versions of `id` to try, in order, and whether they are all of the versions
+    /// that match `requirements`. A compatible locked version is tried first, followed by the
+    /// rest from the newest down.
+    fn candidates(
+        &self,
+        id: &PackageId,
+        requirements: &[VersionReq],
+    ) -> Result<(Vec<PackageVersion>, bool), Unresolved> {
+        let matches = |version: &Version| requirements.iter().all(|r| r.matches(version));
+        let locked = self
+            .lockfile
+            .and_then(|l| l.get(id))
+            .filter(|p| matches(&p.version))
+            .map(|p| PackageVersion {
+                version: p.version.clone(),
+                deployment: p.deployment.clone(),
+            });
+
+        let Some(available) = self.available.get(id) else {
+            // Locked versions are used without querying the registries
+            return match locked {
+                Some(locked) => Ok((vec![locked], false)),
+                None => Err(Unresolved::MissingVersions(id.clone())),
+            };
+        };
+        let mut rest = available
+            .iter()
+            .filter(|v| matches(&v.version))
+            .filter(|v| locked.as_ref().map_or(true, |l| l.version != v.version))
+            .cloned()
+            .collect::<Vec<_>>();
+        rest.sort_by(|a, b| b.version.cmp(&a.version));
+        Ok((locked.into_iter().chain(rest).collect(), true))
+    }
+}
+
+/// The state of a [Resolver::search].
+struct Search {
+    selected: BTreeMap<PackageId, PackageVersion>,
+    /// The requirements of the root package and of every selected version
+    requirements: Vec<Requirement>,
+    /// The number of versions that have been tried
+    steps: usize,
+    /// The first conflict that was found, reported if there is no solution
+    conflict: Option<Unresolved>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::Registry;
+
+    use super::*;
+
+    const PACKAGE_A: &str = "ytdci6e6yiglca2d6awwpi2lt5h6sqvg";
+    const PACKAGE_B: &str = "tijz7x6fimbgu24sbbtp4nllhfxbgblp";
+    const PACKAGE_C: &str = "bok7gwabmlyzlwbddf4nfur4jyrz27ji";
+
+    fn versions(versions: &[&str]) -> Vec<PackageVersion> {
+        versions
+            .iter()
+            .map(|v| PackageVersion {
+                version: Version::parse(v).unwrap(),
+                deployment: format!("deployment_{v}"),
+            })
+            .collect()
+    }
+
+    fn select<'a>(
+        available: &'a [PackageVersion],
+        requirements: &[&str],
+        locked: Option<&str>,
+    ) -> Option<&'a str> {
+        let requirements = requirements
+            .iter()
+            .map(|r| VersionReq::parse(r).unwrap())
+            .collect::<Vec<_>>();
+        let locked = locked.map(|l| Version::parse(l).unwrap());
+
+        select_version(available, &requirements, locked.as_ref()).map(|v| v.deployment.as_str())
+    }
+
+    #[test]
+    fn selects_highest_compatible_version() {
+        let available = versions(&["0.1.0", "0.1.3", "0.2.0", "1.0.0", "1.4.2"]);
+
+        assert_eq!(
+            select(&available, &["0.1.0"], None),
+            Some("deployment_0.1.3")
+        );
+        assert_eq!(select(&available, &["1"], None), Some("deployment_1.4.2"));
+        assert_eq!(
+            select(&available, &[">=0.1.0, <1.0.0"], None),
+            Some("deployment_0.2.0")
+        );
+    }
+
+    #[test]
+    fn intersects_requirements() {
+        let available = versions(&["1.0.0", "1.2.0", "1.5.0", "2.0.0"]);
+
+        assert_eq!(
+            select(&available, &["1.0", "<1.5"], None),
+            Some("deployment_1.2.0")
+        );
+        assert_eq!(select(&available, &["1.0", "2.0"], None), None);
+    }
+
+    #[test]
+    fn prefers_compatible_locked_version() {
+        let available = versions(&["1.0.0", "1.2.0", "1.5.0"]);
+
+        assert_eq!(
+            select(&available, &["1.0"], Some("1.2.0")),
+            Some("deployment_1.2.0")
+        );
+        assert_eq!(
+            select(&available, &["1.3"], Some("1.2.0")),
+            Some("deployment_1.5.0")
+        );
+    }
+
+    /// A local registry in a temporary directory, removed when dropped.
+    struct TestRegistry(PathBuf);
+    impl TestRegistry {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "ambient_package_semantic_{name}_{}",
+                std::process::id()
+            ));
+            std::fs::remove_dir_all(&root).ok();
+            std::fs::create_dir_all(root.join("packages")).unwrap();
+            Self(root)
+        }
+
+        fn write(&self, path: &Path, contents: &str) {
+            let path = self.0.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        fn add_package(&self, id: &str, versions: &[&str]) {
+            let versions = versions
+                .iter()
+                .map(|v| PackageVersion {
+                    version: Version::parse(v).unwrap(),
+                    deployment: deployment(id, v),
+                })
+                .collect::<Vec<_>>();
+            self.write(
+                &Registry::package_versions_path(Path::new(""), &PackageId::new(id).unwrap()),
+                &serde_json::to_string(&versions).unwrap(),
+            );
+        }
+
+        fn add_deployment(&self, id: &str, version: &str, dependencies: &str) {
+            self.write(
+                &Registry::deployment_path(Path::new(""), &deployment(id, version))
+                    .join("ambient.toml"),
+                &manifest(id, version, dependencies),
+            );
+        }
+
+        fn registries(&self) -> Registries {
+            Registries(vec![Registry::Local(self.0.clone())])
+        }
+    }
+    impl Drop for TestRegistry {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.0).ok();
+        }
+    }
+
+    fn deployment(id: &str, version: &str) -> String {
+        format!("{}_{}", &id[..4], version.replace('.', "_"))
+    }
+
+    fn manifest(id: &str, version: &str, dependencies: &str) -> String {
+        format!(
+            "[package]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"{version}\"\ncontent = {{ type = \"Asset\", code = true }}\n\n[dependencies]\n{dependencies}"
+        )
+    }
+
+    fn dependency(name: &str, id: &str, version: &str) -> String {
+        format!("{name} = {{ id = \"{id}\", version = \"{version}\" }}\n")
+    }
+
+    fn locked(lockfile: &Lockfile) -> Vec<(&str, String)> {
+        lockfile
+            .packages
+            .iter()
+            .map(|p| (p.id.as_str(), p.version.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn resolves_transitive_requirements() {
+        let registry = TestRegistry::new("transitive");
+        registry.add_package(PACKAGE_A, &["1.0.0", "1.2.0", "2.0.0"]);
+        registry.add_package(PACKAGE_B, &["0.1.0", "0.1.4", "0.2.0"]);
+        registry.add_deployment(PACKAGE_A, "1.0.0", "");
+        registry.add_deployment(
+            PACKAGE_A,
+            "1.2.0",
+            &format!("b = {{ id = \"{PACKAGE_B}\", version = \"0.1\" }}"),
+        );
+        registry.add_deployment(PACKAGE_B, "0.1.4", "");
+
+        // B is only required by the selected version of A, so it is found on a later pass
+        registry.write(
+            Path::new("root/ambient.toml"),
+            &manifest(
+                PACKAGE_A,
+                "0.0.1",
+                &format!("a = {{ id = \"{PACKAGE_A}\", version = \"1\" }}"),
+            ),
+        );
+        let root = RetrievableFile::Path(registry.0.join("root/ambient.toml"));
+
+        let lockfile = resolve_versions(&root, false, &registry.registries(), None)
+            .await
+            .unwrap();
+        let mut expected = vec![
+            (PACKAGE_A, "1.2.0".to_string()),
+            (PACKAGE_B, "0.1.4".to_string()),
+        ];
+        expected.sort();
+        assert_eq!(locked(&lockfile), expected);
+        assert_eq!(
+            lockfile
+                .get(&PackageId::new(PACKAGE_B).unwrap())
+                .unwrap()
+                .deployment,
+            deployment(PACKAGE_B, "0.1.4")
+        );
+    }
+
+    #[tokio::test]
+    async fn locked_versions_do_not_query_registries() {
+        let registry = TestRegistry::new("locked");
+        // Only the deployment is available: the version listing for A is missing, so
+        // querying the registry for it would fail.
+        registry.add_deployment(PACKAGE_A, "1.0.0", "");
+        registry.write(
+            Path::new("root/ambient.toml"),
+            &manifest(
+                PACKAGE_B,
+                "0.0.1",
+                &format!("a = {{ id = \"{PACKAGE_A}\", version = \"1\" }}"),
+            ),
+        );
+        let root = RetrievableFile::Path(registry.0.join("root/ambient.toml"));
+
+        let lockfile = Lockfile {
+            packages: vec![LockedPackage {
+                id: PackageId::new(PACKAGE_A).unwrap(),
+                version: Version::parse("1.0.0").unwrap(),
+                deployment: deployment(PACKAGE_A, "1.0.0"),
+            }],
+        };
+        let resolved = resolve_versions(&root, false, &registry.registries(), Some(&lockfile))
+            .await
+            .unwrap();
+        assert_eq!(resolved, lockfile);
+
+        // A lockfile that no longer satisfies the requirements must query the registry
+        let outdated = Lockfile {
+            packages: vec![LockedPackage {
+                version: Version::parse("0.9.0").unwrap(),
+                ..lockfile.packages[0].clone()
+            }],
+        };
+        assert!(matches!(
+            *resolve_versions(&root, false, &registry.registries(), Some(&outdated))
+                .await
+                .unwrap_err(),
+            PackageAddError::FailedToGetPackageVersions { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn backtracks_to_older_versions() {
+        let registry = TestRegistry::new("backtrack");
+        registry.add_package(PACKAGE_A, &["1.0.0"]);
+        registry.add_package(PACKAGE_B, &["1.0.0", "2.0.0"]);
+        registry.add_package(PACKAGE_C, &["1.0.0", "1.1.0"]);
+        registry.add_deployment(PACKAGE_A, "1.0.0", "");
+        registry.add_deployment(PACKAGE_B, "1.0.0", "");
+        // B 2.0.0 can't be used, as no version of A satisfies its requirement
+        registry.add_deployment(PACKAGE_B, "2.0.0", &dependency("a", PACKAGE_A, "2"));
+        registry.add_deployment(PACKAGE_C, "1.0.0", &dependency("b", PACKAGE_B, "1"));
+        registry.add_deployment(PACKAGE_C, "1.1.0", &dependency("b", PACKAGE_B, "2"));
+        let resolve = |name: &str, dependencies: &str| {
+            registry.write(
+                &Path::new(name).join("ambient.toml"),
+                &manifest(PACKAGE_A, "0.0.1", dependencies),
+            );
+            let root = RetrievableFile::Path(registry.0.join(name).join("ambient.toml"));
+            let registries = registry.registries();
+            async move { resolve_versions(&root, false, &registries, None).await }
+        };
+        let expected = |versions: &[(&'static str, &str)]| {
+            let mut versions = versions
+                .iter()
+                .map(|(id, v)| (*id, v.to_string()))
+                .collect::<Vec<_>>();
+            versions.sort();
+            versions
+        };
+
+        // C 1.1.0 requires B 2.0.0, which can't be used, so C 1.0.0 is used instead
+        let lockfile = resolve("nested", &dependency("c", PACKAGE_C, "1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            locked(&lockfile),
+            expected(&[(PACKAGE_B, "1.0.0"), (PACKAGE_C, "1.0.0")])
+        );
+
+        // The older version of the shared dependency B satisfies both C and the root
+        let lockfile = resolve(
+            "shared",
+            &format!(
+                "{}{}",
+                dependency("c", PACKAGE_C, "1"),
+                dependency("b", PACKAGE_B, "1")
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            locked(&lockfile),
+            expected(&[(PACKAGE_B, "1.0.0"), (PACKAGE_C, "1.0.0")])
+        );
+
+        // An exact version is kept even if a newer compatible version exists
+        let error = resolve("exact", &dependency("c", PACKAGE_C, "1.1.0"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            *error,
+            PackageAddError::NoMatchingVersion { ref package_id, .. }
+                if package_id.as_str() == PACKAGE_A
+        ));
+
+        let error = resolve("conflict", &dependency("b", PACKAGE_B, "3"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            *error,
+            PackageAddError::NoMatchingVersion { ref package_id, ref requirements }
+                if package_id.as_str() == PACKAGE_B && requirements == "^3"
+        ));
+    }
+}
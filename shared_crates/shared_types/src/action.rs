@@ -0,0 +1,184 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+use crate::{GamepadButton, MouseButton, VirtualKeyCode};
+
+/// An analog axis on a gamepad.
+#[derive(
+    Debug, Hash, PartialEq, Eq, Clone, Copy, EnumString, EnumIter, Display, Serialize, Deserialize,
+)]
+pub enum GamepadAxis {
+    /// The horizontal axis of the left stick, with +X being right.
+    LeftStickX,
+    /// The vertical axis of the left stick, with +Y being up.
+    LeftStickY,
+    /// The horizontal axis of the right stick, with +X being right.
+    RightStickX,
+    /// The vertical axis of the right stick, with +Y being up.
+    RightStickY,
+    /// How far the left trigger is pressed.
+    LeftTrigger,
+    /// How far the right trigger is pressed.
+    RightTrigger,
+}
+
+/// An input that can be bound to an action.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum InputSource {
+    /// A key on the keyboard.
+    Key(VirtualKeyCode),
+    /// A button on the mouse.
+    MouseButton(MouseButton),
+    /// A button on any connected gamepad.
+    GamepadButton(GamepadButton),
+    /// An analog axis on any connected gamepad.
+    GamepadAxis(GamepadAxis),
+}
+
+/// A binding from an [InputSource] to an action.
+///
+/// Buttons and keys contribute `1.0` to the action's value while they are held, and analog
+/// axes contribute their current value. Negative bindings subtract their contribution instead,
+/// which is how axes are built out of buttons (e.g. `D` and `-A` for moving right and left).
+///
+/// Bindings are written as `Kind:Name`, with a leading `-` for negative bindings: `Key:Space`,
+/// `Mouse:Left`, `Gamepad:South`, `-GamepadAxis:LeftStickY`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ActionBinding {
+    /// The input this binding reads from.
+    pub source: InputSource,
+    /// Whether this binding subtracts from the action's value instead of adding to it.
+    pub negative: bool,
+}
+impl ActionBinding {
+    /// Creates a binding that adds `source` to the action's value.
+    pub fn new(source: InputSource) -> Self {
+        Self {
+            source,
+            negative: false,
+        }
+    }
+
+    /// Returns this binding with its contribution negated.
+    pub fn negated(self) -> Self {
+        Self {
+            negative: !self.negative,
+            ..self
+        }
+    }
+
+    /// Parses a comma-separated list of bindings, as produced by [ActionBinding::format_list].
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, ActionBindingParseError> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|binding| !binding.is_empty())
+            .map(Self::from_str)
+            .collect()
+    }
+
+    /// Formats `bindings` as a comma-separated list.
+    pub fn format_list(bindings: &[Self]) -> String {
+        bindings
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+impl From<InputSource> for ActionBinding {
+    fn from(source: InputSource) -> Self {
+        Self::new(source)
+    }
+}
+impl From<VirtualKeyCode> for ActionBinding {
+    fn from(key: VirtualKeyCode) -> Self {
+        Self::new(InputSource::Key(key))
+    }
+}
+impl From<MouseButton> for ActionBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::new(InputSource::MouseButton(button))
+    }
+}
+impl From<GamepadButton> for ActionBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::new(InputSource::GamepadButton(button))
+    }
+}
+impl From<GamepadAxis> for ActionBinding {
+    fn from(axis: GamepadAxis) -> Self {
+        Self::new(InputSource::GamepadAxis(axis))
+    }
+}
+impl fmt::Display for ActionBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match self.source {
+            InputSource::Key(key) => write!(f, "Key:{key}"),
+            InputSource::MouseButton(MouseButton::Left) => write!(f, "Mouse:Left"),
+            InputSource::MouseButton(MouseButton::Right) => write!(f, "Mouse:Right"),
+            InputSource::MouseButton(MouseButton::Middle) => write!(f, "Mouse:Middle"),
+            InputSource::MouseButton(MouseButton::Other(button)) => write!(f, "Mouse:{button}"),
+            InputSource::GamepadButton(button) => write!(f, "Gamepad:{button}"),
+            InputSource::GamepadAxis(axis) => write!(f, "GamepadAxis:{axis}"),
+        }
+    }
+}
+impl FromStr for ActionBinding {
+    type Err = ActionBindingParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ActionBindingParseError(s.to_string());
+
+        let (negative, binding) = match s.strip_prefix('-') {
+            Some(binding) => (true, binding),
+            None => (false, s),
+        };
+        let (kind, name) = binding.split_once(':').ok_or_else(err)?;
+        let source = match kind {
+            "Key" => InputSource::Key(name.parse().map_err(|_| err())?),
+            "Mouse" => InputSource::MouseButton(match name {
+                "Left" => MouseButton::Left,
+                "Right" => MouseButton::Right,
+                "Middle" => MouseButton::Middle,
+                other => MouseButton::Other(other.parse().map_err(|_| err())?),
+            }),
+            "Gamepad" => InputSource::GamepadButton(name.parse().map_err(|_| err())?),
+            "GamepadAxis" => InputSource::GamepadAxis(name.parse().map_err(|_| err())?),
+            _ => return Err(err()),
+        };
+
+        Ok(Self { source, negative })
+    }
+}
+impl TryFrom<String> for ActionBinding {
+    type Error = ActionBindingParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+impl From<ActionBinding> for String {
+    fn from(value: ActionBinding) -> Self {
+        value.to_string()
+    }
+}
+
+/// The error returned when an [ActionBinding] could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionBindingParseError(pub String);
+impl fmt::Display for ActionBindingParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid action binding {:?}; expected e.g. `Key:Space`, `Mouse:Left`, `Gamepad:South` or `-GamepadAxis:LeftStickY`",
+            self.0
+        )
+    }
+}
+impl std::error::Error for ActionBindingParseError {}
@@ -29,4 +29,7 @@ pub enum AssetType {
     /// Represents a vorbis backed file
     VorbisTrack,
     SoundGraph,
+
+    /// A file copied as-is, to be read at runtime
+    Data,
 }
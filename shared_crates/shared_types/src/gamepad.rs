@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// A button on a gamepad, named after its position on a standard (Xbox-style) layout.
+#[derive(
+    Debug, Hash, PartialEq, Eq, Clone, Copy, EnumString, EnumIter, Display, Serialize, Deserialize,
+)]
+pub enum GamepadButton {
+    /// The bottom face button (A on Xbox, Cross on PlayStation).
+    South,
+    /// The right face button (B on Xbox, Circle on PlayStation).
+    East,
+    /// The top face button (Y on Xbox, Triangle on PlayStation).
+    North,
+    /// The left face button (X on Xbox, Square on PlayStation).
+    West,
+    /// The left shoulder button.
+    LeftBumper,
+    /// The right shoulder button.
+    RightBumper,
+    /// Pressing down on the left stick.
+    LeftStick,
+    /// Pressing down on the right stick.
+    RightStick,
+    /// The left center button (Back/View on Xbox, Share on PlayStation).
+    Select,
+    /// The right center button (Start/Menu on Xbox, Options on PlayStation).
+    Start,
+    /// The center button (Xbox button on Xbox, PS button on PlayStation).
+    Mode,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+impl From<GamepadButton> for u32 {
+    fn from(value: GamepadButton) -> Self {
+        value as u32
+    }
+}
+impl TryFrom<u32> for GamepadButton {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        use strum::IntoEnumIterator;
+        Self::iter().nth(value as usize).ok_or(value)
+    }
+}
+
+/// An analog stick on a gamepad.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+/// An analog trigger on a gamepad.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadTrigger {
+    Left,
+    Right,
+}
+
+macro_rules! impl_left_right_u32 {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for u32 {
+            fn from(value: $ty) -> Self {
+                match value {
+                    <$ty>::Left => 0,
+                    <$ty>::Right => 1,
+                }
+            }
+        }
+        impl TryFrom<u32> for $ty {
+            type Error = u32;
+
+            fn try_from(value: u32) -> Result<Self, Self::Error> {
+                match value {
+                    0 => Ok(Self::Left),
+                    1 => Ok(Self::Right),
+                    x => Err(x),
+                }
+            }
+        })*
+    };
+}
+impl_left_right_u32!(GamepadStick, GamepadTrigger);
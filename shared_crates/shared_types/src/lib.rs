@@ -1,6 +1,15 @@
 mod winit;
 pub use crate::winit::*;
 
+mod gamepad;
+pub use crate::gamepad::*;
+
+mod action;
+pub use crate::action::*;
+
+mod touch;
+pub use crate::touch::*;
+
 mod procedurals;
 pub use crate::procedurals::*;
 
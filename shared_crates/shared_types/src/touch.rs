@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// The phase of a touch, or of a gesture made with touches.
+#[derive(
+    Debug, Hash, PartialEq, Eq, Clone, Copy, EnumString, EnumIter, Display, Serialize, Deserialize,
+)]
+pub enum TouchPhase {
+    /// The touch or gesture started.
+    Started,
+    /// The touch or gesture moved.
+    Moved,
+    /// The touch or gesture ended normally.
+    Ended,
+    /// The touch or gesture was interrupted, e.g. by the system or another gesture.
+    Cancelled,
+}
+impl TouchPhase {
+    /// Returns true if this is the last phase of the touch or gesture.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Ended | Self::Cancelled)
+    }
+}
+impl From<TouchPhase> for u32 {
+    fn from(value: TouchPhase) -> Self {
+        value as u32
+    }
+}
+impl TryFrom<u32> for TouchPhase {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        use strum::IntoEnumIterator;
+        Self::iter().nth(value as usize).ok_or(value)
+    }
+}
+#[cfg(feature = "native")]
+impl From<winit::event::TouchPhase> for TouchPhase {
+    fn from(value: winit::event::TouchPhase) -> Self {
+        match value {
+            winit::event::TouchPhase::Started => Self::Started,
+            winit::event::TouchPhase::Moved => Self::Moved,
+            winit::event::TouchPhase::Ended => Self::Ended,
+            winit::event::TouchPhase::Cancelled => Self::Cancelled,
+        }
+    }
+}
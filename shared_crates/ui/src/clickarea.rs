@@ -11,7 +11,7 @@ use ambient_guest_bridge::{
     },
     ecs::{EntityId, World},
 };
-use ambient_shared_types::MouseButton;
+use ambient_shared_types::{MouseButton, TouchPhase};
 use glam::{Vec2, Vec3};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +34,9 @@ impl From<bool> for MouseInput {
 
 #[derive(Debug, Clone)]
 /// An area that tracks mouse events.
+///
+/// Touches are treated as the left mouse button: the first finger on the screen moves the
+/// cursor, and pressing and lifting it call the mouse input callbacks.
 pub struct ClickArea {
     /// The inner element.
     pub inner: Element,
@@ -138,8 +141,12 @@ impl ElementComponent for ClickArea {
         } = *self;
         let id = use_ref_with(hooks, |_| None);
         let mouse_over_count = use_ref_with(hooks, |_| 0);
+        // Touches move the cursor, but the mouse over state only catches up on the next frame,
+        // so touch input is queued (with the number of frames it has waited) until it has
+        let pending_touch_input = use_ref_with(hooks, |_| Vec::<(MouseInput, u32)>::new());
+        let active_touch = use_ref_with(hooks, |_| None);
         use_frame(hooks, {
-            to_owned![id, mouse_over_count];
+            to_owned![id, mouse_over_count, pending_touch_input, on_mouse_input];
             move |world| {
                 if let Some(id) = *id.lock() {
                     let next = world.get(id, is_mouse_over()).unwrap_or(0);
@@ -160,10 +167,46 @@ impl ElementComponent for ClickArea {
                         }
                     }
                     *state = next;
+
+                    let mut pending_touch_input = pending_touch_input.lock();
+                    for (input, frames_waited) in pending_touch_input.iter_mut() {
+                        *frames_waited += 1;
+                        if *frames_waited > 1 && *state > 0 {
+                            for handler in &on_mouse_input {
+                                handler(world, id, *input, MouseButton::Left);
+                            }
+                        }
+                    }
+                    pending_touch_input.retain(|(_, frames_waited)| *frames_waited <= 1);
                 }
             }
         });
 
+        use_runtime_message::<messages::WindowTouchInput>(hooks, {
+            to_owned![pending_touch_input];
+            move |_world, event| {
+                let Ok(phase) = TouchPhase::try_from(event.phase) else {
+                    return;
+                };
+                // Only the first finger on the screen acts as the mouse
+                let mut active_touch = active_touch.lock();
+                let input = match phase {
+                    TouchPhase::Started if active_touch.is_none() => {
+                        *active_touch = Some(event.touch_id);
+                        MouseInput::Pressed
+                    }
+                    TouchPhase::Ended | TouchPhase::Cancelled
+                        if *active_touch == Some(event.touch_id) =>
+                    {
+                        *active_touch = None;
+                        MouseInput::Released
+                    }
+                    _ => return,
+                };
+                pending_touch_input.lock().push((input, 0));
+            }
+        });
+
         use_runtime_message::<messages::WindowMouseInput>(hooks, {
             to_owned![id, mouse_over_count];
             move |world, event| {
@@ -3,6 +3,7 @@
 //! The layout is roughly based on [Windows Forms](https://docs.microsoft.com/en-us/dotnet/desktop/winforms/controls/layout?view=netdesktop-6.0#container-flow-layout).
 //!
 //! There are two major layout components, [Dock] and [Flow] (which includes [FlowColumn] and [FlowRow]).
+//! [Grid] places its children in the cells of a grid.
 use crate::{use_window_logical_resolution, UIBase, UIExt};
 use ambient_cb::Cb;
 use ambient_color::Color;
@@ -71,6 +72,61 @@ impl ElementComponent for Flow {
     }
 }
 
+/// A grid layout, where each child is placed in one or more cells formed by the columns and rows of the grid.
+///
+/// The sizes of the columns and rows are set with `grid_columns` and `grid_rows` (see [GridTrack]), and the space
+/// between them with `grid_gap`. A child can choose its cell using the `grid_column` and `grid_row` components, and span
+/// several cells using `grid_column_span` and `grid_row_span`; the other children are placed in the next free cells, row by row.
+///
+/// Children that fit their parent fill their cells, and only their minimum size counts towards the size of
+/// [GridTrack::Auto] tracks. The other children are aligned in their cells using the grid's `align_horizontal`
+/// and `align_vertical`.
+#[derive(Debug, Clone)]
+pub struct Grid(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(Grid);
+impl ElementComponent for Grid {
+    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+        Element::from(UIBase)
+            .init(layout(), Layout::Grid)
+            .init_default(children())
+            .children(self.0)
+    }
+}
+
+/// The size of a column or a row of a [Grid].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+    /// A fixed size, in pixels.
+    Pixels(f32),
+    /// A share of the space left by the other tracks, relative to the other fraction tracks.
+    Fraction(f32),
+    /// Like [GridTrack::Fraction], but at least `min` pixels.
+    MinFraction {
+        /// The minimum size, in pixels.
+        min: f32,
+        /// The share of the space left by the other tracks.
+        fraction: f32,
+    },
+    /// Fits the largest child in the track.
+    Auto,
+}
+impl GridTrack {
+    /// Converts the tracks to the value of `grid_columns` or `grid_rows`.
+    pub fn to_components(tracks: impl IntoIterator<Item = GridTrack>) -> Vec<Vec2> {
+        tracks.into_iter().map(Vec2::from).collect()
+    }
+}
+impl From<GridTrack> for Vec2 {
+    fn from(track: GridTrack) -> Self {
+        match track {
+            GridTrack::Pixels(pixels) => vec2(pixels, 0.),
+            GridTrack::Fraction(fraction) => vec2(0., fraction),
+            GridTrack::MinFraction { min, fraction } => vec2(min, fraction),
+            GridTrack::Auto => Vec2::ZERO,
+        }
+    }
+}
+
 /// A bookcase layout is a min-max layout; it should be a list of [BookFile]s, where each [BookFile]
 /// has a `container` and a `book`. The book's determine the size of the entire [Bookcase], but their
 /// sizes are not manipulated. The containers are resized to fit the bookcase though, to align them.
@@ -154,6 +210,8 @@ impl ElementComponent for Centered {
 }
 
 /// A [FixedGrid] is a grid of elements with a fixed stride.
+///
+/// To size the cells to their content or to the available space, use a [Grid] instead.
 #[element_component]
 pub fn FixedGrid(
     _: &mut Hooks,